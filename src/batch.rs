@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use miden_assembly::DefaultSourceManager;
+use miden_assembly_syntax::diagnostics::{IntoDiagnostic, Report};
+use miden_core::field::PrimeField64;
+use miden_processor::Felt as RawFelt;
+
+use crate::{
+    config::{DebuggerConfig, Expected},
+    debug::CycleMeasurement,
+    ui::{
+        commands,
+        count::HumanCount,
+        state::{self, State},
+        style::Style,
+    },
+};
+
+/// Run the program described by `config` to completion, without any user interaction.
+///
+/// All output is written to stdout. Returns `Ok(false)` if execution of the program, or of any
+/// command in the script given by `--script`, failed.
+pub fn run(config: Box<DebuggerConfig>, logger: Box<dyn log::Log>) -> Result<bool, Report> {
+    log::set_boxed_logger(logger).into_diagnostic()?;
+    log::set_max_level(log::LevelFilter::Trace);
+
+    match config.script.clone() {
+        Some(path) => run_script(config, &path),
+        None => run_program(config),
+    }
+}
+
+fn run_program(config: Box<DebuggerConfig>) -> Result<bool, Report> {
+    let expected = load_expectations(&config)?;
+    let style = Style::current();
+
+    let source_manager = Arc::new(DefaultSourceManager::default());
+    let package = state::load_package(&config)?;
+    let libs = state::load_libraries(&config, source_manager.clone())?;
+    let executor = state::create_executor(&config, &package, &libs)?;
+
+    let program = package.unwrap_program();
+    let measurement = match config.measure {
+        Some(n) => {
+            let executor = state::create_executor(&config, &package, &libs)?
+                .into_debug(&program, source_manager.clone());
+            Some((CycleMeasurement::run(executor, &*source_manager), n))
+        }
+        None => None,
+    };
+    let succeeded = match executor.try_execute(&program, source_manager) {
+        Ok(trace) => {
+            println!("outputs: {}", commands::format_outputs(&trace.outputs()[..]));
+            if let Some(ty) = config.result_type {
+                let result = ty
+                    .decode(&trace.outputs()[..])
+                    .ok_or_else(|| Report::msg(format!("outputs cannot be decoded as {ty}")))?;
+                println!("result: {result}");
+            }
+            let cycles = trace.last_cycle().as_usize();
+            println!("cycles: {}", HumanCount(cycles));
+            if let Some(warning) = commands::cycle_budget_warning(&config, cycles) {
+                eprintln!("{} {warning}", style.warning("warning:"));
+            }
+
+            let mismatches = check_outputs(&expected, &trace.outputs()[..]);
+            if !mismatches.is_empty() {
+                println!("outputs do not match expectations:");
+                for mismatch in mismatches.iter() {
+                    println!("  {mismatch}");
+                }
+            }
+            mismatches.is_empty()
+        }
+        Err(failure) => {
+            println!("{}", style.plain(&failure.to_string()));
+            println!("cycles: {}", HumanCount(failure.cycle));
+            false
+        }
+    };
+    if let Some((measurement, n)) = measurement {
+        for line in commands::render_measurement(&measurement, n) {
+            println!("{line}");
+        }
+    }
+    Ok(succeeded)
+}
+
+fn run_script(config: Box<DebuggerConfig>, path: &std::path::Path) -> Result<bool, Report> {
+    let script = std::fs::read_to_string(path)
+        .map_err(|err| Report::msg(format!("unable to read script '{}': {err}", path.display())))?;
+    let mut state = State::new(config)?;
+    if let Some(pattern) = state.config.break_at.clone() {
+        let line = match commands::run_to_procedure(&mut state, &pattern) {
+            Ok(status) => status,
+            Err(err) => format!("error: {err}"),
+        };
+        println!("{}", Style::current().output_line(&line));
+    }
+    // The style is fetched for each line, as the script may change it via `set color`
+    let summary = commands::run_script(&mut state, &script, &mut |line| {
+        println!("{}", Style::current().output_line(&line))
+    });
+    Ok(summary.failed == 0)
+}
+
+/// Gather the expectations given via `--expect` and `--expect-file`, in that order
+fn load_expectations(config: &DebuggerConfig) -> Result<Vec<Expected>, Report> {
+    let mut expected = config.expect.clone();
+    if let Some(path) = config.expect_file.as_deref() {
+        let content = std::fs::read_to_string(path).map_err(|err| {
+            Report::msg(format!("unable to read expectations from '{}': {err}", path.display()))
+        })?;
+        for (i, line) in content.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let expectation = line.parse::<Expected>().map_err(|err| {
+                Report::msg(format!("invalid expectation at {}:{i}: {err}", path.display()))
+            })?;
+            expected.push(expectation);
+        }
+    }
+    Ok(expected)
+}
+
+/// Compare `outputs`, from the top of the operand stack, against `expected`, returning a
+/// description of each mismatch
+fn check_outputs(expected: &[Expected], outputs: &[RawFelt]) -> Vec<String> {
+    let mut mismatches = vec![];
+    let mut position = 0;
+    for expectation in expected {
+        match expectation {
+            Expected::Felts(felts) => {
+                for (offset, felt) in felts.iter().enumerate() {
+                    let expected = felt.0.as_canonical_u64();
+                    match outputs.get(position + offset) {
+                        Some(actual) if actual == &felt.0 => (),
+                        Some(actual) => mismatches.push(format!(
+                            "position {}: expected {expected}, got {}",
+                            position + offset,
+                            actual.as_canonical_u64()
+                        )),
+                        None => mismatches.push(format!(
+                            "position {}: expected {expected}, got nothing",
+                            position + offset
+                        )),
+                    }
+                }
+            }
+            Expected::Typed { ty, value, felts } => {
+                match outputs.get(position..(position + felts.len())) {
+                    Some(actual) if actual == felts.as_slice() => (),
+                    Some(actual) => {
+                        let actual = ty.decode(actual).unwrap_or_default();
+                        mismatches.push(format!(
+                            "position {position}: expected {ty}:{value}, got {ty}:{actual}"
+                        ));
+                    }
+                    None => mismatches
+                        .push(format!("position {position}: expected {ty}:{value}, got nothing")),
+                }
+            }
+        }
+        position += expectation.size_in_felts();
+    }
+    mismatches
+}
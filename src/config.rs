@@ -1,13 +1,28 @@
 use std::{
     borrow::Cow,
+    fmt,
     path::{Path, PathBuf},
     str::FromStr,
 };
 
-use crate::{exec::ExecutionConfig, felt::Felt, input::InputFile, linker::LinkLibrary};
+use miden_processor::Felt as RawFelt;
+
+use crate::{
+    exec::ExecutionConfig,
+    felt::{Felt, FromMidenRepr, ProgramArg, ToMidenRepr},
+    input::InputFile,
+    linker::LinkLibrary,
+};
+
+/// The number of columns between tab stops when showing source code, unless `--tab-width` is given
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// The maximum number of commands retained in the command history, unless `--history-size` is
+/// given
+pub const DEFAULT_HISTORY_SIZE: usize = 1000;
 
 /// Run a compiled Miden program with the Miden VM
-#[derive(Default, Debug)]
+#[derive(Debug)]
 #[cfg_attr(feature = "tui", derive(clap::Parser))]
 #[cfg_attr(feature = "tui", command(author, version, about = "The interactive Miden debugger", long_about = None))]
 pub struct DebuggerConfig {
@@ -21,8 +36,13 @@ pub struct DebuggerConfig {
     /// Specify the path to a file containing program inputs.
     ///
     /// Program inputs are stack and advice provider values which the program can
-    /// access during execution. The inputs file is a TOML file which describes
-    /// what the inputs are, or where to source them from.
+    /// access during execution, and the initial contents of memory. The inputs file
+    /// is a TOML file which describes what the inputs are, or where to source them from.
+    ///
+    /// Memory is given either as `[[memory.word]]` entries, each with a word-aligned
+    /// `address` and a `value` of four elements, or as `[[memory]]` regions, each with
+    /// an element address `addr`, and either inline `data`, or the `path` of a file
+    /// holding four bytes per element in big-endian order, e.g. from `dump memory`.
     #[cfg_attr(feature = "tui", arg(long, value_name = "FILE"))]
     pub inputs: Option<ExecutionConfig>,
     /// Arguments to place on the operand stack before calling the program entrypoint.
@@ -31,11 +51,34 @@ pub struct DebuggerConfig {
     ///
     /// Example: `-- a b` will push `a` on the stack, then `b`.
     ///
-    /// These arguments must be valid field element values expressed in decimal format.
+    /// Each argument is a field element, in decimal or hexadecimal format, unless it has a type
+    /// suffix, i.e. one of `u8`, `i8`, `u16`, `i16`, `u32`, `i32`, `u64`, `i64`, `u128`, `i128`, or
+    /// `felt`, e.g. `5u64` or `-3i32`. Typed arguments are encoded as compiled code expects, so
+    /// those wider than 32 bits push one element per 32-bit limb, the least significant nearest
+    /// the top of the stack.
     ///
     /// NOTE: These arguments will override any stack values provided via --inputs
     #[cfg_attr(feature = "tui", arg(last(true), value_name = "ARGV"))]
-    pub args: Vec<Felt>,
+    pub args: Vec<ProgramArg>,
+    /// Print the operand stack and advice inputs the program will be executed with, then exit.
+    ///
+    /// The inputs are printed after merging the `--inputs` file and any arguments, with each
+    /// stack listed from bottom to top. If `--batch` is also given, the program is then executed.
+    #[cfg_attr(feature = "tui", arg(long, help_heading = "Execution"))]
+    pub dump_inputs: bool,
+    /// Specify the path to a file describing assertion error codes.
+    ///
+    /// The file is TOML, or JSON if it has a `.json` extension, and maps each error code to a
+    /// name and optional description, which are shown in place of the bare code when an
+    /// assertion fails.
+    ///
+    /// By default, `<name>.errors.toml` or `<name>.errors.json` next to the program file is used,
+    /// if it exists. Codes defined by the package itself are always available.
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "FILE", help_heading = "Execution")
+    )]
+    pub error_codes: Option<PathBuf>,
     /// The working directory for the debugger
     ///
     /// By default this will be the working directory the debugger is executed from
@@ -67,6 +110,18 @@ pub struct DebuggerConfig {
         help_heading = "Output"
     ))]
     pub color: ColorChoice,
+    /// The format in which to report where, and why, execution stopped when running a script
+    ///
+    /// With `json`, each stop is reported as a single JSON object on a line prefixed with `@@`,
+    /// while the output of commands remains textual.
+    #[cfg_attr(feature = "tui", arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = OutputFormat::Text,
+        help_heading = "Output"
+    ))]
+    pub output_format: OutputFormat,
     /// Specify the function to call as the entrypoint for the program
     /// in the format `<module_name>::<function>`
     #[cfg_attr(feature = "tui", arg(long, help_heading = "Execution"))]
@@ -104,6 +159,202 @@ pub struct DebuggerConfig {
         )
     )]
     pub link_libraries: Vec<LinkLibrary>,
+    /// Print the paths searched for libraries given via `--link-library`, in the order they are
+    /// searched, and exit
+    #[cfg_attr(feature = "tui", arg(long, help_heading = "Linker"))]
+    pub print_search_paths: bool,
+    /// Run the program to completion without starting the interactive debugger
+    ///
+    /// The final operand stack and cycle count are printed to stdout, or if execution fails, the
+    /// stack trace and error. The exit status is non-zero if execution fails.
+    ///
+    /// When combined with `--script`, the script is executed instead, and the exit status is
+    /// non-zero if any command in the script fails. When combined with `--entrypoint`, the given
+    /// procedure of the library is run.
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, visible_alias = "run", help_heading = "Interface")
+    )]
+    pub batch: bool,
+    /// Decode the operand stack outputs as a value of type TYPE when running with `--batch`
+    ///
+    /// This cannot be combined with `--script`, as the script decides where the program stops.
+    #[cfg_attr(
+        feature = "tui",
+        arg(
+            long,
+            value_enum,
+            value_name = "TYPE",
+            requires = "batch",
+            conflicts_with = "script",
+            help_heading = "Interface"
+        )
+    )]
+    pub result_type: Option<ResultType>,
+    /// Assert that the operand stack outputs match VALUES when running with `--batch`
+    ///
+    /// VALUES is either a comma-separated list of field elements, e.g. `1,2,3`, or a typed value,
+    /// e.g. `u64:300` or `u64=300`. When given more than once, e.g. for a program returning
+    /// several values, each expectation applies to the outputs which follow those of the previous
+    /// one, starting from the top of the stack.
+    ///
+    /// This cannot be combined with `--script`, as the script decides where the program stops.
+    #[cfg_attr(
+        feature = "tui",
+        arg(
+            long,
+            visible_alias = "assert-output",
+            value_name = "VALUES",
+            requires = "batch",
+            conflicts_with = "script",
+            help_heading = "Interface"
+        )
+    )]
+    pub expect: Vec<Expected>,
+    /// Read expectations, as accepted by `--expect`, from FILE, one per line
+    ///
+    /// Blank lines, and lines starting with `#`, are ignored. These expectations apply to the
+    /// outputs which follow any given via `--expect`. Like `--expect`, this cannot be combined
+    /// with `--script`.
+    #[cfg_attr(
+        feature = "tui",
+        arg(
+            long,
+            value_name = "FILE",
+            requires = "batch",
+            conflicts_with = "script",
+            help_heading = "Interface"
+        )
+    )]
+    pub expect_file: Option<PathBuf>,
+    /// Show the N lines of source code the program spends the most cycles on when running with
+    /// `--batch`, as shown by the `measure` command, e.g. `--measure=20`
+    ///
+    /// N is 10 by default. This cannot be combined with `--script`, whose commands may use
+    /// `measure` instead.
+    #[cfg_attr(
+        feature = "tui",
+        arg(
+            long,
+            value_name = "N",
+            num_args(0..=1),
+            require_equals(true),
+            default_missing_value = "10",
+            requires = "batch",
+            conflicts_with = "script",
+            help_heading = "Interface"
+        )
+    )]
+    pub measure: Option<usize>,
+    /// The maximum number of cycles the program may execute before it is considered to have failed
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "N", help_heading = "Execution")
+    )]
+    pub max_cycles: Option<u32>,
+    /// Warn when the program terminates after executing more than N cycles
+    ///
+    /// This is useful for catching regressions in the cost of a program, without failing it.
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "N", help_heading = "Execution")
+    )]
+    pub cycle_budget_warning: Option<usize>,
+    /// Do not run the program to completion at startup in order to capture its execution trace
+    ///
+    /// Capturing the trace doubles the cost of executing the program before debugging can begin,
+    /// which is noticeable for large programs. Without it, `read`, `cycles`, and `outputs` before
+    /// the program has finished, are unavailable until the trace is captured with `capture`.
+    #[cfg_attr(feature = "tui", arg(long, help_heading = "Execution"))]
+    pub no_capture_trace: bool,
+    /// Run until a procedure matching PROC is called at startup, so that debugging starts there
+    ///
+    /// PROC is a glob pattern, as accepted by `break in`, e.g. `*::main`. The breakpoint is removed
+    /// once it is hit. If no such procedure is called, the program runs to completion, and this is
+    /// reported. With `--batch`, this only applies to the commands of `--script`.
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "PROC", help_heading = "Interface")
+    )]
+    pub break_at: Option<String>,
+    /// Execute the debugger commands in FILE at startup
+    ///
+    /// Each line of FILE is a command, as accepted by the `:` command line. Blank lines, and lines
+    /// starting with `#`, are ignored. The script stops at the first command which fails, unless
+    /// that command is prefixed with `-`, or `--continue-on-error` is given.
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "FILE", help_heading = "Interface")
+    )]
+    pub script: Option<PathBuf>,
+    /// Keep executing the commands of a script after one of them fails
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, requires = "script", help_heading = "Interface")
+    )]
+    pub continue_on_error: bool,
+    /// Append a transcript of the session, i.e. the commands entered and their output, to FILE
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "FILE", help_heading = "Interface")
+    )]
+    pub session_log: Option<PathBuf>,
+    /// The shell command used to rebuild the program, via the `make` and `rebuild` commands
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "CMD", help_heading = "Interface")
+    )]
+    pub build_cmd: Option<String>,
+    /// The file used to persist command history across sessions
+    ///
+    /// By default this is `$XDG_DATA_HOME/miden-debug/history`
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "FILE", help_heading = "Interface")
+    )]
+    pub history_file: Option<PathBuf>,
+    /// The maximum number of commands to retain in the command history
+    #[cfg_attr(
+        feature = "tui",
+        arg(
+            long,
+            value_name = "N",
+            default_value_t = DEFAULT_HISTORY_SIZE,
+            help_heading = "Interface"
+        )
+    )]
+    pub history_size: usize,
+    /// The file which configures the key bindings of the TUI
+    ///
+    /// By default this is `$XDG_CONFIG_HOME/miden-debug/keybindings.toml`, if it exists
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "FILE", help_heading = "Interface")
+    )]
+    pub keybindings: Option<PathBuf>,
+    /// The theme used to highlight source code, from which the colors of the other panes are
+    /// derived
+    ///
+    /// This is either one of the themes bundled with the debugger, or the name of a `.tmTheme`
+    /// file in `$XDG_CONFIG_HOME/miden-debug/themes`. Use `:set theme` to list them. By default,
+    /// the theme last selected via `:set theme` is used.
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "NAME", help_heading = "Interface")
+    )]
+    pub theme: Option<String>,
+    /// The number of columns between tab stops when showing source code, 4 by default
+    #[cfg_attr(
+        feature = "tui",
+        arg(long, value_name = "N", value_parser = parse_tab_width, help_heading = "Interface")
+    )]
+    pub tab_width: Option<usize>,
+    /// Capture the mouse, so that panes are focused by clicking them, and scrolled with the wheel
+    ///
+    /// Clicking a line of source code selects it, and clicking its line number toggles a
+    /// breakpoint on it. While the mouse is captured, the terminal cannot select text with it.
+    #[cfg_attr(feature = "tui", arg(long, help_heading = "Interface"))]
+    pub mouse: bool,
 }
 
 /// ColorChoice represents the color preferences of an end user.
@@ -132,6 +383,200 @@ pub enum ColorChoice {
     Never,
 }
 
+/// The format in which stops are reported, see [DebuggerConfig::output_format]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    /// Describe each stop in prose
+    #[default]
+    Text,
+    /// Report each stop as a JSON object, on a line prefixed with `@@`
+    Json,
+}
+
+/// The type of value which the operand stack outputs of a program should be decoded as
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "tui", derive(clap::ValueEnum))]
+pub enum ResultType {
+    Felt,
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    U128,
+    I128,
+}
+
+impl FromStr for ResultType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "felt" => Ok(Self::Felt),
+            "bool" => Ok(Self::Bool),
+            "u8" => Ok(Self::U8),
+            "i8" => Ok(Self::I8),
+            "u16" => Ok(Self::U16),
+            "i16" => Ok(Self::I16),
+            "u32" => Ok(Self::U32),
+            "i32" => Ok(Self::I32),
+            "u64" => Ok(Self::U64),
+            "i64" => Ok(Self::I64),
+            "u128" => Ok(Self::U128),
+            "i128" => Ok(Self::I128),
+            other => Err(format!("unknown type '{other}'")),
+        }
+    }
+}
+
+impl fmt::Display for ResultType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Felt => "felt",
+            Self::Bool => "bool",
+            Self::U8 => "u8",
+            Self::I8 => "i8",
+            Self::U16 => "u16",
+            Self::I16 => "i16",
+            Self::U32 => "u32",
+            Self::I32 => "i32",
+            Self::U64 => "u64",
+            Self::I64 => "i64",
+            Self::U128 => "u128",
+            Self::I128 => "i128",
+        };
+        f.write_str(name)
+    }
+}
+
+impl ResultType {
+    /// The number of field elements occupied by a value of this type on the operand stack
+    pub fn size_in_felts(&self) -> usize {
+        match self {
+            Self::Felt
+            | Self::Bool
+            | Self::U8
+            | Self::I8
+            | Self::U16
+            | Self::I16
+            | Self::U32
+            | Self::I32 => 1,
+            Self::U64 | Self::I64 => 2,
+            Self::U128 | Self::I128 => 4,
+        }
+    }
+
+    /// Decode a value of this type from `felts`, given in operand stack order, and render it.
+    ///
+    /// Returns `None` if there are not enough elements in `felts`.
+    pub fn decode(&self, felts: &[RawFelt]) -> Option<String> {
+        fn decode_as<T: FromMidenRepr + fmt::Display>(felts: &[RawFelt]) -> String {
+            T::from_felts(felts).to_string()
+        }
+
+        if felts.len() < self.size_in_felts() {
+            return None;
+        }
+        Some(match self {
+            Self::Felt => decode_as::<RawFelt>(felts),
+            Self::Bool => decode_as::<bool>(felts),
+            Self::U8 => decode_as::<u8>(felts),
+            Self::I8 => decode_as::<i8>(felts),
+            Self::U16 => decode_as::<u16>(felts),
+            Self::I16 => decode_as::<i16>(felts),
+            Self::U32 => decode_as::<u32>(felts),
+            Self::I32 => decode_as::<i32>(felts),
+            Self::U64 => decode_as::<u64>(felts),
+            Self::I64 => decode_as::<i64>(felts),
+            Self::U128 => decode_as::<u128>(felts),
+            Self::I128 => decode_as::<i128>(felts),
+        })
+    }
+
+    /// Parse `value` as a value of this type, and encode it as field elements in operand stack
+    /// order.
+    pub fn encode(&self, value: &str) -> Result<Vec<RawFelt>, String> {
+        fn encode_as<T>(value: &str) -> Result<Vec<RawFelt>, String>
+        where
+            T: ToMidenRepr + FromStr,
+            <T as FromStr>::Err: fmt::Display,
+        {
+            let value =
+                value.parse::<T>().map_err(|err| format!("invalid value '{value}': {err}"))?;
+            Ok(value.to_felts().into_vec())
+        }
+
+        match self {
+            Self::Felt => encode_as::<Felt>(value),
+            Self::Bool => encode_as::<bool>(value),
+            Self::U8 => encode_as::<u8>(value),
+            Self::I8 => encode_as::<i8>(value),
+            Self::U16 => encode_as::<u16>(value),
+            Self::I16 => encode_as::<i16>(value),
+            Self::U32 => encode_as::<u32>(value),
+            Self::I32 => encode_as::<i32>(value),
+            Self::U64 => encode_as::<u64>(value),
+            Self::I64 => encode_as::<i64>(value),
+            Self::U128 => encode_as::<u128>(value),
+            Self::I128 => encode_as::<i128>(value),
+        }
+    }
+}
+
+/// An expectation about the operand stack outputs of a program, as given to `--expect`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    /// A sequence of field elements, e.g. `1,2,3`
+    Felts(Vec<Felt>),
+    /// A single value of the given type, e.g. `u64:300`, or equivalently `u64=300`
+    Typed {
+        ty: ResultType,
+        /// The value as it was written
+        value: String,
+        /// The encoding of `value` on the operand stack
+        felts: Vec<RawFelt>,
+    },
+}
+
+impl Expected {
+    /// The number of operand stack elements covered by this expectation
+    pub fn size_in_felts(&self) -> usize {
+        match self {
+            Self::Felts(felts) => felts.len(),
+            Self::Typed { felts, .. } => felts.len(),
+        }
+    }
+}
+
+impl FromStr for Expected {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some((ty, value)) = s.split_once([':', '=']) {
+            let ty = ty.trim().parse::<ResultType>()?;
+            let value = value.trim();
+            let felts = ty.encode(value)?;
+            return Ok(Self::Typed {
+                ty,
+                value: value.to_string(),
+                felts,
+            });
+        }
+
+        let felts = s
+            .split(',')
+            .map(|felt| felt.trim().parse::<Felt>())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::Felts(felts))
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("invalid color choice: {0}")]
 pub struct ColorChoiceParseError(std::borrow::Cow<'static, str>);
@@ -239,7 +684,51 @@ impl ColorChoice {
     }
 }
 
+impl Default for DebuggerConfig {
+    fn default() -> Self {
+        Self {
+            input: Default::default(),
+            inputs: Default::default(),
+            args: Default::default(),
+            dump_inputs: Default::default(),
+            error_codes: Default::default(),
+            working_dir: Default::default(),
+            sysroot: Default::default(),
+            color: Default::default(),
+            output_format: Default::default(),
+            entrypoint: Default::default(),
+            search_path: Default::default(),
+            link_libraries: Default::default(),
+            print_search_paths: Default::default(),
+            batch: Default::default(),
+            result_type: Default::default(),
+            expect: Default::default(),
+            expect_file: Default::default(),
+            measure: Default::default(),
+            max_cycles: Default::default(),
+            cycle_budget_warning: Default::default(),
+            no_capture_trace: Default::default(),
+            break_at: Default::default(),
+            script: Default::default(),
+            continue_on_error: Default::default(),
+            session_log: Default::default(),
+            build_cmd: Default::default(),
+            history_file: Default::default(),
+            history_size: DEFAULT_HISTORY_SIZE,
+            keybindings: Default::default(),
+            theme: Default::default(),
+            tab_width: Default::default(),
+            mouse: Default::default(),
+        }
+    }
+}
+
 impl DebuggerConfig {
+    /// The number of columns between tab stops when showing source code, see `--tab-width`
+    pub fn tab_width(&self) -> usize {
+        self.tab_width.unwrap_or(DEFAULT_TAB_WIDTH)
+    }
+
     pub fn working_dir(&self) -> Cow<'_, Path> {
         match self.working_dir.as_deref() {
             Some(path) => Cow::Borrowed(path),
@@ -249,6 +738,57 @@ impl DebuggerConfig {
         }
     }
 
+    /// Returns the path of the file used to persist command history, if one can be determined
+    pub fn history_path(&self) -> Option<PathBuf> {
+        if let Some(path) = self.history_file.as_deref() {
+            return Some(path.to_path_buf());
+        }
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+            })?;
+        Some(data_home.join("miden-debug").join("history"))
+    }
+
+    /// Returns the path of the file which configures the key bindings, if one can be determined
+    pub fn keybindings_path(&self) -> Option<PathBuf> {
+        if let Some(path) = self.keybindings.as_deref() {
+            return Some(path.to_path_buf());
+        }
+        Some(config_dir()?.join("keybindings.toml"))
+    }
+
+    /// Returns the path of the directory searched for `.tmTheme` files, if one can be determined
+    pub fn themes_dir(&self) -> Option<PathBuf> {
+        Some(config_dir()?.join("themes"))
+    }
+
+    /// Returns the path of the file used to persist the settings changed from within the
+    /// debugger, e.g. the theme, if one can be determined
+    pub fn settings_path(&self) -> Option<PathBuf> {
+        Some(config_dir()?.join("settings.toml"))
+    }
+
+    /// Returns the path of the file used to persist command aliases, if one can be determined.
+    ///
+    /// Aliases are stored alongside the command history, in a file named `aliases`.
+    pub fn aliases_path(&self) -> Option<PathBuf> {
+        let history = self.history_path()?;
+        Some(history.parent()?.join("aliases"))
+    }
+
+    /// Returns the paths searched for libraries which are linked by name, in the order they are
+    /// searched: the toolchain directory, the paths given via `-L`, and the working directory
+    pub fn library_search_paths(&self) -> Vec<PathBuf> {
+        self.toolchain_dir()
+            .into_iter()
+            .chain(self.search_path.iter().cloned())
+            .chain(self.working_dir.iter().cloned())
+            .collect()
+    }
+
     pub fn toolchain_dir(&self) -> Option<PathBuf> {
         let sysroot = if let Some(sysroot) = self.sysroot.as_deref() {
             Cow::Borrowed(sysroot)
@@ -268,6 +808,24 @@ impl DebuggerConfig {
     }
 }
 
+/// Parse the number of columns between tab stops, as given to `--tab-width` or `set tab-width`
+pub fn parse_tab_width(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(width @ 1..=16) => Ok(width),
+        _ => Err(format!("invalid tab width '{s}', expected a number from 1 to 16")),
+    }
+}
+
+/// The directory containing the configuration files of the debugger, i.e.
+/// `$XDG_CONFIG_HOME/miden-debug`, if one can be determined
+fn config_dir() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("miden-debug"))
+}
+
 fn midenup_home() -> Option<PathBuf> {
     use std::process::Command;
 
@@ -9,6 +9,16 @@ pub struct Breakpoint {
     pub id: u8,
     pub creation_cycle: usize,
     pub ty: BreakpointType,
+    /// Whether this breakpoint is checked during execution, disabled breakpoints are never hit
+    pub enabled: bool,
+    /// The number of times this breakpoint has been hit since it was created
+    pub hits: usize,
+    /// The number of times execution reached the location of this breakpoint since it was created,
+    /// whether or not it stopped there, e.g. because the breakpoint is disabled
+    pub passes: usize,
+    /// Whether execution was at the location of this breakpoint as of the last cycle checked, so
+    /// that each of the cycles spent there is not counted as a pass
+    pub passing: bool,
 }
 
 impl Default for Breakpoint {
@@ -17,23 +27,39 @@ impl Default for Breakpoint {
             id: 0,
             creation_cycle: 0,
             ty: BreakpointType::Step,
+            enabled: true,
+            hits: 0,
+            passes: 0,
+            passing: false,
         }
     }
 }
 
 impl Breakpoint {
-    /// Return the number of cycles this breakpoint indicates we should skip, or `None` if the
-    /// number of cycles is context-specific, or the breakpoint is triggered by something other
-    /// than cycle count.
+    /// Return the number of cycles remaining, as of `current_cycle`, before this breakpoint is hit,
+    /// or `None` if the number of cycles is context-specific, or the breakpoint is triggered by
+    /// something other than cycle count.
     pub fn cycles_to_skip(&self, current_cycle: usize) -> Option<usize> {
         let cycles_passed = current_cycle - self.creation_cycle;
         match &self.ty {
-            BreakpointType::Step => Some(1),
+            BreakpointType::Step => Some(1usize.saturating_sub(cycles_passed)),
             BreakpointType::StepN(n) => Some(n.saturating_sub(cycles_passed)),
             BreakpointType::StepTo(to) if to >= &current_cycle => Some(to.abs_diff(current_cycle)),
             _ => None,
         }
     }
+
+    /// Count a pass through the location of this breakpoint, if execution has just reached it, as
+    /// of a cycle executed at `loc` in `procedure`. A breakpoint on a procedure is at its location
+    /// while the procedure executes.
+    pub fn record_pass(&mut self, loc: Option<&ResolvedLocation>, procedure: Option<&str>) {
+        let at_location = loc.is_some_and(|loc| self.should_break_at(loc))
+            || procedure.is_some_and(|procedure| self.should_break_in(procedure));
+        if at_location && !self.passing {
+            self.passes += 1;
+        }
+        self.passing = at_location;
+    }
 }
 impl Deref for Breakpoint {
     type Target = BreakpointType;
@@ -52,6 +78,14 @@ pub enum BreakpointType {
     StepN(usize),
     /// Break at a given cycle
     StepTo(usize),
+    /// Break once N assembly instructions have been executed in full, of which `completed` have
+    /// been so far
+    StepInstructions { n: usize, completed: usize },
+    /// Break at the Nth instruction boundary from now, i.e. on the first cycle of the Nth assembly
+    /// instruction to start, of which `started` have started so far.
+    ///
+    /// Unlike [BreakpointType::StepN], an instruction which takes several cycles counts only once.
+    StepOps { n: usize, started: usize },
     /// Break at the first cycle of the next instruction
     Next,
     /// Break when we exit the current call frame
@@ -61,11 +95,19 @@ pub enum BreakpointType {
     /// Break when any cycle corresponds to a source location whose file matches PATTERN and occurs
     /// on LINE
     Line { pattern: Pattern, line: u32 },
+    /// Break on the line N lines after (or before, if negative) the current line of the current
+    /// file, see [BreakpointType::resolve]
+    RelativeLine(i32),
+    /// Break on LINE of the current file, see [BreakpointType::resolve]
+    CurrentFileLine(u32),
     /// Break anytime the given operation occurs
     #[allow(unused)]
     Opcode(miden_core::operations::Operation),
     /// Break when any cycle causes us to push a frame for PROCEDURE on the call stack
     Called(Pattern),
+    /// Break whenever execution moves to a different context, e.g. on a `call`, `syscall` or
+    /// `dyncall`, or on returning from one
+    ContextSwitch,
 }
 impl BreakpointType {
     /// Return true if this breakpoint indicates we should break for `current_op`
@@ -98,9 +140,64 @@ impl BreakpointType {
         }
     }
 
+    /// Return true if this breakpoint stops at `line` of the source file whose URI is `uri`
+    pub fn is_set_on_line(&self, uri: &str, line: u32) -> bool {
+        match self {
+            Self::Line {
+                pattern,
+                line: bp_line,
+            } => *bp_line == line && pattern.matches_path(Path::new(uri)),
+            _ => false,
+        }
+    }
+
+    /// Resolve a breakpoint which is relative to `current`, the source location of the current
+    /// frame, into a [BreakpointType::Line] breakpoint on that file, so that it is unambiguous
+    /// where it will be hit. All other breakpoints are returned unchanged.
+    ///
+    /// Returns an error if the breakpoint is relative, but there is no current source location.
+    pub fn resolve(self, current: Option<&ResolvedLocation>) -> Result<Self, String> {
+        let (current, line) = match self {
+            Self::RelativeLine(offset) => {
+                let current = current.ok_or(NO_CURRENT_LOCATION)?;
+                let line =
+                    current.line.checked_add_signed(offset).filter(|line| *line > 0).ok_or_else(
+                        || {
+                            format!(
+                                "invalid breakpoint expression: line {}{offset:+} is out of range",
+                                current.line
+                            )
+                        },
+                    )?;
+                (current, line)
+            }
+            Self::CurrentFileLine(line) => (current.ok_or(NO_CURRENT_LOCATION)?, line),
+            ty => return Ok(ty),
+        };
+        let uri = current.source_file.deref().content().uri().as_str();
+        let pattern = Pattern::new(&Pattern::escape(uri))
+            .map_err(|err| format!("invalid breakpoint expression: bad pattern: {err}"))?;
+        Ok(Self::Line { pattern, line })
+    }
+
     /// Returns true if this breakpoint is internal to the debugger (i.e. not creatable via :b)
     pub fn is_internal(&self) -> bool {
-        matches!(self, BreakpointType::Next | BreakpointType::Step | BreakpointType::Finish)
+        matches!(
+            self,
+            BreakpointType::Next
+                | BreakpointType::Step
+                | BreakpointType::StepInstructions { .. }
+                | BreakpointType::Finish
+        )
+    }
+
+    /// Returns true if this breakpoint is at a location which execution may pass through, i.e. a
+    /// file, a line, or a procedure, see [Breakpoint::passes]
+    pub fn is_located(&self) -> bool {
+        matches!(
+            self,
+            BreakpointType::File(_) | BreakpointType::Line { .. } | BreakpointType::Called(_)
+        )
     }
 
     /// Returns true if this breakpoint is removed upon being hit
@@ -110,12 +207,17 @@ impl BreakpointType {
             BreakpointType::Next
                 | BreakpointType::Finish
                 | BreakpointType::Step
+                | BreakpointType::StepInstructions { .. }
                 | BreakpointType::StepN(_)
+                | BreakpointType::StepOps { .. }
                 | BreakpointType::StepTo(_)
         )
     }
 }
 
+const NO_CURRENT_LOCATION: &str =
+    "cannot resolve breakpoint relative to the current line: there is no current source location";
+
 impl FromStr for BreakpointType {
     type Err = String;
 
@@ -124,22 +226,44 @@ impl FromStr for BreakpointType {
 
         // b next
         // b finish
-        // b after {n}
+        // b after {n} [cycles|ops]
         // b for {opcode}
         // b at {cycle}
         // b in {procedure}
+        // b context
         // b {file}[:{line}]
+        // b :{line}
+        // b +{n}
+        // b -{n}
         if s == "next" {
             return Ok(BreakpointType::Next);
         }
         if s == "finish" {
             return Ok(BreakpointType::Finish);
         }
+        if s == "context" {
+            return Ok(BreakpointType::ContextSwitch);
+        }
         if let Some(n) = s.strip_prefix("after ") {
+            let (n, ops) = match n.trim().split_once(char::is_whitespace) {
+                Some((n, "cycle" | "cycles")) => (n, false),
+                Some((n, "op" | "ops")) => (n, true),
+                Some((_, unit)) => {
+                    return Err(format!(
+                        "invalid breakpoint expression: expected 'cycles' or 'ops', got '{}'",
+                        unit.trim()
+                    ));
+                }
+                None => (n, false),
+            };
             let n = n.trim().parse::<usize>().map_err(|err| {
-                format!("invalid breakpoint expression: could not parse cycle count: {err}")
+                let unit = if ops { "instruction" } else { "cycle" };
+                format!("invalid breakpoint expression: could not parse {unit} count: {err}")
             })?;
-            return Ok(BreakpointType::StepN(n));
+            return Ok(match ops {
+                true => BreakpointType::StepOps { n, started: 0 },
+                false => BreakpointType::StepN(n),
+            });
         }
         if let Some(_opcode) = s.strip_prefix("for ") {
             todo!()
@@ -155,6 +279,18 @@ impl FromStr for BreakpointType {
                 .map_err(|err| format!("invalid breakpoint expression: bad pattern: {err}"))?;
             return Ok(BreakpointType::Called(pattern));
         }
+        if let Some(line) = s.strip_prefix(':') {
+            let line = line.trim().parse::<u32>().map_err(|err| {
+                format!("invalid breakpoint expression: could not parse line: {err}")
+            })?;
+            return Ok(BreakpointType::CurrentFileLine(line));
+        }
+        if s.starts_with(['+', '-']) {
+            let offset = s.parse::<i32>().map_err(|err| {
+                format!("invalid breakpoint expression: could not parse line offset: {err}")
+            })?;
+            return Ok(BreakpointType::RelativeLine(offset));
+        }
         match s.split_once(':') {
             Some((file, line)) => {
                 let pattern = Pattern::new(file.trim())
@@ -172,3 +308,85 @@ impl FromStr for BreakpointType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use miden_debug_types::{SourceFile, SourceId, SourceLanguage, SourceSpan, Uri};
+
+    use super::*;
+
+    fn location(line: u32) -> ResolvedLocation {
+        let source_file = SourceFile::new(
+            SourceId::default(),
+            SourceLanguage::Masm,
+            Uri::from("src/[lib].masm"),
+            "begin\n    nop\nend\n",
+        );
+        ResolvedLocation {
+            source_file: Arc::new(source_file),
+            line,
+            col: 1,
+            span: SourceSpan::default(),
+        }
+    }
+
+    #[test]
+    fn parse_relative_breakpoints() {
+        assert_eq!("+5".parse::<BreakpointType>(), Ok(BreakpointType::RelativeLine(5)));
+        assert_eq!("-2".parse::<BreakpointType>(), Ok(BreakpointType::RelativeLine(-2)));
+        assert_eq!(":42".parse::<BreakpointType>(), Ok(BreakpointType::CurrentFileLine(42)));
+        assert_eq!(
+            "src/lib.masm:42".parse::<BreakpointType>(),
+            Ok(BreakpointType::Line {
+                pattern: Pattern::new("src/lib.masm").unwrap(),
+                line: 42
+            })
+        );
+        assert!("+x".parse::<BreakpointType>().is_err());
+        assert!(":".parse::<BreakpointType>().is_err());
+    }
+
+    #[test]
+    fn parse_step_breakpoints() {
+        assert_eq!("after 3".parse::<BreakpointType>(), Ok(BreakpointType::StepN(3)));
+        assert_eq!("after 3 cycles".parse::<BreakpointType>(), Ok(BreakpointType::StepN(3)));
+        assert_eq!(
+            "after 3 ops".parse::<BreakpointType>(),
+            Ok(BreakpointType::StepOps { n: 3, started: 0 })
+        );
+        assert!("after 3 lines".parse::<BreakpointType>().is_err());
+        assert!("after x ops".parse::<BreakpointType>().is_err());
+    }
+
+    #[test]
+    fn resolve_relative_breakpoints() {
+        let current = location(10);
+        let file = Pattern::new(&Pattern::escape("src/[lib].masm")).unwrap();
+        assert_eq!(
+            BreakpointType::RelativeLine(5).resolve(Some(&current)),
+            Ok(BreakpointType::Line {
+                pattern: file.clone(),
+                line: 15
+            })
+        );
+        assert_eq!(
+            BreakpointType::RelativeLine(-3).resolve(Some(&current)),
+            Ok(BreakpointType::Line {
+                pattern: file.clone(),
+                line: 7
+            })
+        );
+        assert_eq!(
+            BreakpointType::CurrentFileLine(42).resolve(Some(&current)),
+            Ok(BreakpointType::Line {
+                pattern: file,
+                line: 42
+            })
+        );
+        assert!(BreakpointType::RelativeLine(-10).resolve(Some(&current)).is_err());
+        assert_eq!(BreakpointType::RelativeLine(1).resolve(None).unwrap_err(), NO_CURRENT_LOCATION);
+        assert_eq!(BreakpointType::StepTo(3).resolve(None), Ok(BreakpointType::StepTo(3)));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::Arc,
+};
+
+use miden_debug_types::{Location, SourceManager};
+
+use super::ResolvedLocation;
+use crate::exec::DebugExecutor;
+
+/// The number of cycles spent executing each line of source code, and each instruction.
+///
+/// Each cycle is attributed to the source location of the assembly operation executed in that
+/// cycle. Cycles which have no such location, e.g. those spent on control flow, or on code for
+/// which no source is available, are attributed to no line, and counted separately.
+///
+/// Each cycle is also attributed to the mnemonic of that assembly operation, so that all of the
+/// cycles of an instruction which takes several are attributed to it. Cycles which are not part of
+/// an assembly operation are attributed to the VM operation executed instead, e.g. `end`.
+#[derive(Debug, Default)]
+pub struct CycleMeasurement {
+    lines: BTreeMap<(Arc<str>, u32), LineCycles>,
+    unknown: usize,
+    ops: BTreeMap<String, OpCycles>,
+    /// Locations resolved so far, as resolving a location may require loading its source file
+    resolved: HashMap<Location, Option<(Arc<str>, u32, String)>>,
+}
+
+/// The cycles attributed to a single line of source code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineCycles {
+    /// The URI of the source file
+    pub file: Arc<str>,
+    pub line: u32,
+    /// The text of the line, without surrounding whitespace
+    pub text: String,
+    pub cycles: usize,
+}
+
+/// The cycles attributed to a single instruction, over all of its executions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpCycles {
+    /// The mnemonic of the instruction, e.g. `push`, or the VM operation executed if it is not
+    /// part of one
+    pub name: String,
+    /// The number of times the instruction was executed
+    pub count: usize,
+    pub cycles: usize,
+}
+
+impl OpCycles {
+    /// The average number of cycles taken by each execution of the instruction
+    pub fn cycles_per_execution(&self) -> f64 {
+        self.cycles as f64 / self.count.max(1) as f64
+    }
+}
+
+impl CycleMeasurement {
+    /// Step `executor` until the program terminates, attributing each cycle executed to a line of
+    /// source code.
+    ///
+    /// If execution fails, the measurement covers the cycles executed before the failure.
+    pub fn run(mut executor: DebugExecutor, source_manager: &dyn SourceManager) -> Self {
+        let mut measurement = Self::default();
+        // Consecutive cycles of the same assembly operation are those of a single execution of it
+        let mut previous_asmop = None;
+        while !executor.stopped {
+            if executor.step().is_err() {
+                break;
+            }
+            if executor.stopped {
+                break;
+            }
+            let location = executor.current_asmop.as_ref().and_then(|asmop| asmop.location());
+            measurement.record(location, source_manager);
+            match executor.current_asmop.as_ref() {
+                Some(asmop) => measurement
+                    .record_op(mnemonic(asmop.op()), previous_asmop.as_ref() != Some(asmop)),
+                None => match executor.current_op {
+                    Some(op) => measurement.record_op(mnemonic(&op.to_string()), true),
+                    None => measurement.record_op("<control flow>", true),
+                },
+            }
+            previous_asmop = executor.current_asmop.clone();
+        }
+        measurement
+    }
+
+    /// Attribute a single cycle to the instruction `name`, which starts an execution of it if
+    /// `first` is true
+    pub fn record_op(&mut self, name: &str, first: bool) {
+        let op = self.ops.entry(name.to_string()).or_insert_with(|| OpCycles {
+            name: name.to_string(),
+            count: 0,
+            cycles: 0,
+        });
+        op.cycles += 1;
+        op.count += first as usize;
+    }
+
+    /// Attribute a single cycle to `location`, or to no line if `None`
+    pub fn record(&mut self, location: Option<&Location>, source_manager: &dyn SourceManager) {
+        let resolved = location.and_then(|location| {
+            self.resolved
+                .entry(location.clone())
+                .or_insert_with(|| {
+                    let resolved = ResolvedLocation::resolve(location, source_manager)?;
+                    let text = resolved
+                        .source_file
+                        .as_str()
+                        .lines()
+                        .nth(resolved.line.saturating_sub(1) as usize)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string();
+                    Some((Arc::from(resolved.source_file.uri().as_str()), resolved.line, text))
+                })
+                .clone()
+        });
+        match resolved {
+            Some((file, line, text)) => {
+                self.lines
+                    .entry((file.clone(), line))
+                    .or_insert_with(|| LineCycles {
+                        file,
+                        line,
+                        text,
+                        cycles: 0,
+                    })
+                    .cycles += 1;
+            }
+            None => self.unknown += 1,
+        }
+    }
+
+    /// The total number of cycles measured
+    pub fn total(&self) -> usize {
+        self.lines.values().map(|line| line.cycles).sum::<usize>() + self.unknown
+    }
+
+    /// The number of cycles which could not be attributed to a line of source code
+    pub fn unknown(&self) -> usize {
+        self.unknown
+    }
+
+    /// The number of distinct lines to which cycles were attributed
+    pub fn num_lines(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// The `n` lines to which the most cycles were attributed, in descending order of cycles
+    pub fn hottest(&self, n: usize) -> Vec<&LineCycles> {
+        let mut lines = self.lines.values().collect::<Vec<_>>();
+        lines.sort_by(|a, b| b.cycles.cmp(&a.cycles));
+        lines.truncate(n);
+        lines
+    }
+
+    /// The number of distinct instructions to which cycles were attributed
+    pub fn num_ops(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// The `n` instructions to which the most cycles were attributed, in descending order of
+    /// cycles
+    pub fn hottest_ops(&self, n: usize) -> Vec<&OpCycles> {
+        let mut ops = self.ops.values().collect::<Vec<_>>();
+        ops.sort_by(|a, b| b.cycles.cmp(&a.cycles));
+        ops.truncate(n);
+        ops
+    }
+
+    /// The total cycles attributed to each source file, in descending order of cycles
+    pub fn files(&self) -> Vec<(&Path, usize)> {
+        let mut files = BTreeMap::<&str, usize>::new();
+        for line in self.lines.values() {
+            *files.entry(&line.file).or_default() += line.cycles;
+        }
+        let mut files = files
+            .into_iter()
+            .map(|(file, cycles)| (Path::new(file), cycles))
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| b.1.cmp(&a.1));
+        files
+    }
+}
+
+/// The mnemonic of the instruction `op`, i.e. without its immediate values, e.g. `push` for
+/// `push.1`, or `assert` for the VM operation `assert(0)`
+fn mnemonic(op: &str) -> &str {
+    op.split(['.', '(']).next().unwrap_or(op).trim()
+}
@@ -5,14 +5,20 @@ use std::{
 };
 
 use clap::{Parser, ValueEnum};
-use miden_assembly_syntax::ast::types::{ArrayType, PointerType, Type};
+use miden_assembly_syntax::ast::types::{ArrayType, PointerType, StructType, Type};
 
-use super::NativePtr;
+use super::{NativePtr, Symbols};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReadMemoryExpr {
+    /// The address to read from, unless it is given by `symbol`, in which case it must be resolved
+    /// via [ReadMemoryExpr::resolve] first
     pub addr: NativePtr,
+    /// The name of the symbol at whose address to read, if given as `@NAME`
+    pub symbol: Option<String>,
     pub ty: Type,
+    /// The names of the fields of any struct types in `ty`
+    pub fields: FieldNames,
     pub count: u8,
     pub mode: MemoryMode,
     pub format: FormatType,
@@ -21,17 +27,24 @@ impl FromStr for ReadMemoryExpr {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let argv = s.split_whitespace();
-        let args = Read::parse(argv)?;
+        let args = Read::parse(split_args(s))?;
 
-        let ty = args.ty.unwrap_or_else(|| Type::from(ArrayType::new(Type::Felt, 4)));
-        let addr = match args.mode {
-            MemoryMode::Word => NativePtr::new(args.addr, 0),
-            MemoryMode::Byte => NativePtr::from_ptr(args.addr),
+        let (ty, fields) = args
+            .ty
+            .or(args.type_arg)
+            .unwrap_or_else(|| (Type::from(ArrayType::new(Type::Felt, 4)), FieldNames::default()));
+        let (addr, symbol) = match args.addr {
+            Address::Addr(addr) => match args.mode {
+                MemoryMode::Word => (NativePtr::new(addr, 0), None),
+                MemoryMode::Byte => (NativePtr::from_ptr(addr), None),
+            },
+            Address::Symbol(name) => (NativePtr::new(0, 0), Some(name)),
         };
         Ok(Self {
             addr,
+            symbol,
             ty,
+            fields,
             count: args.count,
             mode: args.mode,
             format: args.format,
@@ -39,20 +52,163 @@ impl FromStr for ReadMemoryExpr {
     }
 }
 
+impl ReadMemoryExpr {
+    /// Resolve the address of the symbol given as `@NAME`, if any, using `symbols`.
+    ///
+    /// Symbols are at byte addresses, so in `word` mode, the symbol must be element-aligned.
+    pub fn resolve(&self, symbols: &Symbols) -> Result<Self, String> {
+        let mut expr = self.clone();
+        if let Some(name) = self.symbol.as_deref() {
+            let addr = symbols.get(name)?;
+            expr.addr = match self.mode {
+                MemoryMode::Byte => NativePtr::from_ptr(addr),
+                MemoryMode::Word if addr % 4 == 0 => NativePtr::new(addr / 4, 0),
+                MemoryMode::Word => {
+                    return Err(format!(
+                        "symbol '@{name}' is at byte address {addr:#x}, which is not \
+                         element-aligned, use `-m bytes` to read it"
+                    ));
+                }
+            };
+        }
+        Ok(expr)
+    }
+}
+
+/// The format of the `examine` command, i.e. the `/NFU` of `x/NFU ADDR`, after that of GDB.
+///
+/// `N` is the number of units to read, `F` their format, one of `x` (hex), `d` (signed decimal),
+/// `u` (unsigned decimal), or `t` (binary), and `U` their size, one of `b` (byte), `h` (halfword,
+/// i.e. 2 bytes), `w` (word, i.e. 4 bytes), or `g` (giant, i.e. 8 bytes). Each part is optional,
+/// and defaults to reading a single 4-byte unit in hex.
+///
+/// Note that a "word" here is that of GDB, i.e. a 32-bit value, which is the size of a field
+/// element in the Rust address space, not a Miden word of four elements.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExamineFormat {
+    pub count: u8,
+    pub format: FormatType,
+    /// Whether units are read as signed integers, i.e. with the `d` format
+    pub signed: bool,
+    /// The size of each unit in bytes
+    pub size: u32,
+}
+impl Default for ExamineFormat {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            format: FormatType::Hex,
+            signed: false,
+            size: 4,
+        }
+    }
+}
+impl ExamineFormat {
+    /// The type of each unit
+    pub fn ty(&self) -> Type {
+        match (self.size, self.signed) {
+            (1, false) => Type::U8,
+            (1, true) => Type::I8,
+            (2, false) => Type::U16,
+            (2, true) => Type::I16,
+            (4, false) => Type::U32,
+            (4, true) => Type::I32,
+            (_, false) => Type::U64,
+            (_, true) => Type::I64,
+        }
+    }
+
+    /// The number of units shown on each line of output, as in GDB
+    pub fn units_per_line(&self) -> usize {
+        match self.size {
+            1 | 2 => 8,
+            4 => 4,
+            _ => 2,
+        }
+    }
+}
+impl FromStr for ExamineFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut spec = Self::default();
+        let letters = s.trim_start_matches(|c: char| c.is_ascii_digit());
+        let count = &s[..(s.len() - letters.len())];
+        if !count.is_empty() {
+            spec.count = match count.parse::<u8>() {
+                Ok(0) => return Err("invalid format: the count must be at least 1".into()),
+                Ok(count) => count,
+                Err(_) => {
+                    return Err(format!("invalid format: the count must be at most {}", u8::MAX));
+                }
+            };
+        }
+        for letter in letters.chars() {
+            match letter {
+                'x' => (spec.format, spec.signed) = (FormatType::Hex, false),
+                'd' => (spec.format, spec.signed) = (FormatType::Decimal, true),
+                'u' => (spec.format, spec.signed) = (FormatType::Decimal, false),
+                't' => (spec.format, spec.signed) = (FormatType::Binary, false),
+                'b' => spec.size = 1,
+                'h' => spec.size = 2,
+                'w' => spec.size = 4,
+                'g' => spec.size = 8,
+                _ => {
+                    return Err(format!(
+                        "invalid format letter '{letter}', expected one of x, d, u, t (format) or \
+                         b, h, w, g (size)"
+                    ));
+                }
+            }
+        }
+        Ok(spec)
+    }
+}
+
+/// The address given to [Read], either a literal address, or the name of a symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Address {
+    Addr(u32),
+    /// The name of a symbol, given as `@NAME`, see [Symbols]
+    Symbol(String),
+}
+impl Default for Address {
+    fn default() -> Self {
+        Self::Addr(0)
+    }
+}
+impl FromStr for Address {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('@') {
+            Some("") => Err("expected symbol name after '@'".into()),
+            Some(name) => Ok(Self::Symbol(name.to_string())),
+            None => parse_address(s).map(Self::Addr),
+        }
+    }
+}
+
 #[derive(Default, Debug, Parser)]
 #[command(name = "read")]
 pub struct Read {
-    /// The memory address to start reading from
-    #[arg(required(true), value_name = "ADDR", value_parser(parse_address))]
-    pub addr: u32,
+    /// The memory address to start reading from, or `@NAME` to read from the address of a symbol
+    #[arg(required(true), value_name = "ADDR", value_parser(Address::from_str))]
+    pub addr: Address,
     /// The type of value to read from ADDR, defaults to 'word'
+    ///
+    /// In addition to primitive types, arrays and structs may be given, e.g. `[u8;4]` or
+    /// `struct{a:u32,b:u64}`.
     #[arg(
         short = 't',
         long = "type",
         value_name = "TYPE",
         value_parser(TypeParser)
     )]
-    pub ty: Option<Type>,
+    pub ty: Option<(Type, FieldNames)>,
+    /// The type of value to read from ADDR, as an alternative to `--type`
+    #[arg(value_name = "TYPE", value_parser(TypeParser), conflicts_with = "ty")]
+    pub type_arg: Option<(Type, FieldNames)>,
     /// The number of values to read
     #[arg(short = 'c', long = "count", value_name = "N", default_value_t = 1)]
     pub count: u8,
@@ -93,11 +249,50 @@ impl Read {
     }
 }
 
+/// Split `s` into arguments at whitespace, other than that within the braces or brackets of a
+/// type, or between `struct` and its `{`, so that types may be spaced out, e.g.
+/// `struct { a: u32, b: [u8; 4] }`
+fn split_args(s: &str) -> Vec<&str> {
+    let mut args = vec![];
+    let mut depth = 0usize;
+    let mut start = None;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(begin) = start {
+                    let arg = &s[begin..i];
+                    if arg == "struct" && s[i..].trim_start().starts_with('{') {
+                        continue;
+                    }
+                    args.push(arg);
+                    start = None;
+                }
+                continue;
+            }
+            _ => (),
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(begin) = start {
+        args.push(&s[begin..]);
+    }
+    args
+}
+
+/// The names of the fields of the struct types contained in a [Type], in field order.
+///
+/// Each entry holds the name of a field, and the names associated with the field's type. The
+/// element type of an array shares the names of the array itself.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FieldNames(pub Vec<(String, FieldNames)>);
+
 #[doc(hidden)]
 #[derive(Clone)]
 struct TypeParser;
 impl clap::builder::TypedValueParser for TypeParser {
-    type Value = Type;
+    type Value = (Type, FieldNames);
 
     fn parse_ref(
         &self,
@@ -109,29 +304,102 @@ impl clap::builder::TypedValueParser for TypeParser {
 
         let value = value.to_str().ok_or_else(|| Error::new(ErrorKind::InvalidUtf8))?;
 
-        Ok(match value {
-            "i1" => Type::I1,
-            "i8" => Type::I8,
-            "i16" => Type::I16,
-            "i32" => Type::I32,
-            "i64" => Type::I64,
-            "i128" => Type::I128,
-            "u8" => Type::U8,
-            "u16" => Type::U16,
-            "u32" => Type::U32,
-            "u64" => Type::U64,
-            "u128" => Type::U128,
-            "felt" => Type::Felt,
-            "word" => Type::from(ArrayType::new(Type::Felt, 4)),
-            "ptr" | "pointer" => Type::from(PointerType::new(Type::U32)),
-            _ => {
-                return Err(Error::raw(
-                    ErrorKind::InvalidValue,
-                    format!("invalid/unsupported type '{value}'"),
-                ));
+        let (ty, fields, rest) =
+            parse_type(value).map_err(|err| Error::raw(ErrorKind::InvalidValue, err))?;
+        if !rest.trim().is_empty() {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("invalid type '{value}': unexpected trailing input '{rest}'"),
+            ));
+        }
+        Ok((ty, fields))
+    }
+}
+
+/// Parse a type expression from the start of `input`, returning the type, the names of any struct
+/// fields it contains, and the remaining unparsed input.
+///
+/// The following grammar is supported:
+///
+/// * Primitives, i.e. `u32`, `felt`, `word`, `ptr`
+/// * Arrays, i.e. `[TYPE;N]`
+/// * Structs, i.e. `struct{NAME:TYPE,..}`, laid out using the default ABI alignment rules
+fn parse_type(input: &str) -> Result<(Type, FieldNames, &str), String> {
+    let input = input.trim_start();
+    if let Some(rest) = input.strip_prefix("struct") {
+        let mut rest = rest
+            .trim_start()
+            .strip_prefix('{')
+            .ok_or_else(|| format!("invalid struct type: expected '{{' at '{rest}'"))?;
+        let mut tys = vec![];
+        let mut names = vec![];
+        loop {
+            rest = rest.trim_start();
+            if let Some(remaining) = rest.strip_prefix('}') {
+                rest = remaining;
+                break;
             }
-        })
+            let (name, remaining) = rest
+                .split_once(':')
+                .ok_or_else(|| format!("invalid struct type: expected 'NAME:TYPE' at '{rest}'"))?;
+            let name = name.trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("invalid struct type: invalid field name '{name}'"));
+            }
+            let (ty, fields, remaining) = parse_type(remaining)?;
+            tys.push(ty);
+            names.push((name.to_string(), fields));
+            rest = remaining.trim_start();
+            if let Some(remaining) = rest.strip_prefix(',') {
+                rest = remaining;
+            } else if !rest.starts_with('}') {
+                return Err(format!("invalid struct type: expected ',' or '}}' at '{rest}'"));
+            }
+        }
+        if tys.len() > u8::MAX as usize {
+            return Err("invalid struct type: too many fields".into());
+        }
+        return Ok((Type::from(StructType::new(tys)), FieldNames(names), rest));
+    }
+
+    if let Some(rest) = input.strip_prefix('[') {
+        let (ty, fields, rest) = parse_type(rest)?;
+        let rest = rest
+            .trim_start()
+            .strip_prefix(';')
+            .ok_or_else(|| format!("invalid array type: expected ';' at '{rest}'"))?;
+        let (len, rest) = rest
+            .split_once(']')
+            .ok_or_else(|| format!("invalid array type: expected ']' at '{rest}'"))?;
+        let len = len
+            .trim()
+            .parse::<usize>()
+            .map_err(|err| format!("invalid array type: invalid length: {err}"))?;
+        return Ok((Type::from(ArrayType::new(ty, len)), fields, rest));
     }
+
+    let end = input
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(input.len());
+    let (name, rest) = input.split_at(end);
+    let ty = match name {
+        "i1" => Type::I1,
+        "i8" => Type::I8,
+        "i16" => Type::I16,
+        "i32" => Type::I32,
+        "i64" => Type::I64,
+        "i128" => Type::I128,
+        "u8" => Type::U8,
+        "u16" => Type::U16,
+        "u32" => Type::U32,
+        "u64" => Type::U64,
+        "u128" => Type::U128,
+        "felt" => Type::Felt,
+        "word" => Type::from(ArrayType::new(Type::Felt, 4)),
+        "ptr" | "pointer" => Type::from(PointerType::new(Type::U32)),
+        _ => return Err(format!("invalid/unsupported type '{input}'")),
+    };
+    Ok((ty, FieldNames::default(), rest))
 }
 
 fn parse_address(s: &str) -> Result<u32, String> {
@@ -263,3 +531,98 @@ impl clap::builder::TypedValueParser for FormatTypeParser {
         value.parse().map_err(|err| Error::raw(ErrorKind::InvalidValue, err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_memory_expr_symbol() {
+        let symbols = Symbols::parse_str("COUNTER = 0x100\nFLAG = 0x103", false).unwrap();
+        let expr = "@COUNTER -t u32".parse::<ReadMemoryExpr>().unwrap();
+        assert_eq!(expr.symbol.as_deref(), Some("COUNTER"));
+        assert_eq!(expr.resolve(&symbols).unwrap().addr, NativePtr::new(0x40, 0));
+
+        let expr = "@FLAG -t u8 -m bytes".parse::<ReadMemoryExpr>().unwrap();
+        assert_eq!(expr.resolve(&symbols).unwrap().addr, NativePtr::from_ptr(0x103));
+        let expr = "@FLAG -t u8".parse::<ReadMemoryExpr>().unwrap();
+        assert!(expr.resolve(&symbols).unwrap_err().contains("not element-aligned"));
+
+        // Literal addresses are unaffected by symbols
+        let expr = "0x100".parse::<ReadMemoryExpr>().unwrap();
+        assert_eq!(expr.resolve(&Symbols::default()).unwrap(), expr);
+        assert!("@ -t u8".parse::<ReadMemoryExpr>().is_err());
+    }
+
+    #[test]
+    fn examine_format() {
+        assert_eq!("".parse::<ExamineFormat>().unwrap(), ExamineFormat::default());
+        let spec = "4xb".parse::<ExamineFormat>().unwrap();
+        assert_eq!((spec.count, spec.format, spec.size), (4, FormatType::Hex, 1));
+        assert_eq!(spec.ty(), Type::U8);
+        assert_eq!(spec.units_per_line(), 8);
+
+        // Letters may be given in either order, and the last of each kind wins
+        let spec = "2gd".parse::<ExamineFormat>().unwrap();
+        assert_eq!(spec, "2dg".parse().unwrap());
+        assert_eq!(spec.ty(), Type::I64);
+        let spec = "xut".parse::<ExamineFormat>().unwrap();
+        assert_eq!((spec.count, spec.format, spec.ty()), (1, FormatType::Binary, Type::U32));
+
+        assert!("0x".parse::<ExamineFormat>().unwrap_err().contains("at least 1"));
+        assert!("256x".parse::<ExamineFormat>().unwrap_err().contains("at most 255"));
+        assert!("4q".parse::<ExamineFormat>().unwrap_err().contains("'q'"));
+        assert!("x4".parse::<ExamineFormat>().unwrap_err().contains("'4'"));
+    }
+
+    #[test]
+    fn read_memory_expr_struct_type() {
+        let expr = "0x100 -m bytes -t struct{a:u32,b:u8,c:[u8;4],d:u64}"
+            .parse::<ReadMemoryExpr>()
+            .unwrap();
+        assert_eq!(expr.addr, NativePtr::from_ptr(0x100));
+
+        let Type::Struct(struct_ty) = &expr.ty else {
+            panic!("expected struct type, got {}", &expr.ty);
+        };
+        let offsets = struct_ty.fields().iter().map(|field| field.offset).collect::<Vec<_>>();
+        // `c` has 1-byte alignment and follows `b` immediately, `d` is padded to 4 bytes
+        assert_eq!(offsets, vec![0, 4, 5, 12]);
+        assert_eq!(expr.ty.size_in_bytes(), 20);
+
+        let names = expr.fields.0.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn read_memory_expr_nested_types() {
+        let expr = "0 -t [struct{x:u16,y:struct{z:i8}};2]".parse::<ReadMemoryExpr>().unwrap();
+        let Type::Array(array_ty) = &expr.ty else {
+            panic!("expected array type, got {}", &expr.ty);
+        };
+        assert_eq!(array_ty.len(), 2);
+        assert!(array_ty.element_type().is_struct());
+        assert_eq!(expr.fields.0[1].0, "y");
+        assert_eq!(expr.fields.0[1].1.0[0].0, "z");
+    }
+
+    #[test]
+    fn read_memory_expr_spaced_types() {
+        let expected = "0x100 -t struct{a:u32,b:[u8;4]} -c 2".parse::<ReadMemoryExpr>().unwrap();
+        let expr = "0x100 -t struct { a: u32, b: [u8; 4] } -c 2".parse::<ReadMemoryExpr>().unwrap();
+        assert_eq!(expr, expected);
+
+        // The type may also be given after the address, without `-t`
+        let expr = "0x100 struct { a: u32, b: [u8; 4] } -c 2".parse::<ReadMemoryExpr>().unwrap();
+        assert_eq!(expr, expected);
+        assert!("0x100 u32 -t u8".parse::<ReadMemoryExpr>().is_err());
+    }
+
+    #[test]
+    fn read_memory_expr_invalid_types() {
+        assert!("0 -t struct{a:u32".parse::<ReadMemoryExpr>().is_err());
+        assert!("0 -t struct{a:u32;b:u8}".parse::<ReadMemoryExpr>().is_err());
+        assert!("0 -t [u8]".parse::<ReadMemoryExpr>().is_err());
+        assert!("0 -t u33".parse::<ReadMemoryExpr>().is_err());
+    }
+}
@@ -1,13 +1,18 @@
 mod breakpoint;
+mod measure;
 mod memory;
 mod native_ptr;
 mod stacktrace;
+mod symbols;
 
 pub use self::{
     breakpoint::{Breakpoint, BreakpointType},
-    memory::{FormatType, MemoryMode, ReadMemoryExpr},
+    measure::{CycleMeasurement, LineCycles, OpCycles},
+    memory::{Address, ExamineFormat, FieldNames, FormatType, MemoryMode, ReadMemoryExpr},
     native_ptr::NativePtr,
     stacktrace::{
-        CallFrame, CallStack, CurrentFrame, OpDetail, ResolvedLocation, StackTrace, StepInfo,
+        Call, CallFrame, CallStack, CurrentFrame, OpDetail, ResolvedLocation, StackTrace, StepInfo,
+        char_range,
     },
+    symbols::Symbols,
 };
@@ -1,3 +1,5 @@
+use crate::exec::{ADDRESSABLE_BYTES, MemoryReadError};
+
 /// This represents a descriptor for a pointer translated from the IR into a form suitable for
 /// referencing data in Miden's linear memory.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -48,6 +50,21 @@ impl NativePtr {
         }
     }
 
+    /// Like [NativePtr::from_ptr], but rejects a null `addr`, and a value of `size_in_bytes` bytes
+    /// at `addr` which would run past the end of the 32-bit address space.
+    ///
+    /// No other checks are made, e.g. that `addr` is within memory used by the program.
+    pub fn from_ptr_checked(addr: u32, size_in_bytes: usize) -> Result<Self, MemoryReadError> {
+        if addr == 0 {
+            return Err(MemoryReadError::NullPointer);
+        }
+        let end = (addr as u64).checked_add(size_in_bytes as u64);
+        if end.is_none_or(|end| end > ADDRESSABLE_BYTES) {
+            return Err(MemoryReadError::OutOfBounds);
+        }
+        Ok(Self::from_ptr(addr))
+    }
+
     /// Returns true if this pointer is aligned to a word boundary
     pub const fn is_word_aligned(&self) -> bool {
         self.offset == 0 && self.addr.is_multiple_of(4)
@@ -10,7 +10,7 @@ use std::{
 
 use miden_core::operations::AssemblyOp;
 use miden_debug_types::{Location, SourceFile, SourceManager, SourceManagerExt, SourceSpan};
-use miden_processor::{ContextId, operation::Operation, trace::RowIndex};
+use miden_processor::{ContextId, Felt, operation::Operation, trace::RowIndex};
 
 use crate::exec::TraceEvent;
 
@@ -19,6 +19,10 @@ pub struct StepInfo<'a> {
     pub asmop: Option<&'a AssemblyOp>,
     pub clk: RowIndex,
     pub ctx: ContextId,
+    /// The operand stack after the step, top first
+    pub stack: &'a [Felt],
+    /// The element addresses of the words of memory written by the step, in `ctx`
+    pub memory_written: &'a [u32],
 }
 
 #[derive(Debug, Clone)]
@@ -28,18 +32,85 @@ struct SpanContext {
 }
 
 pub struct CallStack {
-    trace_events: Rc<RefCell<BTreeMap<RowIndex, TraceEvent>>>,
+    trace_events: Rc<RefCell<BTreeMap<RowIndex, Vec<TraceEvent>>>>,
     contexts: BTreeSet<Rc<str>>,
     frames: Vec<CallFrame>,
     block_stack: Vec<Option<SpanContext>>,
+    /// The calls which have returned, and whose caller was not itself a call in progress
+    calls: Vec<Call>,
+    /// The calls in progress, one for each of [CallStack::frames], outermost first
+    open_calls: Vec<Call>,
 }
+
+/// A call to a procedure, as observed by the [CallStack], along with the calls made by it in turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    procedure: Option<Rc<str>>,
+    /// The cycle at which the procedure was entered
+    pub enter_cycle: RowIndex,
+    /// The cycle at which the procedure returned, unless it has not returned yet
+    pub exit_cycle: Option<RowIndex>,
+    /// The calls made by the procedure, in the order they were made
+    pub children: Vec<Call>,
+}
+
+impl Call {
+    fn new(procedure: Option<Rc<str>>, enter_cycle: RowIndex) -> Self {
+        Self {
+            procedure,
+            enter_cycle,
+            exit_cycle: None,
+            children: vec![],
+        }
+    }
+
+    /// The name of the procedure called, as shown in the call stack, if known
+    pub fn procedure(&self) -> Option<String> {
+        self.procedure.as_deref().map(|name| display_name(name, ""))
+    }
+}
+
 impl CallStack {
-    pub fn new(trace_events: Rc<RefCell<BTreeMap<RowIndex, TraceEvent>>>) -> Self {
+    pub fn new(trace_events: Rc<RefCell<BTreeMap<RowIndex, Vec<TraceEvent>>>>) -> Self {
         Self {
             trace_events,
             contexts: BTreeSet::default(),
             frames: vec![],
             block_stack: vec![],
+            calls: vec![],
+            open_calls: vec![],
+        }
+    }
+
+    /// The calls made so far, as a tree rooted at each of the outermost calls, in the order they
+    /// were made, i.e. the entrypoint of the program, and any procedure entered after the frame of
+    /// the entrypoint was exited, e.g. via a tail call.
+    ///
+    /// Calls are delimited by the frame trace events emitted by the compiler, so procedures which
+    /// do not emit them are attributed to their caller. Calls which are in progress have no exit
+    /// cycle.
+    pub fn call_tree(&self) -> Vec<Call> {
+        let mut calls = self.calls.clone();
+        let mut open_calls = self.open_calls.iter().rev().cloned();
+        if let Some(innermost) = open_calls.next() {
+            let outermost = open_calls.fold(innermost, |callee, mut caller| {
+                caller.children.push(callee);
+                caller
+            });
+            calls.push(outermost);
+        }
+        calls
+    }
+
+    /// Record that the innermost call in progress returned at `clk`
+    fn exit_call(&mut self, clk: RowIndex) {
+        let Some(mut call) = self.open_calls.pop() else {
+            return;
+        };
+        call.exit_cycle = Some(clk);
+        match self.open_calls.last_mut() {
+            Some(caller) => caller.children.push(call),
+            None => self.calls.push(call),
         }
     }
 
@@ -63,6 +134,16 @@ impl CallStack {
         self.frames.as_slice()
     }
 
+    /// The cycles at which a call frame was entered so far, in ascending order
+    pub fn frame_starts(&self) -> Vec<RowIndex> {
+        self.trace_events
+            .borrow()
+            .iter()
+            .filter(|(_, events)| events.contains(&TraceEvent::FrameStart))
+            .map(|(clk, _)| *clk)
+            .collect()
+    }
+
     /// Updates the call stack from `info`
     ///
     /// Returns the call frame exited this cycle, if any
@@ -71,11 +152,18 @@ impl CallStack {
             // Get the current procedure name context, if available
             let procedure = info.asmop.map(|op| self.cache_procedure_name(op.context_name()));
 
-            // Handle trace events for this cycle
-            let event = self.trace_events.borrow().get(&info.clk).copied();
-            log::trace!("handling {op} at cycle {}: {:?}", info.clk, &event);
-            let popped_frame = self.handle_trace_event(event, procedure.as_ref());
-            let is_frame_end = popped_frame.is_some();
+            // Handle trace events for this cycle, in the order they fired
+            let events = self.trace_events.borrow().get(&info.clk).cloned().unwrap_or_default();
+            log::trace!("handling {op} at cycle {}: {:?}", info.clk, &events);
+            let mut popped_frame = None;
+            let mut is_frame_end = false;
+            for event in events {
+                let popped =
+                    self.handle_trace_event(event, procedure.as_ref(), info.clk, info.stack);
+                // The op belongs to any frame entered after the one exited this cycle
+                is_frame_end = popped.is_some();
+                popped_frame = popped.or(popped_frame);
+            }
 
             // These ops we do not record in call frame details
             let ignore = matches!(
@@ -145,6 +233,7 @@ impl CallStack {
             // Do we have a frame? If not, create one
             if self.frames.is_empty() {
                 self.frames.push(CallFrame::new(procedure.clone()));
+                self.open_calls.push(Call::new(procedure.clone(), info.clk));
             }
 
             let current_frame = self.frames.last_mut().unwrap();
@@ -155,6 +244,9 @@ impl CallStack {
                 current_frame.procedure.is_none() && procedure.is_some();
             if procedure_context_updated {
                 current_frame.procedure.clone_from(&procedure);
+                if let Some(call) = self.open_calls.last_mut() {
+                    call.procedure.clone_from(&procedure);
+                }
             }
 
             // Push op into call frame if this is any op other than `nop` or frame setup
@@ -192,26 +284,30 @@ impl CallStack {
 
     fn handle_trace_event(
         &mut self,
-        event: Option<TraceEvent>,
+        event: TraceEvent,
         procedure: Option<&Rc<str>>,
+        clk: RowIndex,
+        stack: &[Felt],
     ) -> Option<CallFrame> {
-        // Do we need to handle any frame events?
-        if let Some(event) = event {
-            match event {
-                TraceEvent::FrameStart => {
-                    // Record the fact that we exec'd a new procedure in the op context
-                    if let Some(current_frame) = self.frames.last_mut() {
-                        current_frame.push_exec(procedure.cloned());
-                    }
-                    // Push a new frame
-                    self.frames.push(CallFrame::new(procedure.cloned()));
-                }
-                TraceEvent::Unknown(code) => log::debug!("unknown trace event: {code}"),
-                TraceEvent::FrameEnd => {
-                    return self.frames.pop();
+        match event {
+            TraceEvent::FrameStart => {
+                // Record the fact that we exec'd a new procedure in the op context
+                if let Some(current_frame) = self.frames.last_mut() {
+                    current_frame.push_exec(procedure.cloned());
                 }
-                _ => (),
+                // Push a new frame, recording the operand stack on entry as its arguments
+                let mut frame = CallFrame::new(procedure.cloned());
+                let num_arguments = stack.len().min(CallFrame::MAX_ARGUMENTS);
+                frame.arguments = Some(stack[..num_arguments].to_vec());
+                self.frames.push(frame);
+                self.open_calls.push(Call::new(procedure.cloned(), clk));
+            }
+            TraceEvent::User(code) => log::debug!("user trace event: {code}"),
+            TraceEvent::FrameEnd => {
+                self.exit_call(clk);
+                return self.frames.pop();
             }
+            _ => (),
         }
         None
     }
@@ -222,25 +318,36 @@ pub struct CallFrame {
     context: VecDeque<OpDetail>,
     display_name: std::cell::OnceCell<Rc<str>>,
     finishing: bool,
+    arguments: Option<Vec<Felt>>,
 }
 impl CallFrame {
+    /// The maximum number of operand stack elements captured as the arguments of a frame
+    pub const MAX_ARGUMENTS: usize = 16;
+
     pub fn new(procedure: Option<Rc<str>>) -> Self {
         Self {
             procedure,
             context: Default::default(),
             display_name: Default::default(),
             finishing: false,
+            arguments: None,
         }
     }
 
+    /// Returns at most `n` elements from the top of the operand stack at the point this frame was
+    /// entered, top first, which approximate the arguments passed to the procedure.
+    ///
+    /// Returns `None` if the entry of this frame was not observed, e.g. for the root frame, or for
+    /// procedures which do not emit frame trace events.
+    pub fn arguments(&self, n: usize) -> Option<&[Felt]> {
+        let arguments = self.arguments.as_deref()?;
+        Some(&arguments[..n.min(arguments.len())])
+    }
+
     pub fn procedure(&self, strip_prefix: &str) -> Option<Rc<str>> {
         self.procedure.as_ref()?;
         let name = self.display_name.get_or_init(|| {
-            let name = self.procedure.as_deref().unwrap();
-            let name = match name.split_once("::") {
-                Some((module, rest)) if module == strip_prefix => demangle(rest),
-                _ => demangle(name),
-            };
+            let name = display_name(self.procedure.as_deref().unwrap(), strip_prefix);
             Rc::from(name.into_boxed_str())
         });
         Some(Rc::clone(name))
@@ -397,24 +504,7 @@ impl OpDetail {
                 location: Some(loc),
                 resolved,
                 ..
-            } => resolved
-                .get_or_init(|| {
-                    let path = Path::new(loc.uri().as_str());
-                    let source_file = if path.exists() {
-                        source_manager.load_file(path).ok()?
-                    } else {
-                        source_manager.get_by_uri(loc.uri())?
-                    };
-                    let span = SourceSpan::new(source_file.id(), loc.start..loc.end);
-                    let file_line_col = source_file.location(span);
-                    Some(ResolvedLocation {
-                        source_file,
-                        line: file_line_col.line.to_u32(),
-                        col: file_line_col.column.to_u32(),
-                        span,
-                    })
-                })
-                .as_ref(),
+            } => resolved.get_or_init(|| ResolvedLocation::resolve(loc, source_manager)).as_ref(),
             _ => None,
         }
     }
@@ -428,6 +518,51 @@ pub struct ResolvedLocation {
     pub col: u32,
     pub span: SourceSpan,
 }
+impl ResolvedLocation {
+    /// Resolve `loc` to a line and column of its source file, loading that file from disk if
+    /// necessary. Returns `None` if the source file is not available.
+    pub fn resolve(loc: &Location, source_manager: &dyn SourceManager) -> Option<Self> {
+        let path = Path::new(loc.uri().as_str());
+        let source_file = if path.exists() {
+            source_manager.load_file(path).ok()?
+        } else {
+            source_manager.get_by_uri(loc.uri())?
+        };
+        // Debug info need not respect character boundaries, so the span is widened to cover the
+        // characters it touches, as columns are counted in characters
+        let range = char_range(source_file.as_str(), loc.start.to_usize()..loc.end.to_usize());
+        let span = SourceSpan::new(source_file.id(), (range.start as u32)..(range.end as u32));
+        let file_line_col = source_file.location(span);
+        Some(ResolvedLocation {
+            source_file,
+            line: file_line_col.line.to_u32(),
+            col: file_line_col.column.to_u32(),
+            span,
+        })
+    }
+}
+
+/// Clamp `range` to the bounds of `text`, widening it as necessary so that it starts and ends on
+/// character boundaries, i.e. it can be used to slice `text`
+pub fn char_range(text: &str, range: core::ops::Range<usize>) -> core::ops::Range<usize> {
+    let mut start = range.start.min(text.len());
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = range.end.clamp(start, text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    start..end
+}
+
+impl PartialEq for ResolvedLocation {
+    fn eq(&self, other: &Self) -> bool {
+        // A location is identified by its span, which includes the id of its source file
+        self.span == other.span && self.line == other.line && self.col == other.col
+    }
+}
+impl Eq for ResolvedLocation {}
 impl fmt::Display for ResolvedLocation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}:{}:{}", self.source_file.uri().as_str(), self.line, self.col)
@@ -471,6 +606,12 @@ impl<'a> StackTrace<'a> {
     pub fn current_frame(&self) -> Option<&CurrentFrame> {
         self.current_frame.as_ref()
     }
+
+    /// The location of the most recent operation of the current frame, if known, i.e. the source
+    /// code to label when reporting a failure
+    pub fn current_location(&self) -> Option<&ResolvedLocation> {
+        self.current_frame().and_then(|frame| frame.location.as_ref())
+    }
 }
 
 impl fmt::Display for StackTrace<'_> {
@@ -533,6 +674,15 @@ impl fmt::Display for StackTrace<'_> {
     }
 }
 
+/// The name of the procedure `name` as shown in the call stack, i.e. demangled, and without the
+/// module `strip_prefix`, if it belongs to it
+fn display_name(name: &str, strip_prefix: &str) -> String {
+    match name.split_once("::") {
+        Some((module, rest)) if module == strip_prefix => demangle(rest),
+        _ => demangle(name),
+    }
+}
+
 fn demangle(name: &str) -> String {
     let mut input = name.as_bytes();
     let mut demangled = Vec::with_capacity(input.len() * 2);
@@ -540,3 +690,125 @@ fn demangle(name: &str) -> String {
         .expect("failed to write demangled identifier");
     String::from_utf8(demangled).expect("demangled identifier contains invalid utf-8")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_arguments_are_captured_on_entry() {
+        let trace_events = Rc::new(RefCell::new(BTreeMap::from([(
+            RowIndex::from(1u32),
+            vec![TraceEvent::FrameStart],
+        )])));
+        let mut callstack = CallStack::new(trace_events);
+        let stack = (1..=20).map(Felt::new).collect::<Vec<_>>();
+        for clk in 0..2u32 {
+            callstack.next(&StepInfo {
+                op: Some(Operation::Noop),
+                asmop: None,
+                clk: RowIndex::from(clk),
+                ctx: ContextId::root(),
+                stack: &stack,
+                memory_written: &[],
+            });
+        }
+
+        let frames = callstack.frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].arguments(4), None);
+        assert_eq!(frames[1].arguments(2), Some(&stack[..2]));
+        assert_eq!(frames[1].arguments(100), Some(&stack[..CallFrame::MAX_ARGUMENTS]));
+    }
+
+    #[test]
+    fn call_tree_of_nested_calls() {
+        let events = [
+            (1, vec![TraceEvent::FrameStart]),
+            (2, vec![TraceEvent::FrameStart]),
+            // A call may be made in the same cycle as the previous one returns
+            (3, vec![TraceEvent::FrameEnd, TraceEvent::FrameStart]),
+            (4, vec![TraceEvent::FrameEnd]),
+            (5, vec![TraceEvent::FrameEnd]),
+            // The frame of the entrypoint is exited, e.g. by a tail call, after which a new one
+            // begins
+            (6, vec![TraceEvent::FrameEnd]),
+        ];
+        let trace_events = Rc::new(RefCell::new(BTreeMap::from_iter(
+            events.map(|(clk, event)| (RowIndex::from(clk as u32), event)),
+        )));
+        let mut callstack = CallStack::new(trace_events);
+        let call = |enter: u32, exit: Option<u32>, children| Call {
+            procedure: None,
+            enter_cycle: RowIndex::from(enter),
+            exit_cycle: exit.map(RowIndex::from),
+            children,
+        };
+        let step = |callstack: &mut CallStack, clk: u32| {
+            callstack.next(&StepInfo {
+                op: Some(Operation::Noop),
+                asmop: None,
+                clk: RowIndex::from(clk),
+                ctx: ContextId::root(),
+                stack: &[],
+                memory_written: &[],
+            });
+        };
+
+        // Calls in progress have not exited yet
+        for clk in 0..=2 {
+            step(&mut callstack, clk);
+        }
+        assert_eq!(
+            callstack.call_tree(),
+            [call(0, None, vec![call(1, None, vec![call(2, None, vec![])])])]
+        );
+
+        for clk in 3..=7 {
+            step(&mut callstack, clk);
+        }
+        assert_eq!(
+            callstack.call_tree(),
+            [
+                call(
+                    0,
+                    Some(6),
+                    vec![call(
+                        1,
+                        Some(5),
+                        vec![call(2, Some(3), vec![]), call(3, Some(4), vec![])]
+                    )]
+                ),
+                call(7, None, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_location_on_char_boundaries() {
+        use miden_debug_types::{ByteIndex, DefaultSourceManager, SourceLanguage, Uri};
+
+        let source_manager = DefaultSourceManager::default();
+        let uri = Uri::from("unicode.masm");
+        // 'é' and '→' are two and three bytes long respectively
+        let source_file = source_manager.load(
+            SourceLanguage::Masm,
+            uri.clone(),
+            "begin\n    push.1 # é → x\nend\n".into(),
+        );
+        let line = "begin\n".len();
+        let e = line + "    push.1 # ".len();
+
+        // A location which starts and ends mid-character covers the characters it touches
+        let loc = Location::new(uri, ByteIndex::from(e as u32 + 1), ByteIndex::from(e as u32 + 4));
+        let resolved = ResolvedLocation::resolve(&loc, &source_manager).unwrap();
+        assert_eq!(resolved.span.into_slice_index(), e..(e + 6));
+        assert_eq!(&source_file.as_str()[resolved.span.into_slice_index()], "é →");
+        assert_eq!((resolved.line, resolved.col), (2, 14));
+
+        assert_eq!(char_range("é →", 1..4), 0..6);
+        assert_eq!(char_range("é →", 2..2), 2..2);
+        assert_eq!(char_range("é →", 4..99), 3..6);
+        assert_eq!(char_range("é →", 9..99), 6..6);
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+/// The addresses of named data, e.g. globals and data segments, in the memory of a program, so
+/// that they can be referred to as `@NAME` rather than by address.
+///
+/// Symbols are read from a custom package section, in TOML or JSON, which maps each name to the
+/// byte address at which it is found:
+///
+/// ```toml
+/// __stack_pointer = 0x100000
+/// GLOBAL_COUNTER = 4096
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Symbols {
+    addrs: BTreeMap<String, u32>,
+}
+
+impl Symbols {
+    /// The name of the custom package section from which symbols are read, if present
+    pub const SECTION: &str = "symbols";
+
+    /// Parse symbols from `content`, which is JSON if `json` is true, and TOML otherwise
+    pub fn parse_str(content: &str, json: bool) -> Result<Self, String> {
+        let addrs = if json {
+            serde_json::from_str::<BTreeMap<String, u32>>(content).map_err(|err| err.to_string())?
+        } else {
+            toml::from_str::<BTreeMap<String, u32>>(content).map_err(|err| err.to_string())?
+        };
+        Ok(Self { addrs })
+    }
+
+    /// Read the symbols embedded in `package`, if it has a [Symbols::SECTION] section.
+    ///
+    /// The section may be either JSON or TOML.
+    pub fn from_package(package: &miden_mast_package::Package) -> Result<Self, String> {
+        let Some(section) =
+            package.sections.iter().find(|section| section.id.as_str() == Self::SECTION)
+        else {
+            return Ok(Self::default());
+        };
+        let content = core::str::from_utf8(&section.data)
+            .map_err(|err| format!("invalid '{}' section: {err}", Self::SECTION))?;
+        Self::parse_str(content, content.trim_start().starts_with('{'))
+            .map_err(|err| format!("invalid '{}' section: {err}", Self::SECTION))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addrs.is_empty()
+    }
+
+    /// Get the byte address of the symbol `name`
+    pub fn get(&self, name: &str) -> Result<u32, String> {
+        if self.is_empty() {
+            return Err(format!(
+                "cannot resolve '@{name}': the program has no symbol table, i.e. no '{}' section",
+                Self::SECTION
+            ));
+        }
+        self.addrs.get(name).copied().ok_or_else(|| format!("no such symbol '@{name}'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbols_from_package_section() {
+        use miden_mast_package::{
+            MastArtifact, Package, PackageKind, PackageManifest, Section, SectionId,
+        };
+
+        let program = miden_assembly::Assembler::default().assemble_program("begin nop end");
+        let mut package = Package {
+            name: "test".into(),
+            version: None,
+            description: None,
+            kind: PackageKind::Executable,
+            mast: MastArtifact::Executable(std::sync::Arc::new(program.unwrap())),
+            manifest: PackageManifest::new([]),
+            sections: vec![],
+        };
+        let symbols = Symbols::from_package(&package).unwrap();
+        let err = symbols.get("COUNTER").unwrap_err();
+        assert!(err.contains("the program has no symbol table"), "unexpected error: {err}");
+
+        let id = SectionId::custom(Symbols::SECTION).unwrap();
+        package.sections.push(Section::new(id, b"COUNTER = 0x1000".to_vec()));
+        let symbols = Symbols::from_package(&package).unwrap();
+        assert_eq!(symbols.get("COUNTER"), Ok(0x1000));
+        assert_eq!(symbols.get("OTHER"), Err("no such symbol '@OTHER'".to_string()));
+
+        let symbols = Symbols::parse_str(r#"{"COUNTER": 16}"#, true).unwrap();
+        assert_eq!(symbols.get("COUNTER"), Ok(16));
+        assert!(Symbols::parse_str("COUNTER = -1", false).is_err());
+    }
+}
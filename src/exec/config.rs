@@ -1,6 +1,10 @@
-use std::{ffi::OsStr, path::Path};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
-use miden_processor::{ExecutionOptions, StackInputs, advice::AdviceInputs};
+use miden_processor::{ExecutionOptions, Felt as RawFelt, StackInputs, advice::AdviceInputs};
 use serde::Deserialize;
 
 use crate::felt::Felt;
@@ -10,6 +14,8 @@ use crate::felt::Felt;
 pub struct ExecutionConfig {
     pub inputs: StackInputs,
     pub advice_inputs: AdviceInputs,
+    /// Words to write to memory of the root context before the program starts, by address
+    pub memory: BTreeMap<u32, miden_core::Word>,
     pub options: ExecutionOptions,
 }
 
@@ -18,7 +24,7 @@ impl TryFrom<ExecutionConfigFile> for ExecutionConfig {
 
     #[inline]
     fn try_from(file: ExecutionConfigFile) -> Result<Self, Self::Error> {
-        Self::from_inputs_file(file)
+        Self::from_inputs_file(file, None)
     }
 }
 
@@ -32,37 +38,190 @@ impl ExecutionConfig {
 
         let file =
             toml::from_str::<ExecutionConfigFile>(&content).map_err(std::io::Error::other)?;
-        Self::from_inputs_file(file).map_err(std::io::Error::other)
+        Self::from_inputs_file(file, path.parent()).map_err(std::io::Error::other)
     }
 
+    /// Parse the contents of an inputs file, resolving relative paths against the current
+    /// working directory
     pub fn parse_str(content: &str) -> Result<Self, String> {
         let file = toml::from_str::<ExecutionConfigFile>(content).map_err(|err| err.to_string())?;
 
-        Self::from_inputs_file(file)
+        Self::from_inputs_file(file, None)
     }
 
-    fn from_inputs_file(file: ExecutionConfigFile) -> Result<Self, String> {
-        let felts: Vec<_> = file.inputs.stack.into_iter().map(|felt| felt.0).collect();
-        let inputs =
-            StackInputs::new(&felts).map_err(|err| format!("invalid value for 'stack': {err}"))?;
+    /// Convert `file` into an [ExecutionConfig], resolving relative paths against `dir`, i.e. the
+    /// directory containing the inputs file, if known
+    fn from_inputs_file(file: ExecutionConfigFile, dir: Option<&Path>) -> Result<Self, String> {
+        let mut builder = Self::builder()
+            .stack(file.inputs.stack.into_iter().map(|felt| felt.0).collect())
+            .advice_stack(file.inputs.advice.stack.into_iter().map(|felt| felt.0).collect())
+            .options(file.options);
+        for entry in file.inputs.advice.map {
+            builder = builder.advice_map_entry(
+                entry.digest.0,
+                entry.values.into_iter().map(|felt| felt.0).collect(),
+            );
+        }
+
+        match file.memory {
+            MemoryInputs::Words(words) => {
+                for entry in words.word {
+                    builder =
+                        builder.memory_word(entry.address, entry.value.map(|felt| felt.0).into());
+                }
+            }
+            MemoryInputs::Regions(regions) => {
+                // Regions need not be word-aligned, so they are written element by element, and
+                // then merged into the words written before the program starts
+                let mut elements = BTreeMap::<u32, RawFelt>::new();
+                for region in regions {
+                    let address = region.addr;
+                    for (addr, felt) in region.load(dir)? {
+                        if elements.insert(addr, felt).is_some() {
+                            return Err(format!(
+                                "invalid memory region at address {address}: it overlaps another \
+                                 region"
+                            ));
+                        }
+                    }
+                }
+                let mut words = BTreeMap::<u32, [RawFelt; 4]>::new();
+                for (addr, felt) in elements {
+                    let word = words.entry(addr & !3).or_insert([RawFelt::ZERO; 4]);
+                    word[(addr % 4) as usize] = felt;
+                }
+                for (addr, word) in words {
+                    builder = builder.memory_word(addr, word.into());
+                }
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Construct an [ExecutionConfig] in code, rather than from an inputs file
+    pub fn builder() -> ExecutionConfigBuilder {
+        ExecutionConfigBuilder::default()
+    }
+}
+
+/// A builder for an [ExecutionConfig], validating the inputs it is given in the same way as an
+/// inputs file, e.g. for use with [crate::Executor::from_config]
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionConfigBuilder {
+    stack: Vec<RawFelt>,
+    advice_stack: Vec<RawFelt>,
+    advice_map: Vec<(miden_core::Word, Vec<RawFelt>)>,
+    memory: Vec<(u32, miden_core::Word)>,
+    options: ExecutionOptions,
+}
+
+impl ExecutionConfigBuilder {
+    /// Set the contents of the operand stack, top is first
+    pub fn stack(mut self, stack: Vec<RawFelt>) -> Self {
+        self.stack = stack;
+        self
+    }
+
+    /// Set the contents of the advice stack, top is last, i.e. the elements are pushed in order
+    pub fn advice_stack(mut self, stack: Vec<RawFelt>) -> Self {
+        self.advice_stack = stack;
+        self
+    }
+
+    /// Add an entry to the advice map, mapping `digest` to `values`
+    pub fn advice_map_entry(mut self, digest: miden_core::Word, values: Vec<RawFelt>) -> Self {
+        self.advice_map.push((digest, values));
+        self
+    }
+
+    /// Write `word` to the root context's memory at the word-aligned `address`, before the program
+    /// starts
+    pub fn memory_word(mut self, address: u32, word: miden_core::Word) -> Self {
+        self.memory.push((address, word));
+        self
+    }
+
+    /// Set the execution options, of which tracing and debugging are always enabled by the
+    /// [crate::Executor]
+    pub fn options(mut self, options: ExecutionOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Validate the inputs, and construct the [ExecutionConfig]
+    pub fn build(self) -> Result<ExecutionConfig, String> {
+        let inputs = StackInputs::new(&self.stack)
+            .map_err(|err| format!("invalid value for 'stack': {err}"))?;
         let advice_inputs = AdviceInputs::default()
-            .with_stack(file.inputs.advice.stack.into_iter().rev().map(|felt| felt.0))
-            .with_map(file.inputs.advice.map.into_iter().map(|entry| {
-                (entry.digest.0, entry.values.into_iter().map(|felt| felt.0).collect::<Vec<_>>())
-            }));
+            .with_stack(self.advice_stack.into_iter().rev())
+            .with_map(self.advice_map);
+
+        let mut memory = BTreeMap::new();
+        for (address, word) in self.memory {
+            if !address.is_multiple_of(4) {
+                return Err(format!(
+                    "invalid memory word at address {address}: address is not word-aligned"
+                ));
+            }
+            if memory.insert(address, word).is_some() {
+                return Err(format!(
+                    "invalid memory word at address {address}: it overlaps another word"
+                ));
+            }
+        }
 
-        Ok(Self {
+        Ok(ExecutionConfig {
             inputs,
             advice_inputs,
-            options: file.options,
+            memory,
+            options: self.options,
         })
     }
 }
 
+impl ExecutionConfig {
+    /// Describe the operand stack and advice inputs, one line per item, as the program sees them.
+    ///
+    /// Both stacks are listed from bottom to top, with each element labeled by its depth, i.e. the
+    /// element at index 0 is on top of the stack.
+    pub fn describe_inputs(&self) -> Vec<String> {
+        use miden_assembly_syntax::DisplayHex;
+
+        let mut lines = vec!["operand stack (bottom to top):".to_string()];
+        let stack = self.inputs.iter().collect::<Vec<_>>();
+        for (index, felt) in stack.iter().enumerate().rev() {
+            lines.push(format!("  [{index}] {}", felt.as_canonical_u64()));
+        }
+
+        let advice = &self.advice_inputs;
+        lines.push(format!("advice stack (bottom to top, {} elements):", advice.stack.len()));
+        for (depth, felt) in advice.stack.iter().enumerate().rev() {
+            lines.push(format!("  [{depth}] {}", felt.as_canonical_u64()));
+        }
+
+        lines.push(format!("advice map ({} entries):", advice.map.len()));
+        for (key, values) in advice.map.iter() {
+            let values = values
+                .iter()
+                .map(|felt| felt.as_canonical_u64().to_string())
+                .collect::<Vec<_>>();
+            lines.push(format!(
+                "  0x{} => [{}]",
+                DisplayHex::new(&key.as_bytes()),
+                values.join(", ")
+            ));
+        }
+        lines
+    }
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(default)]
 struct ExecutionConfigFile {
     inputs: Inputs,
+    #[serde(deserialize_with = "deserialize_memory_inputs")]
+    memory: MemoryInputs,
     #[serde(deserialize_with = "deserialize_execution_options")]
     options: ExecutionOptions,
 }
@@ -85,6 +244,110 @@ struct Advice {
     map: Vec<AdviceMapEntry>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct Memory {
+    /// Words to write to memory before the program starts
+    word: Vec<MemoryWord>,
+}
+
+/// The contents of memory before the program starts, given either as a table of `[[memory.word]]`
+/// entries, or as an array of `[[memory]]` regions
+#[derive(Debug, Clone)]
+enum MemoryInputs {
+    Words(Memory),
+    Regions(Vec<MemoryRegion>),
+}
+
+impl Default for MemoryInputs {
+    fn default() -> Self {
+        Self::Words(Memory::default())
+    }
+}
+
+/// A region of memory to write before the program starts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemoryRegion {
+    /// The address of the first element of the region
+    addr: u32,
+    /// The elements of the region, in address order
+    data: Option<Vec<Felt>>,
+    /// A file containing the region, with four bytes per element in big-endian order, e.g. as
+    /// written by `dump memory`
+    path: Option<PathBuf>,
+    /// The context whose memory is written, which must be the root context
+    #[serde(default)]
+    ctx: u32,
+}
+
+impl MemoryRegion {
+    /// Load the elements of this region, along with their addresses, resolving a relative `path`
+    /// against `dir`
+    fn load(self, dir: Option<&Path>) -> Result<Vec<(u32, RawFelt)>, String> {
+        let address = self.addr;
+        // Memory is written by instructions prepended to the program, before any other context
+        // exists, so only the root context can be written
+        if self.ctx != 0 {
+            return Err(format!(
+                "invalid memory region at address {address}: only the root context (0) can be \
+                 preloaded, not context {}",
+                self.ctx
+            ));
+        }
+        let data = match (self.data, self.path) {
+            (Some(data), None) => data.into_iter().map(|felt| felt.0).collect::<Vec<_>>(),
+            (None, Some(path)) => {
+                let path = match dir {
+                    Some(dir) => dir.join(path),
+                    None => path,
+                };
+                let bytes = std::fs::read(&path).map_err(|err| {
+                    format!(
+                        "invalid memory region at address {address}: failed to read '{}': {err}",
+                        path.display()
+                    )
+                })?;
+                // A partial element at the end of the file is padded with zeros
+                bytes
+                    .chunks(4)
+                    .map(|chunk| {
+                        let mut elem = [0u8; 4];
+                        elem[..chunk.len()].copy_from_slice(chunk);
+                        RawFelt::new(u32::from_be_bytes(elem) as u64)
+                    })
+                    .collect()
+            }
+            _ => {
+                return Err(format!(
+                    "invalid memory region at address {address}: expected exactly one of 'data' \
+                     or 'path'"
+                ));
+            }
+        };
+        data.into_iter()
+            .enumerate()
+            .map(|(i, felt)| {
+                u32::try_from(i)
+                    .ok()
+                    .and_then(|i| address.checked_add(i))
+                    .map(|addr| (addr, felt))
+                    .ok_or_else(|| {
+                        format!("invalid memory region at address {address}: it is out of bounds")
+                    })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MemoryWord {
+    /// The address of the word, which must be word-aligned
+    address: u32,
+    /// The elements of the word, in the order they are stored in memory
+    value: [Felt; 4],
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct AdviceMapEntry {
     digest: Word,
@@ -133,7 +396,7 @@ impl clap::builder::TypedValueParser for ExecutionConfigParser {
             Error::raw(ErrorKind::ValueValidation, format!("invalid inputs file: {err}"))
         })?;
 
-        ExecutionConfig::from_inputs_file(inputs_file).map_err(|err| {
+        ExecutionConfig::from_inputs_file(inputs_file, inputs_path.parent()).map_err(|err| {
             Error::raw(ErrorKind::ValueValidation, format!("invalid inputs file: {err}"))
         })
     }
@@ -177,6 +440,20 @@ where
     })
 }
 
+fn deserialize_memory_inputs<'de, D>(deserializer: D) -> Result<MemoryInputs, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    match toml::Value::deserialize(deserializer)? {
+        value @ toml::Value::Array(_) => {
+            value.try_into().map(MemoryInputs::Regions).map_err(D::Error::custom)
+        }
+        value => value.try_into().map(MemoryInputs::Words).map_err(D::Error::custom),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use miden_processor::Felt as RawFelt;
@@ -281,4 +558,120 @@ mod tests {
         assert_eq!(file.options.max_cycles(), 100000);
         assert_eq!(file.options.expected_cycles(), 2048);
     }
+
+    #[test]
+    fn execution_config_with_memory_words() {
+        let text = toml::to_string_pretty(&toml! {
+            [[memory.word]]
+            address = 8
+            value = [1, 2, 3, 4]
+        })
+        .unwrap();
+
+        let file = ExecutionConfig::parse_str(&text).unwrap();
+        let word = miden_core::Word::new([1, 2, 3, 4].map(RawFelt::new));
+        assert_eq!(file.memory.into_iter().collect::<Vec<_>>(), vec![(8, word)]);
+    }
+
+    #[test]
+    fn execution_config_with_invalid_memory_words() {
+        let text = toml::to_string_pretty(&toml! {
+            [[memory.word]]
+            address = 6
+            value = [1, 2, 3, 4]
+        })
+        .unwrap();
+        assert_eq!(
+            ExecutionConfig::parse_str(&text).unwrap_err(),
+            "invalid memory word at address 6: address is not word-aligned"
+        );
+
+        let text = toml::to_string_pretty(&toml! {
+            [[memory.word]]
+            address = 8
+            value = [1, 2, 3, 4]
+
+            [[memory.word]]
+            address = 8
+            value = [5, 6, 7, 8]
+        })
+        .unwrap();
+        assert_eq!(
+            ExecutionConfig::parse_str(&text).unwrap_err(),
+            "invalid memory word at address 8: it overlaps another word"
+        );
+    }
+
+    #[test]
+    fn execution_config_with_memory_regions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("image.bin"), [0, 0, 0, 7, 0, 0, 1, 0, 0xff]).unwrap();
+        let path = dir.path().join("inputs.toml");
+        std::fs::write(
+            &path,
+            toml::to_string_pretty(&toml! {
+                [[memory]]
+                addr = 6
+                data = [1, 2, 3]
+
+                [[memory]]
+                addr = 16
+                path = "image.bin"
+            })
+            .unwrap(),
+        )
+        .unwrap();
+
+        let file = ExecutionConfig::parse_file(&path).unwrap();
+        let words = [(4, [0, 0, 1, 2]), (8, [3, 0, 0, 0]), (16, [7, 256, 0xff000000, 0])]
+            .map(|(addr, word)| (addr, miden_core::Word::new(word.map(RawFelt::new))));
+        assert_eq!(file.memory.into_iter().collect::<Vec<_>>(), words);
+    }
+
+    #[test]
+    fn execution_config_with_invalid_memory_regions() {
+        let text = toml::to_string_pretty(&toml! {
+            [[memory]]
+            addr = 8
+            data = [1, 2, 3, 4]
+
+            [[memory]]
+            addr = 11
+            data = [5]
+        })
+        .unwrap();
+        assert_eq!(
+            ExecutionConfig::parse_str(&text).unwrap_err(),
+            "invalid memory region at address 11: it overlaps another region"
+        );
+
+        let text = toml::to_string_pretty(&toml! {
+            [[memory]]
+            addr = 8
+            data = [1]
+            ctx = 2
+        })
+        .unwrap();
+        assert_eq!(
+            ExecutionConfig::parse_str(&text).unwrap_err(),
+            "invalid memory region at address 8: only the root context (0) can be preloaded, not \
+             context 2"
+        );
+
+        let text = toml::to_string_pretty(&toml! {
+            [[memory]]
+            addr = 8
+        })
+        .unwrap();
+        assert_eq!(
+            ExecutionConfig::parse_str(&text).unwrap_err(),
+            "invalid memory region at address 8: expected exactly one of 'data' or 'path'"
+        );
+
+        let text = "[[memory]]\naddr = 0xffffffff\ndata = [1, 2]";
+        assert_eq!(
+            ExecutionConfig::parse_str(text).unwrap_err(),
+            "invalid memory region at address 4294967295: it is out of bounds"
+        );
+    }
 }
@@ -0,0 +1,187 @@
+use std::{collections::BTreeMap, ffi::OsStr, path::Path};
+
+use miden_processor::{ExecutionError, operation::OperationError};
+use serde::Deserialize;
+
+/// A dictionary of assertion error codes, used to describe assertion failures which carry only a
+/// numeric code.
+///
+/// Error codes are read from a TOML or JSON file which maps each code, in decimal or hexadecimal,
+/// to a name and optional description:
+///
+/// ```toml
+/// [0x1234]
+/// name = "ERR_NOT_OWNER"
+/// description = "the caller is not the owner of the account"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorCodes {
+    codes: BTreeMap<u64, ErrorCode>,
+}
+
+/// The name and description of a single error code
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ErrorCode {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl ErrorCodes {
+    /// The name of the custom package section from which error codes are read, if present.
+    ///
+    /// The section has the same format as an error codes file.
+    pub const SECTION: &str = "error_codes";
+
+    /// Parse the error codes file at `path`, which is JSON if it has a `.json` extension, and TOML
+    /// otherwise.
+    pub fn parse_file<P>(path: P) -> Result<Self, String>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Self::parse_str(&content, path.extension() == Some(OsStr::new("json")))
+    }
+
+    /// Parse error codes from `content`, which is JSON if `json` is true, and TOML otherwise
+    pub fn parse_str(content: &str, json: bool) -> Result<Self, String> {
+        let entries = if json {
+            serde_json::from_str::<BTreeMap<String, ErrorCode>>(content)
+                .map_err(|err| err.to_string())?
+        } else {
+            toml::from_str::<BTreeMap<String, ErrorCode>>(content).map_err(|err| err.to_string())?
+        };
+
+        let mut codes = BTreeMap::new();
+        for (key, code) in entries {
+            let value = match key.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => key.parse::<u64>(),
+            }
+            .map_err(|_| format!("invalid error code '{key}': expected an integer"))?;
+            if codes.insert(value, code).is_some() {
+                return Err(format!("invalid error code '{key}': it is defined more than once"));
+            }
+        }
+        Ok(Self { codes })
+    }
+
+    /// Read the error codes embedded in `package`, if it has an [ErrorCodes::SECTION] section.
+    ///
+    /// The section may be either JSON or TOML.
+    pub fn from_package(package: &miden_mast_package::Package) -> Result<Self, String> {
+        let Some(section) =
+            package.sections.iter().find(|section| section.id.as_str() == Self::SECTION)
+        else {
+            return Ok(Self::default());
+        };
+        let content = core::str::from_utf8(&section.data)
+            .map_err(|err| format!("invalid '{}' section: {err}", Self::SECTION))?;
+        Self::parse_str(content, content.trim_start().starts_with('{'))
+            .map_err(|err| format!("invalid '{}' section: {err}", Self::SECTION))
+    }
+
+    /// Add the codes of `other`, replacing any codes which are defined by both
+    pub fn extend(&mut self, other: Self) {
+        self.codes.extend(other.codes);
+    }
+
+    pub fn get(&self, code: u64) -> Option<&ErrorCode> {
+        self.codes.get(&code)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Describe `code` as `NAME (0x1234): description`, if known
+    pub fn describe_code(&self, code: u64) -> Option<String> {
+        let entry = self.get(code)?;
+        Some(match entry.description.as_deref() {
+            Some(description) => format!("{} ({code:#x}): {description}", entry.name),
+            None => format!("{} ({code:#x})", entry.name),
+        })
+    }
+
+    /// Render `err`, translating the code of a failed assertion, if known.
+    ///
+    /// Any other error, or an assertion with an unknown code, is rendered as usual.
+    pub fn describe_error(&self, err: &ExecutionError) -> String {
+        if let ExecutionError::OperationError {
+            err: OperationError::FailedAssertion { err_code, .. },
+            ..
+        } = err
+            && let Some(description) = self.describe_code(err_code.as_canonical_u64())
+        {
+            return format!("assertion failed with error {description}");
+        }
+        err.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODES: &str = r#"
+[0x1234]
+name = "ERR_NOT_OWNER"
+description = "the caller is not the owner of the account"
+
+[7]
+name = "ERR_UNNAMED"
+"#;
+
+    #[test]
+    fn error_codes_toml() {
+        let codes = ErrorCodes::parse_str(CODES, false).unwrap();
+        assert_eq!(
+            codes.describe_code(0x1234).as_deref(),
+            Some("ERR_NOT_OWNER (0x1234): the caller is not the owner of the account")
+        );
+        assert_eq!(codes.describe_code(7).as_deref(), Some("ERR_UNNAMED (0x7)"));
+        assert_eq!(codes.describe_code(8), None);
+    }
+
+    #[test]
+    fn error_codes_json() {
+        let json = r#"{"4660": {"name": "ERR_NOT_OWNER"}, "0x10": {"name": "ERR_LIMIT"}}"#;
+        let codes = ErrorCodes::parse_str(json, true).unwrap();
+        assert_eq!(codes.get(0x1234).map(|code| code.name.as_str()), Some("ERR_NOT_OWNER"));
+        assert_eq!(codes.get(16).map(|code| code.name.as_str()), Some("ERR_LIMIT"));
+    }
+
+    #[test]
+    fn error_codes_invalid() {
+        let err = ErrorCodes::parse_str("[owner]\nname = \"ERR\"", false).unwrap_err();
+        assert_eq!(err, "invalid error code 'owner': expected an integer");
+        let err = ErrorCodes::parse_str(r#"{"16": {"name": "A"}, "0x10": {"name": "B"}}"#, true)
+            .unwrap_err();
+        assert!(err.contains("defined more than once"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn error_codes_from_package_section() {
+        use miden_mast_package::{
+            MastArtifact, Package, PackageKind, PackageManifest, Section, SectionId,
+        };
+
+        let program = miden_assembly::Assembler::default().assemble_program("begin nop end");
+        let mut package = Package {
+            name: "test".into(),
+            version: None,
+            description: None,
+            kind: PackageKind::Executable,
+            mast: MastArtifact::Executable(std::sync::Arc::new(program.unwrap())),
+            manifest: PackageManifest::new([]),
+            sections: vec![],
+        };
+        assert!(ErrorCodes::from_package(&package).unwrap().is_empty());
+
+        let id = SectionId::custom(ErrorCodes::SECTION).unwrap();
+        package.sections.push(Section::new(id, CODES.as_bytes().to_vec()));
+        let codes = ErrorCodes::from_package(&package).unwrap();
+        assert_eq!(codes, ErrorCodes::parse_str(CODES, false).unwrap());
+    }
+}
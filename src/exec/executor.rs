@@ -1,14 +1,22 @@
 use std::{
     cell::{Cell, RefCell},
     collections::{BTreeMap, VecDeque},
-    fmt,
+    fmt::{self, Write as _},
     ops::Deref,
     rc::Rc,
     sync::Arc,
 };
 
-use miden_assembly_syntax::{Library, diagnostics::Report};
-use miden_core::program::{Program, StackInputs};
+use miden_assembly_syntax::{
+    Library,
+    diagnostics::{Diagnostic, Report},
+};
+use miden_core::{
+    Word,
+    mast::{BasicBlockNodeBuilder, JoinNodeBuilder, MastForestContributor, MastNodeId},
+    operations::Operation,
+    program::{Program, StackInputs},
+};
 use miden_debug_types::{SourceManager, SourceManagerExt};
 use miden_mast_package::{
     Dependency, DependencyResolver, LocalResolvedDependency, MastArtifact,
@@ -19,8 +27,11 @@ use miden_processor::{
     trace::RowIndex,
 };
 
-use super::{DebugExecutor, DebuggerHost, ExecutionConfig, ExecutionTrace, TraceEvent};
-use crate::{debug::CallStack, felt::FromMidenRepr};
+use super::{DebugExecutor, DebuggerHost, ErrorCodes, ExecutionConfig, ExecutionTrace, TraceEvent};
+use crate::{
+    debug::CallStack,
+    felt::{FromMidenRepr, UnexpectedOutputs},
+};
 
 /// The [Executor] is responsible for executing a program with the Miden VM.
 ///
@@ -31,11 +42,21 @@ use crate::{debug::CallStack, felt::FromMidenRepr};
 pub struct Executor {
     stack: StackInputs,
     advice: AdviceInputs,
+    memory: BTreeMap<u32, Word>,
     options: ExecutionOptions,
     libraries: Vec<Arc<Library>>,
     dependency_resolver: MemDependencyResolverByDigest,
+    error_codes: Arc<ErrorCodes>,
+    /// Whether outputs are decoded with [FromMidenRepr::from_stack_outputs_exact]
+    strict_outputs: bool,
 }
 impl Executor {
+    /// The cycle limit recommended for use with [Executor::execute_into_with_limit] in tests.
+    ///
+    /// This is generous enough for all but the most expensive programs, while still ensuring a
+    /// program which never terminates fails within a few seconds.
+    pub const RECOMMENDED_MAX_CYCLES: u32 = 1 << 20;
+
     /// Construct an executor with the given arguments on the operand stack
     pub fn new(args: Vec<Felt>) -> Self {
         let config = ExecutionConfig {
@@ -53,6 +74,7 @@ impl Executor {
         let ExecutionConfig {
             inputs,
             advice_inputs,
+            memory,
             options,
         } = config;
         let options = options.with_tracing(true).with_debugging(true);
@@ -61,9 +83,12 @@ impl Executor {
         Self {
             stack: inputs,
             advice: advice_inputs,
+            memory,
             options,
             libraries: Default::default(),
             dependency_resolver,
+            error_codes: Default::default(),
+            strict_outputs: false,
         }
     }
 
@@ -126,6 +151,39 @@ impl Executor {
         self
     }
 
+    /// Limit execution to at most `max_cycles` cycles, after which execution fails with
+    /// [ExecutionError::CycleLimitExceeded].
+    ///
+    /// Returns an error if `max_cycles` is outside the range supported by the VM.
+    pub fn with_max_cycles(&mut self, max_cycles: u32) -> Result<&mut Self, Report> {
+        let options = &self.options;
+        self.options = ExecutionOptions::new(
+            Some(max_cycles),
+            options.expected_cycles().min(max_cycles),
+            options.core_trace_fragment_size(),
+            options.enable_tracing(),
+            options.enable_debugging(),
+        )
+        .map_err(|err| Report::msg(format!("invalid cycle limit: {err}")))?;
+        Ok(self)
+    }
+
+    /// Use `error_codes` to describe assertion failures
+    pub fn with_error_codes(&mut self, error_codes: ErrorCodes) -> &mut Self {
+        self.error_codes = Arc::new(error_codes);
+        self
+    }
+
+    /// Require the outputs decoded by [Executor::execute_into] and
+    /// [Executor::execute_into_with_limit] to consist of exactly one value of the requested type,
+    /// rather than ignoring any elements below it.
+    ///
+    /// See [FromMidenRepr::from_stack_outputs_exact] for how this is determined.
+    pub fn with_strict_outputs(&mut self, strict: bool) -> &mut Self {
+        self.strict_outputs = strict;
+        self
+    }
+
     /// Add a [Library] to the execution context
     pub fn with_library(&mut self, lib: Arc<Library>) -> &mut Self {
         self.libraries.push(lib);
@@ -146,20 +204,29 @@ impl Executor {
             host.load_mast_forest(lib.mast_forest().clone());
         }
 
-        let trace_events: Rc<RefCell<BTreeMap<RowIndex, TraceEvent>>> = Rc::new(Default::default());
+        // Several events may fire before the same cycle, e.g. the end of one frame, and the start
+        // of the next, so those of each cycle are kept in the order they fired
+        let trace_events: Rc<RefCell<BTreeMap<RowIndex, Vec<TraceEvent>>>> =
+            Rc::new(Default::default());
         let frame_start_events = Rc::clone(&trace_events);
         host.register_trace_handler(TraceEvent::FrameStart, move |clk, event| {
-            frame_start_events.borrow_mut().insert(clk, event);
+            frame_start_events.borrow_mut().entry(clk).or_default().push(event);
         });
         let frame_end_events = Rc::clone(&trace_events);
         host.register_trace_handler(TraceEvent::FrameEnd, move |clk, event| {
-            frame_end_events.borrow_mut().insert(clk, event);
+            frame_end_events.borrow_mut().entry(clk).or_default().push(event);
+        });
+        let user_trace_events: Rc<RefCell<Vec<(RowIndex, u32)>>> = Rc::new(Default::default());
+        let fired_user_trace_events = Rc::clone(&user_trace_events);
+        host.register_user_trace_handler(move |clk, event| {
+            fired_user_trace_events.borrow_mut().push((clk, event.as_u32()));
         });
         let assertion_events = Rc::clone(&trace_events);
         host.register_assert_failed_tracer(move |clk, event| {
-            assertion_events.borrow_mut().insert(clk, event);
+            assertion_events.borrow_mut().entry(clk).or_default().push(event);
         });
 
+        let program = with_memory_preamble(program, &self.memory);
         let mut processor = FastProcessor::new(self.stack)
             .with_advice(self.advice)
             .with_options(self.options)
@@ -168,7 +235,7 @@ impl Executor {
 
         let root_context = ContextId::root();
         let resume_ctx = processor
-            .get_initial_resume_context(program)
+            .get_initial_resume_context(&program)
             .expect("failed to get initial resume context");
 
         let callstack = CallStack::new(trace_events);
@@ -177,16 +244,23 @@ impl Executor {
             host,
             resume_ctx: Some(resume_ctx),
             current_stack: vec![],
+            previous_stack: vec![],
             current_op: None,
+            current_node: None,
+            memory_written: vec![],
             current_asmop: None,
+            current_asmop_cycle: 0,
             stack_outputs: Default::default(),
             contexts: Default::default(),
             root_context,
             current_context: root_context,
             callstack,
+            user_trace_events,
             recent: VecDeque::with_capacity(5),
             cycle: 0,
             stopped: false,
+            failed: false,
+            error_codes: self.error_codes,
         }
     }
 
@@ -252,15 +326,81 @@ impl Executor {
         executor.into_execution_trace()
     }
 
+    /// Execute the given program until termination, producing a trace.
+    ///
+    /// Unlike [Executor::execute], a failure to execute the program is returned as an
+    /// [ExecutionFailure] describing the state of the program at the point of failure.
+    pub fn try_execute(
+        self,
+        program: &Program,
+        source_manager: Arc<dyn SourceManager>,
+    ) -> Result<ExecutionTrace, Box<ExecutionFailure>> {
+        let mut executor = self.into_debug(program, source_manager.clone());
+        while !executor.stopped {
+            if let Err(err) = executor.step() {
+                return Err(Box::new(ExecutionFailure::new(err, &executor, &*source_manager)));
+            }
+        }
+        Ok(executor.into_execution_trace())
+    }
+
     /// Execute a program, parsing the operand stack outputs as a value of type `T`
     pub fn execute_into<T>(self, program: &Program, source_manager: Arc<dyn SourceManager>) -> T
     where
         T: FromMidenRepr + PartialEq,
     {
+        let strict = self.strict_outputs;
         let out = self.execute(program, source_manager);
+        if strict {
+            return out.parse_result_exact().unwrap_or_else(|err| panic!("invalid result: {err}"));
+        }
         out.parse_result().expect("invalid result")
     }
 
+    /// Execute a program for at most `max_cycles` cycles, parsing the operand stack outputs as a
+    /// value of type `T`.
+    ///
+    /// Unlike [Executor::execute_into], this never panics, and a program which does not terminate
+    /// cannot hang the caller. This makes it the preferred way of executing programs in test
+    /// suites, where [Executor::RECOMMENDED_MAX_CYCLES] is a reasonable default for `max_cycles`.
+    ///
+    /// On failure, the returned [Report] wraps an [ExecuteIntoError], which can be recovered via
+    /// [Report::downcast_ref] to distinguish a program which exceeded `max_cycles` from one which
+    /// failed, or whose outputs could not be decoded.
+    pub fn execute_into_with_limit<T>(
+        mut self,
+        program: &Program,
+        source_manager: Arc<dyn SourceManager>,
+        max_cycles: u32,
+    ) -> Result<T, Report>
+    where
+        T: FromMidenRepr,
+    {
+        self.with_max_cycles(max_cycles)?;
+        let strict = self.strict_outputs;
+        let trace = self.try_execute(program, source_manager).map_err(|failure| {
+            Report::new(match failure.error {
+                ExecutionError::CycleLimitExceeded(max_cycles) => {
+                    ExecuteIntoError::CycleLimitExceeded { max_cycles }
+                }
+                _ => ExecuteIntoError::Failed(failure),
+            })
+        })?;
+        if strict {
+            return trace.parse_result_exact().map_err(|error| {
+                Report::new(ExecuteIntoError::UnexpectedOutputs {
+                    ty: core::any::type_name::<T>(),
+                    error,
+                })
+            });
+        }
+        trace.parse_result().ok_or_else(|| {
+            Report::new(ExecuteIntoError::InvalidResult {
+                ty: core::any::type_name::<T>(),
+            })
+        })
+    }
+
     pub fn dependency_resolver_mut(&mut self) -> &mut MemDependencyResolverByDigest {
         &mut self.dependency_resolver
     }
@@ -273,59 +413,180 @@ impl Executor {
     }
 }
 
+/// Returns `program`, modified so as to write `memory` to the root context before the original
+/// entrypoint of the program is executed.
+///
+/// The VM provides no means of writing memory before execution starts, so instead this prepends a
+/// block which stores each word, and leaves the operand stack as it found it. As a result,
+/// preloading memory adds a handful of cycles per word to the start of execution.
+fn with_memory_preamble(program: &Program, memory: &BTreeMap<u32, Word>) -> Program {
+    if memory.is_empty() {
+        return program.clone();
+    }
+
+    let mut operations = Vec::with_capacity(memory.len() * 10);
+    for (addr, word) in memory {
+        // `MStoreW` writes the element below the address to the lowest address of the word
+        operations.extend(word.iter().rev().copied().map(Operation::Push));
+        operations.push(Operation::Push(Felt::from(*addr)));
+        operations.push(Operation::MStoreW);
+        operations.extend([Operation::Drop; 4]);
+    }
+
+    let mut forest = (**program.mast_forest()).clone();
+    let preamble = BasicBlockNodeBuilder::new(operations, vec![])
+        .add_to_forest(&mut forest)
+        .expect("invalid memory preamble");
+    let entrypoint = JoinNodeBuilder::new([preamble, program.entrypoint()])
+        .add_to_forest(&mut forest)
+        .expect("invalid memory preamble");
+    forest.make_root(entrypoint);
+    Program::with_kernel(Arc::new(forest), entrypoint, program.kernel().clone())
+}
+
+/// The error produced by [Executor::execute_into_with_limit]
+#[derive(Debug, thiserror::Error)]
+pub enum ExecuteIntoError {
+    /// The program did not terminate within the allowed number of cycles
+    #[error("program did not terminate within {max_cycles} cycles")]
+    CycleLimitExceeded { max_cycles: u32 },
+    /// The program failed for some other reason
+    #[error("{}", .0.diagnostic)]
+    Failed(Box<ExecutionFailure>),
+    /// The program terminated, but its outputs could not be decoded as a value of type `ty`
+    #[error("unable to decode the program outputs as a value of type `{ty}`")]
+    InvalidResult { ty: &'static str },
+    /// The program terminated, but its outputs are not exactly one value of type `ty`, see
+    /// [Executor::with_strict_outputs]
+    #[error("unable to decode the program outputs as a value of type `{ty}`: {error}")]
+    UnexpectedOutputs {
+        ty: &'static str,
+        error: UnexpectedOutputs,
+    },
+}
+
+impl Diagnostic for ExecuteIntoError {}
+
+/// Describes the state of a program at the point where its execution failed
+#[derive(Debug)]
+pub struct ExecutionFailure {
+    /// The error which caused execution to fail
+    pub error: ExecutionError,
+    /// The cycle at which execution failed
+    pub cycle: usize,
+    /// The operand stack, as of the most recent instruction which succeeded
+    pub stack: Vec<Felt>,
+    /// The VM operation which failed, if known
+    pub op: Option<Operation>,
+    /// The id of the MAST node containing `op`, if known
+    pub node: Option<MastNodeId>,
+    /// The rendered stack trace at the point of failure
+    pub stacktrace: String,
+    /// The rendered diagnostic for `error`, including the relevant source code, if available
+    pub diagnostic: String,
+}
+
+impl ExecutionFailure {
+    /// Capture the state of `execution_state`, which has just failed with `err`
+    pub fn new(
+        err: ExecutionError,
+        execution_state: &DebugExecutor,
+        source_manager: &dyn SourceManager,
+    ) -> Self {
+        use miden_assembly_syntax::diagnostics::{
+            LabeledSpan, miette::miette, reporting::PrintDiagnostic,
+        };
+
+        let stacktrace =
+            execution_state.callstack.stacktrace(&execution_state.recent, source_manager);
+
+        let diagnostic = if !execution_state.current_stack.is_empty() {
+            let mut labels = vec![];
+            if let Some(span) = stacktrace.current_location().map(|loc| loc.span) {
+                labels.push(LabeledSpan::new_with_span(
+                    None,
+                    span.start().to_usize()..span.end().to_usize(),
+                ));
+            }
+            let report = miette!(
+                labels = labels,
+                "program execution failed at step {step} (cycle {cycle}): {err}",
+                step = execution_state.cycle,
+                cycle = execution_state.cycle,
+                err = execution_state.describe_error(&err, source_manager),
+            );
+            let report = match stacktrace.current_location().map(|loc| loc.source_file.clone()) {
+                Some(source) => report.with_source_code(source),
+                None => report,
+            };
+            PrintDiagnostic::new(report).to_string()
+        } else {
+            format!(
+                "program execution failed at step {step}: {err}",
+                step = execution_state.cycle,
+                err = execution_state.describe_error(&err, source_manager),
+            )
+        };
+
+        Self {
+            error: err,
+            cycle: execution_state.cycle,
+            stack: execution_state.current_stack.clone(),
+            op: execution_state.current_op,
+            node: execution_state.current_node,
+            stacktrace: stacktrace.to_string(),
+            diagnostic,
+        }
+    }
+
+    /// Render the failing operation and its MAST node, and the operand stack as of the most
+    /// recent instruction which succeeded, if known
+    fn last_known_state(&self) -> Option<String> {
+        if self.stack.is_empty() && self.op.is_none() && self.node.is_none() {
+            return None;
+        }
+        let mut state =
+            String::from("\nLast Known State (at most recent instruction which succeeded):\n");
+        if let Some(op) = self.op {
+            writeln!(state, " | Failed Operation: {op}").unwrap();
+        }
+        if let Some(node) = self.node {
+            writeln!(state, " | MAST Node: {node}").unwrap();
+        }
+        if !self.stack.is_empty() {
+            let stack = self.stack.iter().map(|elem| elem.as_canonical_u64());
+            writeln!(state, " | Operand Stack: [{}]", DisplayValues::new(stack)).unwrap();
+        }
+        state.push(' ');
+        Some(state)
+    }
+}
+
+impl fmt::Display for ExecutionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", &self.stacktrace)?;
+        if let Some(state) = self.last_known_state() {
+            writeln!(f, "{state}")?;
+        }
+        write!(f, "{}", &self.diagnostic)
+    }
+}
+
 #[track_caller]
 fn render_execution_error(
     err: ExecutionError,
     execution_state: &DebugExecutor,
     source_manager: &dyn SourceManager,
 ) -> ! {
-    use miden_assembly_syntax::diagnostics::{
-        LabeledSpan, miette::miette, reporting::PrintDiagnostic,
-    };
+    let failure = ExecutionFailure::new(err, execution_state, source_manager);
 
-    let stacktrace = execution_state.callstack.stacktrace(&execution_state.recent, source_manager);
+    eprintln!("{}", &failure.stacktrace);
 
-    eprintln!("{stacktrace}");
-
-    if !execution_state.current_stack.is_empty() {
-        let stack = execution_state.current_stack.iter().map(|elem| elem.as_canonical_u64());
-        let stack = DisplayValues::new(stack);
-        eprintln!(
-            "\nLast Known State (at most recent instruction which succeeded):
- | Operand Stack: [{stack}]
- "
-        );
-
-        let mut labels = vec![];
-        if let Some(span) = stacktrace
-            .current_frame()
-            .and_then(|frame| frame.location.as_ref())
-            .map(|loc| loc.span)
-        {
-            labels.push(LabeledSpan::new_with_span(
-                None,
-                span.start().to_usize()..span.end().to_usize(),
-            ));
-        }
-        let report = miette!(
-            labels = labels,
-            "program execution failed at step {step} (cycle {cycle}): {err}",
-            step = execution_state.cycle,
-            cycle = execution_state.cycle,
-        );
-        let report = match stacktrace
-            .current_frame()
-            .and_then(|frame| frame.location.as_ref())
-            .map(|loc| loc.source_file.clone())
-        {
-            Some(source) => report.with_source_code(source),
-            None => report,
-        };
-
-        panic!("{}", PrintDiagnostic::new(report));
-    } else {
-        panic!("program execution failed at step {step}: {err}", step = execution_state.cycle);
+    if let Some(state) = failure.last_known_state() {
+        eprintln!("{state}");
     }
+
+    panic!("{}", &failure.diagnostic);
 }
 
 /// Render an iterator of `T`, comma-separated
@@ -354,3 +615,313 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use miden_assembly::DefaultSourceManager;
+
+    use super::*;
+    use crate::exec::MemoryReadError;
+
+    fn execute_with_limit<T: FromMidenRepr>(source: &str, max_cycles: u32) -> Result<T, Report> {
+        let program = miden_assembly::Assembler::default().assemble_program(source).unwrap();
+        Executor::new(vec![]).execute_into_with_limit::<T>(
+            &program,
+            Arc::new(DefaultSourceManager::default()),
+            max_cycles,
+        )
+    }
+
+    #[test]
+    fn preloaded_memory_words() {
+        let config = ExecutionConfig::parse_str(
+            "
+[[memory.word]]
+address = 256
+value = [1, 2, 3, 4]
+
+[[memory.word]]
+address = 0x1000
+value = [5, 6, 7, 8]
+",
+        )
+        .unwrap();
+        let program = miden_assembly::Assembler::default()
+            .assemble_program(
+                "begin padw mem_loadw_le.256 padw mem_loadw_le.4096 swapdw dropw dropw end",
+            )
+            .unwrap();
+        let trace = Executor::from_config(config)
+            .try_execute(&program, Arc::new(DefaultSourceManager::default()))
+            .unwrap();
+        let outputs =
+            trace.outputs().iter().map(|felt| felt.as_canonical_u64()).collect::<Vec<_>>();
+        assert_eq!(outputs[..8], [5, 6, 7, 8, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn preloaded_memory_regions() {
+        let config = ExecutionConfig::parse_str(
+            "
+[[memory]]
+addr = 257
+data = [42, 43]
+",
+        )
+        .unwrap();
+        let program = miden_assembly::Assembler::default()
+            .assemble_program("begin mem_load.258 swap drop end")
+            .unwrap();
+        let result = Executor::from_config(config).execute_into_with_limit::<u32>(
+            &program,
+            Arc::new(DefaultSourceManager::default()),
+            Executor::RECOMMENDED_MAX_CYCLES,
+        );
+        assert_eq!(result.unwrap(), 43);
+    }
+
+    #[test]
+    fn execution_config_builder() {
+        use miden_processor::Felt as RawFelt;
+
+        let digest = miden_core::Word::from([RawFelt::new(9); 4]);
+        let options = ExecutionOptions::new(
+            Some(100_000),
+            64,
+            ExecutionOptions::DEFAULT_CORE_TRACE_FRAGMENT_SIZE,
+            false,
+            false,
+        )
+        .unwrap();
+        let config = ExecutionConfig::builder()
+            .stack(vec![RawFelt::new(1), RawFelt::new(2)])
+            .advice_stack(vec![RawFelt::new(20), RawFelt::new(10)])
+            .advice_map_entry(digest, vec![RawFelt::new(3)])
+            .memory_word(256, [5, 6, 7, 8].map(RawFelt::new).into())
+            .options(options)
+            .build()
+            .unwrap();
+        assert_eq!(config.options.max_cycles(), 100_000);
+        assert_eq!(config.advice_inputs.map.get(&digest).map(|values| values.len()), Some(1));
+
+        // 1 + 10 (the top of the advice stack) + 5 (the first element at address 256)
+        let program = miden_assembly::Assembler::default()
+            .assemble_program("begin adv_push.1 add mem_load.256 add end")
+            .unwrap();
+        let result = Executor::from_config(config).execute_into_with_limit::<u32>(
+            &program,
+            Arc::new(DefaultSourceManager::default()),
+            Executor::RECOMMENDED_MAX_CYCLES,
+        );
+        assert_eq!(result.unwrap(), 16);
+
+        // The inputs are validated as those of an inputs file are
+        let err = ExecutionConfig::builder().memory_word(2, Default::default()).build();
+        assert_eq!(
+            err.unwrap_err(),
+            "invalid memory word at address 2: address is not word-aligned"
+        );
+        let err = ExecutionConfig::builder().stack(vec![RawFelt::new(1); 17]).build();
+        assert!(err.unwrap_err().starts_with("invalid value for 'stack'"));
+    }
+
+    #[test]
+    fn read_pairs_from_rust_memory() {
+        // Three (u32, u64) pairs, each occupying three elements, starting at element 256
+        let config = ExecutionConfig::parse_str(
+            "
+[[memory.word]]
+address = 256
+value = [1, 10, 0, 2]
+
+[[memory.word]]
+address = 260
+value = [0xffffffff, 1, 3, 30]
+
+[[memory.word]]
+address = 264
+value = [0, 0, 0, 0]
+",
+        )
+        .unwrap();
+        let program = miden_assembly::Assembler::default().assemble_program("begin nop end");
+        let trace = Executor::from_config(config)
+            .try_execute(&program.unwrap(), Arc::new(DefaultSourceManager::default()))
+            .unwrap();
+        let pairs = trace.read_pairs_from_rust_memory::<u32, u64>(256 * 4, 3).unwrap();
+        assert_eq!(pairs, [(1, 10), (2, 0x1_ffff_ffff), (3, 30)]);
+        assert_eq!(trace.read_pairs_from_rust_memory::<u32, u64>(256 * 4, 0), Some(vec![]));
+    }
+
+    #[test]
+    fn read_from_rust_memory_rejects_invalid_pointers() {
+        let config = ExecutionConfig::parse_str(
+            "
+[[memory.word]]
+address = 256
+value = [7, 0, 0, 0]
+",
+        )
+        .unwrap();
+        let program = miden_assembly::Assembler::default().assemble_program("begin nop end");
+        let trace = Executor::from_config(config)
+            .try_execute(&program.unwrap(), Arc::new(DefaultSourceManager::default()))
+            .unwrap();
+        assert_eq!(trace.try_read_from_rust_memory::<u32>(256 * 4).unwrap(), 7);
+
+        // A value extending beyond the end of the address space is an error, not zeroes
+        let err = trace.try_read_from_rust_memory::<u64>(u32::MAX - 3).unwrap_err();
+        assert!(matches!(err, MemoryReadError::OutOfBounds), "{err}");
+        assert!(trace.try_read_from_rust_memory::<u32>(u32::MAX - 3).is_ok());
+        assert_eq!(trace.read_from_rust_memory::<u64>(u32::MAX - 3), None);
+        let err = trace.try_read_from_rust_memory::<u32>(0).unwrap_err();
+        assert!(matches!(err, MemoryReadError::NullPointer), "{err}");
+        let err = trace.try_read_from_rust_memory::<u32>(256 * 4 + 1).unwrap_err();
+        assert!(matches!(err, MemoryReadError::UnalignedRead), "{err}");
+    }
+
+    #[test]
+    fn execute_into_with_limit_returns_result() {
+        let result = execute_with_limit::<u32>(
+            "begin push.1 push.2 add swap drop end",
+            Executor::RECOMMENDED_MAX_CYCLES,
+        );
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn execute_into_strict_outputs() {
+        // Returns two values, of which decoding a single u32 only accounts for the first
+        let program = miden_assembly::Assembler::default()
+            .assemble_program("begin push.7 push.3 movup.2 drop movup.2 drop end")
+            .unwrap();
+        let execute = |strict: bool| {
+            let mut executor = Executor::new(vec![]);
+            executor.with_strict_outputs(strict);
+            executor.execute_into_with_limit::<u32>(
+                &program,
+                Arc::new(DefaultSourceManager::default()),
+                Executor::RECOMMENDED_MAX_CYCLES,
+            )
+        };
+        assert_eq!(execute(false).unwrap(), 3);
+
+        let err = execute(true).unwrap_err();
+        assert!(
+            matches!(
+                err.downcast_ref::<ExecuteIntoError>(),
+                Some(ExecuteIntoError::UnexpectedOutputs {
+                    error: UnexpectedOutputs::Leftover {
+                        expected: 1,
+                        found: 2
+                    },
+                    ..
+                })
+            ),
+            "unexpected error: {err}"
+        );
+
+        // A u64 accounts for both elements
+        let trace = Executor::new(vec![])
+            .try_execute(&program, Arc::new(DefaultSourceManager::default()))
+            .unwrap();
+        assert_eq!(trace.parse_result_exact::<u64>(), Ok((7 << 32) | 3));
+        let outputs = miden_processor::StackOutputs::new(&[Felt::new(1)]).unwrap();
+        assert_eq!(u32::from_stack_outputs_exact(&outputs), Ok(1));
+        assert_eq!(
+            <[u8; 80]>::from_stack_outputs_exact(&outputs),
+            Err(UnexpectedOutputs::Missing {
+                expected: 20,
+                available: 16
+            })
+        );
+    }
+
+    #[test]
+    fn stack_outputs_as_felts() {
+        // The stack starts out with 16 zeros, so that 7, 10 are left above the first 14 of them
+        let program = miden_assembly::Assembler::default()
+            .assemble_program("begin push.3 push.4 add push.10 swap movup.2 drop movup.2 drop end")
+            .unwrap();
+        let trace = Executor::new(vec![])
+            .try_execute(&program, Arc::new(DefaultSourceManager::default()))
+            .unwrap();
+        let expected = [7, 10, 0].map(Felt::new);
+        assert_eq!(trace.stack_outputs_as_felts(3), expected);
+        assert!(trace.stack_outputs_as_felts(0).is_empty());
+        let all = trace.stack_outputs_as_felts(usize::MAX);
+        assert_eq!(all.len(), 16);
+        assert_eq!(all[..3], expected);
+        assert!(all[3..].iter().all(|felt| *felt == Felt::ZERO));
+    }
+
+    #[test]
+    fn execute_into_with_limit_stops_divergent_programs() {
+        let err =
+            execute_with_limit::<u32>("begin push.1 while.true push.1 end end", 4096).unwrap_err();
+        assert!(
+            matches!(
+                err.downcast_ref::<ExecuteIntoError>(),
+                Some(ExecuteIntoError::CycleLimitExceeded { max_cycles: 4096 })
+            ),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn execute_into_with_limit_reports_failures() {
+        let err = execute_with_limit::<u32>("begin push.0 assert end", 4096).unwrap_err();
+        assert!(
+            matches!(err.downcast_ref::<ExecuteIntoError>(), Some(ExecuteIntoError::Failed(_))),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn execution_failure_includes_failed_operation() {
+        let program =
+            miden_assembly::Assembler::default().assemble_program("begin push.0 assert end");
+        let Err(failure) = Executor::new(vec![])
+            .try_execute(&program.unwrap(), Arc::new(DefaultSourceManager::default()))
+        else {
+            panic!("expected execution to fail");
+        };
+        assert!(
+            matches!(failure.op, Some(Operation::Assert(_))),
+            "unexpected operation: {:?}",
+            failure.op
+        );
+        let node = failure.node.expect("expected the failing node to be known");
+        let rendered = failure.to_string();
+        assert!(
+            rendered.contains(&format!(" | MAST Node: {node}")),
+            "unexpected output: {rendered}"
+        );
+        assert!(
+            rendered.contains(" | Failed Operation: assert"),
+            "unexpected output: {rendered}"
+        );
+    }
+
+    #[test]
+    fn execution_failure_describes_advice_exhaustion() {
+        let program =
+            miden_assembly::Assembler::default().assemble_program("begin adv_push.2 drop drop end");
+        let Err(failure) = Executor::new(vec![])
+            .try_execute(&program.unwrap(), Arc::new(DefaultSourceManager::default()))
+        else {
+            panic!("expected execution to fail");
+        };
+        assert!(
+            matches!(failure.op, Some(Operation::AdvPop)),
+            "unexpected operation: {:?}",
+            failure.op
+        );
+        let rendered = failure.to_string();
+        assert!(
+            rendered.contains("advice stack exhausted at cycle")
+                && rendered.contains("reading for op advpop (`adv_push.2`)"),
+            "unexpected output: {rendered}"
+        );
+    }
+}
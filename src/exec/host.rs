@@ -17,6 +17,7 @@ pub struct DebuggerHost<S: SourceManager + ?Sized> {
     store: MemMastForestStore,
     tracing_callbacks: BTreeMap<u32, Vec<Box<TraceHandler>>>,
     on_assert_failed: Option<Box<TraceHandler>>,
+    on_user_trace: Vec<Box<TraceHandler>>,
     source_manager: Arc<S>,
 }
 impl<S> DebuggerHost<S>
@@ -29,6 +30,7 @@ where
             store: Default::default(),
             tracing_callbacks: Default::default(),
             on_assert_failed: None,
+            on_user_trace: vec![],
             source_manager,
         }
     }
@@ -45,6 +47,16 @@ where
         self.tracing_callbacks.entry(key).or_default().push(Box::new(callback));
     }
 
+    /// Register a handler to be called for every [TraceEvent::User] event, regardless of id.
+    ///
+    /// Handlers registered for a specific id via [Self::register_trace_handler] are called first.
+    pub fn register_user_trace_handler<F>(&mut self, callback: F)
+    where
+        F: FnMut(RowIndex, TraceEvent) + 'static,
+    {
+        self.on_user_trace.push(Box::new(callback));
+    }
+
     /// Register a handler to be called when an assertion in the VM fails
     pub fn register_assert_failed_tracer<F>(&mut self, callback: F)
     where
@@ -101,6 +113,11 @@ where
                 handler(clk, event);
             }
         }
+        if event.is_user() {
+            for handler in self.on_user_trace.iter_mut() {
+                handler(clk, event);
+            }
+        }
         Ok(())
     }
 }
@@ -1,15 +1,18 @@
 mod config;
+mod error_codes;
 mod executor;
 mod host;
 mod state;
 mod trace;
 mod trace_event;
 
+pub(crate) use self::trace::ADDRESSABLE_BYTES;
 pub use self::{
-    config::ExecutionConfig,
-    executor::Executor,
+    config::{ExecutionConfig, ExecutionConfigBuilder},
+    error_codes::{ErrorCode, ErrorCodes},
+    executor::{ExecuteIntoError, ExecutionFailure, Executor},
     host::DebuggerHost,
-    state::DebugExecutor,
-    trace::{ExecutionTrace, TraceHandler},
+    state::{AdviceView, BlockOp, BlockOps, ContextMemory, DebugExecutor, StackProvenance},
+    trace::{ExecutionTrace, MemoryReadError, TraceHandler, TraceSnapshot},
     trace_event::TraceEvent,
 };
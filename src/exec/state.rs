@@ -1,16 +1,29 @@
-use std::collections::{BTreeSet, VecDeque};
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, VecDeque},
+    fmt::Write,
+    ops::RangeInclusive,
+    rc::Rc,
+    sync::Arc,
+};
 
+use miden_assembly::SourceManager;
 use miden_core::{
+    WORD_SIZE, Word,
     mast::{MastNode, MastNodeId},
     operations::AssemblyOp,
 };
 use miden_processor::{
-    ContextId, Continuation, ExecutionError, FastProcessor, Felt, ResumeContext, StackOutputs,
-    operation::Operation, trace::RowIndex,
+    ContextId, Continuation, ExecutionError, FastProcessor, Felt, MIN_STACK_DEPTH, ResumeContext,
+    StackOutputs,
+    advice::{AdviceError, AdviceInputs, AdviceMap},
+    crypto::merkle::MerkleStore,
+    operation::Operation,
+    trace::RowIndex,
 };
 
-use super::{DebuggerHost, ExecutionTrace};
-use crate::debug::{CallFrame, CallStack, StepInfo};
+use super::{DebuggerHost, ErrorCodes, ExecutionTrace};
+use crate::debug::{CallFrame, CallStack, ResolvedLocation, StepInfo};
 
 /// Resolve a future that is expected to complete immediately (synchronous host methods).
 ///
@@ -43,12 +56,24 @@ pub struct DebugExecutor {
     pub resume_ctx: Option<ResumeContext>,
 
     // State from last step (replaces VmState fields)
-    /// The current operand stack state
+    /// The current operand stack state, from the top, including the elements of the overflow
+    /// table of the current context
     pub current_stack: Vec<Felt>,
-    /// The operation that was just executed
+    /// The operand stack as of the cycle before the current one, in the same form as
+    /// `current_stack`, see [DebugExecutor::stack_provenance]
+    pub previous_stack: Vec<Felt>,
+    /// The operation that was just executed, or if execution failed, the operation which failed
     pub current_op: Option<Operation>,
+    /// The MAST node containing `current_op`, if known
+    pub current_node: Option<MastNodeId>,
+    /// The element addresses of the words of memory written by `current_op`, in
+    /// `current_context`, see [memory_effects]
+    pub memory_written: Vec<u32>,
     /// The assembly-level operation info for the current op
     pub current_asmop: Option<AssemblyOp>,
+    /// The index of the current cycle among the cycles of `current_asmop`, i.e. `0` on the first
+    /// cycle of an assembly instruction
+    pub current_asmop_cycle: u8,
 
     /// The final outcome of the program being executed
     pub stack_outputs: StackOutputs,
@@ -60,12 +85,99 @@ pub struct DebugExecutor {
     pub current_context: ContextId,
     /// The current call stack
     pub callstack: CallStack,
+    /// The user-defined trace events which have fired, along with the cycle at which they
+    /// occurred, in the order they occurred. Consumers are expected to drain this as needed.
+    pub user_trace_events: Rc<RefCell<Vec<(RowIndex, u32)>>>,
     /// A sliding window of the last 5 operations successfully executed by the VM
     pub recent: VecDeque<Operation>,
     /// The current clock cycle
     pub cycle: usize,
     /// Whether or not execution has terminated
     pub stopped: bool,
+    /// Whether execution terminated with an error, rather than by the program returning
+    pub failed: bool,
+    /// The error codes used to describe assertion failures
+    pub error_codes: Arc<ErrorCodes>,
+}
+
+/// A view of the contents of the advice provider at the current cycle, see
+/// [DebugExecutor::with_advice]
+pub struct AdviceView<'a> {
+    stack: &'a [Felt],
+    map: &'a AdviceMap,
+    merkle_store: &'a MerkleStore,
+}
+
+impl<'a> AdviceView<'a> {
+    /// The advice stack, ordered from top (index 0) to bottom
+    pub fn stack(&self) -> &'a [Felt] {
+        self.stack
+    }
+
+    /// The number of entries in the advice map
+    pub fn map_len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Iterate over the entries of the advice map, in order of key
+    pub fn map(&self) -> impl Iterator<Item = (&'a Word, &'a [Felt])> {
+        self.map.iter().map(|(key, values)| (key, &values[..]))
+    }
+
+    /// Get the values mapped to `key` in the advice map
+    pub fn get(&self, key: &Word) -> Option<&'a [Felt]> {
+        self.map.get(key).map(|values| &values[..])
+    }
+
+    /// The number of internal nodes in the Merkle store
+    pub fn merkle_nodes(&self) -> usize {
+        self.merkle_store.num_internal_nodes()
+    }
+}
+
+/// The regions of memory which have been written in a single context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMemory {
+    /// The context to which the memory belongs
+    pub context: ContextId,
+    /// Whether `context` is the root context
+    pub is_root: bool,
+    /// The contiguous ranges of element addresses written in `context`, in ascending order
+    pub regions: Vec<RangeInclusive<u32>>,
+}
+
+/// An operation of the basic block being executed, see [DebugExecutor::current_block_ops]
+#[derive(Debug, Clone)]
+pub struct BlockOp {
+    pub op: Operation,
+    /// The assembly instruction from which `op` was emitted, if known
+    pub asmop: Option<AssemblyOp>,
+    /// The number of cycles from the start of the block to the end of `op`, i.e. including the
+    /// `SPAN` which begins the block, and the `RESPAN` which begins each batch after the first
+    pub cycles: usize,
+}
+
+/// The operations of the basic block being executed, see [DebugExecutor::current_block_ops]
+#[derive(Debug, Clone)]
+pub struct BlockOps {
+    /// The basic block node
+    pub node: MastNodeId,
+    /// The operations of the block, in the order they are executed
+    pub ops: Vec<BlockOp>,
+    /// The index in `ops` of the operation about to execute, if it belongs to this block
+    pub next: Option<usize>,
+}
+
+/// Where an element of the operand stack came from, relative to the cycle before the current one,
+/// see [DebugExecutor::stack_provenance]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StackProvenance {
+    /// The element was pushed by the most recent operation
+    Pushed,
+    /// The element replaced a different value at the same position
+    Changed,
+    /// The element was already on the stack, although possibly at a different depth
+    Unchanged,
 }
 
 /// Extract the current operation and assembly info from the continuation stack
@@ -108,7 +220,208 @@ fn extract_current_op(
     (None, None, None)
 }
 
+/// The element addresses of the words of memory written by `op`, given `stack`, the operand stack
+/// before it executes, from the top.
+///
+/// Words are identified by the address of their first element, so an element written on its own,
+/// i.e. via `MStore`, marks the word containing it as written.
+fn memory_effects(op: Option<Operation>, stack: &[Felt]) -> Vec<u32> {
+    let word_at = |index: usize| {
+        let addr = u32::try_from(stack.get(index)?.as_canonical_u64()).ok()?;
+        Some(addr & !(WORD_SIZE as u32 - 1))
+    };
+    let two_words_at = |index: usize| {
+        word_at(index)
+            .into_iter()
+            .flat_map(|addr| [Some(addr), addr.checked_add(WORD_SIZE as u32)])
+            .flatten()
+            .collect()
+    };
+    match op {
+        Some(Operation::MStore | Operation::MStoreW) => word_at(0).into_iter().collect(),
+        Some(Operation::Pipe) => two_words_at(12),
+        Some(Operation::CryptoStream) => two_words_at(13),
+        _ => vec![],
+    }
+}
+
+/// See [DebugExecutor::stack_provenance]
+fn stack_provenance(prev: &[Felt], next: &[Felt]) -> Vec<StackProvenance> {
+    if prev.is_empty() {
+        return vec![StackProvenance::Unchanged; next.len()];
+    }
+    // The number of elements pushed, or if negative, the number consumed
+    let classify = |shift: isize| {
+        next.iter()
+            .enumerate()
+            .map(|(index, felt)| match index.checked_add_signed(-shift) {
+                None => StackProvenance::Pushed,
+                Some(index) if prev.get(index) == Some(felt) => StackProvenance::Unchanged,
+                Some(_) => StackProvenance::Changed,
+            })
+            .collect::<Vec<_>>()
+    };
+    let shift = next.len() as isize - prev.len() as isize;
+    if shift != 0 || next.len() > MIN_STACK_DEPTH {
+        return classify(shift);
+    }
+    let unchanged = |provenance: &[StackProvenance]| {
+        provenance.iter().filter(|p| **p == StackProvenance::Unchanged).count()
+    };
+    [0, -1]
+        .into_iter()
+        .map(classify)
+        .reduce(|best, next| {
+            if unchanged(&next) > unchanged(&best) {
+                next
+            } else {
+                best
+            }
+        })
+        .unwrap()
+}
+
+/// Iterate over every word of the memory of `processor` in `contexts` which has been written, as
+/// `(context, addr, word)`, in order of context, and then of address
+pub(super) fn written_words(
+    processor: &FastProcessor,
+    contexts: BTreeSet<ContextId>,
+) -> impl Iterator<Item = (ContextId, u32, Word)> + '_ {
+    let memory = processor.memory();
+    contexts.into_iter().flat_map(move |context| {
+        let mut elements = memory.get_memory_state(context);
+        elements.sort_unstable_by_key(|(addr, _)| u32::from(*addr));
+        elements
+            .chunks_exact(WORD_SIZE)
+            .map(|word| {
+                let addr = u32::from(word[0].0);
+                (context, addr, Word::new(core::array::from_fn(|i| word[i].1)))
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
 impl DebugExecutor {
+    /// Call `f` with a view of the current state of the advice provider, returning its result.
+    ///
+    /// The provider only gives up its map and Merkle store by value, so it is taken apart for the
+    /// duration of the call, and put back together afterwards, rather than copied.
+    pub fn with_advice<R>(&mut self, f: impl FnOnce(&AdviceView<'_>) -> R) -> R {
+        let mut state = self.processor.state();
+        let provider = core::mem::take(state.advice_provider_mut());
+        let (stack, map, store, precompile_requests) = provider.into_parts();
+        let result = f(&AdviceView {
+            stack: &stack,
+            map: &map,
+            merkle_store: &store,
+        });
+        let provider = state.advice_provider_mut();
+        *provider = AdviceInputs { stack, map, store }.into();
+        provider.extend_precompile_requests(precompile_requests);
+        result
+    }
+
+    /// Iterate over every word of memory which has been written so far, as `(context, addr,
+    /// word)`, where `addr` is the element address of the first element of `word`.
+    ///
+    /// Words are visited in order of context, and then of address. Since the executor cannot
+    /// advance while the iterator borrows it, all of the words are those as of the current cycle.
+    /// Words which have only been read, and never written, are omitted.
+    pub fn memory_words(&self) -> impl Iterator<Item = (ContextId, u32, Word)> + '_ {
+        let contexts = core::iter::once(self.root_context).chain(self.contexts.iter().copied());
+        written_words(&self.processor, contexts.collect())
+    }
+
+    /// Compute the regions of memory written so far, grouped by context.
+    ///
+    /// Contexts in which no memory has been written are omitted.
+    pub fn memory_map(&self) -> Vec<ContextMemory> {
+        let mut map = Vec::<ContextMemory>::new();
+        for (context, addr, _) in self.memory_words() {
+            if map.last().is_none_or(|memory| memory.context != context) {
+                map.push(ContextMemory {
+                    context,
+                    is_root: context == self.root_context,
+                    regions: vec![],
+                });
+            }
+            let regions = &mut map.last_mut().unwrap().regions;
+            let end = addr + (WORD_SIZE as u32 - 1);
+            match regions.last_mut() {
+                Some(region) if region.end().checked_add(1) == Some(addr) => {
+                    *region = *region.start()..=end;
+                }
+                _ => regions.push(addr..=end),
+            }
+        }
+        map
+    }
+
+    /// The operations of the basic block containing the operation about to execute, or if that
+    /// is not part of a basic block, e.g. it is the `END` of one, the block of the operation which
+    /// was executed last.
+    ///
+    /// Returns `None` if the program has terminated, or no basic block has been executed yet.
+    pub fn current_block_ops(&self) -> Option<BlockOps> {
+        let resume_ctx = self.resume_ctx.as_ref()?;
+        let forest = resume_ctx.current_forest();
+        let (next, node) = match extract_current_op(resume_ctx) {
+            (_, Some(node), Some(index)) => (Some(index), node),
+            _ => (None, self.current_node?),
+        };
+        let MastNode::Block(block) = forest.get_node_by_id(node)? else {
+            return None;
+        };
+
+        let mut ops = Vec::<BlockOp>::new();
+        let mut cycles = 1;
+        for (batch_index, batch) in block.op_batches().iter().enumerate() {
+            if batch_index > 0 {
+                cycles += 1;
+            }
+            for op in batch.ops() {
+                cycles += 1;
+                ops.push(BlockOp {
+                    op: *op,
+                    asmop: forest.get_assembly_op(node, Some(ops.len())).cloned(),
+                    cycles,
+                });
+            }
+        }
+        Some(BlockOps { node, ops, next })
+    }
+
+    /// Classify each element of `current_stack` by where it came from, relative to
+    /// `previous_stack`, i.e. by the effect of the most recent operation.
+    ///
+    /// Elements are matched up by the change in the depth of the stack, i.e. if an operation grew
+    /// the stack by one element, the element on top is [StackProvenance::Pushed], and each of
+    /// the others is compared with the element one position above it in `previous_stack`. At the
+    /// minimum depth, operations which consume elements do not change the depth, as the stack is
+    /// padded with zeros, so in that case the elements are matched up either as they are, or
+    /// shifted by one consumed element, whichever matches the most of them.
+    pub fn stack_provenance(&self) -> Vec<StackProvenance> {
+        stack_provenance(&self.previous_stack, &self.current_stack)
+    }
+
+    /// The top [MIN_STACK_DEPTH] elements of the operand stack, from the top
+    pub fn stack_top(&self) -> &[Felt] {
+        &self.current_stack[..self.current_stack.len().min(MIN_STACK_DEPTH)]
+    }
+
+    /// The elements of the operand stack below the top [MIN_STACK_DEPTH], i.e. those held in the
+    /// overflow table of the current context, from the top.
+    ///
+    /// The overflow elements of callers in other contexts are not visible until they return.
+    pub fn stack_overflow(&self) -> &[Felt] {
+        self.current_stack.get(MIN_STACK_DEPTH..).unwrap_or_default()
+    }
+
+    /// Read the element at `addr` in `ctx`, as of the current cycle
+    pub fn read_memory_element_in_context(&self, addr: u32, ctx: ContextId) -> Option<Felt> {
+        self.processor.memory().read_element(ctx, Felt::new(addr as u64)).ok()
+    }
+
     /// Advance the program state by one cycle.
     ///
     /// If the program has already reached its termination state, it returns the same result
@@ -132,6 +445,7 @@ impl DebugExecutor {
         let (op, node_id, op_idx) = extract_current_op(&resume_ctx);
         let asmop = node_id
             .and_then(|nid| resume_ctx.current_forest().get_assembly_op(nid, op_idx).cloned());
+        let memory_written = memory_effects(op, &self.current_stack);
 
         // Execute one step
         match poll_immediately(self.processor.step(&mut self.host, resume_ctx)) {
@@ -142,7 +456,8 @@ impl DebugExecutor {
                 // Query processor state
                 let state = self.processor.state();
                 let ctx = state.ctx();
-                self.current_stack = state.get_stack_state();
+                self.previous_stack =
+                    core::mem::replace(&mut self.current_stack, state.get_stack_state());
 
                 if self.current_context != ctx {
                     self.contexts.insert(ctx);
@@ -151,6 +466,20 @@ impl DebugExecutor {
 
                 // Track operation
                 self.current_op = op;
+                self.current_node = node_id;
+                self.memory_written = memory_written;
+                // Padding `noop`s which follow an instruction are attributed to it, but are not
+                // counted among its cycles, see [DebugExecutor::completed_instruction]
+                let is_padding = matches!(op, Some(Operation::Noop));
+                self.current_asmop_cycle = match (self.current_asmop.as_ref(), asmop.as_ref()) {
+                    (Some(prev), Some(next))
+                        if prev == next
+                            && (is_padding || self.current_asmop_cycle + 1 < next.num_cycles()) =>
+                    {
+                        self.current_asmop_cycle.saturating_add(1)
+                    }
+                    _ => 0,
+                };
                 self.current_asmop = asmop.clone();
 
                 if let Some(op) = op {
@@ -164,8 +493,12 @@ impl DebugExecutor {
                 let step_info = StepInfo {
                     op,
                     asmop: self.current_asmop.as_ref(),
-                    clk: RowIndex::from(self.cycle as u32),
+                    // The cycle at which `op` was executed, i.e. that at which the trace events
+                    // which precede it fired, rather than the cycle which follows it
+                    clk: RowIndex::from(self.cycle as u32 - 1),
                     ctx: self.current_context,
+                    stack: &self.current_stack,
+                    memory_written: &self.memory_written,
                 };
                 let exited = self.callstack.next(&step_info);
 
@@ -174,8 +507,10 @@ impl DebugExecutor {
             Ok(None) => {
                 // Program completed
                 self.stopped = true;
+                self.memory_written.clear();
                 let state = self.processor.state();
-                self.current_stack = state.get_stack_state();
+                self.previous_stack =
+                    core::mem::replace(&mut self.current_stack, state.get_stack_state());
 
                 // Capture the final stack as StackOutputs (truncate to 16 elements)
                 let len = self.current_stack.len().min(16);
@@ -185,18 +520,158 @@ impl DebugExecutor {
             }
             Err(err) => {
                 self.stopped = true;
+                self.failed = true;
+                // Record the operation which failed, as it is often the only actionable information
+                // when no source code is available
+                self.current_op = op;
+                self.current_node = node_id;
+                self.current_asmop = asmop;
+                self.memory_written.clear();
                 Err(err)
             }
         }
     }
 
+    /// Advance the program state until `cycle` is reached, or execution terminates, whichever
+    /// comes first.
+    ///
+    /// Execution cannot go backwards, so if `cycle` has already been reached, this does nothing.
+    pub fn run_to_cycle(&mut self, cycle: usize) -> Result<(), ExecutionError> {
+        while self.cycle < cycle && !self.stopped {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if the current cycle is the last cycle of an assembly instruction, i.e. the
+    /// instruction has been executed in full
+    pub fn completed_instruction(&self) -> bool {
+        self.current_asmop
+            .as_ref()
+            .is_some_and(|asmop| self.current_asmop_cycle + 1 == asmop.num_cycles())
+    }
+
+    /// Render `err`, with which execution failed, in terms of the program where possible, i.e.
+    /// describing where the advice stack was exhausted, or translating assertion error codes
+    pub fn describe_error(
+        &self,
+        err: &ExecutionError,
+        source_manager: &dyn SourceManager,
+    ) -> String {
+        self.describe_advice_exhaustion(err, source_manager)
+            .unwrap_or_else(|| self.error_codes.describe_error(err))
+    }
+
+    /// If `err` was raised because the program read more from the advice stack than it held,
+    /// describe the operation which did so, and where it is in the source code, if known.
+    ///
+    /// The VM reports this only as a failed read, which is opaque when it happens deep within a
+    /// library procedure.
+    fn describe_advice_exhaustion(
+        &self,
+        err: &ExecutionError,
+        source_manager: &dyn SourceManager,
+    ) -> Option<String> {
+        let ExecutionError::AdviceError {
+            err: AdviceError::StackReadFailed,
+            ..
+        } = err
+        else {
+            return None;
+        };
+
+        let mut description = format!("advice stack exhausted at cycle {}", self.cycle);
+        if let Some(op) = self.current_op {
+            write!(description, " reading for op {op}").unwrap();
+        }
+        if let Some(asmop) = self.current_asmop.as_ref() {
+            write!(description, " (`{}`)", asmop.op()).unwrap();
+            if let Some(loc) =
+                asmop.location().and_then(|loc| ResolvedLocation::resolve(loc, source_manager))
+            {
+                write!(description, " at {loc}").unwrap();
+            }
+        }
+        Some(description)
+    }
+
     /// Consume the [DebugExecutor], converting it into an [ExecutionTrace] at the current cycle.
     pub fn into_execution_trace(self) -> ExecutionTrace {
+        let mut contexts = self.contexts;
+        contexts.insert(self.root_context);
         ExecutionTrace {
             root_context: self.root_context,
+            contexts,
+            frame_starts: self.callstack.frame_starts(),
+            failed: self.failed,
             last_cycle: RowIndex::from(self.cycle as u32),
             processor: self.processor,
             outputs: self.stack_outputs,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felts(values: &[u64]) -> Vec<Felt> {
+        values.iter().copied().map(Felt::new).collect()
+    }
+
+    fn padded(values: &[u64]) -> Vec<Felt> {
+        let mut stack = felts(values);
+        stack.resize(stack.len().max(MIN_STACK_DEPTH), Felt::ZERO);
+        stack
+    }
+
+    #[test]
+    fn stack_provenance_by_depth() {
+        use StackProvenance::*;
+
+        // Pushing grows the stack, so the element on top is new
+        let mut next = padded(&[1, 2]);
+        next.insert(0, Felt::new(3));
+        let provenance = stack_provenance(&padded(&[1, 2]), &next);
+        assert_eq!(provenance.len(), MIN_STACK_DEPTH + 1);
+        assert_eq!(provenance[..3], [Pushed, Unchanged, Unchanged]);
+        assert!(provenance[3..].iter().all(|p| *p == Unchanged));
+
+        // At the minimum depth, consuming elements pads the stack rather than shrinking it
+        let provenance = stack_provenance(&padded(&[1, 2, 3]), &padded(&[3, 3]));
+        assert_eq!(provenance.len(), MIN_STACK_DEPTH);
+        assert_eq!(provenance[..3], [Changed, Unchanged, Unchanged]);
+
+        // Elements which change in place, e.g. swapped, are changed
+        let provenance = stack_provenance(&padded(&[1, 2, 3]), &padded(&[2, 1, 3]));
+        assert_eq!(provenance[..3], [Changed, Changed, Unchanged]);
+
+        // Above the minimum depth, the change in depth is exact
+        let prev = padded(&[1; MIN_STACK_DEPTH + 1]);
+        let provenance = stack_provenance(&prev, &padded(&[2; MIN_STACK_DEPTH]));
+        assert!(provenance.iter().all(|p| *p == Changed));
+
+        assert_eq!(stack_provenance(&[], &felts(&[1])), [Unchanged]);
+    }
+
+    #[test]
+    fn memory_effects_of_operations() {
+        // Storing an element marks the word containing it as written
+        assert_eq!(memory_effects(Some(Operation::MStore), &felts(&[6, 1])), [4]);
+        assert_eq!(memory_effects(Some(Operation::MStoreW), &felts(&[8, 1, 2, 3, 4])), [8]);
+
+        // Streaming operations write two words, at the address held deeper in the stack
+        let mut stack = padded(&[]);
+        stack[12] = Felt::new(16);
+        stack[13] = Felt::new(32);
+        assert_eq!(memory_effects(Some(Operation::Pipe), &stack), [16, 20]);
+        assert_eq!(memory_effects(Some(Operation::CryptoStream), &stack), [32, 36]);
+
+        // Reads, and writes to invalid addresses, which fail, write nothing
+        assert!(memory_effects(Some(Operation::MLoadW), &felts(&[8])).is_empty());
+        assert!(memory_effects(Some(Operation::MStream), &stack).is_empty());
+        assert!(memory_effects(Some(Operation::MStore), &felts(&[1 << 32, 1])).is_empty());
+        assert!(memory_effects(Some(Operation::MStore), &[]).is_empty());
+        assert!(memory_effects(None, &stack).is_empty());
+    }
+}
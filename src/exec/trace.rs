@@ -1,9 +1,18 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+    path::Path,
+};
+
 use miden_core::Word;
 use miden_processor::{ContextId, FastProcessor, Felt, StackOutputs, trace::RowIndex};
 use smallvec::SmallVec;
 
-use super::TraceEvent;
-use crate::{debug::NativePtr, felt::FromMidenRepr};
+use super::{TraceEvent, state::written_words};
+use crate::{
+    debug::NativePtr,
+    felt::{FromMidenRepr, UnexpectedOutputs},
+};
 
 /// A callback to be executed when a [TraceEvent] occurs at a given clock cycle
 pub type TraceHandler = dyn FnMut(RowIndex, TraceEvent);
@@ -15,16 +24,34 @@ pub enum MemoryReadError {
     OutOfBounds,
     #[error("unaligned reads are not supported yet")]
     UnalignedRead,
+    #[error("attempted to read through a null pointer")]
+    NullPointer,
 }
 
+/// The number of bytes addressable in Rust's address space, i.e. the size of the region of Miden
+/// memory which can be addressed by a byte address
+pub(crate) const ADDRESSABLE_BYTES: u64 = 1 << 32;
+
+/// The most bytes read at a time by [ExecutionTrace::read_memory_region_chunked]
+const MEMORY_CHUNK_BYTES: u64 = 64 * 1024;
+
 /// An [ExecutionTrace] represents a final state of a program that was executed.
 ///
 /// It can be used to examine the program results, and the memory of the program at
 /// any cycle up to the last cycle. It is typically used for those purposes once
 /// execution of a program terminates.
+///
+/// An [ExecutionTrace] owns the processor which executed the program, which cannot be cloned. To
+/// retain the results of several executions, e.g. to compare them, take a [TraceSnapshot] of each
+/// via [ExecutionTrace::snapshot].
 pub struct ExecutionTrace {
     pub(super) root_context: ContextId,
+    /// Every context entered during execution, including `root_context`
+    pub(super) contexts: BTreeSet<ContextId>,
     pub(super) last_cycle: RowIndex,
+    /// The cycles at which a call frame was entered, in ascending order
+    pub(super) frame_starts: Vec<RowIndex>,
+    pub(super) failed: bool,
     pub(super) processor: FastProcessor,
     pub(super) outputs: StackOutputs,
 }
@@ -35,14 +62,24 @@ impl ExecutionTrace {
     where
         T: FromMidenRepr,
     {
-        let size = <T as FromMidenRepr>::size_in_felts();
-        let stack = self.outputs.get_num_elements(size);
-        if stack.len() < size {
-            return None;
-        }
-        let mut stack = stack.to_vec();
-        stack.reverse();
-        Some(<T as FromMidenRepr>::pop_from_stack(&mut stack))
+        T::from_stack_outputs(&self.outputs)
+    }
+
+    /// Parse the program outputs on the operand stack as a value of type `T`, returning an error if
+    /// the program returned more than a value of type `T`.
+    ///
+    /// See [FromMidenRepr::from_stack_outputs_exact] for how this is determined.
+    pub fn parse_result_exact<T>(&self) -> Result<T, UnexpectedOutputs>
+    where
+        T: FromMidenRepr,
+    {
+        T::from_stack_outputs_exact(&self.outputs)
+    }
+
+    /// Return the top `n` elements of the operand stack outputs, top of the stack first, or all of
+    /// them if there are fewer than `n`
+    pub fn stack_outputs_as_felts(&self, n: usize) -> Vec<Felt> {
+        self.outputs[..n.min(self.outputs.len())].to_vec()
     }
 
     /// Consume the [ExecutionTrace], extracting just the outputs on the operand stack
@@ -51,12 +88,49 @@ impl ExecutionTrace {
         self.outputs
     }
 
+    /// Return the cycle at which execution of the program terminated
+    #[inline]
+    pub fn last_cycle(&self) -> RowIndex {
+        self.last_cycle
+    }
+
     /// Return a reference to the operand stack outputs
     #[inline]
     pub fn outputs(&self) -> &StackOutputs {
         &self.outputs
     }
 
+    /// Return the cycles at which a call frame was entered, in ascending order.
+    ///
+    /// Frames are only known for code which emits frame trace events, see [TraceEvent].
+    #[inline]
+    pub fn frame_starts(&self) -> &[RowIndex] {
+        &self.frame_starts
+    }
+
+    /// Returns true if execution terminated with an error at [ExecutionTrace::last_cycle], rather
+    /// than by the program returning
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Copy the outputs and written memory of the program, so that they can be kept after the
+    /// [ExecutionTrace] is dropped.
+    ///
+    /// See [TraceSnapshot] for what is, and is not, retained.
+    pub fn snapshot(&self) -> TraceSnapshot {
+        let memory = written_words(&self.processor, self.contexts.clone())
+            .map(|(ctx, addr, word)| ((ctx, addr), word))
+            .collect();
+        TraceSnapshot {
+            root_context: self.root_context,
+            last_cycle: self.last_cycle,
+            outputs: self.outputs,
+            memory,
+        }
+    }
+
     /// Read the word at the given Miden memory address
     pub fn read_memory_word(&self, addr: u32) -> Option<Word> {
         self.read_memory_word_in_context(addr, self.root_context, self.last_cycle)
@@ -133,17 +207,146 @@ impl ExecutionTrace {
         Ok(buf)
     }
 
+    /// Read the bytes at the byte addresses in `range`, i.e. addresses in Rust's address space,
+    /// under `ctx`, at cycle `clk`.
+    ///
+    /// Each element holds four bytes, in big-endian order. If `range` extends beyond the end of
+    /// the addressable memory, it is truncated, so fewer bytes than requested are returned.
+    ///
+    /// The whole region is held in memory, so large regions should be read via
+    /// [ExecutionTrace::read_memory_region_chunked] instead.
+    pub fn read_memory_region(&self, range: Range<u64>, ctx: ContextId, clk: RowIndex) -> Vec<u8> {
+        const U32_MASK: u64 = u32::MAX as u64;
+
+        let end = range.end.min(ADDRESSABLE_BYTES);
+        let start = range.start.min(end);
+        let mut bytes = Vec::with_capacity((end - start).min(MEMORY_CHUNK_BYTES) as usize);
+        let mut addr = start;
+        while addr < end {
+            let elem = self
+                .read_memory_element_in_context((addr / 4) as u32, ctx, clk)
+                .unwrap_or_default();
+            let elem = ((elem.as_canonical_u64() & U32_MASK) as u32).to_be_bytes();
+            let offset = (addr % 4) as usize;
+            let take = core::cmp::min(4 - offset as u64, end - addr) as usize;
+            bytes.extend(&elem[offset..(offset + take)]);
+            addr += take as u64;
+        }
+        bytes
+    }
+
+    /// Like [ExecutionTrace::read_memory_region], but reads the region a fixed number of bytes at
+    /// a time, passing each chunk to `f` along with the address of its first byte, and returns
+    /// the number of bytes read.
+    ///
+    /// Every chunk but the last is a multiple of 16 bytes long. Stops at the first error from `f`.
+    pub fn read_memory_region_chunked<E>(
+        &self,
+        range: Range<u64>,
+        ctx: ContextId,
+        clk: RowIndex,
+        mut f: impl FnMut(u64, &[u8]) -> Result<(), E>,
+    ) -> Result<usize, E> {
+        let end = range.end.min(ADDRESSABLE_BYTES);
+        let mut start = range.start.min(end);
+        let mut read = 0;
+        while start < end {
+            let chunk_end = start.saturating_add(MEMORY_CHUNK_BYTES).min(end);
+            let bytes = self.read_memory_region(start..chunk_end, ctx, clk);
+            f(start, &bytes)?;
+            read += bytes.len();
+            start = chunk_end;
+        }
+        Ok(read)
+    }
+
+    /// Write the bytes at the byte addresses in `range`, under `ctx`, at cycle `clk`, to the file
+    /// at `path`, returning the number of bytes written.
+    ///
+    /// See [ExecutionTrace::read_memory_region] for how the bytes are read. They are written a
+    /// chunk at a time, so the region need not fit in memory.
+    pub fn dump_memory<P>(
+        &self,
+        path: P,
+        range: Range<u64>,
+        ctx: ContextId,
+        clk: RowIndex,
+    ) -> std::io::Result<usize>
+    where
+        P: AsRef<Path>,
+    {
+        use std::io::Write;
+
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let written =
+            self.read_memory_region_chunked(range, ctx, clk, |_, bytes| file.write_all(bytes))?;
+        file.flush()?;
+        Ok(written)
+    }
+
     /// Read a value of the given type, given an address in Rust's address space
+    ///
+    /// Returns `None` if the value cannot be read, see [ExecutionTrace::try_read_from_rust_memory]
+    /// for why.
     #[track_caller]
     pub fn read_from_rust_memory<T>(&self, addr: u32) -> Option<T>
     where
         T: core::any::Any + FromMidenRepr,
     {
-        self.read_from_rust_memory_in_context(addr, self.root_context, self.last_cycle)
+        self.try_read_from_rust_memory(addr).ok()
+    }
+
+    /// Like [ExecutionTrace::read_from_rust_memory], but returns the reason the value cannot be
+    /// read, e.g. that `addr` was rejected by [NativePtr::from_ptr_checked]
+    #[track_caller]
+    pub fn try_read_from_rust_memory<T>(&self, addr: u32) -> Result<T, MemoryReadError>
+    where
+        T: core::any::Any + FromMidenRepr,
+    {
+        self.try_read_from_rust_memory_in_context(addr, self.root_context, self.last_cycle)
+    }
+
+    /// Read `count` key/value pairs laid out consecutively from `addr`, an address in Rust's address
+    /// space, e.g. the entries of a small map returned by the program.
+    ///
+    /// Each pair is a `K` immediately followed by a `V`, and occupies `K::size_in_felts()` plus
+    /// `V::size_in_felts()` elements.
+    #[track_caller]
+    pub fn read_pairs_from_rust_memory<K, V>(&self, addr: u32, count: usize) -> Option<Vec<(K, V)>>
+    where
+        K: core::any::Any + FromMidenRepr,
+        V: core::any::Any + FromMidenRepr,
+    {
+        let key_size = u32::try_from(<K as FromMidenRepr>::size_in_felts() * 4).ok()?;
+        let value_size = u32::try_from(<V as FromMidenRepr>::size_in_felts() * 4).ok()?;
+        let pair_size = key_size.checked_add(value_size)?;
+
+        let mut pairs = Vec::with_capacity(count);
+        let mut addr = addr;
+        for i in 0..count {
+            if i > 0 {
+                addr = addr.checked_add(pair_size)?;
+            }
+            let key = self.read_from_rust_memory_in_context::<K>(
+                addr,
+                self.root_context,
+                self.last_cycle,
+            )?;
+            let value = self.read_from_rust_memory_in_context::<V>(
+                addr.checked_add(key_size)?,
+                self.root_context,
+                self.last_cycle,
+            )?;
+            pairs.push((key, value));
+        }
+        Some(pairs)
     }
 
     /// Read a value of the given type, given an address in Rust's address space, under `ctx`, at
     /// cycle `clk`
+    ///
+    /// Returns `None` if the value cannot be read, see
+    /// [ExecutionTrace::try_read_from_rust_memory_in_context] for why.
     #[track_caller]
     pub fn read_from_rust_memory_in_context<T>(
         &self,
@@ -154,43 +357,167 @@ impl ExecutionTrace {
     where
         T: core::any::Any + FromMidenRepr,
     {
-        use core::any::TypeId;
+        self.try_read_from_rust_memory_in_context(addr, ctx, clk).ok()
+    }
 
-        let ptr = NativePtr::from_ptr(addr);
-        if TypeId::of::<T>() == TypeId::of::<Felt>() {
-            assert_eq!(ptr.offset, 0, "cannot read values of type Felt from unaligned addresses");
+    /// Like [ExecutionTrace::read_from_rust_memory_in_context], but returns the reason the value
+    /// cannot be read.
+    ///
+    /// Fails if `addr` is rejected by [NativePtr::from_ptr_checked], is not element-aligned, or
+    /// refers to memory which was never initialized.
+    #[track_caller]
+    pub fn try_read_from_rust_memory_in_context<T>(
+        &self,
+        addr: u32,
+        ctx: ContextId,
+        clk: RowIndex,
+    ) -> Result<T, MemoryReadError>
+    where
+        T: core::any::Any + FromMidenRepr,
+    {
+        let size_in_felts = <T as FromMidenRepr>::size_in_felts();
+        let ptr = NativePtr::from_ptr_checked(addr, size_in_felts * 4)?;
+        if !ptr.is_element_aligned() {
+            return Err(MemoryReadError::UnalignedRead);
         }
-        assert_eq!(ptr.offset, 0, "support for unaligned reads is not yet implemented");
-        match <T as FromMidenRepr>::size_in_felts() {
-            1 => {
-                let felt = self.read_memory_element_in_context(ptr.addr, ctx, clk)?;
-                Some(T::from_felts(&[felt]))
-            }
-            2 => {
-                let lo = self.read_memory_element_in_context(ptr.addr, ctx, clk)?;
-                let hi = self.read_memory_element_in_context(ptr.addr + 1, ctx, clk)?;
-                Some(T::from_felts(&[lo, hi]))
-            }
-            3 => {
-                let lo_l = self.read_memory_element_in_context(ptr.addr, ctx, clk)?;
-                let lo_h = self.read_memory_element_in_context(ptr.addr + 1, ctx, clk)?;
-                let hi_l = self.read_memory_element_in_context(ptr.addr + 2, ctx, clk)?;
-                Some(T::from_felts(&[lo_l, lo_h, hi_l]))
-            }
+        // The value fits in the address space, so none of its elements are beyond `u32::MAX`
+        let element = |index: u32| {
+            self.read_memory_element_in_context(ptr.addr + index, ctx, clk)
+                .ok_or(MemoryReadError::OutOfBounds)
+        };
+        match size_in_felts {
+            1 => Ok(T::from_felts(&[element(0)?])),
+            2 => Ok(T::from_felts(&[element(0)?, element(1)?])),
+            3 => Ok(T::from_felts(&[element(0)?, element(1)?, element(2)?])),
             n => {
                 assert_ne!(n, 0);
                 let num_words = n.next_multiple_of(4) / 4;
                 let mut words = SmallVec::<[_; 2]>::with_capacity(num_words);
                 for word_index in 0..(num_words as u32) {
                     let addr = ptr.addr + (word_index * 4);
-                    let mut word = self.read_memory_word(addr)?;
+                    let mut word =
+                        self.read_memory_word(addr).ok_or(MemoryReadError::OutOfBounds)?;
                     word.reverse();
-                    dbg!(word_index, word);
                     words.push(word);
                 }
                 words.resize(num_words, Word::new([Felt::ZERO; 4]));
-                Some(T::from_words(&words))
+                Ok(T::from_words(&words))
             }
         }
     }
 }
+
+/// The outputs and memory of a program as of the end of its execution, copied from an
+/// [ExecutionTrace] so that they outlive it.
+///
+/// Unlike an [ExecutionTrace], a snapshot can be cloned and compared, e.g. to check that two runs
+/// of a program with different inputs produce the same results. The tradeoffs are that:
+///
+/// * Taking a snapshot copies every word of memory written during execution, in every context, so
+///   its cost is proportional to the memory used by the program, rather than constant.
+/// * Only the final state is kept: the advice provider, the stack overflow table, and the state of
+///   memory at earlier cycles are not available from a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceSnapshot {
+    root_context: ContextId,
+    last_cycle: RowIndex,
+    outputs: StackOutputs,
+    /// The written words of memory, by context and element address of their first element
+    memory: BTreeMap<(ContextId, u32), Word>,
+}
+
+impl TraceSnapshot {
+    /// Parse the program outputs on the operand stack as a value of type `T`
+    pub fn parse_result<T>(&self) -> Option<T>
+    where
+        T: FromMidenRepr,
+    {
+        T::from_stack_outputs(&self.outputs)
+    }
+
+    /// Return the cycle at which execution of the program terminated
+    #[inline]
+    pub fn last_cycle(&self) -> RowIndex {
+        self.last_cycle
+    }
+
+    /// Return a reference to the operand stack outputs
+    #[inline]
+    pub fn outputs(&self) -> &StackOutputs {
+        &self.outputs
+    }
+
+    /// Iterate over the written words of memory, as `(context, addr, word)`, in order of context,
+    /// and then of address
+    pub fn memory_words(&self) -> impl Iterator<Item = (ContextId, u32, Word)> + '_ {
+        self.memory.iter().map(|(&(ctx, addr), word)| (ctx, addr, *word))
+    }
+
+    /// Read the word at the given Miden memory address, which must be word-aligned.
+    ///
+    /// Memory which was never written reads as zero.
+    pub fn read_memory_word(&self, addr: u32) -> Option<Word> {
+        self.read_memory_word_in_context(addr, self.root_context)
+    }
+
+    /// Read the word at the given Miden memory address, which must be word-aligned, under `ctx`
+    pub fn read_memory_word_in_context(&self, addr: u32, ctx: ContextId) -> Option<Word> {
+        if !addr.is_multiple_of(4) {
+            return None;
+        }
+        Some(self.memory.get(&(ctx, addr)).copied().unwrap_or_default())
+    }
+
+    /// Read the element at the given Miden memory address
+    pub fn read_memory_element(&self, addr: u32) -> Option<Felt> {
+        self.read_memory_element_in_context(addr, self.root_context)
+    }
+
+    /// Read the element at the given Miden memory address, under `ctx`
+    pub fn read_memory_element_in_context(&self, addr: u32, ctx: ContextId) -> Option<Felt> {
+        let word = self.read_memory_word_in_context(addr - (addr % 4), ctx)?;
+        Some(word[(addr % 4) as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use miden_assembly::DefaultSourceManager;
+
+    use super::*;
+    use crate::exec::Executor;
+
+    #[test]
+    fn trace_snapshots_outlive_their_traces() {
+        let program = miden_assembly::Assembler::default()
+            .assemble_program("begin dup mem_store.9 push.2 mul end")
+            .unwrap();
+        let run = |input: u64| {
+            let source_manager = Arc::new(DefaultSourceManager::default());
+            let trace = Executor::new(vec![Felt::new(input)]).execute(&program, source_manager);
+            trace.snapshot()
+        };
+
+        let first = run(3);
+        let second = run(5);
+        assert_ne!(first, second);
+        assert_eq!(first.clone(), first);
+        assert_eq!(first.parse_result::<u32>(), Some(6));
+        assert_eq!(second.parse_result::<u32>(), Some(10));
+        assert_eq!(first.last_cycle(), second.last_cycle());
+
+        assert_eq!(first.read_memory_element(9), Some(Felt::new(3)));
+        assert_eq!(second.read_memory_element(9), Some(Felt::new(5)));
+        assert_eq!(second.read_memory_element(1024), Some(Felt::new(0)));
+        let word = second.read_memory_word(8).unwrap();
+        assert_eq!(word[1], Felt::new(5));
+        assert_eq!(second.read_memory_word(9), None);
+        assert!(
+            second
+                .memory_words()
+                .any(|(_, addr, word)| addr == 8 && word[1] == Felt::new(5))
+        );
+    }
+}
@@ -18,6 +18,14 @@ pub enum TraceEvent {
     FrameStart,
     FrameEnd,
     AssertionFailed(Option<NonZeroU32>),
+    /// A trace event emitted by the program itself, i.e. via `trace.N`, for any `N` which is not
+    /// reserved by the compiler
+    User(u32),
+    /// A trace event not reserved by the compiler.
+    ///
+    /// Such events are now decoded as [TraceEvent::User], but handlers may still be registered
+    /// via this variant, in which case they are called for the user events of the same id.
+    #[deprecated(note = "trace events not reserved by the compiler are decoded as `User`")]
     Unknown(u32),
 }
 impl TraceEvent {
@@ -31,13 +39,20 @@ impl TraceEvent {
         matches!(self, Self::FrameEnd)
     }
 
+    #[inline(always)]
+    #[allow(deprecated)]
+    pub fn is_user(&self) -> bool {
+        matches!(self, Self::User(_) | Self::Unknown(_))
+    }
+
+    #[allow(deprecated)]
     pub fn as_u32(self) -> u32 {
         match self {
             Self::FrameStart => TRACE_FRAME_START,
             Self::FrameEnd => TRACE_FRAME_END,
             Self::AssertionFailed(None) => 0,
             Self::AssertionFailed(Some(code)) => code.get(),
-            Self::Unknown(event) => event,
+            Self::User(event) | Self::Unknown(event) => event,
         }
     }
 }
@@ -46,18 +61,19 @@ impl From<u32> for TraceEvent {
         match raw {
             TRACE_FRAME_START => Self::FrameStart,
             TRACE_FRAME_END => Self::FrameEnd,
-            _ => Self::Unknown(raw),
+            _ => Self::User(raw),
         }
     }
 }
 impl From<TraceEvent> for u32 {
+    #[allow(deprecated)]
     fn from(event: TraceEvent) -> Self {
         match event {
             TraceEvent::FrameStart => TRACE_FRAME_START,
             TraceEvent::FrameEnd => TRACE_FRAME_END,
             TraceEvent::AssertionFailed(None) => 0,
             TraceEvent::AssertionFailed(Some(code)) => code.get(),
-            TraceEvent::Unknown(code) => code,
+            TraceEvent::User(code) | TraceEvent::Unknown(code) => code,
         }
     }
 }
@@ -1,6 +1,6 @@
 use miden_core::Word;
 use miden_core::field::PrimeField64;
-use miden_processor::Felt as RawFelt;
+use miden_processor::{Felt as RawFelt, StackOutputs};
 #[cfg(feature = "proptest")]
 use proptest::{
     arbitrary::Arbitrary,
@@ -139,6 +139,37 @@ pub trait FromMidenRepr: Sized {
         }
         Self::from_felts(&felts)
     }
+
+    /// Decode a value of this type from the top of `outputs`, the operand stack outputs of a
+    /// program, ignoring any elements below it.
+    ///
+    /// Returns `None` if `outputs` holds too few elements.
+    fn from_stack_outputs(outputs: &StackOutputs) -> Option<Self> {
+        let size = Self::size_in_felts();
+        let mut stack = outputs.get(..size)?.to_vec();
+        stack.reverse();
+        Some(Self::pop_from_stack(&mut stack))
+    }
+
+    /// Like [FromMidenRepr::from_stack_outputs], but returns an error unless the value accounts
+    /// for all of `outputs`, i.e. every element below it is zero.
+    ///
+    /// This catches programs which return more than a value of this type, e.g. due to a mismatch
+    /// between the signature of a procedure and how its results are decoded. As the operand stack
+    /// is padded with zeros to its minimum depth, a program which returns additional zeros cannot
+    /// be told apart from one which does not.
+    fn from_stack_outputs_exact(outputs: &StackOutputs) -> Result<Self, UnexpectedOutputs> {
+        let expected = Self::size_in_felts();
+        let value = Self::from_stack_outputs(outputs).ok_or(UnexpectedOutputs::Missing {
+            expected,
+            available: outputs.len(),
+        })?;
+        let found = outputs.iter().rposition(|felt| *felt != RawFelt::ZERO).map_or(0, |i| i + 1);
+        if found > expected {
+            return Err(UnexpectedOutputs::Leftover { expected, found });
+        }
+        Ok(value)
+    }
 }
 
 impl ToMidenRepr for bool {
@@ -684,6 +715,114 @@ impl Felt {
     pub fn new(value: u64) -> Self {
         Self(RawFelt::new(value))
     }
+
+    /// Convert this element to a `bool`, returning an error unless it is `0` or `1`
+    pub fn try_into_bool(self) -> Result<bool, FeltOutOfRange> {
+        match self.0.as_canonical_u64() {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(FeltOutOfRange::new(self, "bool")),
+        }
+    }
+
+    /// Convert this element to a `u8`, returning an error if it does not fit
+    pub fn try_into_u8(self) -> Result<u8, FeltOutOfRange> {
+        self.checked("u8")
+    }
+
+    /// Convert this element to an `i8`, returning an error if it does not fit.
+    ///
+    /// Signed integers are represented by their two's complement bit pattern, as produced by
+    /// `Felt::from(i8)`, so the element must be in the range of a `u8`.
+    pub fn try_into_i8(self) -> Result<i8, FeltOutOfRange> {
+        self.checked::<u8>("i8").map(|value| value as i8)
+    }
+
+    /// Convert this element to a `u16`, returning an error if it does not fit
+    pub fn try_into_u16(self) -> Result<u16, FeltOutOfRange> {
+        self.checked("u16")
+    }
+
+    /// Convert this element to an `i16`, returning an error if it does not fit.
+    ///
+    /// See [Felt::try_into_i8] for how signed integers are represented.
+    pub fn try_into_i16(self) -> Result<i16, FeltOutOfRange> {
+        self.checked::<u16>("i16").map(|value| value as i16)
+    }
+
+    /// Convert this element to a `u32`, returning an error if it does not fit
+    pub fn try_into_u32(self) -> Result<u32, FeltOutOfRange> {
+        self.checked("u32")
+    }
+
+    /// Convert this element to an `i32`, returning an error if it does not fit.
+    ///
+    /// See [Felt::try_into_i8] for how signed integers are represented.
+    pub fn try_into_i32(self) -> Result<i32, FeltOutOfRange> {
+        self.checked::<u32>("i32").map(|value| value as i32)
+    }
+
+    /// Convert this element to an `i64`, returning an error if it exceeds `i64::MAX`.
+    ///
+    /// Unlike the narrower signed integers, negative `i64` values cannot be represented by a
+    /// single field element, as their bit pattern exceeds the field modulus.
+    pub fn try_into_i64(self) -> Result<i64, FeltOutOfRange> {
+        self.checked("i64")
+    }
+
+    /// Interpret the lowest `bits` bits of this element as a two's complement integer, sign
+    /// extending it to an `i64`, e.g. an element holding `0xffffffff` is `-1` as a 32-bit value.
+    ///
+    /// Any higher bits are ignored. Unlike `i64::from(Felt)`, which reinterprets the canonical
+    /// value as is, this recovers negative values of any width up to 64 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `bits` is in the range `1..=64`.
+    pub fn as_signed(self, bits: u32) -> i64 {
+        assert!((1..=64).contains(&bits), "cannot interpret a felt as a {bits}-bit integer");
+        let shift = 64 - bits;
+        ((self.0.as_canonical_u64() << shift) as i64) >> shift
+    }
+
+    fn checked<T: TryFrom<u64>>(self, ty: &'static str) -> Result<T, FeltOutOfRange> {
+        T::try_from(self.0.as_canonical_u64()).map_err(|_| FeltOutOfRange::new(self, ty))
+    }
+}
+
+/// The error returned by [FromMidenRepr::from_stack_outputs_exact] when the stack outputs of a
+/// program do not hold exactly one value of the expected type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UnexpectedOutputs {
+    /// There are fewer elements than the size of the value
+    #[error("expected {expected} output elements, but only {available} are available")]
+    Missing { expected: usize, available: usize },
+    /// There are non-zero elements below the value, the deepest of which is at `found - 1`
+    #[error(
+        "expected {expected} output elements, but found {found}, i.e. the program returned more \
+         than expected"
+    )]
+    Leftover { expected: usize, found: usize },
+}
+
+/// The error returned by the checked conversions from [Felt], e.g. [Felt::try_into_u8], when the
+/// canonical value of the element does not fit in the target type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("field element {value} is out of range for {ty}")]
+pub struct FeltOutOfRange {
+    /// The canonical value of the element
+    pub value: u64,
+    /// The name of the type the element was being converted to
+    pub ty: &'static str,
+}
+
+impl FeltOutOfRange {
+    fn new(felt: Felt, ty: &'static str) -> Self {
+        Self {
+            value: felt.0.as_canonical_u64(),
+            ty,
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for Felt {
@@ -749,6 +888,116 @@ impl core::str::FromStr for Felt {
     }
 }
 
+/// A program argument, i.e. one of the values given via `--args`.
+///
+/// An argument without a suffix is a single field element, in decimal or hexadecimal, e.g. `42`
+/// or `0x2a`. Otherwise, the suffix gives the type of an integer, which is encoded as
+/// [ToMidenRepr::push_to_operand_stack] would, i.e. with the width and sign handling expected by
+/// compiled code. The supported suffixes are `u8`, `i8`, `u16`, `i16`, `u32`, `i32`, `u64`, `i64`,
+/// `u128`, `i128`, and `felt`, e.g. `5u64`, or `-3i32`. Hexadecimal values give the bit pattern
+/// of the integer, so `0xffi8` is `-1i8`.
+///
+/// Values wider than 32 bits span multiple elements, e.g. a `u64` is pushed as two `u32` limbs,
+/// the least significant of which is pushed last, i.e. nearest the top of the stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramArg {
+    /// The elements of this argument, in the order they are pushed on the operand stack
+    felts: SmallVec<[RawFelt; 4]>,
+}
+
+impl ProgramArg {
+    /// The elements of this argument, in the order they are pushed on the operand stack, i.e. the
+    /// last of them ends up on top
+    pub fn felts(&self) -> &[RawFelt] {
+        &self.felts
+    }
+
+    fn encode<T: ToMidenRepr>(value: T) -> Self {
+        let mut felts = vec![];
+        value.push_to_operand_stack(&mut felts);
+        Self {
+            felts: felts.into(),
+        }
+    }
+}
+
+impl From<Felt> for ProgramArg {
+    fn from(felt: Felt) -> Self {
+        Self {
+            felts: smallvec![felt.0],
+        }
+    }
+}
+
+impl core::str::FromStr for ProgramArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        macro_rules! integer {
+            ($value:expr, $ty:ty, $unsigned:ty) => {{
+                let value = match $value.strip_prefix("0x") {
+                    Some(hex) => <$unsigned>::from_str_radix(hex, 16).map(|bits| bits as $ty),
+                    None => $value.parse::<$ty>(),
+                };
+                value
+                    .map(Self::encode)
+                    .map_err(|err| format!("invalid {} value: {err}", stringify!($ty)))
+            }};
+        }
+
+        // Longer suffixes are tried first, as `u8` is also a suffix of `u128`
+        const SUFFIXES: [&str; 11] =
+            ["u128", "i128", "felt", "u16", "i16", "u32", "i32", "u64", "i64", "u8", "i8"];
+        let Some((value, suffix)) = SUFFIXES
+            .into_iter()
+            .find_map(|suffix| s.strip_suffix(suffix).map(|value| (value, suffix)))
+        else {
+            return s.parse::<Felt>().map(Self::from);
+        };
+        match suffix {
+            "felt" => value.parse::<Felt>().map(Self::from),
+            "u8" => integer!(value, u8, u8),
+            "i8" => integer!(value, i8, u8),
+            "u16" => integer!(value, u16, u16),
+            "i16" => integer!(value, i16, u16),
+            "u32" => integer!(value, u32, u32),
+            "i32" => integer!(value, i32, u32),
+            "u64" => integer!(value, u64, u64),
+            "i64" => integer!(value, i64, u64),
+            "u128" => integer!(value, u128, u128),
+            "i128" => integer!(value, i128, u128),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl clap::builder::ValueParserFactory for ProgramArg {
+    type Parser = ProgramArgParser;
+
+    fn value_parser() -> Self::Parser {
+        ProgramArgParser
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ProgramArgParser;
+impl clap::builder::TypedValueParser for ProgramArgParser {
+    type Value = ProgramArg;
+
+    fn parse_ref(
+        &self,
+        _cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::error::Error> {
+        use clap::error::{Error, ErrorKind};
+
+        let value = value.to_str().ok_or_else(|| Error::new(ErrorKind::InvalidUtf8))?.trim();
+        value.parse().map_err(|err| Error::raw(ErrorKind::ValueValidation, err))
+    }
+}
+
 impl From<Felt> for miden_processor::Felt {
     fn from(f: Felt) -> Self {
         f.0
@@ -810,6 +1059,9 @@ impl From<i64> for Felt {
 }
 
 // Reverse Felt to Rust types conversion
+//
+// These conversions truncate values which do not fit in the target type, see the `try_into_*`
+// methods of [Felt] for checked alternatives.
 
 impl From<Felt> for bool {
     fn from(f: Felt) -> Self {
@@ -865,6 +1117,60 @@ impl From<Felt> for i64 {
     }
 }
 
+// Comparison and arithmetic against plain integers
+//
+// Integers are compared against the canonical value of the element, so an integer which is not
+// less than the field modulus is never equal to any element. Arithmetic is performed in the
+// field, i.e. modulo the field modulus, with integer operands first converted to elements.
+
+impl PartialEq<u64> for Felt {
+    fn eq(&self, other: &u64) -> bool {
+        self.0.as_canonical_u64() == *other
+    }
+}
+
+impl PartialEq<Felt> for u64 {
+    fn eq(&self, other: &Felt) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<u64> for Felt {
+    fn partial_cmp(&self, other: &u64) -> Option<core::cmp::Ordering> {
+        Some(self.0.as_canonical_u64().cmp(other))
+    }
+}
+
+impl PartialOrd<Felt> for u64 {
+    fn partial_cmp(&self, other: &Felt) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(&other.0.as_canonical_u64()))
+    }
+}
+
+macro_rules! impl_felt_arithmetic {
+    ($($trait:ident::$method:ident),*) => {
+        $(
+            impl core::ops::$trait for Felt {
+                type Output = Felt;
+
+                fn $method(self, rhs: Felt) -> Felt {
+                    Felt(core::ops::$trait::$method(self.0, rhs.0))
+                }
+            }
+
+            impl core::ops::$trait<u64> for Felt {
+                type Output = Felt;
+
+                fn $method(self, rhs: u64) -> Felt {
+                    Felt(core::ops::$trait::$method(self.0, RawFelt::new(rhs)))
+                }
+            }
+        )*
+    };
+}
+
+impl_felt_arithmetic!(Add::add, Sub::sub, Mul::mul);
+
 #[cfg(feature = "proptest")]
 impl Arbitrary for Felt {
     type Parameters = ();
@@ -901,9 +1207,104 @@ where
 
 #[cfg(test)]
 mod tests {
-    use miden_core::Word;
+    use miden_core::{Word, field::PrimeField64};
+    use miden_processor::Felt as RawFelt;
 
-    use super::{FromMidenRepr, ToMidenRepr, bytes_to_words, push_wasm_ty_to_operand_stack};
+    use super::{
+        Felt, FeltOutOfRange, FromMidenRepr, ProgramArg, ToMidenRepr, bytes_to_words,
+        push_wasm_ty_to_operand_stack,
+    };
+
+    #[test]
+    fn program_arg_suffixes() {
+        let felts = |arg: &str| {
+            let arg = arg.parse::<ProgramArg>().unwrap();
+            arg.felts().iter().map(|felt| felt.as_canonical_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(felts("42"), [42]);
+        assert_eq!(felts("0x2a"), [42]);
+        assert_eq!(felts("0x2afelt"), [42]);
+        assert_eq!(felts("200u8"), [200]);
+        assert_eq!(felts("-3i32"), [u32::MAX as u64 - 2]);
+        assert_eq!(felts("0xffi8"), [255]);
+        assert_eq!(felts("-1i16"), [u16::MAX as u64]);
+        // Multi-element values are pushed most significant limb first
+        assert_eq!(felts("5u64"), [0, 5]);
+        assert_eq!(felts("0x100000002u64"), [1, 2]);
+        assert_eq!(felts("-1i64"), [u32::MAX as u64, u32::MAX as u64]);
+        assert_eq!(felts("1u128"), [0, 0, 0, 1]);
+
+        for invalid in ["256u8", "-1u32", "128i8", "0x1ffi8", "5u7", "-0x1i8", "x"] {
+            assert!(invalid.parse::<ProgramArg>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn checked_conversions_in_range() {
+        assert_eq!(Felt::new(0).try_into_bool(), Ok(false));
+        assert_eq!(Felt::new(1).try_into_bool(), Ok(true));
+        assert_eq!(Felt::new(u8::MAX as u64).try_into_u8(), Ok(u8::MAX));
+        assert_eq!(Felt::from(-1i8).try_into_i8(), Ok(-1));
+        assert_eq!(Felt::from(i8::MIN).try_into_i8(), Ok(i8::MIN));
+        assert_eq!(Felt::new(u16::MAX as u64).try_into_u16(), Ok(u16::MAX));
+        assert_eq!(Felt::from(i16::MIN).try_into_i16(), Ok(i16::MIN));
+        assert_eq!(Felt::new(u32::MAX as u64).try_into_u32(), Ok(u32::MAX));
+        assert_eq!(Felt::from(-2i32).try_into_i32(), Ok(-2));
+        assert_eq!(Felt::new(i64::MAX as u64).try_into_i64(), Ok(i64::MAX));
+    }
+
+    #[test]
+    fn checked_conversions_out_of_range() {
+        let err = |value, ty| FeltOutOfRange { value, ty };
+
+        assert_eq!(Felt::new(2).try_into_bool().unwrap_err(), err(2, "bool"));
+        assert_eq!(Felt::new(256).try_into_u8().unwrap_err(), err(256, "u8"));
+        assert_eq!(Felt::new(256).try_into_i8().unwrap_err(), err(256, "i8"));
+        assert_eq!(Felt::new(1 << 16).try_into_u16().unwrap_err(), err(1 << 16, "u16"));
+        assert_eq!(Felt::new(1 << 16).try_into_i16().unwrap_err(), err(1 << 16, "i16"));
+        assert_eq!(Felt::new(1 << 32).try_into_u32().unwrap_err(), err(1 << 32, "u32"));
+        assert_eq!(Felt::new(1 << 32).try_into_i32().unwrap_err(), err(1 << 32, "i32"));
+        assert_eq!(Felt::new(1 << 63).try_into_i64().unwrap_err(), err(1 << 63, "i64"));
+
+        // The lossy conversions are unchanged
+        assert_eq!(u8::from(Felt::new(256)), 0);
+        assert_eq!(
+            Felt::new(256).try_into_u8().unwrap_err().to_string(),
+            "field element 256 is out of range for u8"
+        );
+    }
+
+    #[test]
+    fn signed_interpretation() {
+        assert_eq!(Felt::new(0x7f).as_signed(8), i8::MAX as i64);
+        assert_eq!(Felt::new(0x80).as_signed(8), i8::MIN as i64);
+        assert_eq!(Felt::from(-1i8).as_signed(8), -1);
+        // Bits above the width are ignored
+        assert_eq!(Felt::new(0x1ff).as_signed(8), -1);
+        assert_eq!(Felt::new(0x100).as_signed(8), 0);
+
+        assert_eq!(Felt::from(i16::MIN).as_signed(16), i16::MIN as i64);
+        assert_eq!(Felt::new(0xfffe).as_signed(16), -2);
+        assert_eq!(Felt::new(0x7fff).as_signed(16), i16::MAX as i64);
+
+        assert_eq!(Felt::new(0xffffffff).as_signed(32), -1);
+        assert_eq!(Felt::from(i32::MIN).as_signed(32), i32::MIN as i64);
+        assert_eq!(Felt::new(5).as_signed(32), 5);
+
+        // At 64 bits, the canonical value is reinterpreted as is, like `i64::from(Felt)`
+        let max = Felt::new(RawFelt::ORDER_U64 - 1);
+        assert_eq!(max.as_signed(64), i64::from(max));
+        assert_eq!(max.as_signed(64), -(1 << 32));
+        assert_eq!(Felt::new(i64::MAX as u64).as_signed(64), i64::MAX);
+        assert_eq!(Felt::new(1 << 63).as_signed(64), i64::MIN);
+        assert_eq!(Felt::new(1).as_signed(1), -1);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot interpret a felt as a 65-bit integer")]
+    fn signed_interpretation_of_invalid_width() {
+        Felt::new(1).as_signed(65);
+    }
 
     #[test]
     fn bool_roundtrip() {
@@ -1043,6 +1444,31 @@ mod tests {
         assert_eq!(popped, bytes);
     }
 
+    #[test]
+    fn compare_with_integers() {
+        assert_eq!(Felt::new(42), 42u64);
+        assert_eq!(42u64, Felt::new(42));
+        assert_ne!(Felt::new(42), 43u64);
+        assert!(Felt::new(1) < 2u64);
+        assert!(3u64 > Felt::new(2));
+        // Elements are compared by their canonical value
+        assert_eq!(Felt::new(RawFelt::ORDER_U64 + 1), 1u64);
+        assert_ne!(Felt::new(1), RawFelt::ORDER_U64 + 1);
+    }
+
+    #[test]
+    fn arithmetic_wraps_at_modulus() {
+        let max = Felt::new(RawFelt::ORDER_U64 - 1);
+        assert_eq!(Felt::new(40) + 2, 42u64);
+        assert_eq!(max + 1, 0u64);
+        assert_eq!(max + Felt::new(2), 1u64);
+        assert_eq!(Felt::new(44) - Felt::new(2), 42u64);
+        assert_eq!(Felt::new(0) - 1, RawFelt::ORDER_U64 - 1);
+        assert_eq!(Felt::new(6) * 7, 42u64);
+        assert_eq!(max * max, 1u64);
+        assert_eq!(Felt::new(1 << 32) * (1 << 32), (1u64 << 32) - 1);
+    }
+
     #[test]
     fn bytes_to_words_test() {
         let bytes = [
@@ -64,6 +64,14 @@ impl InputFile {
         Self::Real(path.to_path_buf())
     }
 
+    /// Get an [InputFile] representing the given in-memory contents, e.g. the serialized bytes of
+    /// a package.
+    ///
+    /// The resulting [InputFile] behaves exactly as if `bytes` had been read from standard input.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self::Stdin(bytes.into_boxed_slice())
+    }
+
     /// Get an [InputFile] representing the contents received from standard input.
     ///
     /// This function returns an error if the contents are not a valid supported file type.
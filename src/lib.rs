@@ -5,9 +5,57 @@ mod felt;
 mod input;
 mod linker;
 
+use std::sync::Arc;
+
+use miden_assembly_syntax::{Library, diagnostics::Report};
+use miden_core::serde::Deserializable;
+use miden_processor::StackOutputs;
+
 pub use self::{
     debug::*,
     exec::*,
-    felt::{Felt, FromMidenRepr, ToMidenRepr, bytes_to_words, push_wasm_ty_to_operand_stack},
+    felt::{
+        Felt, FeltOutOfRange, FromMidenRepr, ProgramArg, ToMidenRepr, UnexpectedOutputs,
+        bytes_to_words, push_wasm_ty_to_operand_stack,
+    },
+    input::InputFile,
     linker::{LibraryKind, LinkLibrary},
 };
+
+/// Execute the executable package serialized as `package_bytes` to completion, with `args` on the
+/// operand stack (the first of which is on top), and return the contents of the operand stack
+/// when it terminates.
+///
+/// The dependencies of the package are resolved against `libs`. This is the headless equivalent
+/// of running the package in the debugger, for callers which only need its outputs. If the program
+/// fails, the message of the returned [Report] is the rendered [ExecutionFailure], i.e. the stack
+/// trace, the last known state of the VM, and the error. As the sources of the package are not
+/// available, it carries no source spans, and no source code is shown.
+pub fn run_program(
+    package_bytes: &[u8],
+    args: Vec<miden_processor::Felt>,
+    libs: Vec<Arc<Library>>,
+) -> Result<StackOutputs, Report> {
+    let package = miden_mast_package::Package::read_from_bytes(package_bytes)
+        .map_err(|err| Report::msg(format!("failed to load Miden package: {err}")))?;
+    if package.is_library() {
+        return Err(Report::msg(format!(
+            "cannot run package '{}': it is a library, not an executable",
+            package.name
+        )));
+    }
+
+    let mut executor = Executor::new(args);
+    for lib in libs {
+        executor.register_library_dependency(lib.clone());
+        executor.with_library(lib);
+    }
+    executor.with_dependencies(package.manifest.dependencies())?;
+
+    let program = package.unwrap_program();
+    let source_manager = Arc::new(miden_assembly::DefaultSourceManager::default());
+    let trace = executor
+        .try_execute(&program, source_manager)
+        .map_err(|failure| Report::msg(failure.to_string()))?;
+    Ok(*trace.outputs())
+}
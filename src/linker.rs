@@ -132,13 +132,8 @@ impl LinkLibrary {
     fn find(&self, config: &DebuggerConfig) -> Result<PathBuf, Report> {
         use std::fs;
 
-        let toolchain_dir = config.toolchain_dir();
-        let search_paths = toolchain_dir
-            .iter()
-            .chain(config.search_path.iter())
-            .chain(config.working_dir.iter());
-
-        for search_path in search_paths {
+        let search_paths = config.library_search_paths();
+        for search_path in search_paths.iter() {
             let reader = fs::read_dir(search_path).map_err(|err| {
                 Report::msg(format!(
                     "invalid library search path '{}': {err}",
@@ -179,8 +174,18 @@ impl LinkLibrary {
             }
         }
 
+        if search_paths.is_empty() {
+            return Err(Report::msg(format!(
+                "unable to locate library '{}': no library search paths were provided",
+                &self.name
+            )));
+        }
+        let searched = search_paths
+            .iter()
+            .map(|path| format!("\n  {}", path.display()))
+            .collect::<String>();
         Err(Report::msg(format!(
-            "unable to locate library '{}' using any of the provided search paths",
+            "unable to locate library '{}' using any of the provided search paths:{searched}",
             &self.name
         )))
     }
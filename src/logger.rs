@@ -1,27 +1,125 @@
 use std::{
     borrow::Cow,
     collections::VecDeque,
+    fmt,
     sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant},
 };
 
-use log::{Level, Log};
+use log::{Level, LevelFilter, Log};
 
 static LOGGER: LazyLock<DebugLogger> = LazyLock::new(DebugLogger::default);
 
-#[derive(Default)]
+/// The number of log records retained by the [DebugLogger], after which the oldest are evicted
+pub const LOG_CAPACITY: usize = 1000;
+
 struct DebugLoggerImpl {
     inner: Option<Box<dyn Log>>,
-    captured: VecDeque<LogEntry>,
+    /// The time from which the timestamps of log records are measured
+    started: Instant,
+    captured: LogBuffer,
+}
+impl Default for DebugLoggerImpl {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            started: Instant::now(),
+            captured: LogBuffer::new(LOG_CAPACITY),
+        }
+    }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LogEntry {
     pub level: Level,
+    pub target: String,
+    /// The time elapsed between the start of the debugger and the record being logged
+    pub timestamp: Duration,
     #[allow(unused)]
     pub file: Option<Cow<'static, str>>,
     #[allow(unused)]
     pub line: Option<u32>,
     pub message: String,
 }
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:>10.3}s] {:5} {}: {}",
+            self.timestamp.as_secs_f64(),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+/// A bounded buffer of log records, in which each new record evicts the oldest once full
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    /// The number of records pushed since the buffer was created, including those evicted
+    pushed: usize,
+}
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            pushed: 0,
+        }
+    }
+
+    pub fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        if self.capacity > 0 {
+            self.entries.push_back(entry);
+        }
+        self.pushed += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of records pushed since the buffer was created, including those evicted, which
+    /// changes whenever a record is pushed
+    pub fn pushed(&self) -> usize {
+        self.pushed
+    }
+
+    /// The number of records which were evicted to make room for newer ones
+    pub fn evicted(&self) -> usize {
+        self.pushed - self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEntry> + '_ {
+        self.entries.iter()
+    }
+
+    /// The retained records at least as severe as `filter`, oldest first
+    pub fn filtered(&self, filter: LevelFilter) -> impl DoubleEndedIterator<Item = &LogEntry> + '_ {
+        self.entries.iter().filter(move |entry| entry.level <= filter)
+    }
+}
+
+/// Cycle through the level filters from the most to the least verbose, excluding `Off`
+pub fn next_level_filter(filter: LevelFilter) -> LevelFilter {
+    match filter {
+        LevelFilter::Off | LevelFilter::Trace => LevelFilter::Debug,
+        LevelFilter::Debug => LevelFilter::Info,
+        LevelFilter::Info => LevelFilter::Warn,
+        LevelFilter::Warn => LevelFilter::Error,
+        LevelFilter::Error => LevelFilter::Trace,
+    }
+}
 
 #[derive(Default, Clone)]
 pub struct DebugLogger(Arc<Mutex<DebugLoggerImpl>>);
@@ -35,17 +133,16 @@ impl Log for DebugLogger {
             .file_static()
             .map(Cow::Borrowed)
             .or_else(|| record.file().map(|f| f.to_string()).map(Cow::Owned));
+        let mut guard = self.0.lock().unwrap();
         let entry = LogEntry {
             level: record.level(),
+            target: record.target().to_string(),
+            timestamp: guard.started.elapsed(),
             file,
             line: record.line(),
             message: format!("{}", record.args()),
         };
-        let mut guard = self.0.lock().unwrap();
-        guard.captured.push_back(entry);
-        if guard.captured.len() > 100 {
-            guard.captured.pop_front();
-        }
+        guard.captured.push(entry);
         if let Some(inner) = guard.inner.as_ref()
             && inner.enabled(record.metadata())
         {
@@ -67,12 +164,104 @@ impl DebugLogger {
         &LOGGER
     }
 
-    pub fn take_captured(&self) -> VecDeque<LogEntry> {
-        let mut guard = self.0.lock().unwrap();
-        core::mem::take(&mut guard.captured)
+    /// Call `f` with the buffer of captured log records, which remain captured afterwards
+    pub fn with_captured<T>(&self, f: impl FnOnce(&LogBuffer) -> T) -> T {
+        f(&self.0.lock().unwrap().captured)
+    }
+
+    /// Write every captured log record to `path`, one per line, returning the number written
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<usize> {
+        use std::io::Write;
+
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let written = self.with_captured(|captured| {
+            for entry in captured.iter() {
+                writeln!(file, "{entry}")?;
+            }
+            Ok::<_, std::io::Error>(captured.len())
+        })?;
+        file.flush()?;
+        Ok(written)
     }
 
     fn set_inner(&self, logger: Box<dyn Log>) {
         drop(self.0.lock().unwrap().inner.replace(logger));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, message: &str) -> LogEntry {
+        LogEntry {
+            level,
+            target: "miden_debug::test".to_string(),
+            timestamp: Duration::from_millis(1500),
+            file: None,
+            line: None,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn log_buffer_evicts_oldest() {
+        let mut buffer = LogBuffer::new(3);
+        assert!(buffer.is_empty());
+        for i in 0..5 {
+            buffer.push(entry(Level::Info, &i.to_string()));
+        }
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.pushed(), 5);
+        assert_eq!(buffer.evicted(), 2);
+        let messages = buffer.iter().map(|entry| entry.message.as_str()).collect::<Vec<_>>();
+        assert_eq!(messages, ["2", "3", "4"]);
+
+        // A buffer without capacity retains nothing, but still counts what was pushed
+        let mut buffer = LogBuffer::new(0);
+        buffer.push(entry(Level::Info, "dropped"));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.evicted(), 1);
+    }
+
+    #[test]
+    fn log_buffer_filters_by_level() {
+        let mut buffer = LogBuffer::new(10);
+        for level in [Level::Trace, Level::Debug, Level::Info, Level::Warn, Level::Error] {
+            buffer.push(entry(level, level.as_str()));
+        }
+        let filtered = |filter| {
+            buffer.filtered(filter).map(|entry| entry.message.as_str()).collect::<Vec<_>>()
+        };
+        assert_eq!(filtered(LevelFilter::Trace).len(), 5);
+        assert_eq!(filtered(LevelFilter::Info), ["INFO", "WARN", "ERROR"]);
+        assert_eq!(filtered(LevelFilter::Error), ["ERROR"]);
+        assert!(filtered(LevelFilter::Off).is_empty());
+
+        // Cycling the filter visits every level, then starts over
+        let mut filter = LevelFilter::Trace;
+        let mut visited = vec![];
+        for _ in 0..5 {
+            filter = next_level_filter(filter);
+            visited.push(filter);
+        }
+        assert_eq!(
+            visited,
+            [
+                LevelFilter::Debug,
+                LevelFilter::Info,
+                LevelFilter::Warn,
+                LevelFilter::Error,
+                LevelFilter::Trace
+            ]
+        );
+    }
+
+    #[test]
+    fn log_entry_display() {
+        assert_eq!(
+            entry(Level::Warn, "out of gas").to_string(),
+            "[     1.500s] WARN  miden_debug::test: out of gas"
+        );
+    }
+}
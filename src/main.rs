@@ -1,4 +1,5 @@
 #![allow(unused)]
+mod batch;
 mod config;
 mod debug;
 mod exec;
@@ -14,7 +15,8 @@ use clap::Parser;
 use miden_assembly_syntax::diagnostics::{IntoDiagnostic, Report, WrapErr};
 
 pub fn main() -> Result<(), Report> {
-    setup_diagnostics();
+    let mut config = Box::new(config::DebuggerConfig::parse());
+    setup_diagnostics(&config);
 
     // Initialize logger, but do not install it, leave that up to the command handler
     let mut builder = env_logger::Builder::from_env("MIDENC_TRACE");
@@ -37,7 +39,6 @@ pub fn main() -> Result<(), Report> {
     }
 
     let logger = Box::new(builder.build());
-    let mut config = Box::new(config::DebuggerConfig::parse());
 
     if config.working_dir.is_none() {
         let cwd = env::current_dir()
@@ -47,13 +48,41 @@ pub fn main() -> Result<(), Report> {
         config.working_dir = Some(cwd);
     }
 
+    if config.print_search_paths {
+        for path in config.library_search_paths() {
+            println!("{}", path.display());
+        }
+        return Ok(());
+    }
+
+    if config.dump_inputs {
+        for line in ui::state::resolve_inputs(&config)?.describe_inputs() {
+            println!("{line}");
+        }
+        if !config.batch {
+            return Ok(());
+        }
+    }
+
+    if config.batch {
+        if !batch::run(config, logger)? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     ui::run(config, logger)
 }
 
-fn setup_diagnostics() {
+fn setup_diagnostics(config: &config::DebuggerConfig) {
     use miden_assembly_syntax::diagnostics::reporting::{self, ReportHandlerOpts};
 
-    let result = reporting::set_hook(Box::new(|_| Box::new(ReportHandlerOpts::new().build())));
+    // The color of diagnostics can be changed at runtime via `set color`, so the handler consults
+    // the current setting each time a report is created
+    ui::style::set_color(config.color);
+    let result = reporting::set_hook(Box::new(|_| {
+        Box::new(ReportHandlerOpts::new().color(ui::style::Style::current().is_colored()).build())
+    }));
     if result.is_ok() {
         reporting::set_panic_hook();
     }
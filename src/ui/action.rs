@@ -1,3 +1,7 @@
+use miden_assembly_syntax::debuginfo::SourceId;
+
+use crate::debug::ResolvedLocation;
+
 type Command = String;
 type Args = Option<String>;
 
@@ -13,15 +17,19 @@ pub enum Action {
     #[allow(unused)]
     Refresh,
     Error(String),
-    #[allow(unused)]
     Help,
     FocusNext,
     FocusPrev,
+    /// Focus the pane at the given index of [crate::ui::layout::PaneId::ALL]
+    FocusPane(usize),
+    /// Focus the pane which was focused before the focused one
+    FocusLast,
     Focus,
     UnFocus,
     Up,
     Down,
-    #[allow(unused)]
+    PageUp,
+    PageDown,
     Submit,
     Update,
     Tab(u32),
@@ -30,6 +38,52 @@ pub enum Action {
     Go,
     Back,
     ToggleFullScreen,
+    /// Hide the focused pane
+    HidePane,
+    /// Show all of the panes which were hidden
+    ShowPanes,
+    /// Grow the focused pane
+    GrowPane,
+    /// Shrink the focused pane
+    ShrinkPane,
+    /// Toggle the rendering of values in the panes between decimal and hexadecimal
+    ToggleFormat,
+    /// Cycle the interpretation of the words shown in the memory pane
+    CycleMemoryView,
+    /// Show or hide the `u64` values of adjacent pairs of elements in the operand stack pane
+    ToggleStackPairs,
+    /// Copy the selected item of the focused pane to the clipboard, see [crate::ui::clipboard]
+    Yank,
+    /// Show the memory from the given element address onwards in the memory pane
+    ShowMemory(u32),
+    /// Enable or disable the selected breakpoint
+    ToggleBreakpointEnabled,
+    /// Create a breakpoint on the given line of the given source file, or remove those set on it
+    ToggleBreakpoint(SourceId, u32),
+    /// Show the given location in the source code pane, or if its line is 0, the line of its file
+    /// which was last selected there
+    ShowLocation(ResolvedLocation),
+    /// Select the given line of the file shown in the source code pane
+    GotoLine(u32),
+    /// Show the current location of the program in the source code pane, if it shows another file
+    SyncSource,
+    /// Select the call frame at the given index, innermost first, showing its location in the
+    /// source code pane
+    SelectFrame(usize),
+    /// Go to the given cycle, going back to the start of the program first if it has been passed
+    GotoCycle(usize),
+    /// Highlight the matches for the given text in the source code pane, as it is typed
+    Search(String),
+    /// Select the next line with a match for the current search
+    NextMatch,
+    /// Select the previous line with a match for the current search
+    PrevMatch,
+    /// Stop searching, clearing the highlighted matches
+    ClearSearch,
+    /// Toggle whether the source code pane follows the program as it executes
+    ToggleFollow,
+    /// Apply the theme selected via `set theme`
+    ThemeChanged,
     StatusLine(String),
     TimedStatusLine(String, u64),
     FocusFooter(Command, Args),
@@ -37,7 +91,17 @@ pub enum Action {
     Noop,
     ClosePopup,
     ShowDebug,
+    /// Show a popup describing the error with which the program failed, if it has
+    ShowError,
+    /// Show a popup listing the procedures of the program, see [crate::ui::procedures]
+    ShowSymbols,
+    /// Show a popup with the given title, displaying the given lines of text
+    ShowText(String, Vec<String>),
     Continue,
     Delete,
     Reload,
+    /// Restart execution of the loaded program, without reloading it
+    Reset,
+    /// Run the given shell command, then reload the program if `true`, and the command succeeded
+    Shell(String, bool),
 }
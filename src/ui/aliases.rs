@@ -0,0 +1,251 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use super::help;
+
+/// The maximum number of aliases which may be expanded in order to expand a single command
+const MAX_EXPANSION_DEPTH: usize = 16;
+
+/// The user-defined aliases of the `:` command line.
+///
+/// An alias maps a name to an expansion, which may refer to the arguments given to the alias
+/// positionally, using `$1` through `$9`. If the expansion does not refer to any arguments, they
+/// are appended to it instead.
+///
+/// Like [super::history::History], aliases are persisted to a file on disk so that they are
+/// available across sessions, and failing to read or write that file is never fatal.
+#[derive(Debug, Default)]
+pub struct Aliases {
+    path: Option<PathBuf>,
+    aliases: BTreeMap<String, String>,
+}
+
+impl Aliases {
+    /// Load the aliases stored at `path`
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let mut aliases = BTreeMap::new();
+        if let Some(path) = path.as_deref() {
+            match std::fs::read_to_string(path) {
+                Ok(content) => {
+                    for line in content.lines() {
+                        let line = line.trim();
+                        if line.is_empty() {
+                            continue;
+                        }
+                        match line.split_once(' ') {
+                            Some((name, expansion)) => {
+                                aliases.insert(name.to_string(), expansion.trim().to_string());
+                            }
+                            None => log::warn!("ignoring invalid alias '{line}'"),
+                        }
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => (),
+                Err(err) => {
+                    log::warn!("unable to read aliases from {}: {err}", path.display());
+                }
+            }
+        }
+
+        Self { path, aliases }
+    }
+
+    /// Define `name` as an alias for `expansion`, replacing any previous definition, and persist
+    /// the updated aliases.
+    pub fn define(&mut self, name: &str, expansion: &str) -> Result<(), String> {
+        let expansion = expansion.trim();
+        if name.is_empty() || name.starts_with(['!', '-']) {
+            return Err(format!("invalid alias name '{name}'"));
+        }
+        if help::lookup(name).is_some() {
+            return Err(format!("cannot define alias '{name}': it is the name of a command"));
+        }
+        if expansion.is_empty() {
+            return Err(format!("cannot define alias '{name}': its expansion is empty"));
+        }
+        if expansion.split_whitespace().next() == Some(name) {
+            return Err(format!("cannot define alias '{name}': it may not expand to itself"));
+        }
+        self.aliases.insert(name.to_string(), expansion.to_string());
+        self.save();
+        Ok(())
+    }
+
+    /// Remove the alias `name`, and persist the updated aliases.
+    ///
+    /// Returns false if there is no such alias.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let removed = self.aliases.remove(name).is_some();
+        if removed {
+            self.save();
+        }
+        removed
+    }
+
+    /// Get the expansion of the alias `name`, if defined
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.aliases.get(name).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// Iterate over the aliases, and their expansions, ordered by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.aliases.iter().map(|(name, expansion)| (name.as_str(), expansion.as_str()))
+    }
+
+    /// Expand `line`, if it invokes an alias, returning `None` if it does not.
+    ///
+    /// Aliases which expand to other aliases are expanded in turn, until the line invokes a
+    /// command. Returns an error if the aliases refer to each other cyclically, or if an
+    /// expansion refers to an argument which was not given.
+    pub fn expand(&self, line: &str) -> Result<Option<String>, String> {
+        let mut line = line.trim().to_string();
+        let mut expanded = vec![];
+        loop {
+            let (name, rest) = line.split_once(' ').unwrap_or((&line, ""));
+            let Some(expansion) = self.get(name) else {
+                break;
+            };
+            if expanded.iter().any(|alias| alias == name) {
+                return Err(format!("alias '{name}' expands to itself"));
+            }
+            if expanded.len() >= MAX_EXPANSION_DEPTH {
+                return Err(format!(
+                    "cannot expand alias '{name}': aliases are nested more than \
+                     {MAX_EXPANSION_DEPTH} deep"
+                ));
+            }
+            let args = rest.split_whitespace().collect::<Vec<_>>();
+            let next = substitute(name, expansion, &args)?;
+            expanded.push(name.to_string());
+            line = next;
+        }
+        Ok((!expanded.is_empty()).then_some(line))
+    }
+
+    fn save(&self) {
+        let Some(path) = self.path.as_deref() else {
+            return;
+        };
+        if let Some(parent) = path.parent()
+            && let Err(err) = std::fs::create_dir_all(parent)
+        {
+            log::warn!("unable to create aliases directory {}: {err}", parent.display());
+            return;
+        }
+        let content = self
+            .aliases
+            .iter()
+            .map(|(name, expansion)| format!("{name} {expansion}\n"))
+            .collect::<String>();
+        if let Err(err) = std::fs::write(path, content) {
+            log::warn!("unable to write aliases to {}: {err}", path.display());
+        }
+    }
+}
+
+/// Substitute `args` for the placeholders `$1..$9` in `expansion`, the expansion of `name`.
+///
+/// If `expansion` contains no placeholders, `args` are appended to it.
+fn substitute(name: &str, expansion: &str, args: &[&str]) -> Result<String, String> {
+    let mut result = String::with_capacity(expansion.len());
+    let mut has_placeholders = false;
+    let mut chars = expansion.chars().peekable();
+    while let Some(c) = chars.next() {
+        let index = match chars.peek().and_then(|next| next.to_digit(10)) {
+            Some(index @ 1..=9) if c == '$' => index as usize,
+            _ => {
+                result.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        has_placeholders = true;
+        let arg = args.get(index - 1).ok_or_else(|| {
+            format!("alias '{name}' expects at least {index} argument(s), got {}", args.len())
+        })?;
+        result.push_str(arg);
+    }
+    if !has_placeholders {
+        for arg in args {
+            result.push(' ');
+            result.push_str(arg);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aliases");
+
+        let mut aliases = Aliases::load(Some(path.clone()));
+        assert!(aliases.is_empty());
+        aliases.define("m32", "read $1 -t u32 -f hex").unwrap();
+        aliases.define("go", "continue").unwrap();
+        assert!(aliases.remove("go"));
+        assert!(!aliases.remove("go"));
+
+        let aliases = Aliases::load(Some(path.clone()));
+        assert_eq!(aliases.iter().collect::<Vec<_>>(), vec![("m32", "read $1 -t u32 -f hex")]);
+    }
+
+    #[test]
+    fn expand_positional_arguments() {
+        let mut aliases = Aliases::default();
+        aliases.define("m32", "read $1 -t u32 -f hex").unwrap();
+        aliases.define("swap", "read $2 -t $1").unwrap();
+        aliases.define("bi", "break in").unwrap();
+
+        assert_eq!(
+            aliases.expand("m32 0x100").unwrap().as_deref(),
+            Some("read 0x100 -t u32 -f hex")
+        );
+        assert_eq!(aliases.expand("swap u8 16").unwrap().as_deref(), Some("read 16 -t u8"));
+        assert_eq!(aliases.expand("bi *::main").unwrap().as_deref(), Some("break in *::main"));
+        assert_eq!(aliases.expand("read 8").unwrap(), None);
+        assert_eq!(
+            aliases.expand("m32").unwrap_err(),
+            "alias 'm32' expects at least 1 argument(s), got 0"
+        );
+    }
+
+    #[test]
+    fn expand_nested_aliases() {
+        let mut aliases = Aliases::default();
+        aliases.define("w", "m32 $1").unwrap();
+        aliases.define("m32", "read $1 -t u32").unwrap();
+        assert_eq!(aliases.expand("w 8").unwrap().as_deref(), Some("read 8 -t u32"));
+    }
+
+    #[test]
+    fn aliases_may_not_recurse() {
+        let mut aliases = Aliases::default();
+        assert_eq!(
+            aliases.define("loop", "loop 1").unwrap_err(),
+            "cannot define alias 'loop': it may not expand to itself"
+        );
+
+        aliases.define("ping", "pong").unwrap();
+        aliases.define("pong", "ping").unwrap();
+        assert_eq!(aliases.expand("ping").unwrap_err(), "alias 'ping' expands to itself");
+    }
+
+    #[test]
+    fn aliases_may_not_shadow_commands() {
+        let mut aliases = Aliases::default();
+        assert_eq!(
+            aliases.define("c", "continue").unwrap_err(),
+            "cannot define alias 'c': it is the name of a command"
+        );
+        assert!(aliases.define("!1", "continue").is_err());
+        assert!(aliases.define("go", " ").is_err());
+    }
+}
@@ -6,14 +6,17 @@ use ratatui::{
     layout::{Constraint, Layout},
     prelude::Rect,
 };
-use tokio::sync::mpsc;
+use tokio::sync::mpsc::{self, UnboundedSender};
 
 use super::{
-    Action,
+    Action, commands,
     pages::{Page, home::Home},
-    panes::{Pane, debug::DebugPane, footer::FooterPane, header::HeaderPane},
+    panes::{
+        Pane, error::ErrorPane, footer::FooterPane, header::HeaderPane, keys::KeyHelpPane,
+        log::LogPane, status_bar::StatusBarPane, symbols::SymbolsPane, text::TextPane,
+    },
     state::{InputMode, State},
-    tui,
+    tui::{self, Screen},
 };
 use crate::config::DebuggerConfig;
 
@@ -29,19 +32,50 @@ pub struct App {
     pub active_page: usize,
     pub footer: FooterPane,
     pub header: HeaderPane,
+    pub status_bar: StatusBarPane,
     pub popup: Option<Box<dyn Pane>>,
     pub last_tick_key_events: Vec<KeyEvent>,
     pub mode: Mode,
     pub state: State,
     pub should_quit: bool,
     pub should_suspend: bool,
+    /// The transcript of the script executed at startup, if any
+    pub script_output: Vec<String>,
+    /// Where execution stopped at startup, if it was run to the procedure given by `--break-at`
+    pub startup_status: Option<String>,
 }
 
 pub type KeyBindings = HashMap<Mode, HashMap<Vec<KeyEvent>, Action>>;
 
 impl App {
     pub async fn new(config: Box<DebuggerConfig>) -> Result<Self, Report> {
-        let state = State::new(config)?;
+        Self::with_state(State::new(config)?)
+    }
+
+    /// Construct the app for the program loaded in `state`, first running it to the procedure
+    /// given by `--break-at`, and executing the script given by `--script`, if any
+    pub fn with_state(mut state: State) -> Result<Self, Report> {
+        let mut script_output = vec![];
+        let mut should_quit = false;
+        let startup_status = state.config.break_at.clone().map(|pattern| {
+            match commands::run_to_procedure(&mut state, &pattern) {
+                Ok(status) | Err(status) => status,
+            }
+        });
+        if let Some(path) = state.config.script.clone() {
+            match std::fs::read_to_string(&path) {
+                Ok(script) => {
+                    let summary = commands::run_script(&mut state, &script, &mut |line| {
+                        script_output.push(line)
+                    });
+                    should_quit = summary.quit;
+                }
+                Err(err) => {
+                    script_output
+                        .push(format!("unable to read script '{}': {err}", path.display()));
+                }
+            }
+        }
         let home = Home::new()?;
         Ok(Self {
             pages: vec![Box::new(home)],
@@ -49,205 +83,315 @@ impl App {
             active_page: 0,
             footer: FooterPane::new(),
             header: HeaderPane::new(),
+            status_bar: StatusBarPane::new(),
             popup: None,
             last_tick_key_events: vec![],
             mode: Mode::Home,
             state,
-            should_quit: false,
+            should_quit,
             should_suspend: false,
+            script_output,
+            startup_status,
         })
     }
 
     pub async fn run(&mut self) -> Result<(), Report> {
         let (action_tx, mut action_rx) = mpsc::unbounded_channel::<Action>();
 
+        let mouse = self.state.config.mouse;
         let mut tui = tui::Tui::new()?
             .tick_rate(4.0) // 4 ticks per second
-            .frame_rate(30.0); // 30 frames per second
+            .frame_rate(30.0) // 30 frames per second
+            .mouse(mouse);
 
         // Starts event handler, enters raw mode, enters alternate screen
         tui.enter()?;
 
+        // The terminal is now ours, so it can be asked to set the system clipboard
+        self.state.clipboard.enable_system();
+
+        self.start(&action_tx)?;
+
+        loop {
+            if let Some(evt) = tui.next().await {
+                self.handle_event(evt, &action_tx)?;
+            }
+
+            while let Ok(action) = action_rx.try_recv() {
+                self.handle_action(action, &action_tx, &mut tui)?;
+            }
+
+            if self.should_suspend {
+                tui.suspend()?;
+                action_tx.send(Action::Resume).into_diagnostic()?;
+                tui = tui::Tui::new()?.mouse(mouse);
+                tui.enter()?;
+            } else if self.should_quit {
+                tui.stop()?;
+                break;
+            }
+        }
+
+        // stops event handler, exits raw mode, exits alternate screen
+        tui.exit()?;
+
+        self.state.save_layout();
+
+        Ok(())
+    }
+
+    /// Initialize the pages and panes, which send the actions they are initialized with via
+    /// `action_tx`, and show how the session started, i.e. the output of the startup script
+    pub fn start(&mut self, action_tx: &UnboundedSender<Action>) -> Result<(), Report> {
         for page in self.pages.iter_mut() {
             page.register_action_handler(action_tx.clone())?;
         }
 
         for page in self.pages.iter_mut() {
             page.init(&self.state)?;
-            page.focus()?;
+            page.focus(&self.state)?;
         }
 
         self.header.init(&self.state)?;
         self.footer.init(&self.state)?;
 
-        loop {
-            if let Some(evt) = tui.next().await {
-                let mut stop_event_propagation = self
-                    .popup
-                    .as_mut()
-                    .and_then(|pane| pane.handle_events(evt.clone(), &mut self.state).ok())
-                    .map(|response| match response {
-                        Some(tui::EventResponse::Continue(action)) => {
-                            action_tx.send(action).ok();
-                            false
-                        }
-                        Some(tui::EventResponse::Stop(action)) => {
-                            action_tx.send(action).ok();
-                            true
-                        }
-                        _ => false,
-                    })
-                    .unwrap_or(false);
-                stop_event_propagation = stop_event_propagation
-                    || self
-                        .pages
-                        .get_mut(self.active_page)
-                        .and_then(|page| page.handle_events(evt.clone(), &mut self.state).ok())
-                        .map(|response| match response {
-                            Some(tui::EventResponse::Continue(action)) => {
-                                action_tx.send(action).ok();
-                                false
-                            }
-                            Some(tui::EventResponse::Stop(action)) => {
-                                action_tx.send(action).ok();
-                                true
-                            }
-                            _ => false,
-                        })
-                        .unwrap_or(false);
-                stop_event_propagation = stop_event_propagation
-                    || self
-                        .footer
-                        .handle_events(evt.clone(), &mut self.state)
-                        .map(|response| match response {
-                            Some(tui::EventResponse::Continue(action)) => {
-                                action_tx.send(action).ok();
-                                false
-                            }
-                            Some(tui::EventResponse::Stop(action)) => {
-                                action_tx.send(action).ok();
-                                true
-                            }
-                            _ => false,
-                        })
-                        .unwrap_or(false);
+        if let Some(status) = self.startup_status.take() {
+            action_tx.send(Action::StatusLine(status)).into_diagnostic()?;
+        }
 
-                if !stop_event_propagation {
-                    match evt {
-                        tui::Event::Quit if self.state.input_mode == InputMode::Normal => {
-                            action_tx.send(Action::Quit).into_diagnostic()?
-                        }
-                        tui::Event::Tick => action_tx.send(Action::Tick).into_diagnostic()?,
-                        tui::Event::Render => action_tx.send(Action::Render).into_diagnostic()?,
-                        tui::Event::Resize(x, y) => {
-                            action_tx.send(Action::Resize(x, y)).into_diagnostic()?
-                        }
-                        tui::Event::Key(key) => {
-                            if let Some(keymap) = self.keybindings.get(&self.mode) {
-                                if let Some(action) = keymap.get(&vec![key]) {
-                                    action_tx.send(action.clone()).into_diagnostic()?;
-                                } else {
-                                    // If the key was not handled as a single key action,
-                                    // then consider it for multi-key combinations.
-                                    self.last_tick_key_events.push(key);
-
-                                    if let Some(action) = keymap.get(&self.last_tick_key_events) {
-                                        action_tx.send(action.clone()).into_diagnostic()?;
-                                    }
-                                }
-                            }
-                        }
-                        _ => (),
-                    }
-                }
-            }
+        // The output of the script is shown directly, rather than via an action, as it has
+        // already been written to the session log, if any
+        if !self.script_output.is_empty() {
+            let output = core::mem::take(&mut self.script_output);
+            self.popup = Some(Box::new(TextPane::new("Script".into(), output)));
+        }
+        Ok(())
+    }
 
-            while let Ok(action) = action_rx.try_recv() {
-                if action != Action::Tick && action != Action::Render {
-                    log::debug!("{action:?}");
+    /// Offer `evt` to the popup, the active page, and the footer in turn, until one of them stops
+    /// its propagation, sending the actions they respond with via `action_tx`. Events which are
+    /// not stopped are then translated into actions per the key bindings of the current mode.
+    pub fn handle_event(
+        &mut self,
+        evt: tui::Event,
+        action_tx: &UnboundedSender<Action>,
+    ) -> Result<(), Report> {
+        // The mouse only interacts with the panes of the page, which are hidden behind a popup
+        if self.popup.is_some() && matches!(evt, tui::Event::Mouse(_)) {
+            return Ok(());
+        }
+        let mut stop_event_propagation = self
+            .popup
+            .as_mut()
+            .and_then(|pane| pane.handle_events(evt.clone(), &mut self.state).ok())
+            .map(|response| match response {
+                Some(tui::EventResponse::Continue(action)) => {
+                    action_tx.send(action).ok();
+                    false
+                }
+                Some(tui::EventResponse::Stop(action)) => {
+                    action_tx.send(action).ok();
+                    true
                 }
-                match action {
-                    Action::Tick => {
-                        self.last_tick_key_events.clear();
+                _ => false,
+            })
+            .unwrap_or(false);
+        stop_event_propagation = stop_event_propagation
+            || self
+                .pages
+                .get_mut(self.active_page)
+                .and_then(|page| page.handle_events(evt.clone(), &mut self.state).ok())
+                .map(|response| match response {
+                    Some(tui::EventResponse::Continue(action)) => {
+                        action_tx.send(action).ok();
+                        false
                     }
-                    Action::Quit if self.state.input_mode == InputMode::Normal => {
-                        self.should_quit = true
+                    Some(tui::EventResponse::Stop(action)) => {
+                        action_tx.send(action).ok();
+                        true
                     }
-                    Action::Suspend => self.should_suspend = true,
-                    Action::Resume => self.should_suspend = false,
-                    Action::Resize(w, h) => {
-                        tui.resize(Rect::new(0, 0, w, h)).into_diagnostic()?;
-                        tui.draw(|f| {
-                            self.draw(f).unwrap_or_else(|err| {
-                                action_tx
-                                    .send(Action::Error(format!("Failed to draw: {err:?}")))
-                                    .unwrap();
-                            })
-                        })
-                        .into_diagnostic()?;
-                    }
-                    Action::Render => {
-                        tui.draw(|f| {
-                            self.draw(f).unwrap_or_else(|err| {
-                                action_tx
-                                    .send(Action::Error(format!("Failed to draw {err:?}")))
-                                    .unwrap()
-                            })
-                        })
-                        .into_diagnostic()?;
+                    _ => false,
+                })
+                .unwrap_or(false);
+        stop_event_propagation = stop_event_propagation
+            || self
+                .footer
+                .handle_events(evt.clone(), &mut self.state)
+                .map(|response| match response {
+                    Some(tui::EventResponse::Continue(action)) => {
+                        action_tx.send(action).ok();
+                        false
                     }
-                    Action::ShowDebug => {
-                        let debug_popup = DebugPane::default();
-                        self.popup = Some(Box::new(debug_popup));
+                    Some(tui::EventResponse::Stop(action)) => {
+                        action_tx.send(action).ok();
+                        true
                     }
-                    Action::ClosePopup => {
-                        if self.popup.is_some() {
-                            self.popup = None;
+                    _ => false,
+                })
+                .unwrap_or(false);
+
+        if !stop_event_propagation {
+            match evt {
+                tui::Event::Quit if self.state.input_mode == InputMode::Normal => {
+                    action_tx.send(Action::Quit).into_diagnostic()?
+                }
+                tui::Event::Tick => action_tx.send(Action::Tick).into_diagnostic()?,
+                tui::Event::Render => action_tx.send(Action::Render).into_diagnostic()?,
+                tui::Event::Resize(x, y) => {
+                    action_tx.send(Action::Resize(x, y)).into_diagnostic()?
+                }
+                tui::Event::Key(key) => {
+                    if let Some(keymap) = self.keybindings.get(&self.mode) {
+                        if let Some(action) = keymap.get(&vec![key]) {
+                            action_tx.send(action.clone()).into_diagnostic()?;
+                        } else {
+                            // If the key was not handled as a single key action,
+                            // then consider it for multi-key combinations.
+                            self.last_tick_key_events.push(key);
+
+                            if let Some(action) = keymap.get(&self.last_tick_key_events) {
+                                action_tx.send(action.clone()).into_diagnostic()?;
+                            }
                         }
                     }
-                    _ => (),
                 }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
 
-                if let Some(popup) = self.popup.as_mut() {
-                    if let Some(action) = popup.update(action.clone(), &mut self.state)? {
-                        action_tx.send(action).into_diagnostic()?;
+    /// Apply `action` to the app, and then to the popup if one is open, or otherwise the active
+    /// page, and to the header and footer, sending the actions they respond with via `action_tx`.
+    ///
+    /// The app is drawn to `screen` when the action is to render it, or to resize the terminal.
+    pub fn handle_action<S: Screen>(
+        &mut self,
+        action: Action,
+        action_tx: &UnboundedSender<Action>,
+        screen: &mut S,
+    ) -> Result<(), Report> {
+        if action != Action::Tick && action != Action::Render {
+            log::debug!("{action:?}");
+        }
+        self.state.session_log.action(self.state.executor.cycle, &action);
+        match action {
+            Action::Tick => {
+                self.last_tick_key_events.clear();
+            }
+            Action::Quit if self.state.input_mode == InputMode::Normal => self.should_quit = true,
+            Action::Suspend => self.should_suspend = true,
+            Action::Resume => self.should_suspend = false,
+            Action::Resize(w, h) => {
+                let terminal = screen.terminal();
+                terminal.resize(Rect::new(0, 0, w, h)).into_diagnostic()?;
+                terminal
+                    .draw(|f| {
+                        self.draw(f).unwrap_or_else(|err| {
+                            action_tx
+                                .send(Action::Error(format!("Failed to draw: {err:?}")))
+                                .unwrap();
+                        })
+                    })
+                    .into_diagnostic()?;
+            }
+            Action::Render => {
+                screen
+                    .terminal()
+                    .draw(|f| {
+                        self.draw(f).unwrap_or_else(|err| {
+                            action_tx
+                                .send(Action::Error(format!("Failed to draw {err:?}")))
+                                .unwrap()
+                        })
+                    })
+                    .into_diagnostic()?;
+            }
+            Action::Shell(ref command, reload) => {
+                // Leave the alternate screen while the command runs, so that its output
+                // is visible, and it can interact with the terminal
+                screen.exit()?;
+                println!("$ {command}");
+                let status = commands::shell(&self.state, command)
+                    .status()
+                    .map_err(|err| format!("unable to run '{command}': {err}"))
+                    .and_then(commands::describe_exit_status);
+                match &status {
+                    Ok(status) | Err(status) => println!("{status}"),
+                }
+                println!("press ENTER to return to the debugger");
+                let _ = std::io::stdin().read_line(&mut String::new());
+                screen.enter()?;
+                screen.terminal().clear().into_diagnostic()?;
+                match status {
+                    Ok(status) => {
+                        action_tx.send(Action::StatusLine(status)).into_diagnostic()?;
+                        if reload {
+                            action_tx.send(Action::Reload).into_diagnostic()?;
+                        }
+                    }
+                    Err(err) => {
+                        action_tx.send(Action::TimedStatusLine(err, 5)).into_diagnostic()?
                     }
-                } else if let Some(page) = self.pages.get_mut(self.active_page)
-                    && let Some(action) = page.update(action.clone(), &mut self.state)?
-                {
-                    action_tx.send(action).into_diagnostic()?;
                 }
-
-                if let Some(action) = self.header.update(action.clone(), &mut self.state)? {
-                    action_tx.send(action).into_diagnostic()?;
+            }
+            Action::ShowDebug => {
+                let log_popup = LogPane::default();
+                self.popup = Some(Box::new(log_popup));
+            }
+            Action::ShowError => {
+                if let Some(error_popup) = ErrorPane::new(&self.state) {
+                    self.popup = Some(Box::new(error_popup));
                 }
-
-                if let Some(action) = self.footer.update(action.clone(), &mut self.state)? {
-                    action_tx.send(action).into_diagnostic()?;
+            }
+            Action::Help => {
+                let help_popup = KeyHelpPane::new(&self.state.keybindings);
+                self.popup = Some(Box::new(help_popup));
+            }
+            Action::ShowSymbols => {
+                self.popup = Some(Box::new(SymbolsPane::new(&self.state)));
+            }
+            // The location is shown by the source code pane, which the popup would hide
+            Action::ShowLocation(_) => self.popup = None,
+            Action::ShowText(ref title, ref lines) => {
+                self.popup = Some(Box::new(TextPane::new(title.clone(), lines.clone())));
+            }
+            Action::ClosePopup => {
+                if self.popup.is_some() {
+                    self.popup = None;
                 }
             }
+            _ => (),
+        }
 
-            if self.should_suspend {
-                tui.suspend()?;
-                action_tx.send(Action::Resume).into_diagnostic()?;
-                tui = tui::Tui::new()?;
-                tui.enter()?;
-            } else if self.should_quit {
-                tui.stop()?;
-                break;
+        if let Some(popup) = self.popup.as_mut() {
+            if let Some(action) = popup.update(action.clone(), &mut self.state)? {
+                action_tx.send(action).into_diagnostic()?;
             }
+        } else if let Some(page) = self.pages.get_mut(self.active_page)
+            && let Some(action) = page.update(action.clone(), &mut self.state)?
+        {
+            action_tx.send(action).into_diagnostic()?;
         }
 
-        // stops event handler, exits raw mode, exits alternate screen
-        tui.exit()?;
+        if let Some(action) = self.header.update(action.clone(), &mut self.state)? {
+            action_tx.send(action).into_diagnostic()?;
+        }
 
+        if let Some(action) = self.footer.update(action.clone(), &mut self.state)? {
+            action_tx.send(action).into_diagnostic()?;
+        }
         Ok(())
     }
 
     fn draw(&mut self, frame: &mut tui::Frame<'_>) -> Result<(), Report> {
-        let vertical_layout =
-            Layout::vertical(vec![Constraint::Max(1), Constraint::Fill(1), Constraint::Max(1)])
-                .split(frame.area());
+        let vertical_layout = Layout::vertical(vec![
+            Constraint::Max(1),
+            Constraint::Fill(1),
+            Constraint::Max(1),
+            Constraint::Max(1),
+        ])
+        .split(frame.area());
 
         self.header.draw(frame, vertical_layout[0], &self.state)?;
 
@@ -258,7 +402,7 @@ impl App {
         if let Some(popup) = self.popup.as_mut() {
             let popup_vertical_layout = Layout::vertical(vec![
                 Constraint::Fill(1),
-                popup.height_constraint(),
+                Constraint::Fill(3),
                 Constraint::Fill(1),
             ])
             .split(frame.area());
@@ -271,7 +415,326 @@ impl App {
             popup.draw(frame, popup_layout[1], &self.state)?;
         }
 
-        self.footer.draw(frame, vertical_layout[2], &self.state)?;
+        self.status_bar.draw(frame, vertical_layout[2], &self.state)?;
+        self.footer.draw(frame, vertical_layout[3], &self.state)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::MouseEventKind;
+
+    use super::*;
+    use crate::{debug::BreakpointType, ui::testing::Harness};
+
+    /// The size of the terminal on which snapshots are rendered
+    const WIDTH: u16 = 100;
+    const HEIGHT: u16 = 30;
+
+    /// A harness for the program in `testdata/batch/{name}.masm`
+    fn harness(name: &str) -> Harness {
+        Harness::new(state(name), WIDTH, HEIGHT)
+    }
+
+    /// The state for the program in `testdata/batch/{name}.masm`, which is loaded relative to the
+    /// package root, so that the paths shown do not depend on where the repository is checked out
+    fn state(name: &str) -> State {
+        State::for_source_file(&std::path::PathBuf::from(format!("testdata/batch/{name}.masm")))
+    }
+
+    #[test]
+    fn snapshot_startup() {
+        let mut harness = harness("add");
+        insta::assert_snapshot!("startup", harness.render());
+    }
+
+    #[test]
+    fn snapshot_step() {
+        let mut harness = harness("add");
+        harness.keys("sssssss");
+        insta::assert_snapshot!("step", harness.render());
+    }
+
+    #[test]
+    fn snapshot_breakpoint_hit() {
+        // The breakpoint is created up front, as the status reporting its creation is only shown
+        // for a while, after which the snapshot would differ
+        let mut state = state("add");
+        state.create_breakpoint(BreakpointType::Line {
+            pattern: glob::Pattern::new("testdata/batch/add.masm").unwrap(),
+            line: 4,
+        });
+        let mut harness = Harness::new(state, WIDTH, HEIGHT);
+        harness.keys("c");
+        insta::assert_snapshot!("breakpoint_hit", harness.render());
+    }
+
+    #[test]
+    fn snapshot_error_popup() {
+        let mut harness = harness("assert");
+        harness.keys("c");
+        insta::assert_snapshot!("error_popup", harness.render());
+    }
+
+    #[test]
+    fn mouse_focus_select_and_scroll() {
+        let mut harness = Harness::new(state("add"), WIDTH, 40);
+        harness.keys("sssssss");
+        let render = harness.render();
+        let rows = render.lines().collect::<Vec<_>>();
+        assert!(rows[2].starts_with("┃  1 | begin"), "{render}");
+        assert!(rows[5].starts_with("┃  4 |     add"), "{render}");
+
+        // Clicking the number of a line toggles a breakpoint on it, and clicking its text
+        // selects it, which the mouse wheel then moves
+        harness.click(3, 5).click(20, 6).mouse(MouseEventKind::ScrollDown, 20, 20);
+        assert!(harness.app.state.breakpoints[0].is_set_on_line("testdata/batch/add.masm", 4));
+        let render = harness.render();
+        let rows = render.lines().collect::<Vec<_>>();
+        assert!(rows[1].starts_with("┏[1] Source Code"), "{render}");
+        assert!(
+            rows.iter().any(|row| row.starts_with("┗━") && row.contains("━6 of 8┛")),
+            "{render}"
+        );
+
+        // Clicking another pane focuses it
+        harness.click(80, 5);
+        let render = harness.render();
+        let rows = render.lines().collect::<Vec<_>>();
+        assert!(rows[1].starts_with("┌[1] Source Code"), "{render}");
+        assert!(rows[1].ends_with("┏[5] Operand Stack━━━━━━┓"), "{render}");
+
+        // The panes are not clicked through a popup
+        harness.action(Action::Help).click(3, 5);
+        assert_eq!(harness.app.state.breakpoints.len(), 1);
+    }
+
+    #[test]
+    fn hide_show_and_resize_panes() {
+        use crate::ui::layout::{PaneId, PaneSize};
+
+        let rows = |harness: &mut Harness| {
+            harness.render().lines().map(str::to_string).collect::<Vec<_>>()
+        };
+        let mut harness = Harness::new(state("add"), WIDTH, HEIGHT);
+
+        // Hiding the focused pane moves the focus to the next one shown
+        harness.keys("v");
+        let render = rows(&mut harness);
+        assert!(render[1].starts_with("┏[2] Disassembly"), "{render:#?}");
+        assert!(render[29].starts_with("the source pane is hidden"), "{render:#?}");
+
+        // The focused pane grows by two lines at a time
+        harness.keys(">>>");
+        let render = rows(&mut harness);
+        assert!(render[13].starts_with("┗━"), "{render:#?}");
+        assert!(render[14].starts_with("┌[3] Stack Trace"), "{render:#?}");
+        assert!(render[29].starts_with("the disassembly pane is 13 lines"), "{render:#?}");
+
+        // Panes are hidden until every pane of a column is, which gives its width to the other
+        for _ in 0..4 {
+            harness.keys("lv");
+        }
+        let layout = &harness.app.state.layout;
+        let hidden = PaneId::ALL.into_iter().filter(|id| !layout.is_visible(*id));
+        assert_eq!(
+            hidden.collect::<Vec<_>>(),
+            [
+                PaneId::Source,
+                PaneId::CallStack,
+                PaneId::Stack,
+                PaneId::Watch,
+                PaneId::Timeline
+            ]
+        );
+        harness.keys("llvv");
+        let render = rows(&mut harness);
+        assert!(render[1].starts_with("┏[2] Disassembly"), "{render:#?}");
+        assert!(render[1].ends_with("at cycle 0┓"), "{render:#?}");
+        assert!(render.iter().all(|row| !row.contains("Advice")), "{render:#?}");
+        assert!(render[14].starts_with("┌[4] Memory"), "{render:#?}");
+        assert!(render[27].starts_with("└─"), "{render:#?}");
+
+        // The last pane shown cannot be hidden
+        harness.keys("v").keys("v");
+        let render = rows(&mut harness);
+        assert!(render[29].starts_with("the memory pane is the only one shown"), "{render:#?}");
+
+        // All panes are shown again, at the sizes they were given
+        harness.keys("V").keys("hh<<<");
+        let layout = &harness.app.state.layout;
+        assert!(PaneId::ALL.into_iter().all(|id| layout.is_visible(id)));
+        assert_eq!(layout.get(PaneId::Disassembly).size, PaneSize::Lines(7));
+        assert_eq!(layout.get(PaneId::Memory).size, PaneSize::Weight(1));
+    }
+
+    #[test]
+    fn focus_panes_by_number() {
+        /// The number of the focused pane, i.e. that whose title is drawn with a thick border
+        fn focused(render: &str) -> char {
+            let (_, title) = render.split_once("┏[").expect("a pane is focused");
+            title.chars().next().unwrap()
+        }
+
+        let mut harness = Harness::new(state("add"), WIDTH, 40);
+        let render = harness.keys("\t").render();
+        assert_eq!(focused(&render), '1');
+        assert!(render.lines().last().unwrap().starts_with("no other pane has been focused"));
+
+        // Panes are focused by their number, and Tab returns to the one focused before
+        assert_eq!(focused(&harness.keys("5").render()), '5');
+        assert_eq!(focused(&harness.keys("\t").render()), '1');
+        assert_eq!(focused(&harness.keys("\t").render()), '5');
+        assert_eq!(focused(&harness.keys("2").keys("2").keys("\t").render()), '5');
+
+        // Hidden panes keep their number, but are not focused, whether by number, by Tab, or by
+        // cycling through the panes
+        harness.keys("3v");
+        let render = harness.render();
+        assert_eq!(focused(&render), '4');
+        assert!(!render.contains("Stack Trace"), "{render}");
+        for keys in ["\t", "3"] {
+            let render = harness.keys(keys).render();
+            assert_eq!(focused(&render), '4');
+            let status = render.lines().last().unwrap();
+            assert!(status.starts_with("the callstack pane is hidden"), "{render}");
+        }
+        assert_eq!(focused(&harness.keys("h").render()), '2');
+        assert_eq!(focused(&harness.keys("l").render()), '4');
+
+        // Once shown again, they are
+        harness.keys("V");
+        assert_eq!(focused(&harness.keys("3").render()), '3');
+        assert_eq!(focused(&harness.keys("\t").render()), '4');
+    }
+
+    #[test]
+    fn footer_commands() {
+        let rows = |harness: &mut Harness| {
+            harness.render().lines().map(str::to_string).collect::<Vec<_>>()
+        };
+        // The memory pane is only given lines in a taller terminal
+        let mut harness = Harness::new(state("add"), WIDTH, 40);
+
+        // The command line accepts the same commands as scripts, e.g. aliases of commands
+        harness.keys(":b testdata/batch/add.masm:4\n").keys(":c\n");
+        assert!(harness.app.state.breakpoints[0].is_set_on_line("testdata/batch/add.masm", 4));
+        let render = rows(&mut harness);
+        assert!(render[5].starts_with("┃→●4 |     add"), "{render:#?}");
+
+        // Textual output is shown in a popup
+        harness.keys(":backtrace\n");
+        let render = rows(&mut harness);
+        assert!(render[9].contains("│#0 $exec::$main at add.masm:4"), "{render:#?}");
+
+        // Commands acting on a pane are carried out by it
+        harness.keys("q").keys(":mem 0x100\n");
+        let render = rows(&mut harness);
+        assert!(render[32].starts_with("│ 0x00000100 0  0  0  0"), "{render:#?}");
+    }
+
+    #[test]
+    fn footer_commands_reload_panes() {
+        let rows = |harness: &mut Harness| {
+            harness.render().lines().map(str::to_string).collect::<Vec<_>>()
+        };
+        // The panes start over when the program is rerun, however the command is given
+        for commands in [":run\n", ":alias rr run\n:rr\n", ":run\nsssssss:!1\n"] {
+            let mut harness = Harness::new(state("add"), WIDTH, HEIGHT);
+            harness.keys("sssssss").keys(commands);
+            let render = rows(&mut harness);
+            assert!(render[1].contains("no source code available"), "{commands:?}: {render:#?}");
+        }
+    }
+
+    #[test]
+    fn yank_and_paste() {
+        let rows = |harness: &mut Harness| {
+            harness.render().lines().map(str::to_string).collect::<Vec<_>>()
+        };
+        let mut harness = Harness::new(state("add"), WIDTH, HEIGHT);
+        harness.keys("sssssss");
+
+        // The selected line of the source code is copied, and without a terminal, only to the
+        // register of the TUI
+        harness.keys("jjy");
+        assert_eq!(harness.app.state.clipboard.register(), Some("    add"));
+        let render = rows(&mut harness);
+        assert!(render[29].starts_with("copied line 4 to the register"), "{render:#?}");
+
+        // As is the selected frame of the stack trace, the top word of the memory pane, and the
+        // selected element of the operand stack
+        harness.keys("lly");
+        let frame = harness.app.state.clipboard.register().unwrap();
+        assert!(frame.starts_with("$exec::$main at testdata/batch/add.masm:"), "{frame}");
+        harness.keys("ly");
+        assert_eq!(harness.app.state.clipboard.register(), Some("[0, 0, 0, 0]"));
+        harness.keys("ljy");
+        assert_eq!(harness.app.state.clipboard.register(), Some("0"));
+        harness.keys("ky");
+        assert_eq!(harness.app.state.clipboard.register(), Some("1"));
+        let render = rows(&mut harness);
+        assert!(
+            render[29].starts_with("copied element 0 of the stack to the register"),
+            "{render:#?}"
+        );
+
+        // The text copied last is pasted into the command line
+        harness.keys("P");
+        let render = rows(&mut harness);
+        assert!(render[29].starts_with(":1 "), "{render:#?}");
+    }
+
+    #[test]
+    fn rebound_keys() {
+        let rows = |harness: &mut Harness| {
+            harness.render().lines().map(str::to_string).collect::<Vec<_>>()
+        };
+        let mut state = state("add");
+        state.keybindings = crate::ui::keybindings::KeyBindings::parse_str(
+            r#"
+[global]
+l = "none"
+L = "none"
+w = "focus-next"
+u = "none"
+U = "toggle-u64"
+
+[popup]
+q = "none"
+x = "close"
+"#,
+        )
+        .unwrap();
+        let mut harness = Harness::new(state, WIDTH, HEIGHT);
+
+        // The hint shows the keys as they are bound
+        let render = rows(&mut harness);
+        assert!(render[29].starts_with("[w,h → pane movement]"), "{render:#?}");
+
+        // Keys handled by the panes and popups themselves are bound the same way
+        harness.keys("5u");
+        assert!(!rows(&mut harness)[29].starts_with("u64 values"));
+        harness.keys("U");
+        let render = rows(&mut harness);
+        assert!(render[29].starts_with("u64 values are shown"), "{render:#?}");
+        harness.keys(":help\n").keys("q");
+        assert!(harness.app.popup.is_some());
+        harness.keys("x");
+        assert!(harness.app.popup.is_none());
+    }
+
+    #[test]
+    fn snapshot_memory_pane() {
+        let state =
+            State::for_source("begin\n    push.7 mem_store.256\n    push.9 mem_store.257\nend\n");
+        let mut harness = Harness::new(state, WIDTH, HEIGHT);
+        harness.keys("c").keys(":mem 256\n");
+        for _ in 0..3 {
+            harness.action(Action::FocusNext);
+        }
+        harness.action(Action::ToggleFullScreen);
+        insta::assert_snapshot!("memory_pane", harness.render());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::ui::{action::Action, state::State};
+
+/// The text last yanked from a pane of the TUI, which can be pasted into the command line.
+///
+/// Yanked text is kept in a register of the TUI, and with the `clipboard` feature, it is also
+/// copied to the system clipboard once enabled via [Clipboard::enable_system], which is only done
+/// while the TUI owns a terminal. The system clipboard is set via the OSC 52 escape sequence, which
+/// the terminal emulator carries out, so that it works the same on any platform, and over SSH.
+#[derive(Debug, Default)]
+pub struct Clipboard {
+    register: Option<String>,
+    /// Whether yanked text is also copied to the system clipboard
+    system: bool,
+}
+
+/// Where yanked text was copied to, see [Clipboard::yank]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Destination {
+    /// The register, and the system clipboard, if the terminal supports OSC 52
+    System,
+    /// Only the register of the TUI
+    Register,
+}
+
+impl Destination {
+    /// Describe `what` as having been copied here, for the status line
+    fn describe(self, what: &str) -> String {
+        match self {
+            // Terminals do not acknowledge OSC 52, nor report whether they support it, so the
+            // text can only be said to have been sent
+            Self::System => format!("sent {what} to the terminal clipboard (OSC 52)"),
+            Self::Register => format!("copied {what} to the register"),
+        }
+    }
+}
+
+impl Clipboard {
+    /// Copy yanked text to the system clipboard too, if this build supports it
+    pub fn enable_system(&mut self) {
+        self.system = cfg!(feature = "clipboard");
+    }
+
+    /// The text last yanked, if any
+    pub fn register(&self) -> Option<&str> {
+        self.register.as_deref()
+    }
+
+    /// Copy `text` to the register, and to the system clipboard if enabled, falling back to the
+    /// register alone if it cannot be written to
+    pub fn yank(&mut self, text: String) -> Destination {
+        let destination = match self.system && copy_to_system(&text).is_ok() {
+            true => Destination::System,
+            false => Destination::Register,
+        };
+        self.register = Some(text);
+        destination
+    }
+}
+
+/// Yank `text`, returning the status line telling the user where `what`, i.e. a description of
+/// the text, was copied to
+pub fn yank(state: &mut State, what: &str, text: String) -> Action {
+    let destination = state.clipboard.yank(text);
+    Action::TimedStatusLine(destination.describe(what), 3)
+}
+
+/// Ask the terminal to set the system clipboard to `text`
+#[cfg(feature = "clipboard")]
+fn copy_to_system(text: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    use base64::Engine;
+
+    // The TUI is drawn to stderr, so the sequence is written there too
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stderr = std::io::stderr();
+    write!(stderr, "\x1b]52;c;{encoded}\x07")?;
+    stderr.flush()
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn copy_to_system(_text: &str) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yank_to_register() {
+        // The system clipboard is only written to once enabled, which tests never do
+        let mut clipboard = Clipboard::default();
+        assert_eq!(clipboard.register(), None);
+        assert_eq!(clipboard.yank("0x3".into()), Destination::Register);
+        assert_eq!(clipboard.register(), Some("0x3"));
+        assert_eq!(clipboard.yank("[1, 2, 3, 4]".into()), Destination::Register);
+        assert_eq!(clipboard.register(), Some("[1, 2, 3, 4]"));
+    }
+
+    #[test]
+    fn describe_destination() {
+        assert_eq!(
+            Destination::System.describe("line 4"),
+            "sent line 4 to the terminal clipboard (OSC 52)"
+        );
+        assert_eq!(Destination::Register.describe("line 4"), "copied line 4 to the register");
+    }
+}
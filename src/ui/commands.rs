@@ -0,0 +1,3461 @@
+use std::{ops::RangeInclusive, path::Path, sync::Arc};
+
+use miden_core::field::PrimeField64;
+use miden_debug_types::{SourceFile, SourceManagerExt, SourceSpan, Uri};
+use miden_processor::Felt as RawFelt;
+
+use crate::{
+    config::{ColorChoice, DebuggerConfig, OutputFormat, ResultType, parse_tab_width},
+    debug::{self, BreakpointType, CycleMeasurement, ReadMemoryExpr, ResolvedLocation},
+    exec::{ExecutionConfig, ExecutionTrace, StackProvenance},
+    felt::{Felt, ProgramArg},
+    ui::{action::Action, count::HumanCount, help, panes::error::error_report, state::State},
+};
+
+/// The maximum depth to which scripts may `source` other scripts
+const MAX_SCRIPT_DEPTH: usize = 16;
+
+/// The number of source lines shown by each `list` command
+const LIST_SIZE: u32 = 10;
+
+/// The number of lines shown by `measure`, unless specified
+pub const DEFAULT_MEASURE_LINES: usize = 10;
+
+/// The number of entries shown by `history`, unless specified
+const DEFAULT_HISTORY_LINES: usize = 20;
+
+/// The maximum number of similarly named procedures suggested by `digest`
+const MAX_PROCEDURE_SUGGESTIONS: usize = 5;
+
+/// The lines of a source file most recently shown by `list`
+pub struct SourceListing {
+    source_file: Arc<SourceFile>,
+    lines: RangeInclusive<u32>,
+    /// The cycle at which the listing was shown, as paging only continues while stopped there
+    cycle: usize,
+}
+
+/// Execute a single debugger command, i.e. `CMD [ARGS..]`, against `state`.
+///
+/// Any textual output produced by the command is passed to `output`, one line at a time. If the
+/// command requires the UI (or other driver) to take further action, e.g. resuming execution,
+/// that action is returned.
+pub fn execute(
+    state: &mut State,
+    line: &str,
+    output: &mut dyn FnMut(String),
+) -> Result<Option<Action>, String> {
+    execute_at_depth(state, line, output, 0)
+}
+
+fn execute_at_depth(
+    state: &mut State,
+    line: &str,
+    output: &mut dyn FnMut(String),
+    depth: usize,
+) -> Result<Option<Action>, String> {
+    let line = line.trim();
+    let (cmd, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let rest = rest.trim();
+    if is_history_expansion(line) {
+        let entry = cmd[1..]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| state.history.get(index))
+            .map(|entry| entry.to_string())
+            .ok_or_else(|| format!("no such history entry '{cmd}'"))?;
+        state.history.push(entry.clone());
+        return execute_at_depth(state, &entry, output, depth);
+    }
+    if let Some(command) = line.strip_prefix('!') {
+        let command = command.trim();
+        if command.is_empty() {
+            return Err("invalid arguments, usage: ! COMMAND".into());
+        }
+        return Ok(Some(Action::Shell(command.to_string(), false)));
+    }
+    if let Some(expanded) = state.aliases.expand(line)? {
+        return execute_at_depth(state, &expanded, output, depth);
+    }
+    // `LINE` is shorthand for `goto LINE`
+    if rest.is_empty() && cmd.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(Some(Action::GotoLine(parse_line(cmd)?)));
+    }
+    // The format of `examine` is given as a suffix of the command, as in `x/4xw`
+    let (cmd, format) = match cmd.split_once('/') {
+        Some((cmd @ ("x" | "examine"), format)) => (cmd, format),
+        _ => (cmd, ""),
+    };
+    let Some(command) = help::lookup(cmd) else {
+        log::debug!("unknown command: '{line}'");
+        return Err(match help::suggest(cmd) {
+            Some(suggestion) => format!("unknown command, did you mean '{suggestion}'?"),
+            None => "unknown command".into(),
+        });
+    };
+    match command.name {
+        "break" if !rest.is_empty() => {
+            let current = state.executor.callstack.current_frame();
+            let current = current.and_then(|frame| frame.last_resolved(&state.source_manager));
+            let ty = rest.parse::<BreakpointType>()?.resolve(current)?;
+            state.create_breakpoint(ty);
+            Ok(Some(Action::TimedStatusLine("breakpoint created".to_string(), 1)))
+        }
+        "read" => {
+            let expr = rest.parse::<ReadMemoryExpr>()?;
+            let result = state.read_memory(&expr)?;
+            Ok(Some(Action::StatusLine(result)))
+        }
+        "examine" => examine(state, format, rest),
+        // `mem ADDR TYPE` reads a value of TYPE, as `read` does
+        "mem" if rest.contains(char::is_whitespace) => {
+            let expr = rest.parse::<ReadMemoryExpr>()?;
+            let result = state.read_memory(&expr)?;
+            Ok(Some(Action::StatusLine(result)))
+        }
+        "mem" if !rest.is_empty() => {
+            let addr = match rest.strip_prefix('@') {
+                // Symbols are at byte addresses, and the pane shows whole elements
+                Some(name) => match state.symbols.get(name)? {
+                    addr if addr % 4 == 0 => addr / 4,
+                    addr => {
+                        return Err(format!(
+                            "symbol '@{name}' is at byte address {addr:#x}, which is not \
+                             element-aligned, use `mem {:#x}` to show the element containing it",
+                            addr / 4
+                        ));
+                    }
+                },
+                None => {
+                    let addr = parse_u64(rest, "address")?;
+                    u32::try_from(addr)
+                        .map_err(|_| format!("invalid address '{rest}': it is out of bounds"))?
+                }
+            };
+            Ok(Some(Action::ShowMemory(addr)))
+        }
+        "continue" if rest.bytes().all(|b| b.is_ascii_digit()) => {
+            let n = parse_step_count(rest)?;
+            let action = resume(state, None)?;
+            state.continue_count = (n > 1).then_some(n);
+            Ok(action)
+        }
+        "step" => {
+            let n = parse_step_count(rest)?;
+            resume(state, Some(BreakpointType::StepInstructions { n, completed: 0 }))
+        }
+        "stepi" => match parse_step_count(rest)? {
+            1 => resume(state, Some(BreakpointType::Step)),
+            n => resume(state, Some(BreakpointType::StepN(n))),
+        },
+        "next" if rest.is_empty() => resume(state, Some(BreakpointType::Next)),
+        "finish" if rest.is_empty() => resume(state, Some(BreakpointType::Finish)),
+        "source" if !rest.is_empty() => {
+            if depth >= MAX_SCRIPT_DEPTH {
+                return Err(format!(
+                    "cannot source '{rest}': scripts are nested more than {MAX_SCRIPT_DEPTH} deep"
+                ));
+            }
+            let path = state.config.working_dir().join(rest);
+            let script = std::fs::read_to_string(&path)
+                .map_err(|err| format!("unable to read script '{}': {err}", path.display()))?;
+            let summary = run_script_at_depth(state, &script, output, depth + 1);
+            if summary.failed > 0 {
+                Err(format!("{} command(s) in '{rest}' failed", summary.failed))
+            } else if summary.quit {
+                Ok(Some(Action::Quit))
+            } else {
+                Ok(None)
+            }
+        }
+        "quit" if rest.is_empty() => Ok(Some(Action::Quit)),
+        "reload" if rest.is_empty() => Ok(Some(Action::Reload)),
+        "reset" if rest.is_empty() => Ok(Some(Action::Reset)),
+        "make" if rest.is_empty() => Ok(Some(Action::Shell(build_command(state)?, false))),
+        "rebuild" if rest.is_empty() => Ok(Some(Action::Shell(build_command(state)?, true))),
+        "set" => {
+            let (setting, value) = rest.split_once(' ').unwrap_or((rest, ""));
+            let value = value.trim();
+            match setting {
+                "args" => state.config.args = parse_args(value)?,
+                "input-file" if !value.is_empty() => {
+                    let path = state.config.working_dir().join(value);
+                    let inputs = ExecutionConfig::parse_file(&path).map_err(|err| {
+                        format!("invalid inputs file '{}': {err}", path.display())
+                    })?;
+                    state.config.inputs = Some(inputs);
+                }
+                "output-format" => {
+                    state.config.output_format = match value {
+                        "text" => OutputFormat::Text,
+                        "json" => OutputFormat::Json,
+                        _ => {
+                            return Err(format!(
+                                "invalid output format '{value}', expected json|text"
+                            ));
+                        }
+                    };
+                    return Ok(Some(Action::StatusLine(format!("output format is {value}"))));
+                }
+                "color" => {
+                    state.config.color = match value {
+                        "on" => ColorChoice::Always,
+                        "off" => ColorChoice::Never,
+                        _ => {
+                            return Err(format!(
+                                "invalid color setting '{value}', expected on|off"
+                            ));
+                        }
+                    };
+                    super::style::set_color(state.config.color);
+                    return Ok(Some(Action::StatusLine(format!("color is {value}"))));
+                }
+                "tab-width" => {
+                    state.config.tab_width = Some(parse_tab_width(value)?);
+                    return Ok(Some(Action::StatusLine(format!("tab width is {value}"))));
+                }
+                "theme" if value.is_empty() => {
+                    let lines = state
+                        .themes
+                        .names()
+                        .map(|name| match name == state.theme.name {
+                            true => format!("{name} (selected)"),
+                            false => name.to_string(),
+                        })
+                        .collect();
+                    return Ok(Some(Action::ShowText("Themes".into(), lines)));
+                }
+                "theme" => {
+                    match state.set_theme(value)? {
+                        None => output(format!("theme is {value}")),
+                        Some(err) => {
+                            output(format!("theme is {value}, but it was not saved: {err}"))
+                        }
+                    }
+                    return Ok(Some(Action::ThemeChanged));
+                }
+                _ => {
+                    return Err("invalid arguments, expected 'args [ARG..]', 'input-file \
+                                PATH', 'color on|off', 'output-format json|text', 'theme \
+                                [NAME]', or 'tab-width N'"
+                        .into());
+                }
+            }
+            Ok(Some(Action::StatusLine(format!(
+                "{setting} will take effect on the next reload"
+            ))))
+        }
+        "run" => {
+            let args = parse_args(rest)?;
+            let previous = core::mem::replace(&mut state.config.args, args);
+            if let Err(err) = state.reload() {
+                state.config.args = previous;
+                return Err(format!("unable to reload program: {err}"));
+            }
+            Ok(Some(Action::StatusLine(format!(
+                "program reloaded with {} argument(s)",
+                state.config.args.len()
+            ))))
+        }
+        "debug" if rest.is_empty() => Ok(Some(Action::ShowDebug)),
+        "error" if rest.is_empty() => {
+            state.execution_failed.as_ref().ok_or("program has not failed")?;
+            Ok(Some(Action::ShowError))
+        }
+        "history" => {
+            let n = match rest {
+                "" => DEFAULT_HISTORY_LINES,
+                n => n.parse::<usize>().map_err(|err| format!("invalid count '{n}': {err}"))?,
+            };
+            let skip = state.history.len().saturating_sub(n);
+            let lines = state
+                .history
+                .iter()
+                .skip(skip)
+                .map(|(index, entry)| format!("{index:>5}  {entry}"))
+                .collect();
+            Ok(Some(Action::ShowText("History".into(), lines)))
+        }
+        "alias" => match rest.split_once(' ') {
+            None if rest.is_empty() => {
+                if state.aliases.is_empty() {
+                    return Ok(Some(Action::StatusLine("no aliases are defined".into())));
+                }
+                let lines = state
+                    .aliases
+                    .iter()
+                    .map(|(name, expansion)| format!("{name} = {expansion}"))
+                    .collect();
+                Ok(Some(Action::ShowText("Aliases".into(), lines)))
+            }
+            None => {
+                let expansion =
+                    state.aliases.get(rest).ok_or_else(|| format!("no such alias '{rest}'"))?;
+                Ok(Some(Action::StatusLine(format!("{rest} = {expansion}"))))
+            }
+            Some((name, expansion)) => {
+                state.aliases.define(name, expansion)?;
+                Ok(Some(Action::TimedStatusLine(format!("alias '{name}' defined"), 1)))
+            }
+        },
+        "unalias" if !rest.is_empty() => {
+            if !state.aliases.remove(rest) {
+                return Err(format!("no such alias '{rest}'"));
+            }
+            Ok(Some(Action::TimedStatusLine(format!("alias '{rest}' removed"), 1)))
+        }
+        "watch" if rest.is_empty() => {
+            if state.watches.is_empty() {
+                return Ok(Some(Action::StatusLine("no expressions are being watched".into())));
+            }
+            state.evaluate_watches();
+            Ok(Some(Action::ShowText("Watches".into(), describe_watches(state))))
+        }
+        "watch" => {
+            let index = state.watches.add(rest)?;
+            state.evaluate_watches();
+            Ok(Some(Action::TimedStatusLine(format!("watching [{index}] {}", rest.trim()), 1)))
+        }
+        "unwatch" if !rest.is_empty() => {
+            let index = rest
+                .parse::<usize>()
+                .map_err(|err| format!("invalid watch index '{rest}': {err}"))?;
+            let watch =
+                state.watches.remove(index).ok_or_else(|| format!("no such watch '{index}'"))?;
+            Ok(Some(Action::TimedStatusLine(format!("stopped watching {}", watch.text), 1)))
+        }
+        "log-save" if !rest.is_empty() => {
+            let path = state.config.working_dir().join(rest);
+            let written = crate::logger::DebugLogger::get()
+                .save(&path)
+                .map_err(|err| format!("unable to write {}: {err}", path.display()))?;
+            Ok(Some(Action::StatusLine(format!(
+                "saved {written} log record(s) to {}",
+                path.display()
+            ))))
+        }
+        "log" => {
+            let cycle = state.executor.cycle;
+            match rest {
+                "" => Ok(Some(Action::StatusLine(match state.session_log.path() {
+                    Some(path) => format!("session log is written to {}", path.display()),
+                    None => "session logging is disabled".into(),
+                }))),
+                "off" => match state.session_log.close(cycle) {
+                    Some(path) => Ok(Some(Action::StatusLine(format!(
+                        "session log written to {}",
+                        path.display()
+                    )))),
+                    None => Err("session logging is not enabled".into()),
+                },
+                file => {
+                    let path = state.config.working_dir().join(file);
+                    state.session_log.open(&path, cycle)?;
+                    Ok(Some(Action::StatusLine(format!(
+                        "session log is written to {}",
+                        path.display()
+                    ))))
+                }
+            }
+        }
+        "trace" => {
+            state.trace_user_events = match rest {
+                "" => !state.trace_user_events,
+                "on" => true,
+                "off" => false,
+                _ => return Err(format!("invalid argument '{rest}', expected 'on' or 'off'")),
+            };
+            let status = if state.trace_user_events {
+                "enabled"
+            } else {
+                "disabled"
+            };
+            Ok(Some(Action::StatusLine(format!("tracing of user trace events {status}"))))
+        }
+        "goto" if !rest.is_empty() => Ok(Some(Action::GotoLine(parse_line(rest)?))),
+        "file" if !rest.is_empty() => {
+            let source_file = match_source_file(state, rest)?;
+            output(format!("showing {}", source_file.uri()));
+            Ok(Some(Action::ShowLocation(ResolvedLocation {
+                source_file,
+                line: 0,
+                col: 1,
+                span: SourceSpan::UNKNOWN,
+            })))
+        }
+        "sync" if rest.is_empty() => Ok(Some(Action::SyncSource)),
+        "outputs" => {
+            let ty = match rest {
+                "" => None,
+                ty => Some(ty.parse::<ResultType>()?),
+            };
+            let mut lines = vec![];
+            let outputs = if state.executor.stopped {
+                if state.execution_failed.is_some() {
+                    return Err("program terminated with an error, it has no outputs".into());
+                }
+                &state.executor.stack_outputs[..]
+            } else {
+                lines.push("program has not finished, its outputs will be:".to_string());
+                &state.execution_trace()?.outputs()[..]
+            };
+            lines.push(format!("outputs: {}", format_outputs(outputs)));
+            if let Some(ty) = ty {
+                let result = ty
+                    .decode(outputs)
+                    .ok_or_else(|| format!("outputs cannot be decoded as {ty}"))?;
+                lines.push(format!("result: {result}"));
+            }
+            Ok(Some(Action::ShowText("Outputs".into(), lines)))
+        }
+        "advice" => advice(state, rest),
+        "stack" => stack(state, rest),
+        "backtrace" => backtrace(state, rest),
+        "calltree" => call_tree(state, rest),
+        "info" if rest == "package" => {
+            Ok(Some(Action::ShowText("Package".into(), describe_package(state))))
+        }
+        "info" if rest == "breakpoints" => match state.breakpoints.is_empty() {
+            true => Ok(Some(Action::StatusLine("there are no breakpoints".into()))),
+            false => Ok(Some(Action::ShowText("Breakpoints".into(), describe_breakpoints(state)))),
+        },
+        "digest" if !rest.is_empty() => {
+            use miden_assembly_syntax::DisplayHex;
+
+            let digest = procedure_digest(state, rest)?;
+            Ok(Some(Action::StatusLine(format!(
+                "{rest}: 0x{}",
+                DisplayHex::new(&digest.as_bytes())
+            ))))
+        }
+        "list" => list(state, rest),
+        "capture" => match state.capture_trace().map_err(|err| err.to_string())? {
+            true => Ok(Some(Action::StatusLine(format!(
+                "execution trace captured ({} cycles)",
+                HumanCount(state.execution_trace()?.last_cycle().as_usize())
+            )))),
+            false => {
+                Ok(Some(Action::StatusLine("the execution trace has already been captured".into())))
+            }
+        },
+        "memory-map" => memory_map(state, rest),
+        "dump" => dump(state, rest),
+        "measure" => {
+            let (ops, rest) = match rest.strip_prefix("--ops") {
+                Some(rest) => (true, rest.trim_start()),
+                None => (false, rest),
+            };
+            let n = match rest {
+                "" => DEFAULT_MEASURE_LINES,
+                n => n.parse::<usize>().map_err(|err| format!("invalid count '{n}': {err}"))?,
+            };
+            let measurement = state.measure_cycles().map_err(|err| err.to_string())?;
+            let lines = match ops {
+                true => render_op_measurement(&measurement, n),
+                false => render_measurement(&measurement, n),
+            };
+            Ok(Some(Action::ShowText("Cycles".into(), lines)))
+        }
+        "cycles" if rest.is_empty() => {
+            let cycle = state.executor.cycle;
+            let total = state.execution_trace()?.last_cycle().as_usize();
+            let progress = if total == 0 {
+                100.0
+            } else {
+                (cycle as f64 / total as f64) * 100.0
+            };
+            Ok(Some(Action::StatusLine(format!(
+                "cycle {} of {} ({progress:.1}%)",
+                HumanCount(cycle),
+                HumanCount(total)
+            ))))
+        }
+        "help" => match rest {
+            "" => Ok(Some(Action::ShowText("Help".into(), help::render_table()))),
+            name => {
+                let command = help::lookup(name).ok_or_else(|| match help::suggest(name) {
+                    Some(suggestion) => {
+                        format!("unknown command '{name}', did you mean '{suggestion}'?")
+                    }
+                    None => format!("unknown command '{name}'"),
+                })?;
+                Ok(Some(Action::ShowText(format!("Help: {}", command.name), command.render())))
+            }
+        },
+        _ => Err(format!("invalid arguments, usage: {}", command.usage)),
+    }
+}
+
+/// Parse the optional count given to `step`, `stepi` or `continue`, which defaults to 1
+fn parse_step_count(count: &str) -> Result<usize, String> {
+    match count {
+        "" => Ok(1),
+        n => match n.parse::<usize>() {
+            Ok(0) => Err("invalid count '0': must be at least 1".into()),
+            Ok(n) => Ok(n),
+            Err(err) => Err(format!("invalid count '{n}': {err}")),
+        },
+    }
+}
+
+/// Resume execution, optionally creating a one-shot breakpoint to stop at first
+fn resume(state: &mut State, stop_at: Option<BreakpointType>) -> Result<Option<Action>, String> {
+    if state.executor.stopped {
+        return Err("program has terminated, cannot continue".into());
+    }
+    if let Some(ty) = stop_at {
+        state.create_breakpoint(ty);
+    }
+    state.stopped = false;
+    Ok(Some(Action::Continue))
+}
+
+/// A summary of the execution of a script
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ScriptSummary {
+    /// The number of commands which failed
+    pub failed: usize,
+    /// Whether the script requested that the debugger quit
+    pub quit: bool,
+}
+
+/// Execute the newline-separated commands in `script`, in order.
+///
+/// Each command is echoed to `output` before it is executed, followed by any output it produces.
+/// Blank lines, and lines starting with `#`, are ignored. Execution stops at the first command
+/// which fails, unless that command is prefixed with `-`, or `--continue-on-error` was given, in
+/// which case the failure is reported, and execution continues with the next command.
+///
+/// Unlike the interactive command line, actions requested by commands are carried out before the
+/// next command is executed, e.g. `continue` runs until the program stops again.
+pub fn run_script(
+    state: &mut State,
+    script: &str,
+    output: &mut dyn FnMut(String),
+) -> ScriptSummary {
+    run_script_at_depth(state, script, output, 0)
+}
+
+fn run_script_at_depth(
+    state: &mut State,
+    script: &str,
+    output: &mut dyn FnMut(String),
+    depth: usize,
+) -> ScriptSummary {
+    let mut summary = ScriptSummary::default();
+    for line in script.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (line, ignore_errors) = match line.strip_prefix('-') {
+            Some(line) => (line.trim_start(), true),
+            None => (line, false),
+        };
+        output(format!(":{line}"));
+        // Only the top-level script is logged, as the commands of nested scripts are part of the
+        // output of the `source` command which executes them
+        let log = depth == 0;
+        if log {
+            state.session_log.command(state.executor.cycle, line);
+        }
+        // Output is passed on as soon as it can be, e.g. while a shell command is running, and
+        // logged at the cycle at which it was produced
+        let mut emit = |state: &mut State, line: String| {
+            if log {
+                state.session_log.output(state.executor.cycle, &line);
+            }
+            output(line);
+        };
+        let mut lines = vec![];
+        let result = execute_at_depth(state, line, &mut |line| lines.push(line), depth);
+        lines.into_iter().for_each(|line| emit(state, line));
+        let result = result.and_then(|action| perform(state, action, &mut emit));
+        if let Err(err) = &result {
+            emit(state, format!("error: {err}"));
+        }
+        match result {
+            Ok(true) => {
+                summary.quit = true;
+                break;
+            }
+            Ok(false) => (),
+            Err(_) => {
+                summary.failed += 1;
+                if !ignore_errors && !state.config.continue_on_error {
+                    break;
+                }
+            }
+        }
+    }
+    summary
+}
+
+/// Carry out `action` on behalf of a script, returning true if the debugger should quit.
+///
+/// The state is passed to `output` along with each line, so that the line can be logged at the
+/// cycle at which it was produced.
+fn perform(
+    state: &mut State,
+    action: Option<Action>,
+    output: &mut dyn FnMut(&mut State, String),
+) -> Result<bool, String> {
+    match action {
+        Some(Action::Quit) => return Ok(true),
+        Some(Action::Continue) => {
+            state.run_until_stopped();
+            let events = state
+                .user_trace_events
+                .iter()
+                .map(|(clk, id)| format!("trace.{id} at cycle {clk}"))
+                .collect::<Vec<_>>();
+            events.into_iter().for_each(|line| output(state, line));
+            let stop = match state.config.output_format {
+                OutputFormat::Text => describe_stop(state),
+                OutputFormat::Json => format!("@@{}", stop_notification(state)),
+            };
+            output(state, stop);
+            if state.executor.stopped
+                && state.execution_failed.is_none()
+                && let Some(warning) = cycle_budget_warning(&state.config, state.executor.cycle)
+            {
+                output(state, format!("warning: {warning}"));
+            }
+        }
+        Some(Action::Reload) => state.reload().map_err(|err| err.to_string())?,
+        Some(Action::Reset) => state.reset().map_err(|err| err.to_string())?,
+        Some(Action::Shell(command, reload)) => {
+            let status = stream_output(shell(state, &command), &mut |line| output(state, line))
+                .map_err(|err| format!("unable to run '{command}': {err}"))?;
+            output(state, describe_exit_status(status)?);
+            if reload {
+                state.reload().map_err(|err| format!("unable to reload program: {err}"))?;
+                output(state, "program reloaded".into());
+            }
+        }
+        Some(Action::StatusLine(line) | Action::TimedStatusLine(line, _)) => output(state, line),
+        Some(Action::ShowText(_, lines)) => lines.into_iter().for_each(|line| output(state, line)),
+        Some(Action::ShowError) => {
+            let report = error_report(state).into_iter().flat_map(|text| text.lines);
+            let lines = report.map(|line| line.to_string()).collect::<Vec<_>>();
+            lines.into_iter().for_each(|line| output(state, line));
+        }
+        // The panes are only shown by the TUI, so point to the commands which print instead
+        Some(Action::ShowMemory(_)) if state.config.batch => {
+            output(state, "warning: the memory pane is only shown in the TUI, see 'read'".into())
+        }
+        Some(Action::GotoLine(_) | Action::ShowLocation(_) | Action::SyncSource)
+            if state.config.batch =>
+        {
+            output(
+                state,
+                "warning: the source code pane is only shown in the TUI, see 'list'".into(),
+            )
+        }
+        Some(Action::ShowDebug) if state.config.batch => {
+            output(state, "warning: the debug log is only shown in the TUI, see 'log-save'".into())
+        }
+        _ => (),
+    }
+    Ok(false)
+}
+
+/// Returns true if `line` is a history expansion, i.e. `!N`
+pub fn is_history_expansion(line: &str) -> bool {
+    line.strip_prefix('!').is_some_and(|index| index.parse::<usize>().is_ok())
+}
+
+/// The name of the command `line` invokes once its history expansion and aliases are expanded,
+/// as [execute] does, or `None` if it does not invoke a known command
+pub fn command_name(state: &State, line: &str) -> Option<&'static str> {
+    let mut line = line.trim().to_string();
+    if is_history_expansion(&line) {
+        line = state.history.get(line[1..].parse().ok()?)?.to_string();
+    }
+    if let Ok(Some(expanded)) = state.aliases.expand(&line) {
+        line = expanded;
+    }
+    let cmd = line.split_whitespace().next()?;
+    help::lookup(cmd).map(|command| command.name)
+}
+
+/// Returns the command used to rebuild the program, as given by `--build-cmd`
+fn build_command(state: &State) -> Result<String, String> {
+    state
+        .config
+        .build_cmd
+        .clone()
+        .ok_or_else(|| "no build command has been configured, see --build-cmd".into())
+}
+
+/// Construct a [std::process::Command] which runs `command` via the shell, in the working
+/// directory of the debugger
+pub fn shell(state: &State, command: &str) -> std::process::Command {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = std::process::Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = std::process::Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    };
+    shell.current_dir(state.config.working_dir());
+    shell
+}
+
+/// Run `command`, passing each line it writes to stdout or stderr to `output` as soon as it is
+/// written, rather than once the command exits
+fn stream_output(
+    mut command: std::process::Command,
+    output: &mut dyn FnMut(String),
+) -> std::io::Result<std::process::ExitStatus> {
+    use std::process::Stdio;
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    std::thread::scope(|scope| {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        scope.spawn({
+            let sender = sender.clone();
+            move || forward_lines(stdout, sender)
+        });
+        scope.spawn(move || forward_lines(stderr, sender));
+        receiver.into_iter().for_each(&mut *output);
+    });
+    child.wait()
+}
+
+/// Send each line read from `stream` to `sender`, until either is closed
+fn forward_lines(stream: impl std::io::Read, sender: std::sync::mpsc::Sender<String>) {
+    use std::io::BufRead;
+
+    for line in std::io::BufReader::new(stream).split(b'\n') {
+        let Ok(mut line) = line else { break };
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        if sender.send(String::from_utf8_lossy(&line).into_owned()).is_err() {
+            break;
+        }
+    }
+}
+
+/// Describe the exit status of a shell command, returning an error if the command failed
+pub fn describe_exit_status(status: std::process::ExitStatus) -> Result<String, String> {
+    if status.success() {
+        Ok("command exited successfully".into())
+    } else {
+        Err(format!("command failed with {status}"))
+    }
+}
+
+/// Parse whitespace-separated program arguments, e.g. `1 0x2`
+fn parse_args(args: &str) -> Result<Vec<ProgramArg>, String> {
+    args.split_whitespace()
+        .map(|arg| {
+            arg.parse::<ProgramArg>()
+                .map_err(|err| format!("invalid argument '{arg}': {err}"))
+        })
+        .collect()
+}
+
+/// Describe the package being debugged, i.e. `info package`: its name, digest, dependencies, and
+/// the procedures it exports.
+///
+/// A dependency is resolved if one of the libraries loaded for the program has the same digest.
+fn describe_package(state: &State) -> Vec<String> {
+    use miden_assembly_syntax::DisplayHex;
+    use miden_mast_package::PackageExport;
+
+    let package = &state.package;
+    let mut lines = vec![format!("name: {}", package.name)];
+    if let Some(version) = package.version.as_ref() {
+        lines.push(format!("version: {version}"));
+    }
+    if let Some(description) = package.description.as_deref() {
+        lines.push(format!("description: {description}"));
+    }
+    lines.push(format!("kind: {}", package.kind));
+    lines.push(format!("digest: 0x{}", DisplayHex::new(&package.digest().as_bytes())));
+
+    lines.push(format!("dependencies ({}):", package.manifest.num_dependencies()));
+    for dependency in package.manifest.dependencies() {
+        let resolved = state.libraries.iter().any(|lib| *lib.digest() == dependency.digest);
+        lines.push(format!(
+            "  {} 0x{} ({})",
+            dependency_name(&dependency.name),
+            DisplayHex::new(&dependency.digest.as_bytes()),
+            if resolved { "resolved" } else { "unresolved" }
+        ));
+    }
+
+    let procedures = package
+        .manifest
+        .exports()
+        .filter_map(|export| match export {
+            PackageExport::Procedure(procedure) => Some(procedure),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    lines.push(format!("exported procedures ({}):", procedures.len()));
+    for procedure in procedures {
+        lines.push(format!(
+            "  {} 0x{}",
+            procedure.path,
+            DisplayHex::new(&procedure.digest.as_bytes())
+        ));
+    }
+    lines
+}
+
+/// List the breakpoints, each with the number of times it was hit, and for those at a location,
+/// the number of times execution passed through it, whether or not it stopped there
+fn describe_breakpoints(state: &State) -> Vec<String> {
+    let specs = state
+        .breakpoints
+        .iter()
+        .map(|bp| match &bp.ty {
+            BreakpointType::Step => "step".to_string(),
+            BreakpointType::StepN(n) => format!("at {}", bp.creation_cycle + n),
+            BreakpointType::StepTo(cycle) => format!("at {cycle}"),
+            BreakpointType::StepInstructions { n, completed } => {
+                format!("after {n} instructions ({completed} completed)")
+            }
+            BreakpointType::StepOps { n, started } => format!("after {n} ops ({started} started)"),
+            BreakpointType::Next => "next".to_string(),
+            BreakpointType::Finish => "finish".to_string(),
+            BreakpointType::File(pattern) => pattern.to_string(),
+            BreakpointType::Line { pattern, line } => format!("{pattern}:{line}"),
+            BreakpointType::RelativeLine(offset) => format!("{offset:+}"),
+            BreakpointType::CurrentFileLine(line) => format!(":{line}"),
+            BreakpointType::Opcode(op) => format!("opcode {op}"),
+            BreakpointType::Called(pattern) => format!("in {pattern}"),
+            BreakpointType::ContextSwitch => "context".to_string(),
+        })
+        .collect::<Vec<_>>();
+    let width = specs.iter().map(|spec| spec.chars().count()).max().unwrap_or(0);
+    state
+        .breakpoints
+        .iter()
+        .zip(specs)
+        .map(|(bp, spec)| {
+            let enabled = if bp.enabled { "x" } else { " " };
+            let mut line = format!("{:>3} [{enabled}] {spec:<width$}  hits: {}", bp.id, bp.hits);
+            if bp.is_located() {
+                line.push_str(&format!(", passes: {}", bp.passes));
+            }
+            line
+        })
+        .collect()
+}
+
+/// Read memory in the format of GDB, i.e. `x/NFU ADDR`, see [debug::ExamineFormat]
+///
+/// Each unit is read as a value of the corresponding integer type at its byte address, as by
+/// `read -m byte`, and the units are shown several to a line, each line starting with the address
+/// of its first unit.
+fn examine(state: &State, format: &str, rest: &str) -> Result<Option<Action>, String> {
+    use debug::{Address, ExamineFormat, FieldNames, MemoryMode, NativePtr};
+
+    let spec = format.parse::<ExamineFormat>()?;
+    if rest.is_empty() || rest.contains(char::is_whitespace) {
+        return Err("invalid arguments, usage: x/NFU ADDR".into());
+    }
+    let addr = match rest.parse::<Address>()? {
+        Address::Addr(addr) => addr,
+        Address::Symbol(name) => state.symbols.get(&name)?,
+    };
+    let mut lines = vec![];
+    let mut line = String::new();
+    for i in 0..(spec.count as u32) {
+        let unit_addr = i
+            .checked_mul(spec.size)
+            .and_then(|offset| addr.checked_add(offset))
+            .ok_or_else(|| format!("invalid read: address {addr:#x} is out of bounds"))?;
+        if (i as usize).is_multiple_of(spec.units_per_line()) {
+            if !line.is_empty() {
+                lines.push(core::mem::take(&mut line));
+            }
+            line = format!("{unit_addr:#010x}:");
+        }
+        let expr = ReadMemoryExpr {
+            addr: NativePtr::from_ptr(unit_addr),
+            symbol: None,
+            ty: spec.ty(),
+            fields: FieldNames::default(),
+            count: 1,
+            mode: MemoryMode::Byte,
+            format: spec.format,
+        };
+        let value = state.read_memory(&expr)?;
+        // Pad hex and binary values to the width of a unit, as GDB does
+        let value = match spec.format {
+            debug::FormatType::Hex => format!("0x{value:0>width$}", width = spec.size as usize * 2),
+            debug::FormatType::Binary => {
+                format!("{value:0>width$}", width = spec.size as usize * 8)
+            }
+            debug::FormatType::Decimal => value,
+        };
+        line.push_str("  ");
+        line.push_str(&value);
+    }
+    lines.push(line);
+    Ok(Some(Action::ShowText("Examine".into(), lines)))
+}
+
+/// Resolve the procedure named `name` to the digest of its MAST root, i.e. `digest NAME`
+///
+/// If the package is a library, the name is first resolved among its exports. Failing that, it is
+/// looked up among the procedures exported by the package and the loaded libraries, and the
+/// procedure names recorded in the package's MAST forest.
+fn procedure_digest(state: &State, name: &str) -> Result<miden_core::Word, String> {
+    use miden_mast_package::{MastArtifact, PackageExport};
+
+    let name = name.trim_start_matches("::");
+    if let MastArtifact::Library(library) = &state.package.mast
+        && let Some(digest) = library.get_procedure_root_by_path(miden_assembly::Path::new(name))
+    {
+        return Ok(digest);
+    }
+
+    let mut procedures = vec![];
+    for export in state.package.manifest.exports() {
+        if let PackageExport::Procedure(procedure) = export {
+            procedures.push((procedure.path.to_string(), procedure.digest));
+        }
+    }
+    for (digest, name) in state.package.procedure_names() {
+        procedures.push((format!("{:#}", rustc_demangle::demangle(name)), digest));
+    }
+    for library in state.libraries.iter() {
+        for export in library.exports().filter_map(|export| export.as_procedure()) {
+            if let Some(digest) = library.get_procedure_root_by_path(&export.path) {
+                procedures.push((export.path.to_string(), digest));
+            }
+        }
+    }
+    for (candidate, _) in procedures.iter_mut() {
+        *candidate = candidate.trim_start_matches("::").to_string();
+    }
+    if let Some((_, digest)) = procedures.iter().find(|(candidate, _)| candidate == name) {
+        return Ok(*digest);
+    }
+
+    // Suggest procedures with a similar name, or with the same name in another module
+    let proc_name = |path: &str| path.rsplit("::").next().unwrap_or(path).to_string();
+    let mut suggestions = procedures
+        .into_iter()
+        .map(|(candidate, _)| (help::edit_distance(name, &candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= 3 || proc_name(candidate) == proc_name(name))
+        .collect::<Vec<_>>();
+    suggestions.sort();
+    suggestions.dedup();
+    match suggestions.len() {
+        0 => Err(format!("no procedure named '{name}' was found")),
+        _ => Err(format!(
+            "no procedure named '{name}' was found, did you mean: {}?",
+            suggestions
+                .into_iter()
+                .take(MAX_PROCEDURE_SUGGESTIONS)
+                .map(|(_, candidate)| candidate)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// The name of a package dependency
+///
+/// [miden_mast_package::DependencyName] provides no access to the underlying string, but serializes as one.
+fn dependency_name(name: &miden_mast_package::DependencyName) -> String {
+    use miden_core::serde::{Deserializable, Serializable};
+
+    String::read_from_bytes(&name.to_bytes()).unwrap_or_else(|_| format!("{name:?}"))
+}
+
+/// Show the call stack, innermost frame first, i.e. `backtrace [N]`, where `N` is the maximum
+/// number of arguments shown for each frame
+fn backtrace(state: &State, args: &str) -> Result<Option<Action>, String> {
+    const DEFAULT_NUM_ARGUMENTS: usize = 4;
+
+    let num_arguments = match args {
+        "" => DEFAULT_NUM_ARGUMENTS,
+        n => n.parse::<usize>().map_err(|err| format!("invalid count '{n}': {err}"))?,
+    };
+    let frames = state.executor.callstack.frames();
+    if frames.is_empty() {
+        return Ok(Some(Action::StatusLine("the call stack is empty".into())));
+    }
+
+    let lines = frames
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, frame)| {
+            let name = frame.procedure("");
+            let mut line = format!("#{i} {}", name.as_deref().unwrap_or("<unknown>"));
+            if let Some(arguments) = frame.arguments(num_arguments) {
+                line.push_str(&format!("(args: {})", format_outputs(arguments)));
+            }
+            if let Some(loc) = frame.last_resolved(&state.source_manager) {
+                let file = Path::new(loc.source_file.uri().as_str());
+                let file = file.file_name().map(Path::new).unwrap_or(file);
+                line.push_str(&format!(" at {}:{}", file.display(), loc.line));
+            }
+            line
+        })
+        .collect();
+    Ok(Some(Action::ShowText("Backtrace".into(), lines)))
+}
+
+/// Show the calls made so far as a tree, at most `DEPTH` calls deep, i.e. `calltree [DEPTH]`
+fn call_tree(state: &State, args: &str) -> Result<Option<Action>, String> {
+    let max_depth = match args {
+        "" => usize::MAX,
+        n => match n.parse::<usize>() {
+            Ok(0) => return Err("invalid depth '0': must be at least 1".into()),
+            Ok(n) => n,
+            Err(err) => return Err(format!("invalid depth '{n}': {err}")),
+        },
+    };
+    let calls = state.executor.callstack.call_tree();
+    if calls.is_empty() {
+        return Ok(Some(Action::StatusLine("no procedure has been entered yet".into())));
+    }
+
+    let mut lines = vec![];
+    describe_calls(&calls, 0, max_depth, state.executor.stopped, &mut lines);
+    Ok(Some(Action::ShowText("Call Tree".into(), lines)))
+}
+
+/// Describe each of `calls`, which are `depth` calls deep, and the calls they made in turn, up to
+/// `max_depth` calls deep, indenting each call by its depth. Calls which have not returned are in
+/// progress, unless the program `terminated`, e.g. the entrypoint, which has no end of frame.
+///
+/// Consecutive calls to the same procedure which returned without making any calls are described
+/// together, so that calls made in a loop take a single line.
+fn describe_calls(
+    calls: &[debug::Call],
+    depth: usize,
+    max_depth: usize,
+    terminated: bool,
+    lines: &mut Vec<String>,
+) {
+    /// The number of calls in `calls`, and in the trees rooted at each of them
+    fn count(calls: &[debug::Call]) -> usize {
+        calls.iter().map(|call| 1 + count(&call.children)).sum()
+    }
+
+    let indent = "  ".repeat(depth);
+    if depth == max_depth {
+        lines.push(format!("{indent}... {} more call(s)", count(calls)));
+        return;
+    }
+    let mut index = 0;
+    while index < calls.len() {
+        let call = &calls[index];
+        let name = call.procedure();
+        let is_leaf = |call: &debug::Call| call.children.is_empty() && call.exit_cycle.is_some();
+        let repeats = match is_leaf(call) {
+            true => calls[index..]
+                .iter()
+                .take_while(|other| is_leaf(other) && other.procedure() == name)
+                .count(),
+            false => 1,
+        };
+        let name = name.as_deref().unwrap_or("<unknown>");
+        let enter = call.enter_cycle;
+        let line = match calls[index + repeats - 1].exit_cycle {
+            Some(exit) => {
+                let cycles = calls[index..(index + repeats)]
+                    .iter()
+                    .map(|call| call.exit_cycle.unwrap().as_usize() - call.enter_cycle.as_usize())
+                    .sum::<usize>();
+                match repeats {
+                    1 => format!("{indent}{name}  [{enter}..{exit}] {cycles} cycles"),
+                    n => format!("{indent}{name}  [{enter}..{exit}] {cycles} cycles in {n} calls"),
+                }
+            }
+            None if terminated => format!("{indent}{name}  [{enter}..] did not return"),
+            None => format!("{indent}{name}  [{enter}..] in progress"),
+        };
+        lines.push(line);
+        if repeats == 1 && !call.children.is_empty() {
+            describe_calls(&call.children, depth + 1, max_depth, terminated, lines);
+        }
+        index += repeats;
+    }
+}
+
+/// Show source code, or recently executed instructions, i.e. `list [insns|-|FILE:LINE|FROM,TO]`
+fn list(state: &mut State, args: &str) -> Result<Option<Action>, String> {
+    if args == "insns" {
+        if state.executor.recent.is_empty() {
+            return Ok(Some(Action::StatusLine("no instructions have been executed".into())));
+        }
+        let lines = state.executor.recent.iter().map(|op| op.to_string()).collect();
+        return Ok(Some(Action::ShowText("Recent Instructions".into(), lines)));
+    }
+
+    let cycle = state.executor.cycle;
+    let current = state
+        .executor
+        .callstack
+        .current_frame()
+        .and_then(|frame| frame.last_resolved(&state.source_manager))
+        .map(|loc| (loc.source_file.clone(), loc.line));
+    // Paging continues from the previous listing only while stopped at the same cycle
+    let previous = state
+        .listing
+        .as_ref()
+        .filter(|listing| listing.cycle == cycle)
+        .map(|listing| (listing.source_file.clone(), listing.lines.clone()));
+    let centered_on_current = || {
+        current
+            .clone()
+            .map(|(source_file, line)| (source_file, centered(line)))
+            .ok_or_else(|| "there is no current source location, see `list FILE:LINE`".to_string())
+    };
+
+    let (source_file, lines) = match args {
+        "" => match previous {
+            Some((source_file, lines)) => {
+                let start = lines.end() + 1;
+                (source_file, start..=(start + LIST_SIZE - 1))
+            }
+            None => centered_on_current()?,
+        },
+        "-" => {
+            let (source_file, lines) = match previous {
+                Some(previous) => previous,
+                None => centered_on_current()?,
+            };
+            if *lines.start() <= 1 {
+                return Err(format!("already at the start of {}", file_name(&source_file)));
+            }
+            let end = lines.start() - 1;
+            (source_file, end.saturating_sub(LIST_SIZE - 1).max(1)..=end)
+        }
+        range if range.contains(',') => {
+            let (from, to) = range.split_once(',').unwrap();
+            let from = parse_line(from)?;
+            let to = parse_line(to)?;
+            if to < from {
+                return Err(format!("invalid range '{range}': {to} is before {from}"));
+            }
+            let source_file = match previous {
+                Some((source_file, _)) => source_file,
+                None => centered_on_current()?.0,
+            };
+            (source_file, from..=to)
+        }
+        location => {
+            let (file, line) = location
+                .rsplit_once(':')
+                .ok_or_else(|| format!("invalid location '{location}', expected FILE:LINE"))?;
+            let line = parse_line(line)?;
+            (find_source_file(state, file)?, centered(line))
+        }
+    };
+
+    let num_lines = source_file.as_str().lines().count() as u32;
+    if *lines.start() > num_lines {
+        return Err(format!(
+            "line {} is beyond the end of {}, which has {num_lines} lines",
+            lines.start(),
+            file_name(&source_file)
+        ));
+    }
+    let lines = *lines.start()..=(*lines.end()).min(num_lines);
+
+    let uri = source_file.uri().as_str();
+    let current_line = current
+        .as_ref()
+        .filter(|(file, _)| file.uri() == source_file.uri())
+        .map(|(_, line)| *line);
+    let width = lines.end().to_string().len();
+    let text = source_file
+        .as_str()
+        .lines()
+        .enumerate()
+        .map(|(index, text)| (index as u32 + 1, text))
+        .filter(|(number, _)| lines.contains(number))
+        .map(|(number, text)| {
+            let marker = if current_line == Some(number) {
+                '>'
+            } else {
+                ' '
+            };
+            let breakpoint = if state.breakpoints.iter().any(|bp| bp.is_set_on_line(uri, number)) {
+                '*'
+            } else {
+                ' '
+            };
+            format!("{marker}{breakpoint}{number:>width$} {text}").trim_end().to_string()
+        })
+        .collect();
+    let title = file_name(&source_file);
+
+    state.listing = Some(SourceListing {
+        source_file,
+        lines,
+        cycle,
+    });
+    Ok(Some(Action::ShowText(title, text)))
+}
+
+/// The lines listed when centering the listing on `line`
+fn centered(line: u32) -> RangeInclusive<u32> {
+    let start = line.saturating_sub(LIST_SIZE / 2).max(1);
+    start..=(start + LIST_SIZE - 1)
+}
+
+/// The name of `source_file`, without the directory containing it
+fn file_name(source_file: &SourceFile) -> String {
+    let path = Path::new(source_file.uri().as_str());
+    path.file_name().map(Path::new).unwrap_or(path).display().to_string()
+}
+
+fn parse_line(line: &str) -> Result<u32, String> {
+    match line.trim().parse::<u32>() {
+        Ok(0) => Err("invalid line number '0': lines are numbered from 1".into()),
+        Ok(line) => Ok(line),
+        Err(err) => Err(format!("invalid line number '{}': {err}", line.trim())),
+    }
+}
+
+/// Find the source file named `file`, which is either a path relative to the working directory, or
+/// the URI of a file known to the source manager
+fn find_source_file(state: &State, file: &str) -> Result<Arc<SourceFile>, String> {
+    let path = state.config.working_dir().join(file);
+    if path.is_file() {
+        return state
+            .source_manager
+            .load_file(&path)
+            .map_err(|err| format!("unable to load {}: {err}", path.display()));
+    }
+    state
+        .source_manager
+        .get_by_uri(&Uri::from(file.to_string().into_boxed_str()))
+        .ok_or_else(|| format!("no such source file '{file}'"))
+}
+
+/// Find the source file named `name` as [find_source_file] does, or failing that, the only file
+/// known to the source manager whose URI contains `name`, ignoring case
+fn match_source_file(state: &State, name: &str) -> Result<Arc<SourceFile>, String> {
+    if let Ok(source_file) = find_source_file(state, name) {
+        return Ok(source_file);
+    }
+    let needle = name.to_lowercase();
+    let mut candidates = state
+        .source_files()
+        .into_iter()
+        .filter(|file| file.uri().as_str().to_lowercase().contains(&needle))
+        .collect::<Vec<_>>();
+    match candidates.len() {
+        0 => Err(format!("no such source file '{name}'")),
+        1 => Ok(candidates.pop().unwrap()),
+        _ => {
+            let uris = candidates.iter().map(|file| file.uri().to_string()).collect::<Vec<_>>();
+            Err(format!("'{name}' matches several source files: {}", uris.join(", ")))
+        }
+    }
+}
+
+/// Render `measurement`, listing the `n` lines to which the most cycles were attributed, followed
+/// by the cycles attributed to each file
+pub fn render_measurement(measurement: &CycleMeasurement, n: usize) -> Vec<String> {
+    let total = measurement.total();
+    let width = HumanCount(total).to_string().len();
+    let hottest = measurement.hottest(n);
+
+    let mut lines = vec![format!(
+        "cycles by line (top {} of {}):",
+        hottest.len(),
+        measurement.num_lines()
+    )];
+    for line in hottest {
+        let file = Path::new(line.file.as_ref());
+        let file = file.file_name().map(Path::new).unwrap_or(file);
+        lines.push(format!(
+            "  {:>width$}  {}:{}  {}",
+            HumanCount(line.cycles).to_string(),
+            file.display(),
+            line.line,
+            line.text
+        ));
+    }
+    lines.push("cycles by file:".into());
+    let unknown =
+        Some((Path::new("<unknown>"), measurement.unknown())).filter(|(_, cycles)| *cycles > 0);
+    for (file, cycles) in measurement.files().into_iter().chain(unknown) {
+        let file = file.file_name().map(Path::new).unwrap_or(file);
+        lines.push(format!("  {:>width$}  {}", HumanCount(cycles).to_string(), file.display()));
+    }
+    lines.push(format!("total: {} cycles", HumanCount(total)));
+    lines
+}
+
+/// Render `measurement`, listing the `n` instructions to which the most cycles were attributed,
+/// with the number of times each was executed, and the cycles each execution took on average
+pub fn render_op_measurement(measurement: &CycleMeasurement, n: usize) -> Vec<String> {
+    let total = measurement.total();
+    let width = HumanCount(total).to_string().len();
+    let hottest = measurement.hottest_ops(n);
+    let name_width = hottest.iter().map(|op| op.name.len()).max().unwrap_or(0);
+
+    let mut lines = vec![format!(
+        "cycles by instruction (top {} of {}):",
+        hottest.len(),
+        measurement.num_ops()
+    )];
+    for op in hottest {
+        lines.push(format!(
+            "  {:>width$}  {:<name_width$}  {} executed, {:.1} cycles each",
+            HumanCount(op.cycles).to_string(),
+            op.name,
+            HumanCount(op.count),
+            op.cycles_per_execution()
+        ));
+    }
+    lines.push(format!("total: {} cycles", HumanCount(total)));
+    lines
+}
+
+/// Show the regions of memory written so far, grouped by context, i.e. `memory-map [-x]`
+fn memory_map(state: &State, args: &str) -> Result<Option<Action>, String> {
+    /// The number of elements of each region to preview
+    const PREVIEW_LEN: usize = 4;
+
+    let hex = match args {
+        "" => false,
+        "-x" | "--hex" => true,
+        _ => return Err(format!("invalid argument '{args}', expected '-x'")),
+    };
+    let map = state.executor.memory_map();
+    if map.is_empty() {
+        return Ok(Some(Action::StatusLine("no memory has been written".into())));
+    }
+
+    let mut lines = vec![];
+    for context in map {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        let root = if context.is_root { " (root)" } else { "" };
+        lines.push(format!("context {}{root}:", context.context));
+        for region in context.regions {
+            let len = u64::from(region.end() - region.start()) + 1;
+            let preview = region
+                .clone()
+                .take(PREVIEW_LEN)
+                .map(|addr| {
+                    let felt = state
+                        .executor
+                        .read_memory_element_in_context(addr, context.context)
+                        .unwrap_or(RawFelt::ZERO);
+                    format_felt(felt, hex)
+                })
+                .collect::<Vec<_>>();
+            let ellipsis = if len > PREVIEW_LEN as u64 { ", .." } else { "" };
+            lines.push(format!(
+                "  {:#010x}..={:#010x} ({len} elements) [{}{ellipsis}]",
+                region.start(),
+                region.end(),
+                preview.join(", ")
+            ));
+        }
+    }
+    Ok(Some(Action::ShowText("Memory Map".into(), lines)))
+}
+
+/// Write a region of memory to a file, i.e. `dump memory FILE START LEN [--format raw|hex]`, or
+/// show it, i.e. `dump START LEN [--width N] [--fill XX]`
+fn dump(state: &State, args: &str) -> Result<Option<Action>, String> {
+    let mut args = args.split_whitespace().collect::<Vec<_>>();
+    if args.first() != Some(&"memory") {
+        return show_memory_region(state, args);
+    }
+    let hex = match args.iter().position(|arg| *arg == "--format") {
+        Some(index) => {
+            let hex = match args.get(index + 1) {
+                Some(&"raw") => false,
+                Some(&"hex") => true,
+                _ => return Err("invalid format, expected '--format raw|hex'".into()),
+            };
+            args.drain(index..(index + 2));
+            hex
+        }
+        None => false,
+    };
+    let ["memory", file, start, len] = args.as_slice() else {
+        return Err(
+            "invalid arguments, usage: dump memory FILE START LEN [--format raw|hex]".into()
+        );
+    };
+    let start = parse_u64(start, "address")?;
+    let len = parse_u64(len, "length")?;
+
+    let execution_trace = state.execution_trace()?;
+    let context = state.executor.current_context;
+    let cycle = miden_processor::trace::RowIndex::from(state.executor.cycle);
+    let path = state.config.working_dir().join(file);
+    let range = start..start.saturating_add(len);
+    let written = if hex {
+        write_hexdump(&path, execution_trace, range, context, cycle)
+    } else {
+        execution_trace.dump_memory(&path, range, context, cycle)
+    }
+    .map_err(|err| format!("failed to write '{}': {err}", path.display()))?;
+
+    let mut status = format!(
+        "wrote {written} bytes from {start:#x} in context {context} at cycle {} to {}",
+        HumanCount(state.executor.cycle),
+        path.display()
+    );
+    if (written as u64) < len {
+        status.push_str(&format!(
+            " (warning: {len} bytes were requested, but memory ends at {:#x})",
+            u64::from(u32::MAX) + 1
+        ));
+    }
+    Ok(Some(Action::StatusLine(status)))
+}
+
+/// Write the bytes at the byte addresses in `range`, under `context`, at `cycle`, to the file at
+/// `path` in the style of `hexdump`, a chunk at a time, returning the number of bytes written
+fn write_hexdump(
+    path: &Path,
+    execution_trace: &ExecutionTrace,
+    range: std::ops::Range<u64>,
+    context: miden_processor::ContextId,
+    cycle: miden_processor::trace::RowIndex,
+) -> std::io::Result<usize> {
+    use std::io::Write;
+
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    let written =
+        execution_trace.read_memory_region_chunked(range, context, cycle, |start, bytes| {
+            hexdump(start, bytes).into_iter().try_for_each(|line| writeln!(file, "{line}"))
+        })?;
+    file.flush()?;
+    Ok(written)
+}
+
+/// Show a region of memory as a hex dump, i.e. `dump START LEN [--width N] [--fill XX]`
+fn show_memory_region(state: &State, mut args: Vec<&str>) -> Result<Option<Action>, String> {
+    /// The usual number of bytes per row
+    const DEFAULT_WIDTH: usize = 16;
+    /// The widest rows which may be requested
+    const MAX_WIDTH: usize = 64;
+    /// The longest region which may be shown, larger ones may be written to a file instead
+    const MAX_LEN: u64 = 64 * 1024;
+    const USAGE: &str = "dump START LEN [--width N] [--fill XX]";
+
+    let mut option = |name: &str| -> Result<Option<&str>, String> {
+        let Some(index) = args.iter().position(|arg| *arg == name) else {
+            return Ok(None);
+        };
+        let value = args
+            .get(index + 1)
+            .copied()
+            .ok_or_else(|| format!("expected a value for '{name}'"))?;
+        args.drain(index..(index + 2));
+        Ok(Some(value))
+    };
+    let width = match option("--width")? {
+        Some(value) => match value.parse::<usize>() {
+            Ok(width @ 1..=MAX_WIDTH) => width,
+            _ => {
+                return Err(format!(
+                    "invalid width '{value}', expected a number from 1 to {MAX_WIDTH}"
+                ));
+            }
+        },
+        None => DEFAULT_WIDTH,
+    };
+    let fill = match option("--fill")? {
+        Some(value) if value.chars().count() == 2 => value.to_string(),
+        Some(value) => return Err(format!("invalid fill '{value}', expected two characters")),
+        None => "..".to_string(),
+    };
+    let [start, len] = args.as_slice() else {
+        return Err(format!("invalid arguments, usage: {USAGE}"));
+    };
+    let start = parse_u64(start, "address")?;
+    let len = parse_u64(len, "length")?;
+    if len > MAX_LEN {
+        return Err(format!(
+            "invalid length '{len}', at most {MAX_LEN} bytes can be shown, use 'dump memory' to \
+             write larger regions to a file"
+        ));
+    }
+
+    let execution_trace = state.execution_trace()?;
+    let context = state.executor.current_context;
+    let cycle = miden_processor::trace::RowIndex::from(state.executor.cycle);
+    let bytes =
+        execution_trace.read_memory_region(start..start.saturating_add(len), context, cycle);
+    let mut lines = hex_rows(start, &bytes, width, &fill);
+    if (bytes.len() as u64) < len {
+        lines.push(format!(
+            "(warning: {len} bytes were requested, but memory ends at {:#x})",
+            u64::from(u32::MAX) + 1
+        ));
+    }
+    Ok(Some(Action::ShowText(
+        format!("Memory at {start:#x} in context {context}"),
+        lines,
+    )))
+}
+
+/// Parse a non-negative integer, in decimal, or hexadecimal with a `0x` prefix
+fn parse_u64(value: &str, what: &str) -> Result<u64, String> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse::<u64>(),
+    }
+    .map_err(|err| format!("invalid {what} '{value}': {err}"))
+}
+
+/// Render `bytes`, the first of which is at address `start`, in the style of `hexdump -C`: `width`
+/// bytes per row, preceded by the address of the first, and followed by their printable
+/// characters. Zero bytes, which include those of memory never written, are shown as `fill`.
+fn hex_rows(start: u64, bytes: &[u8], width: usize, fill: &str) -> Vec<String> {
+    bytes
+        .chunks(width)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex = chunk
+                .iter()
+                .map(|&byte| match byte {
+                    0 => fill.to_string(),
+                    byte => format!("{byte:02x}"),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let text = chunk.iter().map(|&byte| printable(byte)).collect::<String>();
+            let addr = start + (i * width) as u64;
+            // Each byte takes 2 characters, followed by a space
+            format!("{addr:08x}  {hex:<hex_width$}  |{text}|", hex_width = width * 3 - 1)
+        })
+        .collect()
+}
+
+/// The character shown for `byte` in the text column of a hex dump
+fn printable(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+/// Render `bytes`, the first of which is at address `start`, in the style of `xxd`: 16 bytes
+/// per line, preceded by the address of the first, and followed by their printable characters.
+fn hexdump(start: u64, bytes: &[u8]) -> Vec<String> {
+    const BYTES_PER_LINE: usize = 16;
+
+    bytes
+        .chunks(BYTES_PER_LINE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex = chunk
+                .chunks(2)
+                .map(|pair| pair.iter().map(|byte| format!("{byte:02x}")).collect::<String>())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let text = chunk.iter().map(|&byte| printable(byte)).collect::<String>();
+            let addr = start + (i * BYTES_PER_LINE) as u64;
+            // Each pair of bytes takes 4 characters, followed by a space
+            format!("{addr:08x}: {hex:<width$}  {text}", width = BYTES_PER_LINE / 2 * 5 - 1)
+        })
+        .collect()
+}
+
+/// Render each watched expression with its value, as of the most recent evaluation
+fn describe_watches(state: &State) -> Vec<String> {
+    state
+        .watches
+        .iter()
+        .enumerate()
+        .map(|(i, watch)| {
+            let marker = if watch.changed { " (changed)" } else { "" };
+            match watch.value.as_ref() {
+                Some(Ok(value)) => format!("[{i}] {} = {value}{marker}", watch.text),
+                Some(Err(err)) => format!("[{i}] {}: {err}", watch.text),
+                None => format!("[{i}] {}", watch.text),
+            }
+        })
+        .collect()
+}
+
+/// Show the operand stack from the top, i.e. `stack [--all] [-x] [--delta]`.
+///
+/// Only the top 16 elements are shown, unless `--all` is given, in which case the elements of the
+/// overflow table follow them. Elements changed by the most recent run are marked with `*`, and
+/// with `--delta`, each element pushed or changed by the most recent operation is marked as such.
+fn stack(state: &State, args: &str) -> Result<Option<Action>, String> {
+    const USAGE: &str = "usage: stack [--all] [-x] [--delta]";
+
+    let mut all = false;
+    let mut hex = false;
+    let mut delta = false;
+    for arg in args.split_whitespace() {
+        match arg {
+            "--all" | "-a" => all = true,
+            "-x" | "--hex" => hex = true,
+            "--delta" | "-d" => delta = true,
+            _ => return Err(format!("invalid argument '{arg}', {USAGE}")),
+        }
+    }
+
+    let provenance = match delta {
+        true => state.executor.stack_provenance(),
+        false => vec![],
+    };
+    let element = |index: usize, felt: &RawFelt| {
+        let changed = match state.changes.stack.contains(&index) {
+            true => " *",
+            false => "",
+        };
+        let marker = match provenance.get(index) {
+            Some(StackProvenance::Pushed) => " (pushed)",
+            Some(StackProvenance::Changed) => " (changed)",
+            Some(StackProvenance::Unchanged) | None => "",
+        };
+        format!("[{index}] {}{changed}{marker}", format_felt(*felt, hex))
+    };
+
+    let top = state.executor.stack_top();
+    let overflow = state.executor.stack_overflow();
+    let mut lines = vec![format!("stack depth is {}", top.len() + overflow.len())];
+    lines.extend(top.iter().enumerate().map(|(i, felt)| element(i, felt)));
+    if overflow.is_empty() {
+        return Ok(Some(Action::ShowText("Operand Stack".into(), lines)));
+    }
+    if all {
+        lines.push(format!("-- overflow table ({} elements) --", overflow.len()));
+        lines.extend(overflow.iter().enumerate().map(|(i, felt)| element(top.len() + i, felt)));
+    } else {
+        lines.push(format!(
+            "-- {} more elements in the overflow table, see `stack --all` --",
+            overflow.len()
+        ));
+    }
+    Ok(Some(Action::ShowText("Operand Stack".into(), lines)))
+}
+
+/// Inspect the advice provider, i.e. `advice stack [N]`, `advice map [KEY]`, or `advice merkle`,
+/// each of which accepts `-x` to render field elements in hexadecimal
+fn advice(state: &mut State, args: &str) -> Result<Option<Action>, String> {
+    let mut args = args.split_whitespace().collect::<Vec<_>>();
+    let hex = args.iter().any(|arg| matches!(*arg, "-x" | "--hex"));
+    args.retain(|arg| !matches!(*arg, "-x" | "--hex"));
+    let format = |felt: &RawFelt| format_felt(*felt, hex);
+
+    let lines = state.executor.with_advice(|advice| {
+        Ok(match args.as_slice() {
+            ["stack"] | ["stack", _] => {
+                let limit = match args.get(1) {
+                    Some(n) => {
+                        n.parse::<usize>().map_err(|err| format!("invalid count '{n}': {err}"))?
+                    }
+                    None => advice.stack().len(),
+                };
+                let mut lines = vec![format!("advice stack depth is {}", advice.stack().len())];
+                lines.extend(
+                    advice
+                        .stack()
+                        .iter()
+                        .take(limit)
+                        .enumerate()
+                        .map(|(i, felt)| format!("[{i}] {}", format(felt))),
+                );
+                lines
+            }
+            ["map"] => {
+                let mut lines = vec![format!("advice map has {} entries", advice.map_len())];
+                lines.extend(
+                    advice.map().map(|(key, values)| format!("{key} ({} elements)", values.len())),
+                );
+                lines
+            }
+            ["map", key] => {
+                let word = miden_core::Word::try_from(*key)
+                    .map_err(|err| format!("invalid advice map key '{key}': {err}"))?;
+                let values = advice
+                    .get(&word)
+                    .ok_or_else(|| format!("advice map has no entry for '{key}'"))?;
+                let mut lines = vec![format!("{key} ({} elements)", values.len())];
+                lines.extend(values.chunks(4).enumerate().map(|(i, word)| {
+                    let word = word.iter().map(format).collect::<Vec<_>>();
+                    format!("[{i}] [{}]", word.join(", "))
+                }));
+                lines
+            }
+            ["merkle"] => {
+                vec![format!("merkle store has {} internal nodes", advice.merkle_nodes())]
+            }
+            _ => {
+                return Err(
+                    "invalid arguments, expected 'stack [N]', 'map [KEY]', or 'merkle'".to_string()
+                );
+            }
+        })
+    })?;
+    Ok(Some(Action::ShowText("Advice".into(), lines)))
+}
+
+/// Render `felt` as either a decimal or hexadecimal integer
+pub fn format_felt(felt: RawFelt, hex: bool) -> String {
+    if hex {
+        format!("{:#x}", felt.as_canonical_u64())
+    } else {
+        felt.as_canonical_u64().to_string()
+    }
+}
+
+/// Render the elements of `outputs`, from the top of the operand stack, e.g. `[3, 0, 0]`
+pub fn format_outputs(outputs: &[RawFelt]) -> String {
+    let outputs = outputs.iter().map(|elem| elem.as_canonical_u64().to_string());
+    format!("[{}]", outputs.collect::<Vec<_>>().join(", "))
+}
+
+/// If a cycle budget is configured, and a program which took `cycles` cycles to run exceeds it,
+/// describe by how much
+pub fn cycle_budget_warning(config: &DebuggerConfig, cycles: usize) -> Option<String> {
+    let budget = config.cycle_budget_warning?;
+    if cycles <= budget {
+        return None;
+    }
+    Some(format!(
+        "execution took {} cycles, exceeding the budget of {} by {}",
+        HumanCount(cycles),
+        HumanCount(budget),
+        HumanCount(cycles - budget)
+    ))
+}
+
+/// Describe where, and why, execution last stopped as a JSON object, for consumption by tools
+/// which drive the debugger, see [OutputFormat::Json].
+///
+/// The `reason` is one of `breakpoint`, `step`, `interrupted`, `terminated`, or `error`.
+pub fn stop_notification(state: &State) -> serde_json::Value {
+    let breakpoints = state
+        .breakpoints_hit
+        .iter()
+        .filter(|bp| !bp.is_internal())
+        .map(|bp| bp.id)
+        .collect::<Vec<_>>();
+    let reason = if state.executor.stopped {
+        if state.execution_failed.is_some() {
+            "error"
+        } else {
+            "terminated"
+        }
+    } else if state.interrupted {
+        "interrupted"
+    } else if !breakpoints.is_empty() {
+        "breakpoint"
+    } else {
+        "step"
+    };
+
+    let frame = state.executor.callstack.current_frame();
+    let procedure = frame.and_then(|frame| frame.procedure("")).map(|name| name.to_string());
+    let location = frame.and_then(|frame| frame.last_resolved(&state.source_manager));
+    let mut notification = serde_json::json!({
+        "reason": reason,
+        "cycle": state.executor.cycle,
+        "procedure": procedure,
+        "file": location.map(|loc| loc.source_file.uri().as_str().to_string()),
+        "line": location.map(|loc| loc.line),
+        "breakpoints": breakpoints,
+        "granularity": step_granularity(state),
+    });
+    if let Some(err) = state.execution_failed.as_ref() {
+        notification["error"] = state.executor.describe_error(err, &*state.source_manager).into();
+    }
+    if let Some((from, to)) = context_switch(state) {
+        notification["context"] = serde_json::json!({ "from": from, "to": to });
+    }
+    if let Some(count) = state.hit_count {
+        notification["hits"] = serde_json::json!({ "count": count.hits, "target": count.target });
+    }
+    notification
+}
+
+/// Run until a procedure matching `pattern` is called, as requested via `--break-at`, returning
+/// a description of where execution stopped.
+///
+/// The breakpoint is removed once execution stops. If no such procedure is called, the program
+/// runs to completion, and an error describing how it terminated is returned instead.
+pub fn run_to_procedure(state: &mut State, pattern: &str) -> Result<String, String> {
+    state.create_breakpoint(format!("in {pattern}").parse::<BreakpointType>()?);
+    let id = state.breakpoints.last().map(|bp| bp.id);
+    state.run_until_stopped();
+    state.breakpoints.retain(|bp| Some(bp.id) != id);
+    match state.breakpoints_hit.iter().any(|bp| Some(bp.id) == id) {
+        true => Ok(describe_stop(state)),
+        false => Err(format!(
+            "no procedure matching '{pattern}' was called, {}",
+            describe_stop(state)
+        )),
+    }
+}
+
+/// Describe where, and why, execution last stopped
+pub fn describe_stop(state: &State) -> String {
+    let cycle = HumanCount(state.executor.cycle);
+    let hits = state
+        .hit_count
+        .map(|count| format!(" ({} of {} breakpoint hits)", count.hits, count.target))
+        .unwrap_or_default();
+    if state.executor.stopped {
+        return match state.execution_failed.as_ref() {
+            Some(err) => format!(
+                "program terminated with an error at cycle {cycle}{hits}: {}",
+                state.executor.describe_error(err, &*state.source_manager)
+            ),
+            None => format!("program terminated successfully at cycle {cycle}{hits}"),
+        };
+    }
+
+    let reason = if state.interrupted {
+        "interrupted"
+    } else {
+        "stopped"
+    };
+    let mut description = format!("{reason} at cycle {cycle}");
+    if let Some(frame) = state.executor.callstack.current_frame() {
+        if let Some(procedure) = frame.procedure("") {
+            description.push_str(&format!(" in {procedure}"));
+        }
+        if let Some(loc) = frame.last_resolved(&state.source_manager) {
+            let file = Path::new(loc.source_file.uri().as_str());
+            let file = file.file_name().map(Path::new).unwrap_or(file);
+            description.push_str(&format!(" at {}:{}", file.display(), loc.line));
+        }
+    }
+    if let Some(granularity) = step_granularity(state) {
+        description.push_str(&format!(" (stepped by {granularity})"));
+    }
+    if let Some((from, to)) = context_switch(state) {
+        description.push_str(&format!(" (entered context {to} from context {from})"));
+    }
+    description.push_str(&hits);
+    description
+}
+
+/// The contexts moved from and to, if execution was stopped by a context switch breakpoint
+fn context_switch(state: &State) -> Option<(u32, u32)> {
+    state
+        .breakpoints_hit
+        .iter()
+        .any(|bp| matches!(bp.ty, BreakpointType::ContextSwitch))
+        .then_some(state.context_switch)
+        .flatten()
+        .map(|(from, to)| (u32::from(from), u32::from(to)))
+}
+
+/// The granularity of the step which caused execution to stop, if it was stopped by `step`, i.e.
+/// by `instruction`, or by `stepi`, i.e. by `cycle`
+fn step_granularity(state: &State) -> Option<&'static str> {
+    state.breakpoints_hit.iter().find_map(|bp| match bp.ty {
+        BreakpointType::StepInstructions { .. } | BreakpointType::StepOps { .. } => {
+            Some("instruction")
+        }
+        BreakpointType::Step | BreakpointType::StepN(_) => Some("cycle"),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::main_masm;
+
+    const PROGRAM: &str = "
+proc store
+    push.42
+    mem_store.8
+end
+
+begin
+    exec.store
+    push.8 mem_load
+    drop
+end
+";
+
+    fn run(state: &mut State, script: &str) -> (ScriptSummary, Vec<String>) {
+        let mut output = vec![];
+        let summary = run_script(state, script, &mut |line| output.push(line));
+        (summary, output)
+    }
+
+    #[test]
+    fn script_breakpoint_continue_and_read() {
+        let mut state = State::for_source(PROGRAM);
+        let script = "
+# stop on entry to the procedure which stores the value
+b in *store
+
+c
+r 8 -t felt
+";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary, ScriptSummary::default());
+        assert_eq!(
+            output,
+            vec![
+                ":b in *store",
+                "breakpoint created",
+                ":c",
+                "stopped at cycle 6 in $exec::store",
+                ":r 8 -t felt",
+                "42",
+            ]
+        );
+    }
+
+    #[test]
+    fn run_to_procedure_at_startup() {
+        let mut state = State::for_source(PROGRAM);
+        assert_eq!(
+            run_to_procedure(&mut state, "*store"),
+            Ok("stopped at cycle 6 in $exec::store".to_string())
+        );
+        // The breakpoint is removed once hit
+        assert!(state.breakpoints.is_empty());
+
+        state.reset().unwrap();
+        let err = run_to_procedure(&mut state, "*missing").unwrap_err();
+        assert!(
+            err.starts_with(
+                "no procedure matching '*missing' was called, program terminated successfully"
+            ),
+            "unexpected error: {err}"
+        );
+        assert!(state.breakpoints.is_empty());
+        assert!(run_to_procedure(&mut state, "[").is_err());
+    }
+
+    #[test]
+    fn script_advice_exhausted() {
+        let (_dir, path) = main_masm("begin\n    push.1\n    adv_push.1\n    drop\nend\n");
+
+        let mut state = State::for_source_file(&path);
+        let (summary, output) = run(&mut state, "c\nerror");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output[1],
+            format!(
+                "program terminated with an error at cycle 7: advice stack exhausted at cycle 7 \
+                 reading for op advpop (`adv_push.1`) at {}:3:5",
+                path.display()
+            )
+        );
+        assert!(output[3].starts_with("cycle 7: advice stack exhausted"), "{output:#?}");
+    }
+
+    #[test]
+    fn goto_and_file_commands() {
+        let (dir, main) = main_masm("begin\n    push.1\n    drop\nend\n");
+        let lib = dir.path().join("lib.masm");
+        std::fs::write(&lib, "proc foo\n    nop\nend\n").unwrap();
+
+        let mut state = State::for_source_file(&main);
+        state.source_manager.load_file(&main).unwrap();
+        state.source_manager.load_file(&lib).unwrap();
+        let mut execute = |line: &str| execute(&mut state, line, &mut |_| ());
+        assert_eq!(execute("120"), Ok(Some(Action::GotoLine(120))));
+        assert_eq!(execute("goto 7"), Ok(Some(Action::GotoLine(7))));
+        assert!(execute("0").unwrap_err().starts_with("invalid line number '0'"));
+        assert!(execute("goto x").unwrap_err().starts_with("invalid line number 'x'"));
+
+        let Ok(Some(Action::ShowLocation(loc))) = execute("file LIB") else {
+            panic!("expected `file` to show lib.masm");
+        };
+        assert_eq!(loc.source_file.uri().as_str(), lib.to_str().unwrap());
+        // The file is shown where it was last left
+        assert_eq!(loc.line, 0);
+        assert_eq!(execute("file other.masm"), Err("no such source file 'other.masm'".into()));
+        let err = execute("file .masm").unwrap_err();
+        assert_eq!(
+            err,
+            format!("'.masm' matches several source files: {}, {}", main.display(), lib.display())
+        );
+        assert_eq!(execute("sync"), Ok(Some(Action::SyncSource)));
+    }
+
+    #[test]
+    fn script_read_symbols() {
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) = run(&mut state, "r @ANSWER");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output[1],
+            "error: cannot resolve '@ANSWER': the program has no symbol table, i.e. no 'symbols' \
+             section"
+        );
+
+        // The value stored at element address 8 is at byte address 32
+        state.symbols = crate::debug::Symbols::parse_str(
+            "ANSWER = 32
+ODD = 33",
+            false,
+        )
+        .unwrap();
+        let script = "
+c
+r @ANSWER -t felt
+r @ANSWER -t u8 -m bytes
+-r @ODD -t felt
+-r @MISSING
+mem @ANSWER
+-mem @ODD
+";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 3);
+        assert_eq!(
+            output[2..],
+            [
+                ":r @ANSWER -t felt",
+                "42",
+                ":r @ANSWER -t u8 -m bytes",
+                "0",
+                ":r @ODD -t felt",
+                "error: symbol '@ODD' is at byte address 0x21, which is not element-aligned, use \
+                 `-m bytes` to read it",
+                ":r @MISSING",
+                "error: no such symbol '@MISSING'",
+                ":mem @ANSWER",
+                ":mem @ODD",
+                "error: symbol '@ODD' is at byte address 0x21, which is not element-aligned, use \
+                 `mem 0x8` to show the element containing it",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_read_arrays() {
+        let mut state = State::for_source(
+            "begin
+    push.1 mem_store.8 push.2 mem_store.9 push.3 mem_store.10
+    push.0x01020304 mem_store.12 push.0x05060708 mem_store.13
+    push.0x090a0b0c mem_store.14 push.0x0d0e0f10 mem_store.15
+end",
+        );
+        let script = "
+c
+r 8 -t [u32;3]
+r 8 -t u32 -c 3
+r 12 -t [u8;16]
+r 49 -m byte -t [u8;3]
+r 8 -t [felt;3]
+-r 49 -m byte -t [u32;3]
+";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output[2..],
+            [
+                ":r 8 -t [u32;3]",
+                "[1, 2, 3]",
+                ":r 8 -t u32 -c 3",
+                "[1, 2, 3]",
+                ":r 12 -t [u8;16]",
+                "[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]",
+                ":r 49 -m byte -t [u8;3]",
+                "[2, 3, 4]",
+                ":r 8 -t [felt;3]",
+                "[1, 2, 3]",
+                ":r 49 -m byte -t [u32;3]",
+                "error: read failed: type '[u32; 3]' must be aligned to a 4-byte boundary",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_read_structs() {
+        let mut state = State::for_source(
+            "begin push.1 mem_store.8 push.2 mem_store.9 push.3 mem_store.10 end",
+        );
+        let script = "
+c
+r 8 -t struct { a: u32, b: [u32; 2] }
+r 8 struct{a:u32,b:[u32;2]}
+mem 8 struct { a: u32, b: [u32; 2] }
+";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output[2..],
+            [
+                ":r 8 -t struct { a: u32, b: [u32; 2] }",
+                "{ a: 1, b: [2, 3] }",
+                ":r 8 struct{a:u32,b:[u32;2]}",
+                "{ a: 1, b: [2, 3] }",
+                ":mem 8 struct { a: u32, b: [u32; 2] }",
+                "{ a: 1, b: [2, 3] }",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_examine() {
+        let mut state = State::for_source(
+            "begin
+    push.1 mem_store.8 push.2 mem_store.9 push.4294967295 mem_store.10
+    push.0x01020304 mem_store.12 push.0x05060708 mem_store.13
+    push.0x090a0b0c mem_store.14 push.0x0d0e0f10 mem_store.15
+end",
+        );
+        let script = "
+c
+x 48
+x/4xw 32
+x/3dw 32
+x/10xb 48
+x/2ug 48
+x/2th 48
+-x/xw 49
+-x/4q 48
+";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(
+            output[2..],
+            [
+                ":x 48",
+                "0x00000030:  0x01020304",
+                ":x/4xw 32",
+                "0x00000020:  0x00000001  0x00000002  0xffffffff  0x00000000",
+                ":x/3dw 32",
+                "0x00000020:  1  2  -1",
+                ":x/10xb 48",
+                "0x00000030:  0x01  0x02  0x03  0x04  0x05  0x06  0x07  0x08",
+                "0x00000038:  0x09  0x0a",
+                ":x/2ug 48",
+                "0x00000030:  72623859790382856  651345242494996240",
+                ":x/2th 48",
+                "0x00000030:  0000000100000010  0000001100000100",
+                ":x/xw 49",
+                "error: read failed: type 'u32' must be aligned to a 4-byte boundary",
+                ":x/4q 48",
+                "error: invalid format letter 'q', expected one of x, d, u, t (format) or b, h, w, \
+                 g (size)",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_error_codes() {
+        let mut state = State::for_source("begin push.1 push.2 assert_eq end");
+        let codes = "[0]\nname = \"ERR_MISMATCH\"\ndescription = \"the values differ\"";
+        state.executor.error_codes =
+            Arc::new(crate::exec::ErrorCodes::parse_str(codes, false).unwrap());
+        let (summary, output) = run(&mut state, "c\nerror");
+        assert_eq!(summary, ScriptSummary::default());
+        let error = "assertion failed with error ERR_MISMATCH (0x0): the values differ";
+        assert!(output[1].ends_with(error), "unexpected output: {output:?}");
+        assert!(output[3].ends_with(error), "unexpected output: {output:?}");
+    }
+
+    #[test]
+    fn script_json_stop_notifications() {
+        fn parse_notifications(output: &[String]) -> Vec<serde_json::Value> {
+            output
+                .iter()
+                .filter_map(|line| line.strip_prefix("@@"))
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+
+        let mut state = State::for_source(PROGRAM);
+        let script = "set output-format json\ns\nb in *store\nc\nread 8 -t felt\nc";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary, ScriptSummary::default());
+        // Command output remains textual
+        assert_eq!(output[4..6], [":b in *store", "breakpoint created"]);
+        assert_eq!(output[8..10], [":read 8 -t felt", "42"]);
+
+        let notifications = parse_notifications(&output);
+        assert_eq!(notifications.len(), 3);
+        assert_eq!(notifications[0]["reason"], "step");
+        assert_eq!(notifications[0]["cycle"], 1);
+        assert_eq!(notifications[0]["breakpoints"], serde_json::json!([]));
+        assert_eq!(notifications[1]["reason"], "breakpoint");
+        assert_eq!(notifications[1]["cycle"], 6);
+        assert_eq!(notifications[1]["procedure"], "$exec::store");
+        // The first id was taken by the breakpoint used to implement `s`
+        assert_eq!(notifications[1]["breakpoints"], serde_json::json!([1]));
+        // The program has no source locations
+        assert!(notifications[1]["file"].is_null());
+        assert!(notifications[1]["line"].is_null());
+        assert_eq!(notifications[2]["reason"], "breakpoint");
+        assert_eq!(notifications[2]["cycle"], 7);
+
+        let mut state = State::for_source(PROGRAM);
+        let (_, output) = run(&mut state, "set output-format json\nc");
+        assert_eq!(parse_notifications(&output)[0]["reason"], "terminated");
+
+        let mut state = State::for_source("begin push.1 assertz end");
+        let (_, output) = run(&mut state, "set output-format json\nc");
+        let notification = &parse_notifications(&output)[0];
+        assert_eq!(notification["reason"], "error");
+        assert!(notification["error"].as_str().is_some_and(|err| !err.is_empty()));
+    }
+
+    #[test]
+    fn script_step_instructions() {
+        // `push.1` takes 2 cycles, and `u32max` 9, ending at cycles 7 and 17 respectively
+        let mut state = State::for_source("begin push.1 push.2 u32max drop end");
+        let (summary, output) = run(&mut state, "s\ns\nsi\ns\ns\n-s 2");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output[..10],
+            [
+                ":s",
+                "stopped at cycle 7 in $exec::$main (stepped by instruction)",
+                ":s",
+                "stopped at cycle 8 in $exec::$main (stepped by instruction)",
+                ":si",
+                "stopped at cycle 9 in $exec::$main (stepped by cycle)",
+                ":s",
+                "stopped at cycle 17 in $exec::$main (stepped by instruction)",
+                ":s",
+                "stopped at cycle 18 in $exec::$main (stepped by instruction)",
+            ]
+        );
+        // Stepping stops early when the program terminates
+        assert_eq!(output[10], ":s 2");
+        assert!(output[11].starts_with("program terminated successfully"), "{output:?}");
+
+        let mut state = State::for_source("begin push.1 push.2 u32max drop end");
+        let (summary, output) = run(&mut state, "s 3\nsi 3\n-s 0");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output,
+            [
+                ":s 3",
+                "stopped at cycle 17 in $exec::$main (stepped by instruction)",
+                ":si 3",
+                "stopped at cycle 20 in $exec::$main (stepped by cycle)",
+                ":s 0",
+                "error: invalid count '0': must be at least 1",
+            ]
+        );
+
+        let mut state = State::for_source("begin push.1 push.2 push.3 u32max assertz end");
+        let (_, output) = run(&mut state, "s 10");
+        assert!(output[1].starts_with("program terminated with an error"), "{output:?}");
+    }
+
+    #[test]
+    fn script_stops_at_first_error() {
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) = run(&mut state, "bogus\nc");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output, vec![":bogus", "error: unknown command"]);
+        assert_eq!(state.executor.cycle, 0);
+
+        let (summary, output) = run(&mut state, "-bogus\nc\nc\nq\nc");
+        assert_eq!(
+            summary,
+            ScriptSummary {
+                failed: 2,
+                quit: false
+            }
+        );
+        assert_eq!(output[2], ":c");
+        assert!(output[3].starts_with("program terminated successfully"));
+        assert_eq!(output[5], "error: program has terminated, cannot continue");
+    }
+
+    #[test]
+    fn script_continue_on_error() {
+        let mut state = State::for_source(PROGRAM);
+        state.config.continue_on_error = true;
+        let (summary, output) = run(&mut state, "bogus\nc");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output[..3], [":bogus", "error: unknown command", ":c"]);
+        assert!(state.executor.stopped);
+    }
+
+    #[test]
+    fn script_trace_user_events() {
+        let mut state = State::for_source("begin push.1 trace.42 drop trace.0xfc00 end");
+        let (summary, output) = run(&mut state, "trace\nc");
+        assert_eq!(summary, ScriptSummary::default());
+        assert_eq!(
+            output,
+            vec![
+                ":trace",
+                "tracing of user trace events enabled",
+                ":c",
+                "trace.42 at cycle 7",
+                "trace.64512 at cycle 10",
+                "program terminated successfully at cycle 10",
+            ]
+        );
+
+        let mut state = State::for_source("begin trace.42 end");
+        let (_, output) = run(&mut state, "trace on\ntrace off\nc");
+        assert_eq!(output[3], "tracing of user trace events disabled");
+        assert!(output[5].starts_with("program terminated successfully"));
+    }
+
+    #[test]
+    fn script_cycle_budget_warning() {
+        let mut state = State::for_source(PROGRAM);
+        state.config.cycle_budget_warning = Some(10);
+        let (_, output) = run(&mut state, "c");
+        assert_eq!(
+            output,
+            vec![
+                ":c",
+                "program terminated successfully at cycle 14",
+                "warning: execution took 14 cycles, exceeding the budget of 10 by 4",
+            ]
+        );
+
+        let mut state = State::for_source(PROGRAM);
+        state.config.cycle_budget_warning = Some(14);
+        let (_, output) = run(&mut state, "c");
+        assert_eq!(output.len(), 2);
+    }
+
+    #[test]
+    fn script_execution_error() {
+        let mut state = State::for_source("begin push.1 push.2 assert_eq end");
+        let (summary, output) = run(&mut state, "error");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output, vec![":error", "error: program has not failed"]);
+
+        let (summary, output) = run(&mut state, "c\nerror");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(state.status(), crate::ui::state::ExecutionStatus::Failed);
+        assert!(output[1].starts_with("program terminated with an error at cycle"));
+        assert_eq!(output[2], ":error");
+        assert!(output[3].starts_with("cycle "), "{output:?}");
+        assert!(output[3].contains("assertion failed"), "{output:?}");
+        assert!(output.iter().any(|line| line == "Stack Trace:"), "{output:?}");
+    }
+
+    #[test]
+    fn script_outputs_and_cycles() {
+        let mut state = State::for_source("begin push.1 push.2 add swap drop end");
+        let (summary, output) = run(&mut state, "cycles\noutputs\nc\ncycles\noutputs u64");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output,
+            vec![
+                ":cycles",
+                "cycle 0 of 15 (0.0%)",
+                ":outputs",
+                "program has not finished, its outputs will be:",
+                "outputs: [3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]",
+                ":c",
+                "program terminated successfully at cycle 15",
+                ":cycles",
+                "cycle 15 of 15 (100.0%)",
+                ":outputs u64",
+                "outputs: [3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]",
+                "result: 3",
+            ]
+        );
+
+        let (summary, output) = run(&mut state, "outputs u7");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output, vec![":outputs u7", "error: unknown type 'u7'"]);
+
+        let mut state = State::for_source("begin push.1 push.2 assert_eq end");
+        let (_, output) = run(&mut state, "c\noutputs");
+        assert_eq!(output[3], "error: program terminated with an error, it has no outputs");
+    }
+
+    #[test]
+    fn script_backtrace() {
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) = run(&mut state, "bt\nsi\nsi\nsi\nbacktrace 8\nbt x");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output,
+            vec![
+                ":bt",
+                "the call stack is empty",
+                ":si",
+                "stopped at cycle 1 (stepped by cycle)",
+                ":si",
+                "stopped at cycle 2 (stepped by cycle)",
+                ":si",
+                "stopped at cycle 3 (stepped by cycle)",
+                ":backtrace 8",
+                "#0 <unknown>",
+                ":bt x",
+                "error: invalid count 'x': invalid digit found in string",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_relative_breakpoints() {
+        let source = "begin\n    push.1\n    push.2\n    add\n    push.3\n    mul\n    drop\nend\n";
+        let (_dir, path) = main_masm(source);
+
+        let mut state = State::for_source_file(&path);
+        let (summary, output) = run(&mut state, "b +1");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output[1],
+            "error: cannot resolve breakpoint relative to the current line: there is no current \
+             source location"
+        );
+
+        let (summary, output) = run(&mut state, "si\nsi\nsi\nsi\nsi\nsi\nb +2\nc\nb :6\nc");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output[11..],
+            [
+                "stopped at cycle 6 in $exec::$main at main.masm:2 (stepped by cycle)",
+                ":b +2",
+                "breakpoint created",
+                ":c",
+                "stopped at cycle 9 in $exec::$main at main.masm:4",
+                ":b :6",
+                "breakpoint created",
+                ":c",
+                "stopped at cycle 11 in $exec::$main at main.masm:6",
+            ]
+        );
+        let file = glob::Pattern::escape(path.to_str().unwrap());
+        assert_eq!(state.breakpoints.len(), 2);
+        assert!(state.breakpoints.iter().all(|bp| matches!(
+            &bp.ty,
+            BreakpointType::Line { pattern, .. } if pattern.as_str() == file
+        )));
+    }
+
+    #[test]
+    fn script_list() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/list/main.masm");
+        let mut state = State::for_source_file(&fixture);
+        let (summary, output) = run(&mut state, "list");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output[1], "error: there is no current source location, see `list FILE:LINE`");
+
+        let script = "
+si
+si
+si
+si
+si
+si
+list
+list
+list -
+list -
+b *main.masm:19
+list 17,19
+list testdata/list/main.masm:5
+list insns
+";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output[11],
+            "stopped at cycle 6 in $exec::$main at main.masm:15 (stepped by cycle)"
+        );
+        let expected = "
+:list
+  10     push.0
+  11     drop
+  12 end
+  13
+  14 begin
+> 15     push.1
+  16     push.2
+  17     exec.add_and_triple
+  18     push.4
+  19     add
+:list
+  20     drop
+  21     push.5
+  22     push.6
+  23     add
+  24     drop
+  25 end
+:list -
+  10     push.0
+  11     drop
+  12 end
+  13
+  14 begin
+> 15     push.1
+  16     push.2
+  17     exec.add_and_triple
+  18     push.4
+  19     add
+:list -
+  1 # Computes (a + b) * 3 for the two inputs on top of the stack
+  2
+  3 proc add_and_triple
+  4     add
+  5     push.3
+  6     mul
+  7 end
+  8
+  9 proc unused
+:b *main.masm:19
+breakpoint created
+:list 17,19
+  17     exec.add_and_triple
+  18     push.4
+ *19     add
+:list testdata/list/main.masm:5
+   1 # Computes (a + b) * 3 for the two inputs on top of the stack
+   2
+   3 proc add_and_triple
+   4     add
+   5     push.3
+   6     mul
+   7 end
+   8
+   9 proc unused
+  10     push.0
+:list insns
+push(2147483648)
+push(4294967294)
+mstore
+drop
+pad
+";
+        assert_eq!(output[12..], expected.trim_start_matches('\n').lines().collect::<Vec<_>>());
+
+        let (_, output) = run(&mut state, "list 30,40");
+        assert_eq!(output[1], "error: line 30 is beyond the end of main.masm, which has 25 lines");
+        let (_, output) = run(&mut state, "list main.masm");
+        assert_eq!(output[1], "error: invalid location 'main.masm', expected FILE:LINE");
+    }
+
+    #[test]
+    fn script_capture() {
+        let mut state = State::for_source(PROGRAM);
+        // Simulate starting with --no-capture-trace
+        state.config.no_capture_trace = true;
+        state.execution_trace = None;
+
+        let (summary, output) = run(&mut state, "-cycles\n-read 8\ncapture\ncapture\ncycles");
+        assert_eq!(summary.failed, 2);
+        assert_eq!(
+            output,
+            vec![
+                ":cycles",
+                "error: the execution trace has not been captured, see `capture`",
+                ":read 8",
+                "error: the execution trace has not been captured, see `capture`",
+                ":capture",
+                "execution trace captured (14 cycles)",
+                ":capture",
+                "the execution trace has already been captured",
+                ":cycles",
+                "cycle 0 of 14 (0.0%)",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_set_color() {
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) = run(&mut state, "set color off\n-set color maybe");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output,
+            vec![
+                ":set color off",
+                "color is off",
+                ":set color maybe",
+                "error: invalid color setting 'maybe', expected on|off",
+            ]
+        );
+        assert_eq!(state.config.color, ColorChoice::Never);
+        assert!(output.iter().all(|line| !line.contains('\x1b')));
+    }
+
+    #[test]
+    fn script_measure() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/measure/main.masm");
+        let mut state = State::for_source_file(&fixture);
+        let (summary, output) = run(&mut state, "measure\nmeasure 2\nmeasure --ops 5\ncycles");
+        assert_eq!(summary.failed, 0);
+        let expected = "
+:measure
+cycles by line (top 8 of 8):
+   9  main.masm:5  sub.1
+   6  main.masm:7  neq.0
+   3  main.masm:6  dup
+   2  main.masm:1  begin
+   2  main.masm:2  push.3
+   2  main.masm:3  push.1
+   2  main.masm:9  drop
+   1  main.masm:4  while.true
+cycles by file:
+  27  main.masm
+  14  <unknown>
+total: 41 cycles
+:measure 2
+cycles by line (top 2 of 8):
+   9  main.masm:5  sub.1
+   6  main.masm:7  neq.0
+cycles by file:
+  27  main.masm
+  14  <unknown>
+total: 41 cycles
+:measure --ops 5
+cycles by instruction (top 5 of 10):
+   9  sub             3 executed, 3.0 cycles each
+   6  neq             3 executed, 2.0 cycles each
+   6  push            5 executed, 1.2 cycles each
+   5  <control flow>  5 executed, 1.0 cycles each
+   5  end             5 executed, 1.0 cycles each
+total: 41 cycles
+:cycles
+cycle 0 of 41 (0.0%)
+";
+        assert_eq!(output, expected.trim_start_matches('\n').lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn script_memory_map() {
+        let source = "
+proc callee
+    push.7 mem_store.0
+end
+
+begin
+    push.42 mem_store.8
+    push.1.2.3.4 mem_storew_be.12 dropw
+    call.callee
+end
+";
+        let mut state = State::for_source(source);
+        let (summary, output) = run(&mut state, "memory-map\nc\nmemory-map\nmm -x");
+        assert_eq!(summary.failed, 0);
+        let expected = [
+            ":memory-map",
+            "no memory has been written",
+            ":c",
+            "program terminated successfully at cycle 39",
+            ":memory-map",
+            "context 0 (root):",
+            "  0x00000008..=0x0000000f (8 elements) [42, 0, 0, 0, ..]",
+            "  0xfffffffc..=0xffffffff (4 elements) [0, 0, 2147483648, 0]",
+            "",
+            "context 31:",
+            "  0x00000000..=0x00000003 (4 elements) [7, 0, 0, 0]",
+            "  0xfffffffc..=0xffffffff (4 elements) [0, 0, 2147483648, 0]",
+            ":mm -x",
+            "context 0 (root):",
+            "  0x00000008..=0x0000000f (8 elements) [0x2a, 0x0, 0x0, 0x0, ..]",
+            "  0xfffffffc..=0xffffffff (4 elements) [0x0, 0x0, 0x80000000, 0x0]",
+            "",
+            "context 31:",
+            "  0x00000000..=0x00000003 (4 elements) [0x7, 0x0, 0x0, 0x0]",
+            "  0xfffffffc..=0xffffffff (4 elements) [0x0, 0x0, 0x80000000, 0x0]",
+        ];
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn script_advice() {
+        use miden_processor::advice::AdviceInputs;
+
+        let config = crate::exec::ExecutionConfig {
+            advice_inputs: AdviceInputs::default()
+                .with_stack([RawFelt::new(5), RawFelt::new(7), RawFelt::new(255)])
+                .with_map([(
+                    miden_core::Word::from([1u32, 2, 3, 4]),
+                    (1..=6).map(RawFelt::new).collect::<Vec<_>>(),
+                )]),
+            ..Default::default()
+        };
+        let mut state = State::for_source("begin adv_push.2 add swap drop end");
+        state.config.inputs = Some(config);
+        state.reload().unwrap();
+
+        const KEY: &str = "0x0100000000000000020000000000000003000000000000000400000000000000";
+        let script = format!(
+            "advice stack\nadvice stack 1 -x\nadvice map\nadvice map {KEY}\nadvice merkle\nc\n\
+             advice stack"
+        );
+        let (summary, output) = run(&mut state, &script);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output,
+            vec![
+                ":advice stack".to_string(),
+                "advice stack depth is 3".to_string(),
+                "[0] 5".to_string(),
+                "[1] 7".to_string(),
+                "[2] 255".to_string(),
+                ":advice stack 1 -x".to_string(),
+                "advice stack depth is 3".to_string(),
+                "[0] 0x5".to_string(),
+                ":advice map".to_string(),
+                "advice map has 1 entries".to_string(),
+                format!("{KEY} (6 elements)"),
+                format!(":advice map {KEY}"),
+                format!("{KEY} (6 elements)"),
+                "[0] [1, 2, 3, 4]".to_string(),
+                "[1] [5, 6]".to_string(),
+                ":advice merkle".to_string(),
+                "merkle store has 255 internal nodes".to_string(),
+                ":c".to_string(),
+                "program terminated successfully at cycle 12".to_string(),
+                ":advice stack".to_string(),
+                "advice stack depth is 1".to_string(),
+                "[0] 255".to_string(),
+            ]
+        );
+        assert_eq!(
+            state.executor.stack_outputs.get_element(0).map(|felt| felt.as_canonical_u64()),
+            Some(12)
+        );
+
+        let (summary, output) = run(&mut state, "advice map 0x01");
+        assert_eq!(summary.failed, 1);
+        assert!(output[1].starts_with("error: invalid advice map key '0x01'"), "{output:?}");
+    }
+
+    #[test]
+    fn script_set_args_and_run() {
+        let mut state = State::for_source("begin push.0 drop end");
+        state.config.args = vec![Felt::new(1).into(), Felt::new(2).into()];
+        state.reload().unwrap();
+
+        let script = "c\noutputs\nset args 5 7\nreload\nc\noutputs\nrun 10 0x14\nc\noutputs";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 0);
+        let results =
+            output.iter().filter(|line| line.starts_with("outputs: ")).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].ends_with(", 2, 1]"), "{output:?}");
+        assert!(results[1].ends_with(", 7, 5]"), "{output:?}");
+        assert!(results[2].ends_with(", 20, 10]"), "{output:?}");
+        assert!(output.contains(&"args will take effect on the next reload".to_string()));
+        assert!(output.contains(&"program reloaded with 2 argument(s)".to_string()));
+    }
+
+    #[test]
+    fn script_typed_args() {
+        let mut state = State::for_source("begin push.0 drop end");
+        let (summary, output) = run(&mut state, "run 0x100000002u64 -3i32 0xffi8\nc\noutputs");
+        assert_eq!(summary.failed, 0);
+        assert!(output[1].ends_with("with 3 argument(s)"), "{output:?}");
+        // Each limb of the u64 is an element, the least significant nearest the top
+        assert!(output.last().unwrap().ends_with(", 255, 4294967293, 2, 1]"), "{output:?}");
+
+        let (summary, output) = run(&mut state, "-run 256u8\n-run -1u32");
+        assert_eq!(summary.failed, 2);
+        assert!(output[1].starts_with("error: invalid argument '256u8': invalid u8 value"));
+        assert!(output[3].starts_with("error: invalid argument '-1u32': invalid u32 value"));
+    }
+
+    #[test]
+    fn script_history() {
+        let mut state = State::for_source(PROGRAM);
+        state.history = crate::ui::history::History::load(None, 100);
+        for i in 0..30 {
+            state.history.push(format!("cycles {i}"));
+        }
+        let (summary, output) = run(&mut state, "history\nhistory 2\n-history x");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output[1], "   11  cycles 10");
+        assert_eq!(output[20], "   30  cycles 29");
+        assert_eq!(&output[21..24], [":history 2", "   29  cycles 28", "   30  cycles 29"]);
+        assert!(output[25].starts_with("error: invalid count 'x'"), "{output:?}");
+    }
+
+    #[test]
+    fn script_reset() {
+        let mut state = State::for_source("begin push.0 drop end");
+        let script = "c\ncycles\nset args 5 7\nbreak at 2\nreset\ncycles\nc\nc\noutputs";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(output[3], "cycle 9 of 9 (100.0%)");
+        assert_eq!(output[10], "cycle 0 of 9 (0.0%)");
+        assert_eq!(output[12], "stopped at cycle 2");
+        assert!(output.last().unwrap().ends_with(", 7, 5]"), "{output:?}");
+    }
+
+    #[test]
+    fn script_set_invalid_inputs() {
+        let mut state = State::for_source("begin push.0 drop end");
+        state.config.args = vec![Felt::new(1).into(), Felt::new(2).into()];
+        state.reload().unwrap();
+
+        let (summary, output) =
+            run(&mut state, "-set args 1 x\n-run 1 x\n-set input-file missing.toml");
+        assert_eq!(summary.failed, 3);
+        assert!(output[1].starts_with("error: invalid argument 'x'"), "{output:?}");
+        assert!(output[3].starts_with("error: invalid argument 'x'"), "{output:?}");
+        assert!(output[5].starts_with("error: invalid inputs file"), "{output:?}");
+
+        // The session is unaffected
+        assert_eq!(state.config.args, vec![Felt::new(1).into(), Felt::new(2).into()]);
+        assert!(state.config.inputs.is_none());
+        let (_, output) = run(&mut state, "c\noutputs");
+        assert!(output[3].ends_with(", 2, 1]"), "{output:?}");
+    }
+
+    #[test]
+    fn every_command_is_dispatched() {
+        // Keep files created by examples, e.g. session logs, out of the source tree
+        let dir = tempfile::tempdir().unwrap();
+        for command in help::COMMANDS {
+            let examples = if command.examples.is_empty() {
+                &[command.name][..]
+            } else {
+                command.examples
+            };
+            for example in examples {
+                let mut state = State::for_source(PROGRAM);
+                state.config.working_dir = Some(dir.path().to_path_buf());
+                if let Err(err) = execute(&mut state, example, &mut |_| ()) {
+                    assert!(
+                        !err.starts_with("unknown command")
+                            && !err.starts_with("invalid arguments"),
+                        "'{example}' was not dispatched: {err}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn script_info_breakpoints() {
+        let (_dir, path) = main_masm(
+            "begin\n    push.3 push.1\n    while.true\n        sub.1\n        dup.0 neq.0\n    end\n    drop\nend\n",
+        );
+        let file = path.display();
+
+        let mut state = State::for_source_file(&path);
+        let script = format!("info breakpoints\nb {file}:4\nb {file}:5\nb at 1000");
+        let (summary, output) = run(&mut state, &script);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(output[1], "there are no breakpoints");
+
+        // Execution passes through disabled breakpoints on each iteration, without stopping
+        state.breakpoints[0].enabled = false;
+        state.breakpoints[1].enabled = false;
+        let (summary, output) = run(&mut state, "c\ninfo breakpoints");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            &output[output.len() - 3..],
+            &[
+                format!("  0 [ ] {file}:4  hits: 0, passes: 3"),
+                format!("  1 [ ] {file}:5  hits: 0, passes: 3"),
+                format!("  2 [x] at 1000{}  hits: 0", " ".repeat(file.to_string().len() - 5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn script_continue_count() {
+        let (_dir, path) = main_masm(
+            "begin\n    push.3 push.1\n    while.true\n        sub.1\n        dup.0 neq.0\n    end\n    drop\nend\n",
+        );
+        let file = path.display();
+
+        // The breakpoint is hit once on each iteration, however many cycles its line takes
+        let mut state = State::for_source_file(&path);
+        let (summary, output) = run(&mut state, &format!("b {file}:5\ncontinue 2"));
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output[3],
+            "stopped at cycle 24 in $exec::$main at main.masm:5 (2 of 2 breakpoint hits)"
+        );
+        assert_eq!(state.breakpoints[0].hits, 2);
+        assert_eq!(state.executor.current_stack[0], RawFelt::new(1));
+
+        // Termination before the Nth hit is reported along with the hits which occurred
+        let (summary, output) = run(&mut state, "c 5\ncontinue 0");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output,
+            vec![
+                ":c 5",
+                "program terminated successfully at cycle 41 (1 of 5 breakpoint hits)",
+                ":continue 0",
+                "error: invalid count '0': must be at least 1",
+            ]
+        );
+        assert_eq!(state.breakpoints[0].hits, 3);
+    }
+
+    #[test]
+    fn script_calltree() {
+        // Calls are delimited by the frame start and end trace events emitted by the compiler
+        let mut state = State::for_source(
+            "
+proc inner
+    trace.240 push.7 drop trace.252
+end
+
+proc outer
+    trace.240 push.1 drop exec.inner exec.inner push.3 drop trace.252
+end
+
+begin
+    push.0 drop exec.outer push.4 drop
+end
+",
+        );
+        let (summary, output) = run(
+            &mut state,
+            "calltree\nb at 13\nc\ncalltree\nc\ncalltree\ncalltree 1\ncalltree 0",
+        );
+        assert_eq!(summary.failed, 1);
+        assert_eq!(
+            output,
+            [
+                ":calltree",
+                "no procedure has been entered yet",
+                ":b at 13",
+                "breakpoint created",
+                ":c",
+                "stopped at cycle 13 in $exec::inner",
+                // The second call to inner begins in the cycle in which the first returns
+                ":calltree",
+                "$exec::$main  [1..] in progress",
+                "  $exec::outer  [7..] in progress",
+                "    $exec::inner  [10..12] 2 cycles",
+                "    $exec::inner  [12..] in progress",
+                ":c",
+                "program terminated successfully at cycle 19",
+                // Consecutive calls which made no calls of their own are described together
+                ":calltree",
+                "$exec::$main  [1..] did not return",
+                "  $exec::outer  [7..16] 9 cycles",
+                "    $exec::inner  [10..14] 4 cycles in 2 calls",
+                ":calltree 1",
+                "$exec::$main  [1..] did not return",
+                "  ... 3 more call(s)",
+                ":calltree 0",
+                "error: invalid depth '0': must be at least 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_info_package() {
+        use miden_core::Word;
+        use miden_mast_package::{Dependency, PackageExport, PackageManifest, ProcedureExport};
+
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) = run(&mut state, "info package\ninfo");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(&output[..3], &[":info package", "name: test", "kind: executable"]);
+        assert!(output[3].starts_with("digest: 0x"), "unexpected output: {output:?}");
+        assert_eq!(&output[4..7], &["dependencies (0):", "exported procedures (0):", ":info"]);
+
+        let mut package = (*state.package).clone();
+        package.manifest = PackageManifest::new([PackageExport::Procedure(ProcedureExport {
+            path: Arc::from(miden_assembly::Path::new("test::store")),
+            digest: Word::default(),
+            signature: None,
+            attributes: Default::default(),
+        })])
+        .with_dependencies([Dependency {
+            name: String::from("std").into(),
+            digest: Word::default(),
+        }]);
+        state.package = Arc::new(package);
+        let zeros = "0".repeat(64);
+        let (summary, output) = run(&mut state, "info package");
+        assert_eq!(summary, ScriptSummary::default());
+        assert_eq!(
+            &output[4..],
+            &[
+                "dependencies (1):".to_string(),
+                format!("  std 0x{zeros} (unresolved)"),
+                "exported procedures (1):".to_string(),
+                format!("  test::store 0x{zeros}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn script_set_theme() {
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) =
+            run(&mut state, "set theme\nset theme InspiredGitHub\n-set theme bogus");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output[0], ":set theme");
+        assert!(output.contains(&"base16-eighties.dark (selected)".to_string()), "{output:?}");
+        assert!(output.contains(&"InspiredGitHub".to_string()), "{output:?}");
+        let output =
+            &output[output.iter().position(|line| line == ":set theme InspiredGitHub").unwrap()..];
+        assert_eq!(output[1], "theme is InspiredGitHub");
+        assert_eq!(output[2], ":set theme bogus");
+        assert!(output[3].starts_with("error: unknown theme 'bogus', the available themes are: "));
+        assert_eq!(state.theme.name, "InspiredGitHub");
+    }
+
+    #[test]
+    fn script_digest() {
+        use miden_assembly::ast::{Module, ModuleKind};
+        use miden_assembly_syntax::DisplayHex;
+        use miden_mast_package::{MastArtifact, PackageKind};
+
+        let source_manager = Arc::new(miden_debug_types::DefaultSourceManager::default());
+        let module = Module::parser(ModuleKind::Library)
+            .parse_str(
+                "test::math",
+                "pub proc add_two\n    push.2 add\nend\n\npub proc add_three\n    push.3 add\nend\n",
+                source_manager.clone(),
+            )
+            .unwrap();
+        let library = Arc::new(
+            miden_assembly::Assembler::new(source_manager)
+                .assemble_library([module])
+                .unwrap(),
+        );
+        let digest = |name: &str| {
+            let digest = library.get_procedure_root_by_path(name).unwrap();
+            format!("0x{}", DisplayHex::new(&digest.as_bytes()))
+        };
+
+        // Procedures are resolved among those exported by the loaded libraries
+        let mut state = State::for_source(PROGRAM);
+        state.libraries.push(library.clone());
+        let script = "digest test::math::add_two\n-digest test::math::add_tow\n-digest \
+                      math::add_three\n-digest bogus";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 3);
+        assert_eq!(
+            output,
+            [
+                ":digest test::math::add_two".to_string(),
+                format!("test::math::add_two: {}", digest("test::math::add_two")),
+                ":digest test::math::add_tow".into(),
+                "error: no procedure named 'test::math::add_tow' was found, did you mean: \
+                 test::math::add_two?"
+                    .into(),
+                ":digest math::add_three".into(),
+                "error: no procedure named 'math::add_three' was found, did you mean: \
+                 test::math::add_three?"
+                    .into(),
+                ":digest bogus".into(),
+                "error: no procedure named 'bogus' was found".into(),
+            ]
+        );
+
+        // As well as in the package itself, if it is a library
+        let mut package = (*state.package).clone();
+        package.kind = PackageKind::Library;
+        package.mast = MastArtifact::Library(library.clone());
+        state.package = Arc::new(package);
+        state.libraries.clear();
+        let (summary, output) = run(&mut state, "digest test::math::add_three");
+        assert_eq!(summary, ScriptSummary::default());
+        assert_eq!(
+            output[1],
+            format!("test::math::add_three: {}", digest("test::math::add_three"))
+        );
+    }
+
+    #[test]
+    fn script_stack_overflow() {
+        let mut state = State::for_source("begin push.100 push.200 drop drop end");
+        let (summary, output) = run(&mut state, "b at 7\nc\nstack\nstack --all -x");
+        assert_eq!(summary, ScriptSummary::default());
+        let mut expected =
+            vec!["stack depth is 18".to_string(), "[0] 200".into(), "[1] 100".into()];
+        expected.extend((2..16).map(|i| format!("[{i}] 0")));
+        expected.push("-- 2 more elements in the overflow table, see `stack --all` --".into());
+        assert_eq!(&output[5..23], expected.as_slice(), "unexpected output: {output:#?}");
+        assert_eq!(output[23], ":stack --all -x");
+        assert_eq!(
+            &output[41..],
+            &["-- overflow table (2 elements) --", "[16] 0x0", "[17] 0x0"],
+            "unexpected output: {output:#?}"
+        );
+    }
+
+    #[test]
+    fn script_stack_delta() {
+        let mut state = State::for_source("begin push.100 push.200 swap drop drop end");
+        let (summary, output) = run(&mut state, "b at 7\nc\nstack --delta\nb at 8\nc\nstack -d");
+        assert_eq!(summary, ScriptSummary::default());
+        assert_eq!(&output[5..8], &["stack depth is 18", "[0] 200 (pushed)", "[1] 100"]);
+        let swapped = output.iter().position(|line| line == ":stack -d").unwrap();
+        assert_eq!(
+            &output[(swapped + 1)..(swapped + 4)],
+            &["stack depth is 18", "[0] 100 * (changed)", "[1] 200 * (changed)"],
+            "unexpected output: {output:#?}"
+        );
+        assert!(run(&mut state, "stack --deltas").1[1].starts_with("error: invalid argument"));
+    }
+
+    #[test]
+    fn script_watch() {
+        let mut state = State::for_source(PROGRAM);
+        let script = "watch 8 -t felt\nwatch 9 -t word\nc\nwatch\nreload\nwatch\nunwatch 1\nwatch";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary, ScriptSummary::default());
+        let error = "read failed: type 'word' must be aligned to a word boundary";
+        assert_eq!(
+            output,
+            vec![
+                ":watch 8 -t felt".to_string(),
+                "watching [0] 8 -t felt".into(),
+                ":watch 9 -t word".into(),
+                "watching [1] 9 -t word".into(),
+                ":c".into(),
+                "program terminated successfully at cycle 14".into(),
+                ":watch".into(),
+                "[0] 8 -t felt = 42".into(),
+                format!("[1] 9 -t word: {error}"),
+                ":reload".into(),
+                ":watch".into(),
+                "[0] 8 -t felt = 42".into(),
+                format!("[1] 9 -t word: {error}"),
+                ":unwatch 1".into(),
+                "stopped watching 9 -t word".into(),
+                ":watch".into(),
+                "[0] 8 -t felt = 42".into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn script_context_switch_breakpoint() {
+        let mut state = State::for_source(
+            "
+proc callee
+    push.1 drop
+end
+
+begin
+    call.callee
+end
+",
+        );
+        let (summary, output) = run(&mut state, "b context\nc\nc\nc");
+        assert_eq!(summary, ScriptSummary::default());
+        assert_eq!(
+            output,
+            vec![
+                ":b context",
+                "breakpoint created",
+                ":c",
+                "stopped at cycle 9 (entered context 9 from context 0)",
+                ":c",
+                "stopped at cycle 15 in $exec::callee (entered context 0 from context 9)",
+                ":c",
+                "program terminated successfully at cycle 16",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_aliases() {
+        let mut state = State::for_source(PROGRAM);
+        let script = "
+alias
+alias m $1 -t felt
+alias m r $1 -t felt
+alias bi b in $1
+alias
+alias m
+bi *store
+c
+m 8
+unalias m
+-m 8
+-unalias m
+-alias c continue
+";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 3);
+        let expected = [
+            ":alias",
+            "no aliases are defined",
+            ":alias m $1 -t felt",
+            "alias 'm' defined",
+            ":alias m r $1 -t felt",
+            "alias 'm' defined",
+            ":alias bi b in $1",
+            "alias 'bi' defined",
+            ":alias",
+            "bi = b in $1",
+            "m = r $1 -t felt",
+            ":alias m",
+            "m = r $1 -t felt",
+            ":bi *store",
+            "breakpoint created",
+            ":c",
+        ];
+        assert_eq!(output[..expected.len()], expected);
+        let rest = &output[expected.len()..];
+        let at = rest.iter().position(|line| line == ":m 8").unwrap();
+        assert_eq!(
+            rest[at..],
+            [
+                ":m 8",
+                "42",
+                ":unalias m",
+                "alias 'm' removed",
+                ":m 8",
+                "error: unknown command, did you mean 'mm'?",
+                ":unalias m",
+                "error: no such alias 'm'",
+                ":alias c continue",
+                "error: cannot define alias 'c': it is the name of a command",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_dump_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = State::for_source(PROGRAM);
+        state.config.working_dir = Some(dir.path().to_path_buf());
+        let script = "c\ndump memory raw.bin 32 8\ndump memory hex.txt 0x1e 20 --format hex\ndump \
+                      memory end.bin 0xfffffffe 8\n-dump memory raw.bin 32\n-dump memory x 0 4 \
+                      --format elf";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(
+            &output[3..],
+            &[
+                format!(
+                    "wrote 8 bytes from 0x20 in context 0 at cycle 14 to {}",
+                    dir.path().join("raw.bin").display()
+                ),
+                ":dump memory hex.txt 0x1e 20 --format hex".into(),
+                format!(
+                    "wrote 20 bytes from 0x1e in context 0 at cycle 14 to {}",
+                    dir.path().join("hex.txt").display()
+                ),
+                ":dump memory end.bin 0xfffffffe 8".into(),
+                format!(
+                    "wrote 2 bytes from 0xfffffffe in context 0 at cycle 14 to {} (warning: 8 bytes \
+                     were requested, but memory ends at 0x100000000)",
+                    dir.path().join("end.bin").display()
+                ),
+                ":dump memory raw.bin 32".into(),
+                "error: invalid arguments, usage: dump memory FILE START LEN [--format raw|hex]"
+                    .into(),
+                ":dump memory x 0 4 --format elf".into(),
+                "error: invalid format, expected '--format raw|hex'".into(),
+            ]
+        );
+
+        assert_eq!(std::fs::read(dir.path().join("raw.bin")).unwrap(), [0, 0, 0, 42, 0, 0, 0, 0]);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hex.txt")).unwrap(),
+            "0000001e: 0000 0000 002a 0000 0000 0000 0000 0000  .....*..........\n\
+             0000002e: 0000 0000                                ....\n"
+        );
+        assert_eq!(std::fs::read(dir.path().join("end.bin")).unwrap(), [0, 0]);
+
+        // Regions larger than a chunk are written a chunk at a time
+        let (summary, _) = run(
+            &mut state,
+            "dump memory big.bin 0 0x20010\ndump memory big.txt 0 0x20010 --format hex",
+        );
+        assert_eq!(summary.failed, 0);
+        let big = std::fs::read(dir.path().join("big.bin")).unwrap();
+        assert_eq!(big.len(), 0x20010);
+        assert_eq!(big[32..40], [0, 0, 0, 42, 0, 0, 0, 0]);
+        let text = std::fs::read_to_string(dir.path().join("big.txt")).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 0x2001);
+        assert_eq!(
+            lines[0x2000],
+            "00020000: 0000 0000 0000 0000 0000 0000 0000 0000  ................"
+        );
+    }
+
+    #[test]
+    fn script_dump_region() {
+        let mut state = State::for_source(PROGRAM);
+        let script = "c\ndump 0x1e 20\ndump 32 6 --width 4 --fill 00\ndump 0xfffffffe 4\n-dump \
+                      32\n-dump 0 4 --width 0\n-dump 0 4 --fill x\n-dump 0 0x100000000";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 4);
+        assert_eq!(
+            &output[2..],
+            &[
+                ":dump 0x1e 20",
+                "0000001e  .. .. .. .. .. 2a .. .. .. .. .. .. .. .. .. ..  |.....*..........|",
+                "0000002e  .. .. .. ..                                      |....|",
+                ":dump 32 6 --width 4 --fill 00",
+                "00000020  00 00 00 2a  |...*|",
+                "00000024  00 00        |..|",
+                ":dump 0xfffffffe 4",
+                "fffffffe  .. ..                                            |..|",
+                "(warning: 4 bytes were requested, but memory ends at 0x100000000)",
+                ":dump 32",
+                "error: invalid arguments, usage: dump START LEN [--width N] [--fill XX]",
+                ":dump 0 4 --width 0",
+                "error: invalid width '0', expected a number from 1 to 64",
+                ":dump 0 4 --fill x",
+                "error: invalid fill 'x', expected two characters",
+                ":dump 0 0x100000000",
+                "error: invalid length '4294967296', at most 65536 bytes can be shown, use 'dump \
+                 memory' to write larger regions to a file",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_session_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.log");
+
+        let mut state = State::for_source("begin push.1 push.2 add swap drop end");
+        let script = format!("cycles\nlog {}\nc\n-bogus\nlog off\ncycles", path.display());
+        let (summary, _) = run(&mut state, &script);
+        assert_eq!(summary.failed, 1);
+
+        // Strip the timestamp from each line of the transcript
+        let transcript = std::fs::read_to_string(&path).unwrap();
+        let transcript = transcript
+            .lines()
+            .map(|line| line.split_once(' ').unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transcript,
+            vec![
+                "[cycle 0] # session log started",
+                format!("[cycle 0]   session log is written to {}", path.display()).as_str(),
+                "[cycle 0] :c",
+                "[cycle 15]   program terminated successfully at cycle 15",
+                "[cycle 15] :bogus",
+                "[cycle 15]   error: unknown command",
+                "[cycle 15] :log off",
+                "[cycle 15] # session log stopped",
+            ]
+        );
+    }
+
+    #[test]
+    fn script_save_debug_log() {
+        use log::Log;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = State::for_source(PROGRAM);
+        state.config.working_dir = Some(dir.path().to_path_buf());
+        crate::logger::DebugLogger::get().log(
+            &log::Record::builder()
+                .level(log::Level::Warn)
+                .target("miden_debug::test")
+                .args(format_args!("saved by script_save_debug_log"))
+                .build(),
+        );
+
+        let (summary, output) = run(&mut state, "log-save debug.log");
+        assert_eq!(summary.failed, 0);
+        assert!(output[1].starts_with("saved "), "{output:?}");
+        let saved = std::fs::read_to_string(dir.path().join("debug.log")).unwrap();
+        assert!(
+            saved
+                .lines()
+                .any(|line| line
+                    .ends_with("WARN  miden_debug::test: saved by script_save_debug_log")),
+            "{saved}"
+        );
+
+        let (summary, output) = run(&mut state, "log-save");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output[1], "error: invalid arguments, usage: log-save FILE");
+    }
+
+    #[test]
+    fn script_shell_commands() {
+        let mut state = State::for_source(PROGRAM);
+        let script = "! echo hello\n-!false\n-make\nc";
+        let (summary, output) = run(&mut state, script);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(
+            output[..7],
+            [
+                ":! echo hello",
+                "hello",
+                "command exited successfully",
+                ":!false",
+                "error: command failed with exit status: 1",
+                ":make",
+                "error: no build command has been configured, see --build-cmd",
+            ]
+        );
+
+        // What the command writes to stderr is shown too
+        let (summary, output) = run(&mut state, "! echo oops >&2");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(output, [":! echo oops >&2", "oops", "command exited successfully"]);
+
+        state.config.build_cmd = Some("echo built".into());
+        let (summary, output) = run(&mut state, "rebuild\ncycles");
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            output[..4],
+            [":rebuild", "built", "command exited successfully", "program reloaded"]
+        );
+        assert!(output[5].starts_with("cycle 0 of"), "unexpected output: {output:?}");
+
+        state.config.build_cmd = Some("false".into());
+        let (summary, output) = run(&mut state, "rebuild");
+        assert_eq!(summary.failed, 1);
+        assert_eq!(output, [":rebuild", "error: command failed with exit status: 1"]);
+    }
+
+    #[test]
+    fn script_help() {
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) = run(&mut state, "help\nhelp b\n-help brek\n-brek\n-c now");
+        assert_eq!(summary.failed, 3);
+        assert_eq!(output[0], ":help");
+        assert!(output[1].starts_with("break, b, breakpoint  create a breakpoint"), "{output:?}");
+        assert_eq!(output.iter().filter(|line| line.starts_with(":")).count(), 5);
+
+        let detail = output.iter().position(|line| line == ":help b").unwrap();
+        assert_eq!(output[detail + 1], "break - create a breakpoint");
+        assert_eq!(output[detail + 3], "usage: break SPEC");
+        assert_eq!(output[detail + 4], "aliases: b, breakpoint");
+
+        assert!(
+            output.contains(&"error: unknown command 'brek', did you mean 'break'?".to_string())
+        );
+        assert!(output.contains(&"error: unknown command, did you mean 'break'?".to_string()));
+        assert_eq!(output.last().unwrap(), "error: invalid arguments, usage: continue [N]");
+    }
+
+    #[test]
+    fn script_quit() {
+        let mut state = State::for_source(PROGRAM);
+        let (summary, output) = run(&mut state, "q\nc");
+        assert_eq!(
+            summary,
+            ScriptSummary {
+                failed: 0,
+                quit: true
+            }
+        );
+        assert_eq!(output, vec![":q"]);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeSet;
+
+use crate::ui::{help, state::State};
+
+/// Commands whose arguments are breakpoint expressions
+const BREAKPOINT_COMMANDS: &[&str] = &["b", "break", "breakpoint"];
+
+/// The keywords which may begin a breakpoint expression
+const BREAKPOINT_KEYWORDS: &[&str] = &["after", "at", "finish", "in", "next"];
+
+/// The result of completing the word under the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The byte offset in the input at which the word being completed starts
+    pub start: usize,
+    /// The candidates which may replace the word being completed, in sorted order
+    pub candidates: Vec<String>,
+}
+
+/// Provides completion candidates for the `:` command line.
+///
+/// Candidates are derived from the set of known commands and user-defined aliases, breakpoint
+/// keywords, the procedures exported by the loaded package and its libraries, and the source files
+/// which have been loaded into the source manager. The set of procedures and files is a snapshot,
+/// and must be refreshed via [Completer::new] whenever the program is reloaded, and the aliases
+/// via [Completer::set_aliases] whenever they may have changed.
+#[derive(Debug, Default, Clone)]
+pub struct Completer {
+    procedures: BTreeSet<String>,
+    files: BTreeSet<String>,
+    aliases: BTreeSet<String>,
+}
+
+impl Completer {
+    pub fn new(state: &State) -> Self {
+        let mut procedures = BTreeSet::default();
+        for (_, name) in state.package.procedure_names() {
+            procedures.insert(format!("{:#}", rustc_demangle::demangle(name)));
+        }
+        for export in state.package.manifest.exports().filter(|export| export.is_procedure()) {
+            procedures.insert(export.path().to_string().trim_start_matches("::").to_string());
+        }
+        for library in state.libraries.iter() {
+            for export in library.exports().filter_map(|export| export.as_procedure()) {
+                procedures.insert(export.path.to_string().trim_start_matches("::").to_string());
+            }
+        }
+
+        let files = state
+            .source_files()
+            .into_iter()
+            .map(|file| file.content().uri().as_str().to_string());
+
+        let mut completer = Self::with_candidates(procedures, files);
+        completer.set_aliases(state);
+        completer
+    }
+
+    pub fn with_candidates(
+        procedures: impl IntoIterator<Item = String>,
+        files: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            procedures: procedures.into_iter().collect(),
+            files: files.into_iter().collect(),
+            aliases: BTreeSet::default(),
+        }
+    }
+
+    /// Refresh the names of the aliases defined in `state`
+    pub fn set_aliases(&mut self, state: &State) {
+        self.aliases = state.aliases.iter().map(|(name, _)| name.to_string()).collect();
+    }
+
+    /// Compute the candidates for completing the last word of `line`
+    pub fn complete(&self, line: &str) -> Option<Completion> {
+        let (start, word, candidates): (usize, &str, Vec<&str>) = match line.split_once(' ') {
+            None => {
+                let mut names = help::command_names().collect::<Vec<_>>();
+                names.extend(self.aliases.iter().map(String::as_str));
+                (0, line, names)
+            }
+            Some((cmd, rest)) if BREAKPOINT_COMMANDS.contains(&cmd) => {
+                let offset = cmd.len() + 1;
+                let trimmed = rest.trim_start();
+                let offset = offset + (rest.len() - trimmed.len());
+                match trimmed.split_once(' ') {
+                    Some(("in", procedure)) => {
+                        let start = line.len() - procedure.len();
+                        (start, procedure, self.procedures.iter().map(String::as_str).collect())
+                    }
+                    Some(_) => return None,
+                    None => {
+                        let candidates = BREAKPOINT_KEYWORDS
+                            .iter()
+                            .copied()
+                            .chain(self.files.iter().map(String::as_str))
+                            .collect();
+                        (offset, trimmed, candidates)
+                    }
+                }
+            }
+            Some(_) => return None,
+        };
+
+        let mut candidates = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word) && *candidate != word)
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return None;
+        }
+        candidates.sort();
+        candidates.dedup();
+        Some(Completion { start, candidates })
+    }
+
+    /// Return the text which would be appended to `line` by accepting the most likely completion
+    pub fn hint(&self, line: &str) -> Option<String> {
+        let completion = self.complete(line)?;
+        let word = &line[completion.start..];
+        let candidate = completion.candidates.first()?;
+        Some(candidate[word.len()..].to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completer() -> Completer {
+        Completer::with_candidates(
+            ["std::math::u64::add".to_string(), "root::main".to_string()],
+            ["src/lib.rs".to_string(), "src/main.rs".to_string()],
+        )
+    }
+
+    #[test]
+    fn complete_command_names() {
+        let completion = completer().complete("b").unwrap();
+        assert_eq!(completion.start, 0);
+        assert_eq!(
+            completion.candidates,
+            vec![
+                "backtrace".to_string(),
+                "break".to_string(),
+                "breakpoint".to_string(),
+                "bt".to_string()
+            ]
+        );
+
+        let completion = completer().complete("re").unwrap();
+        assert_eq!(
+            completion.candidates,
+            vec![
+                "read".to_string(),
+                "rebuild".to_string(),
+                "reload".to_string(),
+                "reset".to_string()
+            ]
+        );
+
+        assert!(completer().complete("x").is_none());
+    }
+
+    #[test]
+    fn complete_aliases_and_loaded_files() {
+        use miden_debug_types::SourceManagerExt;
+
+        let mut state = State::for_source("begin push.1 drop end");
+        state
+            .source_manager
+            .load_file(std::path::Path::new("testdata/batch/add.masm"))
+            .unwrap();
+        let mut completer = Completer::new(&state);
+        assert!(completer.complete("m3").is_none());
+        assert!(completer.files.iter().any(|file| file.ends_with("add.masm")), "{completer:?}");
+
+        // Aliases defined since the completer was created are completed once refreshed
+        state.aliases.define("m32", "read $1 -t u32").unwrap();
+        completer.set_aliases(&state);
+        assert_eq!(completer.complete("m3").unwrap().candidates, vec!["m32"]);
+    }
+
+    #[test]
+    fn complete_breakpoint_keywords_and_files() {
+        let completion = completer().complete("b a").unwrap();
+        assert_eq!(completion.start, 2);
+        assert_eq!(completion.candidates, vec!["after".to_string(), "at".to_string()]);
+
+        let completion = completer().complete("break src/m").unwrap();
+        assert_eq!(completion.start, 6);
+        assert_eq!(completion.candidates, vec!["src/main.rs".to_string()]);
+    }
+
+    #[test]
+    fn complete_procedure_names() {
+        let completion = completer().complete("b in std::").unwrap();
+        assert_eq!(completion.start, 5);
+        assert_eq!(completion.candidates, vec!["std::math::u64::add".to_string()]);
+
+        let completion = completer().complete("b in ").unwrap();
+        assert_eq!(completion.candidates.len(), 2);
+    }
+
+    #[test]
+    fn completion_hints() {
+        assert_eq!(completer().hint("relo").as_deref(), Some("ad"));
+        assert_eq!(completer().hint("b in root").as_deref(), Some("::main"));
+        assert_eq!(completer().hint("read 0x0"), None);
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt;
+
+/// Displays a count, e.g. of cycles, with `,` separating each group of three digits
+#[derive(Copy, Clone)]
+pub struct HumanCount(pub usize);
+
+impl fmt::Display for HumanCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.0.to_string();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                f.write_str(",")?;
+            }
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn human_count_separators() {
+        assert_eq!(HumanCount(0).to_string(), "0");
+        assert_eq!(HumanCount(999).to_string(), "999");
+        assert_eq!(HumanCount(1000).to_string(), "1,000");
+        assert_eq!(HumanCount(123456).to_string(), "123,456");
+        assert_eq!(HumanCount(1234567).to_string(), "1,234,567");
+    }
+}
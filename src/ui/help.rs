@@ -0,0 +1,619 @@
+/// Describes a command accepted by the `:` command line, and by scripts
+#[derive(Debug)]
+pub struct CommandHelp {
+    /// The canonical name of the command
+    pub name: &'static str,
+    /// Alternative names for the command
+    pub aliases: &'static [&'static str],
+    /// A one-line description of the command
+    pub summary: &'static str,
+    /// The syntax of the command
+    pub usage: &'static str,
+    /// Examples of the command in use
+    pub examples: &'static [&'static str],
+    /// Further details about the command, if any
+    pub notes: &'static str,
+}
+
+impl CommandHelp {
+    /// Returns the canonical name of this command, followed by its aliases
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + use<> {
+        core::iter::once(self.name).chain(self.aliases.iter().copied())
+    }
+
+    /// Render the detailed help for this command
+    pub fn render(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} - {}", self.name, self.summary), String::new()];
+        lines.push(format!("usage: {}", self.usage));
+        if !self.aliases.is_empty() {
+            lines.push(format!("aliases: {}", self.aliases.join(", ")));
+        }
+        if !self.notes.is_empty() {
+            lines.push(String::new());
+            lines.extend(self.notes.lines().map(str::to_string));
+        }
+        if !self.examples.is_empty() {
+            lines.push(String::new());
+            lines.push("examples:".to_string());
+            lines.extend(self.examples.iter().map(|example| format!("  {example}")));
+        }
+        lines
+    }
+}
+
+/// Every command accepted by the `:` command line, in the order they are listed by `help`
+pub const COMMANDS: &[CommandHelp] = &[
+    CommandHelp {
+        name: "break",
+        aliases: &["b", "breakpoint"],
+        summary: "create a breakpoint",
+        usage: "break SPEC",
+        examples: &[
+            "b in std::math::u64::*",
+            "b src/lib.rs:12",
+            "b at 100",
+            "b after 3 ops",
+            "b +5",
+            "b :42",
+        ],
+        notes: "SPEC is one of:
+  next            stop at the next line
+  finish          stop when the current procedure returns
+  after N         stop after N more cycles, also written `after N cycles`
+  after N ops     stop at the start of the Nth instruction from now, an
+                  instruction which takes several cycles counts once
+  at N            stop at cycle N
+  in PATTERN      stop when a procedure matching PATTERN is called
+  context         stop whenever execution moves to a different context
+  FILE[:LINE]     stop when FILE, or LINE of FILE, is reached
+  :LINE           stop when LINE of the current file is reached
+  +N, -N          stop when the line N lines after, or before, the current line is reached
+
+PATTERN and FILE are glob patterns, e.g. `*::main`. The current file and line are those of the
+current frame, and are resolved when the breakpoint is created.",
+    },
+    CommandHelp {
+        name: "read",
+        aliases: &["r"],
+        summary: "read memory",
+        usage: "read ADDR [TYPE | -t TYPE] [-c N] [-m MODE] [-f FORMAT]",
+        examples: &["r 8", "r 0x100 -t u64 -f hex", "r 16 -t [u8;4]", "r 16 -t u32 -c 4"],
+        notes: "TYPE is a primitive type, array, or struct, e.g. `u32`, `[u8;4]`, or
+`struct{a:u32,b:u8}`. MODE is `word` or `byte`, and FORMAT is `decimal`, `hex`, or `binary`.
+With `-c N`, N consecutive values of TYPE are read. Values must be aligned per their type, or
+for arrays, per their element type. ADDR may be given as `@NAME` to read from the address of a
+global or data segment, if the package has a `symbols` section.",
+    },
+    CommandHelp {
+        name: "examine",
+        aliases: &["x"],
+        summary: "read memory in the format of GDB",
+        usage: "x/NFU ADDR",
+        examples: &["x 0x100", "x/4xw 0x100", "x/8xb @NAME", "x/2dg 0x100"],
+        notes: "N is the number of units to read, F their format, and U their size, each of which
+may be omitted, to read a single word in hex. F is one of `x` (hex), `d` (signed decimal), `u`
+(unsigned decimal), or `t` (binary), and U is one of `b` (byte), `h` (halfword, 2 bytes), `w`
+(word, 4 bytes), or `g` (giant, 8 bytes). ADDR is a byte address, or `@NAME` for the address of a
+symbol, and each unit must be aligned per its size.",
+    },
+    CommandHelp {
+        name: "watch",
+        aliases: &[],
+        summary: "watch the value of a memory expression, or show the watched expressions",
+        usage: "watch [EXPR]",
+        examples: &["watch 8", "watch 0x100 -t u64 -f hex", "watch"],
+        notes:
+            "EXPR has the syntax of the arguments of `read`. Watched expressions are shown in the
+watch pane, and re-evaluated whenever the program advances, with those whose value changed
+marked. In the pane, `d` stops watching the selected expression. Watched expressions are kept when
+the program is reloaded.",
+    },
+    CommandHelp {
+        name: "unwatch",
+        aliases: &[],
+        summary: "stop watching an expression",
+        usage: "unwatch INDEX",
+        examples: &["unwatch 0"],
+        notes: "INDEX is that shown by `watch`.",
+    },
+    CommandHelp {
+        name: "mem",
+        aliases: &[],
+        summary: "show memory in the memory pane",
+        usage: "mem ADDR [TYPE]",
+        examples: &["mem 0x100", "mem 0x100 struct{a:u32,b:[u8;4]}"],
+        notes: "ADDR is an element address, which may be given in hexadecimal with a `0x` prefix,
+or as `@NAME` for the address of a symbol, which must be element-aligned, and is rounded down to a word boundary. The memory pane shows one word per row, in the current
+context, as of the current cycle, and highlights the words which were written when the program
+last ran. When it is focused, `j`/`k` scroll by a word, PageUp/PageDown by a page, and `i` shows the
+elements as felts, u32 values, or bytes. If TYPE is given, the value of that type at ADDR is
+read instead, as `read ADDR -t TYPE` does.",
+    },
+    CommandHelp {
+        name: "continue",
+        aliases: &["c"],
+        summary: "resume execution until the Nth breakpoint hit, by default the first, or the end",
+        usage: "continue [N]",
+        examples: &["continue", "c 10"],
+        notes: "With N, execution runs through the first N-1 hits of any breakpoint, and stops on the
+Nth. A breakpoint on a source location is hit once each time execution passes through it. If the
+program terminates first, the number of hits which occurred is reported.",
+    },
+    CommandHelp {
+        name: "step",
+        aliases: &["s"],
+        summary: "execute the next N assembly instructions, by default 1",
+        usage: "step [N]",
+        examples: &["step", "s 3"],
+        notes:
+            "Instructions which take multiple cycles are executed in full, see `stepi` to execute
+a single cycle.",
+    },
+    CommandHelp {
+        name: "stepi",
+        aliases: &["si"],
+        summary: "execute the next N cycles, by default 1",
+        usage: "stepi [N]",
+        examples: &["stepi", "si 3"],
+        notes: "This may stop in the middle of an instruction which takes multiple cycles.",
+    },
+    CommandHelp {
+        name: "next",
+        aliases: &["n"],
+        summary: "resume execution until the next line is reached",
+        usage: "next",
+        examples: &[],
+        notes: "",
+    },
+    CommandHelp {
+        name: "finish",
+        aliases: &["e"],
+        summary: "resume execution until the current procedure returns",
+        usage: "finish",
+        examples: &[],
+        notes: "",
+    },
+    CommandHelp {
+        name: "backtrace",
+        aliases: &["bt"],
+        summary: "show the call stack, and the arguments of each frame",
+        usage: "backtrace [N]",
+        examples: &["bt", "backtrace 8"],
+        notes: "Frames are listed innermost first. The arguments of a frame are the top N elements
+of the operand stack when it was entered, 4 by default, and are only known for procedures which
+emit frame trace events, e.g. those produced by the compiler.",
+    },
+    CommandHelp {
+        name: "calltree",
+        aliases: &[],
+        summary: "show the calls made so far as a tree, with the cycles spent in each",
+        usage: "calltree [DEPTH]",
+        examples: &["calltree", "calltree 3"],
+        notes: "Each call is shown with the cycles at which it was entered and returned, below the
+call which made it. Calls which have not returned yet are shown as in progress. Consecutive calls
+to the same procedure which made no calls of their own are shown together, and calls more than
+DEPTH deep are only counted.
+
+Like the arguments shown by `backtrace`, calls are only known for procedures which emit frame trace
+events, e.g. those produced by the compiler, and are otherwise attributed to their caller.",
+    },
+    CommandHelp {
+        name: "info",
+        aliases: &[],
+        summary: "show information about the program being debugged",
+        usage: "info package | info breakpoints",
+        examples: &["info package", "info breakpoints"],
+        notes: "`info package` shows the name, digest, dependencies, and exported procedures of the
+package. A dependency is resolved if a library with the same digest has been loaded, e.g. via
+`--link-library`, or from the toolchain sysroot.
+
+`info breakpoints` lists the breakpoints, with the number of times each was hit. Breakpoints on a
+file, line, or procedure also show the number of times execution passed through them, whether or
+not it stopped there, e.g. because the breakpoint is disabled, such as each iteration of a loop.",
+    },
+    CommandHelp {
+        name: "digest",
+        aliases: &[],
+        summary: "show the MAST root digest of a procedure",
+        usage: "digest MODULE::PROCEDURE",
+        examples: &["digest std::math::u64::add"],
+        notes: "The procedure is resolved among those of the package, including its exports, and
+those exported by the loaded libraries. If it cannot be found, procedures with similar names are
+suggested.",
+    },
+    CommandHelp {
+        name: "list",
+        aliases: &["l"],
+        summary: "show the source code around the current location",
+        usage: "list [insns|-|FILE:LINE|FROM,TO]",
+        examples: &["list", "list -", "list src/main.masm:12", "list 20,30", "list insns"],
+        notes: "Without arguments, the 10 lines around the current location are shown, and
+repeating `list` shows the following 10 lines, while `list -` shows the preceding 10 lines.
+`list FROM,TO` shows a range of lines of the file being listed, and `list insns` shows the most
+recently executed instructions instead.
+
+The current line is marked with `>`, and lines with a breakpoint are marked with `*`.",
+    },
+    CommandHelp {
+        name: "goto",
+        aliases: &[],
+        summary: "select a line of the file shown in the source code pane",
+        usage: "goto LINE",
+        examples: &["goto 120", "120"],
+        notes: "`:LINE` is shorthand for `goto LINE`. Lines beyond the end of the file select the
+last line.",
+    },
+    CommandHelp {
+        name: "file",
+        aliases: &[],
+        summary: "show another source file in the source code pane",
+        usage: "file NAME",
+        examples: &["file src/lib.masm", "file lib"],
+        notes: "NAME is a path relative to the working directory, or (part of) the name of a file
+known to the debugger. If several files match, they are listed. A file shown before is shown where
+it was left.
+
+While another file is shown, the source code pane is detached from execution, until the program
+moves on to another location, or `sync` is used.",
+    },
+    CommandHelp {
+        name: "sync",
+        aliases: &[],
+        summary: "show the current location in the source code pane",
+        usage: "sync",
+        examples: &[],
+        notes: "",
+    },
+    CommandHelp {
+        name: "outputs",
+        aliases: &[],
+        summary: "show the outputs of the program",
+        usage: "outputs [TYPE]",
+        examples: &["outputs", "outputs u64"],
+        notes: "If the program has not finished, the outputs it will produce are shown instead.",
+    },
+    CommandHelp {
+        name: "cycles",
+        aliases: &[],
+        summary: "show the current cycle, and progress through the program",
+        usage: "cycles",
+        examples: &[],
+        notes: "",
+    },
+    CommandHelp {
+        name: "capture",
+        aliases: &[],
+        summary: "capture the execution trace, if deferred by --no-capture-trace",
+        usage: "capture",
+        examples: &[],
+        notes: "The execution trace is captured by running the program to completion, and is used
+by `read`, `cycles`, and by `outputs` before the program has finished. When started with
+--no-capture-trace, these commands are unavailable until the trace has been captured.",
+    },
+    CommandHelp {
+        name: "stack",
+        aliases: &[],
+        summary: "show the operand stack",
+        usage: "stack [--all] [-x] [--delta]",
+        examples: &["stack", "stack --all -x", "stack --delta"],
+        notes: "Elements are shown from the top of the stack. Only the top 16 are shown, unless
+`--all` is given, in which case they are followed by the elements of the overflow table of the
+current context. The overflow elements of callers in other contexts are not visible until they
+return. Elements whose value changed when the program last ran are marked with `*`. `-x` renders
+field elements in hexadecimal. `--delta` marks the elements pushed by the most recent operation,
+and those it changed in place.",
+    },
+    CommandHelp {
+        name: "advice",
+        aliases: &[],
+        summary: "inspect the advice provider",
+        usage: "advice stack [N] | map [KEY] | merkle [-x]",
+        examples: &["advice stack 4", "advice map", "advice stack -x"],
+        notes: "`-x` renders field elements in hexadecimal.",
+    },
+    CommandHelp {
+        name: "memory-map",
+        aliases: &["mm"],
+        summary: "show the regions of memory written so far, grouped by context",
+        usage: "memory-map [-x]",
+        examples: &["memory-map", "mm -x"],
+        notes: "Each region shows the range of element addresses written, and its first few
+elements. Contexts in which no memory has been written are omitted. `-x` renders field
+elements in hexadecimal.",
+    },
+    CommandHelp {
+        name: "dump",
+        aliases: &[],
+        summary: "show a region of memory as a hex dump, or write it to a file",
+        usage: "dump START LEN [--width N] [--fill XX] | dump memory FILE START LEN [--format raw|hex]",
+        examples: &[
+            "dump 0x100 64",
+            "dump 0 32 --width 8 --fill 00",
+            "dump memory memory.bin 0x100 64",
+            "dump memory memory.txt 0 32 --format hex",
+        ],
+        notes: "START is a byte address, and LEN a number of bytes, either of which may be given in
+hexadecimal with a `0x` prefix. Memory is read in the current context, as of the current cycle,
+with each element holding four bytes in big-endian order.
+
+`dump START LEN` shows the region in the style of `hexdump -C`, with 16 bytes per row unless
+`--width` is given, up to 64. Zero bytes, including those of memory never written, are shown as
+`..`, or as the two characters given by `--fill`. At most 64 KiB may be shown at once.
+
+`dump memory` writes the raw bytes to FILE. `--format hex` writes a text dump in the style of `xxd`
+instead. A region extending beyond the end of memory is truncated, with a warning.",
+    },
+    CommandHelp {
+        name: "measure",
+        aliases: &[],
+        summary: "run the program, and show the lines of source code it spends the most cycles on",
+        usage: "measure [--ops] [N]",
+        examples: &["measure", "measure 20", "measure --ops"],
+        notes: "The program is run from the start until it terminates, independently of the
+program being debugged, and each cycle is attributed to the line of source code being executed.
+The N lines with the most cycles are shown, 10 by default, followed by the cycles of each file.
+Cycles which cannot be attributed to a line, e.g. those spent on control flow, are counted as
+<unknown>.
+
+With `--ops`, the cycles are attributed to instructions instead, e.g. `push`, each of which is shown
+with the number of times it was executed, and the cycles each execution took on average. Cycles
+spent outside of any instruction are attributed to the VM operation executed, e.g. `end`.",
+    },
+    CommandHelp {
+        name: "error",
+        aliases: &[],
+        summary: "show the error with which the program terminated",
+        usage: "error",
+        examples: &[],
+        notes: "The error is shown along with the source code at which it was raised, if known, and
+the innermost frames of the stack trace. It is shown automatically when the program fails, and
+can be shown again with `E`.",
+    },
+    CommandHelp {
+        name: "trace",
+        aliases: &[],
+        summary: "toggle the reporting of user-defined trace events",
+        usage: "trace [on|off]",
+        examples: &["trace on"],
+        notes: "",
+    },
+    CommandHelp {
+        name: "set",
+        aliases: &[],
+        summary: "change the inputs of the program, or how output is formatted",
+        usage: "set args [ARG..] | input-file PATH | color on|off | output-format json|text | theme \
+                [NAME] | tab-width N",
+        examples: &[
+            "set args 1 2",
+            "set input-file inputs.toml",
+            "set color off",
+            "set output-format json",
+            "set theme",
+            "set theme InspiredGitHub",
+            "set tab-width 8",
+        ],
+        notes: "Changes to the inputs take effect on the next reload. Arguments are given as for
+`run`. With `output-format json`,
+each stop is reported as a JSON object on a line prefixed with `@@`, with the fields `reason`
+(breakpoint, step, interrupted, terminated, or error), `cycle`, `procedure`, `file`, `line`,
+`breakpoints` (the ids of those hit), and for errors, `error`.
+
+`set theme` lists the themes, i.e. those bundled with the debugger, and the `.tmTheme` files in
+`$XDG_CONFIG_HOME/miden-debug/themes`. The selected theme is remembered for later sessions, see
+also `--theme`.
+
+`set tab-width` sets the number of columns between tab stops in the source code pane, from 1 to 16,
+see also `--tab-width`.",
+    },
+    CommandHelp {
+        name: "run",
+        aliases: &[],
+        summary: "reload the program with the given arguments",
+        usage: "run [ARG..]",
+        examples: &["run 1 2", "run 5u64 -3i32"],
+        notes: "Each argument is a field element, unless it has a type suffix, i.e. one of u8, i8,
+u16, i16, u32, i32, u64, i64, u128, i128, or felt. Typed arguments are encoded as compiled code
+expects them, so values wider than 32 bits push one element per 32-bit limb, the least
+significant nearest the top of the stack. Hexadecimal values give the bit pattern, e.g. 0xffi8.",
+    },
+    CommandHelp {
+        name: "reload",
+        aliases: &[],
+        summary: "reload the program, and restart execution",
+        usage: "reload",
+        examples: &[],
+        notes: "Breakpoints are preserved.",
+    },
+    CommandHelp {
+        name: "reset",
+        aliases: &[],
+        summary: "restart execution, without reloading the program",
+        usage: "reset",
+        examples: &[],
+        notes: "Unlike `reload`, the program is not read from disk again, but the current arguments
+are used, e.g. those given via `set args`. Breakpoints are preserved.",
+    },
+    CommandHelp {
+        name: "!",
+        aliases: &[],
+        summary: "run a shell command in the working directory",
+        usage: "! COMMAND",
+        examples: &["! cargo miden build", "! ls *.masp"],
+        notes: "The output of COMMAND is shown, along with whether it succeeded.",
+    },
+    CommandHelp {
+        name: "make",
+        aliases: &[],
+        summary: "run the build command given by `--build-cmd`",
+        usage: "make",
+        examples: &[],
+        notes: "",
+    },
+    CommandHelp {
+        name: "rebuild",
+        aliases: &[],
+        summary: "run the build command given by `--build-cmd`, then reload the program",
+        usage: "rebuild",
+        examples: &[],
+        notes: "The program is only reloaded if the build command succeeds.",
+    },
+    CommandHelp {
+        name: "source",
+        aliases: &[],
+        summary: "execute the commands in a script",
+        usage: "source FILE",
+        examples: &["source session.txt"],
+        notes: "Each line of FILE is a command. Blank lines, and lines starting with `#`, are
+ignored. The script stops at the first command which fails, unless that command is
+prefixed with `-`.",
+    },
+    CommandHelp {
+        name: "history",
+        aliases: &[],
+        summary: "show the most recent commands",
+        usage: "history [N]",
+        examples: &["history", "history 5"],
+        notes: "Shows the last N entries of the command history, or the last 20 if N is not given.
+`!N` executes entry N of the history again.",
+    },
+    CommandHelp {
+        name: "alias",
+        aliases: &[],
+        summary: "define an alias for a command, or list the aliases defined",
+        usage: "alias [NAME [EXPANSION]]",
+        examples: &["alias", "alias m32 read $1 -t u32 -f hex", "alias m32"],
+        notes: "`$1` through `$9` in EXPANSION are replaced by the arguments given to the alias. If
+EXPANSION refers to no arguments, they are appended to it instead. Aliases are saved alongside
+the command history, and may not have the name of a command.",
+    },
+    CommandHelp {
+        name: "unalias",
+        aliases: &[],
+        summary: "remove an alias",
+        usage: "unalias NAME",
+        examples: &["unalias m32"],
+        notes: "",
+    },
+    CommandHelp {
+        name: "log",
+        aliases: &[],
+        summary: "write a transcript of the session to a file",
+        usage: "log [FILE|off]",
+        examples: &["log session.log", "log off"],
+        notes: "Every command entered, and its output, is appended to FILE along with the time,
+and the current cycle. Without arguments, shows where the transcript is being written.",
+    },
+    CommandHelp {
+        name: "log-save",
+        aliases: &[],
+        summary: "write the records of the debug log to a file",
+        usage: "log-save FILE",
+        examples: &["log-save debug.log"],
+        notes: "The records currently retained by the debug log, see `debug`, are written to FILE,
+replacing its contents.",
+    },
+    CommandHelp {
+        name: "debug",
+        aliases: &[],
+        summary: "show the debug log",
+        usage: "debug",
+        examples: &[],
+        notes: "The debug log retains the most recent records logged by the debugger, and follows
+the newest of them until scrolled with j/k. Press `f` to cycle the least severe level shown, `G`
+to follow the log again, and `o` or Esc to close it. The log can also be opened with `o`.",
+    },
+    CommandHelp {
+        name: "help",
+        aliases: &[],
+        summary: "show the available commands, or the details of one of them",
+        usage: "help [COMMAND]",
+        examples: &["help", "help break"],
+        notes: "",
+    },
+    CommandHelp {
+        name: "quit",
+        aliases: &["q"],
+        summary: "quit the debugger",
+        usage: "quit",
+        examples: &[],
+        notes: "",
+    },
+];
+
+/// Returns the names, including aliases, of all commands
+pub fn command_names() -> impl Iterator<Item = &'static str> {
+    COMMANDS.iter().flat_map(CommandHelp::names)
+}
+
+/// Find the command named `name`, which may be an alias
+pub fn lookup(name: &str) -> Option<&'static CommandHelp> {
+    COMMANDS.iter().find(|command| command.names().any(|n| n == name))
+}
+
+/// Returns the name of the command most similar to `name`, if any is similar enough to suggest
+pub fn suggest(name: &str) -> Option<&'static str> {
+    command_names()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= 2 && *distance < candidate.len())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Render a table of all commands, and their summaries
+pub fn render_table() -> Vec<String> {
+    let names = COMMANDS.iter().map(|command| command.names().collect::<Vec<_>>().join(", "));
+    let names = names.collect::<Vec<_>>();
+    let width = names.iter().map(String::len).max().unwrap_or_default();
+    let mut lines = COMMANDS
+        .iter()
+        .zip(names)
+        .map(|(command, names)| format!("{names:width$}  {}", command.summary))
+        .collect::<Vec<_>>();
+    lines.push(String::new());
+    lines.push("use `help COMMAND` for more details about a command".to_string());
+    lines
+}
+
+/// The Levenshtein distance between `a` and `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_by_alias() {
+        assert_eq!(lookup("b").map(|command| command.name), Some("break"));
+        assert_eq!(lookup("breakpoint").map(|command| command.name), Some("break"));
+        assert!(lookup("bogus").is_none());
+    }
+
+    #[test]
+    fn suggest_similar_commands() {
+        assert_eq!(suggest("brek"), Some("break"));
+        assert_eq!(suggest("contniue"), Some("continue"));
+        assert_eq!(suggest("bogus"), None);
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut names = command_names().collect::<Vec<_>>();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+/// The command history of the `:` command line.
+///
+/// History is loaded from, and persisted to, a file on disk so that it is available across
+/// sessions. Failing to read or write the history file is never fatal, instead a warning is
+/// emitted and the history is kept in memory only.
+#[derive(Debug, Default)]
+pub struct History {
+    path: Option<PathBuf>,
+    max_entries: usize,
+    /// Entries in the order they were executed, i.e. the most recent entry is last
+    entries: Vec<String>,
+}
+
+impl History {
+    /// Load the history stored at `path`, retaining at most `max_entries` of the most recent
+    pub fn load(path: Option<PathBuf>, max_entries: usize) -> Self {
+        let mut entries = vec![];
+        if let Some(path) = path.as_deref() {
+            match std::fs::read_to_string(path) {
+                Ok(content) => {
+                    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                        if entries.last().is_none_or(|last: &String| last != line) {
+                            entries.push(line.to_string());
+                        }
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => (),
+                Err(err) => {
+                    log::warn!("unable to read command history from {}: {err}", path.display());
+                }
+            }
+        }
+
+        let mut history = Self {
+            path,
+            max_entries,
+            entries,
+        };
+        history.truncate();
+        history
+    }
+
+    /// Record `entry` as the most recently executed command, and persist the updated history.
+    ///
+    /// Consecutive duplicate entries are only recorded once.
+    pub fn push(&mut self, entry: impl Into<String>) {
+        let entry = entry.into();
+        if entry.trim().is_empty() || self.entries.last().is_some_and(|last| last == &entry) {
+            return;
+        }
+        self.entries.push(entry);
+        self.truncate();
+        self.save();
+    }
+
+    /// Get the entry with the given index, as displayed by [History::iter]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index.checked_sub(1)?).map(String::as_str)
+    }
+
+    /// Get the `n`th most recent entry, where `0` is the most recently executed command
+    pub fn get_recent(&self, n: usize) -> Option<&str> {
+        self.entries.iter().rev().nth(n).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over the history from oldest to newest, along with the index of each entry
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (usize, &str)> + '_ {
+        self.entries.iter().enumerate().map(|(i, entry)| (i + 1, entry.as_str()))
+    }
+
+    fn truncate(&mut self) {
+        if self.entries.len() > self.max_entries {
+            let excess = self.entries.len() - self.max_entries;
+            self.entries.drain(..excess);
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = self.path.as_deref() else {
+            return;
+        };
+        if let Some(parent) = path.parent()
+            && let Err(err) = std::fs::create_dir_all(parent)
+        {
+            log::warn!("unable to create command history directory {}: {err}", parent.display());
+            return;
+        }
+        let mut content = self.entries.join("\n");
+        content.push('\n');
+        if let Err(err) = std::fs::write(path, content) {
+            log::warn!("unable to write command history to {}: {err}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DebuggerConfig;
+
+    /// A history file in a directory which does not exist yet, within a temporary directory that
+    /// is removed when dropped
+    fn history_file() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("history");
+        (dir, path)
+    }
+
+    #[test]
+    fn history_roundtrip() {
+        let (_dir, path) = history_file();
+        let config = DebuggerConfig {
+            history_file: Some(path.clone()),
+            ..Default::default()
+        };
+        assert_eq!(config.history_path().as_deref(), Some(path.as_path()));
+
+        let mut history = History::load(config.history_path(), 10);
+        assert!(history.is_empty());
+        history.push("b in foo");
+        history.push("b in foo");
+        history.push("r 0x100");
+        assert_eq!(history.len(), 2);
+
+        let history = History::load(Some(path.clone()), 10);
+        assert_eq!(history.iter().collect::<Vec<_>>(), vec![(1, "b in foo"), (2, "r 0x100")]);
+        assert_eq!(history.get(2), Some("r 0x100"));
+        assert_eq!(history.get(0), None);
+        assert_eq!(history.get_recent(0), Some("r 0x100"));
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let (_dir, path) = history_file();
+
+        let mut history = History::load(Some(path.clone()), 3);
+        for i in 0..5 {
+            history.push(format!("r {i}"));
+        }
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.get(1), Some("r 2"));
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().collect::<Vec<_>>(), vec!["r 2", "r 3", "r 4"]);
+    }
+
+    #[test]
+    fn history_io_failures_are_not_fatal() {
+        // A directory cannot be read or written as a file
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(Some(dir.path().to_path_buf()), 10);
+        history.push("q");
+        assert_eq!(history.len(), 1);
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use signal_hook::{SigId, consts::SIGINT};
+
+/// A flag used to ask a long-running operation, e.g. `continue`, to stop at the next opportunity.
+///
+/// The flag may be raised from anywhere, including a signal handler, and is checked periodically
+/// by the operation being interrupted.
+#[derive(Debug, Default, Clone)]
+pub struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+    /// Request an interruption
+    pub fn raise(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true if an interruption was requested since the last call, clearing the request
+    pub fn take(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+
+    /// Raise this flag whenever SIGINT is received, e.g. when Ctrl-C is pressed outside of the
+    /// TUI, until the returned guard is dropped.
+    ///
+    /// While the guard is alive, SIGINT no longer terminates the process. Once it is dropped,
+    /// SIGINT terminates the process again.
+    pub fn on_sigint(&self) -> std::io::Result<SigintGuard> {
+        let id = signal_hook::flag::register(SIGINT, Arc::clone(&self.0))?;
+        if let Err(err) = DefaultSigint::acquire() {
+            signal_hook::low_level::unregister(id);
+            return Err(err);
+        }
+        Ok(SigintGuard(id))
+    }
+}
+
+/// Emulates the default action of SIGINT, i.e. terminating the process, while no [SigintGuard]
+/// is alive.
+///
+/// Hooking SIGINT replaces its default action for the rest of the process, even once the hook is
+/// unregistered, so instead, a hook which takes the default action while its flag is set is
+/// registered once, and the flag is cleared while any guard is alive.
+struct DefaultSigint {
+    terminate: Arc<AtomicBool>,
+    guards: usize,
+}
+
+static DEFAULT_SIGINT: Mutex<Option<DefaultSigint>> = Mutex::new(None);
+
+impl DefaultSigint {
+    /// Stop SIGINT from terminating the process, until a matching [DefaultSigint::release]
+    fn acquire() -> std::io::Result<()> {
+        let mut default = DEFAULT_SIGINT.lock().unwrap_or_else(|err| err.into_inner());
+        if default.is_none() {
+            let terminate = Arc::new(AtomicBool::new(true));
+            signal_hook::flag::register_conditional_default(SIGINT, Arc::clone(&terminate))?;
+            *default = Some(Self {
+                terminate,
+                guards: 0,
+            });
+        }
+        let default = default.as_mut().unwrap();
+        default.guards += 1;
+        default.terminate.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Let SIGINT terminate the process again, once every guard has been released
+    fn release() {
+        let mut default = DEFAULT_SIGINT.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(default) = default.as_mut() {
+            default.guards -= 1;
+            default.terminate.store(default.guards == 0, Ordering::SeqCst);
+        }
+    }
+
+    /// Whether SIGINT currently terminates the process
+    #[cfg(test)]
+    fn terminates() -> bool {
+        let default = DEFAULT_SIGINT.lock().unwrap_or_else(|err| err.into_inner());
+        default.as_ref().is_none_or(|default| default.terminate.load(Ordering::SeqCst))
+    }
+}
+
+/// Lets SIGINT terminate the process again when dropped, see [Interrupt::on_sigint]
+pub struct SigintGuard(SigId);
+
+impl Drop for SigintGuard {
+    fn drop(&mut self) {
+        signal_hook::low_level::unregister(self.0);
+        DefaultSigint::release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sigint_is_handled_while_guarded() {
+        let interrupt = Interrupt::default();
+        let guard = interrupt.on_sigint().unwrap();
+        assert!(!DefaultSigint::terminates());
+
+        // While the guard is alive, SIGINT only raises the flag
+        signal_hook::low_level::raise(SIGINT).unwrap();
+        assert!(interrupt.take());
+
+        // Other tests may hold guards concurrently, so SIGINT is not raised once it is dropped
+        drop(guard);
+    }
+}
@@ -0,0 +1,881 @@
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The context in which a key binding applies
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyMode {
+    /// Bindings which apply regardless of the focused pane, unless it handles the key itself
+    Global,
+    /// Bindings which apply while the source code pane is focused, taking precedence over those
+    /// of [KeyMode::Global]
+    Source,
+    /// Bindings which apply while the timeline pane is focused, taking precedence over those of
+    /// [KeyMode::Global]
+    Timeline,
+    /// Bindings which apply while a popup is shown, e.g. the output of a command, taking
+    /// precedence over those of [KeyMode::Global], which otherwise apply to the popup alone
+    Popup,
+    /// Bindings which apply while a command is being entered in the footer
+    Footer,
+}
+
+impl KeyMode {
+    pub const ALL: [Self; 5] =
+        [Self::Global, Self::Source, Self::Timeline, Self::Popup, Self::Footer];
+
+    /// The name of the table of the keybindings file which configures this mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Source => "source",
+            Self::Timeline => "timeline",
+            Self::Popup => "popup",
+            Self::Footer => "footer",
+        }
+    }
+}
+
+impl fmt::Display for KeyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The categories under which actions are listed by the key bindings help
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyCategory {
+    Execution,
+    Navigation,
+    Panes,
+    Misc,
+}
+
+impl KeyCategory {
+    pub const ALL: [Self; 4] = [Self::Execution, Self::Navigation, Self::Panes, Self::Misc];
+}
+
+impl fmt::Display for KeyCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Execution => "Execution",
+            Self::Navigation => "Navigation",
+            Self::Panes => "Panes",
+            Self::Misc => "Misc",
+        })
+    }
+}
+
+/// An action which may be bound to a key, each of which is available in a single [KeyMode]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyAction {
+    // Global
+    Interrupt,
+    FocusNext,
+    FocusPrev,
+    /// Focus the pane with the given index, starting from 0, see [crate::ui::layout::PaneId::ALL]
+    FocusPane(u32),
+    FocusLast,
+    Down,
+    Up,
+    PageDown,
+    PageUp,
+    Go,
+    Back,
+    FullScreen,
+    HidePane,
+    ShowPanes,
+    GrowPane,
+    ShrinkPane,
+    /// Select the tab with the given index, starting from 0
+    Tab(u32),
+    TabNext,
+    TabPrev,
+    CommandLine,
+    Quit,
+    ToggleFormat,
+    CycleMemoryView,
+    ShowLog,
+    ShowError,
+    ShowSymbols,
+    Help,
+    Finish,
+    Step,
+    Next,
+    Continue,
+    Delete,
+    ToggleEnabled,
+    Submit,
+    Yank,
+    Paste,
+    ToggleStackPairs,
+    // Source
+    ToggleBreakpoint,
+    Search,
+    NextMatch,
+    PrevMatch,
+    ClearSearch,
+    ToggleFollow,
+    // Timeline
+    CursorBack,
+    CursorForward,
+    LargerSteps,
+    SmallerSteps,
+    // Popup
+    ClosePopup,
+    Filter,
+    BreakIn,
+    // Footer
+    Complete,
+    CompletePrev,
+    AcceptHint,
+    Execute,
+    Cancel,
+    HistoryPrev,
+    HistoryNext,
+}
+
+/// The actions which may be bound, other than [KeyAction::FocusPane] and [KeyAction::Tab], along
+/// with their names and a summary of what they do
+const ACTIONS: &[(KeyAction, &str, &str)] = &[
+    (KeyAction::Interrupt, "interrupt", "interrupt the program while it is running"),
+    (KeyAction::FocusNext, "focus-next", "focus the next pane"),
+    (KeyAction::FocusPrev, "focus-prev", "focus the previous pane"),
+    (KeyAction::FocusLast, "focus-last", "focus the pane focused before this one"),
+    (KeyAction::Down, "down", "move down in the focused pane"),
+    (KeyAction::Up, "up", "move up in the focused pane"),
+    (KeyAction::PageDown, "page-down", "move down a page in the focused pane"),
+    (KeyAction::PageUp, "page-up", "move up a page in the focused pane"),
+    (KeyAction::Go, "go", "go to the selected item, e.g. a frame or memory address"),
+    (KeyAction::Back, "back", "go back to the previous location"),
+    (
+        KeyAction::FullScreen,
+        "fullscreen",
+        "toggle showing the focused pane full screen",
+    ),
+    (KeyAction::HidePane, "hide-pane", "hide the focused pane"),
+    (KeyAction::ShowPanes, "show-panes", "show all of the panes which were hidden"),
+    (KeyAction::GrowPane, "grow-pane", "grow the focused pane"),
+    (KeyAction::ShrinkPane, "shrink-pane", "shrink the focused pane"),
+    (KeyAction::TabNext, "tab-next", "select the next tab"),
+    (KeyAction::TabPrev, "tab-prev", "select the previous tab"),
+    (KeyAction::CommandLine, "command", "enter a command"),
+    (KeyAction::Quit, "quit", "quit the debugger"),
+    (
+        KeyAction::ToggleFormat,
+        "toggle-format",
+        "toggle between decimal and hexadecimal",
+    ),
+    (KeyAction::CycleMemoryView, "cycle-memory-view", "change how memory is shown"),
+    (KeyAction::ShowLog, "show-log", "show the debug log"),
+    (
+        KeyAction::ShowError,
+        "show-error",
+        "show the error with which the program failed",
+    ),
+    (
+        KeyAction::ShowSymbols,
+        "show-symbols",
+        "list the procedures, to show their source or break in them",
+    ),
+    (KeyAction::Help, "help", "show the key bindings"),
+    (KeyAction::Finish, "finish", "run until the current procedure returns"),
+    (KeyAction::Step, "step", "step to the next cycle"),
+    (KeyAction::Next, "next", "step to the next instruction"),
+    (KeyAction::Continue, "continue", "run until a breakpoint is hit"),
+    (KeyAction::Delete, "delete", "delete the selected item, e.g. a breakpoint"),
+    (
+        KeyAction::ToggleEnabled,
+        "toggle-enabled",
+        "enable or disable the selected breakpoint",
+    ),
+    (KeyAction::Submit, "submit", "act on the selected item"),
+    (KeyAction::Yank, "yank", "copy the selected item to the clipboard"),
+    (KeyAction::Paste, "paste", "enter a command, starting with the text last copied"),
+    (
+        KeyAction::ToggleStackPairs,
+        "toggle-u64",
+        "toggle showing pairs of stack elements as u64 values",
+    ),
+    (
+        KeyAction::ToggleBreakpoint,
+        "toggle-breakpoint",
+        "toggle a breakpoint on the line",
+    ),
+    (KeyAction::Search, "search", "search the source code"),
+    (KeyAction::NextMatch, "next-match", "select the next match of the search"),
+    (KeyAction::PrevMatch, "prev-match", "select the previous match of the search"),
+    (KeyAction::ClearSearch, "clear-search", "stop searching"),
+    (
+        KeyAction::ToggleFollow,
+        "toggle-follow",
+        "toggle following the program as it executes",
+    ),
+    (KeyAction::CursorBack, "cursor-back", "move the cursor back"),
+    (KeyAction::CursorForward, "cursor-forward", "move the cursor forward"),
+    (KeyAction::LargerSteps, "larger-steps", "move the cursor ten times as far"),
+    (KeyAction::SmallerSteps, "smaller-steps", "move the cursor a tenth as far"),
+    (KeyAction::ClosePopup, "close", "close the popup"),
+    (KeyAction::Filter, "filter", "filter the items listed"),
+    (KeyAction::BreakIn, "break-in", "break in the selected procedure"),
+    (
+        KeyAction::Complete,
+        "complete",
+        "complete the command, or cycle through completions",
+    ),
+    (KeyAction::CompletePrev, "complete-prev", "cycle backwards through completions"),
+    (KeyAction::AcceptHint, "accept-hint", "accept the hinted completion"),
+    (KeyAction::Execute, "execute", "execute the command"),
+    (KeyAction::Cancel, "cancel", "discard the command"),
+    (
+        KeyAction::HistoryPrev,
+        "history-prev",
+        "recall the previous command of the history",
+    ),
+    (KeyAction::HistoryNext, "history-next", "recall the next command of the history"),
+];
+
+impl KeyAction {
+    /// The mode in which this action is available
+    pub fn mode(&self) -> KeyMode {
+        match self {
+            Self::ToggleBreakpoint
+            | Self::Search
+            | Self::NextMatch
+            | Self::PrevMatch
+            | Self::ClearSearch
+            | Self::ToggleFollow => KeyMode::Source,
+            Self::CursorBack | Self::CursorForward | Self::LargerSteps | Self::SmallerSteps => {
+                KeyMode::Timeline
+            }
+            Self::ClosePopup | Self::Filter | Self::BreakIn => KeyMode::Popup,
+            Self::Complete
+            | Self::CompletePrev
+            | Self::AcceptHint
+            | Self::Execute
+            | Self::Cancel
+            | Self::HistoryPrev
+            | Self::HistoryNext => KeyMode::Footer,
+            _ => KeyMode::Global,
+        }
+    }
+
+    /// The category under which this action is listed by the key bindings help
+    pub fn category(&self) -> KeyCategory {
+        match self {
+            Self::Interrupt | Self::Finish | Self::Step | Self::Next | Self::Continue => {
+                KeyCategory::Execution
+            }
+            Self::FocusNext
+            | Self::FocusPrev
+            | Self::FocusPane(_)
+            | Self::FocusLast
+            | Self::Down
+            | Self::Up
+            | Self::PageDown
+            | Self::PageUp
+            | Self::Go
+            | Self::Back
+            | Self::Tab(_)
+            | Self::TabNext
+            | Self::TabPrev => KeyCategory::Navigation,
+            Self::FullScreen
+            | Self::HidePane
+            | Self::ShowPanes
+            | Self::GrowPane
+            | Self::ShrinkPane
+            | Self::ToggleFormat
+            | Self::CycleMemoryView
+            | Self::Delete
+            | Self::ToggleEnabled
+            | Self::Submit
+            | Self::Yank
+            | Self::ToggleBreakpoint
+            | Self::Search
+            | Self::NextMatch
+            | Self::PrevMatch
+            | Self::ClearSearch
+            | Self::ToggleFollow
+            | Self::ToggleStackPairs
+            | Self::CursorBack
+            | Self::CursorForward
+            | Self::LargerSteps
+            | Self::SmallerSteps
+            | Self::Filter
+            | Self::BreakIn => KeyCategory::Panes,
+            _ => KeyCategory::Misc,
+        }
+    }
+
+    /// A summary of what this action does
+    pub fn summary(&self) -> String {
+        match self {
+            Self::FocusPane(index) => format!("focus pane {}", index + 1),
+            Self::Tab(index) => format!("select tab {}", index + 1),
+            action => ACTIONS
+                .iter()
+                .find(|(candidate, ..)| candidate == action)
+                .map(|(_, _, summary)| summary.to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for KeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FocusPane(index) => write!(f, "focus-{}", index + 1),
+            Self::Tab(index) => write!(f, "tab-{}", index + 1),
+            action => {
+                let (_, name, _) = ACTIONS
+                    .iter()
+                    .find(|(candidate, ..)| candidate == action)
+                    .expect("every action has a name");
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+impl core::str::FromStr for KeyAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(index) = s.strip_prefix("focus-").and_then(|n| n.parse::<u32>().ok()) {
+            return match index {
+                1..=9 => Ok(Self::FocusPane(index - 1)),
+                _ => Err(format!("invalid pane '{index}', expected 1-9")),
+            };
+        }
+        if let Some(index) = s.strip_prefix("tab-").and_then(|n| n.parse::<u32>().ok()) {
+            return match index {
+                1..=9 => Ok(Self::Tab(index - 1)),
+                _ => Err(format!("invalid tab '{index}', expected 1-9")),
+            };
+        }
+        ACTIONS
+            .iter()
+            .find(|(_, name, _)| *name == s)
+            .map(|(action, ..)| *action)
+            .ok_or_else(|| format!("unknown action '{s}'"))
+    }
+}
+
+/// A key, along with the modifiers which must be held when it is pressed.
+///
+/// Chords are written as the name of the key, prefixed by any of `ctrl-`, `alt-`, or `shift-`,
+/// e.g. `ctrl-c`. The name of a key is either a single character, which is case-sensitive, or one
+/// of `enter`, `esc`, `tab`, `backtab`, `backspace`, `delete`, `insert`, `home`, `end`, `pageup`,
+/// `pagedown`, `up`, `down`, `left`, `right`, `space`, or `f1` through `f12`.
+///
+/// As the case of a character conveys whether shift was held, `shift-j` is the same chord as `J`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }.normalized()
+    }
+
+    /// Fold the shift modifier into keys which convey it by themselves, so that equivalent chords
+    /// compare equal
+    fn normalized(mut self) -> Self {
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            match self.code {
+                KeyCode::Char(c) => {
+                    self.code = KeyCode::Char(c.to_ascii_uppercase());
+                    self.modifiers.remove(KeyModifiers::SHIFT);
+                }
+                KeyCode::Tab | KeyCode::BackTab => {
+                    self.code = KeyCode::BackTab;
+                    self.modifiers.remove(KeyModifiers::SHIFT);
+                }
+                _ => (),
+            }
+        }
+        self
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+/// The names of keys other than characters, see [KeyChord]
+const KEY_NAMES: [(KeyCode, &str); 16] = [
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Esc, "esc"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "backtab"),
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "pageup"),
+    (KeyCode::PageDown, "pagedown"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Char(' '), "space"),
+];
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, prefix) in [
+            (KeyModifiers::CONTROL, "ctrl-"),
+            (KeyModifiers::ALT, "alt-"),
+            (KeyModifiers::SHIFT, "shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(prefix)?;
+            }
+        }
+        match KEY_NAMES.iter().find(|(code, _)| *code == self.code) {
+            Some((_, name)) => f.write_str(name),
+            None => match self.code {
+                KeyCode::Char(c) => write!(f, "{c}"),
+                KeyCode::F(n) => write!(f, "f{n}"),
+                code => write!(f, "{code:?}"),
+            },
+        }
+    }
+}
+
+impl core::str::FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = s;
+        // A key may itself be `-`, so a prefix is only a modifier if something follows it
+        loop {
+            let Some((prefix, rest)) = key.split_once('-').filter(|(_, rest)| !rest.is_empty())
+            else {
+                break;
+            };
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            key = rest;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => {
+                let name = key.to_ascii_lowercase();
+                match KEY_NAMES.iter().find(|(_, candidate)| *candidate == name) {
+                    Some((code, _)) => *code,
+                    None => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n @ 1..=12) => KeyCode::F(n),
+                        _ => return Err(format!("unknown key '{key}'")),
+                    },
+                }
+            }
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+/// The table which maps key chords to actions, in each [KeyMode].
+///
+/// The default table is extended by the keybindings file, i.e. `keybindings.toml` in the
+/// configuration directory, or that given via `--keybindings`, which has a table for each mode,
+/// mapping chords to the names of actions. An action of `"none"` removes the default binding of
+/// a chord, e.g.:
+///
+/// ```toml
+/// [global]
+/// ctrl-n = "next"
+/// n = "none"
+///
+/// [footer]
+/// ctrl-p = "history-prev"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    /// The bindings of each mode, in the order they are shown to the user
+    bindings: BTreeMap<KeyMode, Vec<(KeyChord, KeyAction)>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use KeyAction::*;
+
+        let mut global = vec![
+            ("ctrl-c", Interrupt),
+            ("right", FocusNext),
+            ("l", FocusNext),
+            ("L", FocusNext),
+            ("left", FocusPrev),
+            ("h", FocusPrev),
+            ("H", FocusPrev),
+            ("tab", FocusLast),
+            ("down", Down),
+            ("j", Down),
+            ("J", Down),
+            ("up", Up),
+            ("k", Up),
+            ("K", Up),
+            ("pagedown", PageDown),
+            ("pageup", PageUp),
+            ("g", Go),
+            ("G", Go),
+            ("backspace", Back),
+            ("b", Back),
+            ("B", Back),
+            ("f", FullScreen),
+            ("F", FullScreen),
+            ("v", HidePane),
+            ("V", ShowPanes),
+            (">", GrowPane),
+            ("<", ShrinkPane),
+            ("]", TabNext),
+            ("[", TabPrev),
+            (":", CommandLine),
+            ("q", Quit),
+            ("x", ToggleFormat),
+            ("i", CycleMemoryView),
+            ("o", ShowLog),
+            ("E", ShowError),
+            ("p", ShowSymbols),
+            ("?", Help),
+            ("e", Finish),
+            ("s", Step),
+            ("n", Next),
+            ("c", Continue),
+            ("d", Delete),
+            ("space", ToggleEnabled),
+            ("enter", Submit),
+            ("y", Yank),
+            ("P", Paste),
+            ("u", ToggleStackPairs),
+        ];
+        // The panes are numbered in their titles, and focused by their number, while tabs are
+        // selected by their number with Alt held
+        const PANES: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        global.extend(PANES.iter().zip(0..).map(|(key, index)| (*key, FocusPane(index))));
+        const TABS: [&str; 9] =
+            ["alt-1", "alt-2", "alt-3", "alt-4", "alt-5", "alt-6", "alt-7", "alt-8", "alt-9"];
+        global.extend(TABS.iter().zip(0..).map(|(key, index)| (*key, Tab(index))));
+        let source = vec![
+            ("b", ToggleBreakpoint),
+            ("/", Search),
+            ("n", NextMatch),
+            ("N", PrevMatch),
+            ("esc", ClearSearch),
+            ("f", ToggleFollow),
+        ];
+        let timeline =
+            vec![("h", CursorBack), ("l", CursorForward), ("+", LargerSteps), ("-", SmallerSteps)];
+        let popup = vec![("esc", ClosePopup), ("q", ClosePopup), ("/", Filter), ("b", BreakIn)];
+        let footer = vec![
+            ("tab", Complete),
+            ("backtab", CompletePrev),
+            ("right", AcceptHint),
+            ("enter", Execute),
+            ("esc", Cancel),
+            ("up", HistoryPrev),
+            ("down", HistoryNext),
+        ];
+
+        let parse = |bindings: Vec<(&str, KeyAction)>| {
+            bindings
+                .into_iter()
+                .map(|(chord, action)| (chord.parse().expect("invalid default binding"), action))
+                .collect()
+        };
+        Self {
+            bindings: BTreeMap::from([
+                (KeyMode::Global, parse(global)),
+                (KeyMode::Source, parse(source)),
+                (KeyMode::Timeline, parse(timeline)),
+                (KeyMode::Popup, parse(popup)),
+                (KeyMode::Footer, parse(footer)),
+            ]),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Load the default bindings, extended by those of the keybindings file at `path`
+    pub fn parse_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Self::parse_str(&content)
+    }
+
+    /// Parse the contents of a keybindings file, see [KeyBindings]
+    pub fn parse_str(content: &str) -> Result<Self, String> {
+        let tables = toml::from_str::<BTreeMap<String, BTreeMap<String, String>>>(content)
+            .map_err(|err| err.to_string())?;
+        let mut bindings = Self::default();
+        for (table, entries) in tables {
+            let mode =
+                KeyMode::ALL.into_iter().find(|mode| mode.name() == table).ok_or_else(|| {
+                    let tables = KeyMode::ALL.map(|mode| format!("[{mode}]"));
+                    format!("unknown table [{table}], expected one of {}", tables.join(", "))
+                })?;
+            let mut configured = BTreeMap::<String, KeyChord>::new();
+            for (key, action) in entries {
+                let invalid = |reason: String| format!("[{mode}] {key} = \"{action}\": {reason}");
+                let chord = key.parse::<KeyChord>().map_err(invalid)?;
+                if let Some((other, _)) = configured.iter().find(|(_, other)| **other == chord) {
+                    return Err(invalid(format!("'{key}' is the same key as '{other}'")));
+                }
+                configured.insert(key.clone(), chord);
+                if action == "none" {
+                    bindings.unbind(mode, chord);
+                    continue;
+                }
+                let action = action.parse::<KeyAction>().map_err(invalid)?;
+                if action.mode() != mode {
+                    return Err(invalid(format!(
+                        "'{action}' is only available in [{}]",
+                        action.mode()
+                    )));
+                }
+                if mode == KeyMode::Footer
+                    && chord.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+                    && matches!(chord.code, KeyCode::Char(_))
+                {
+                    return Err(invalid(format!(
+                        "'{chord}' is typed into the command line, so it cannot be bound there"
+                    )));
+                }
+                bindings.bind(mode, chord, action);
+            }
+        }
+        Ok(bindings)
+    }
+
+    /// Bind `chord` to `action` in `mode`, replacing any existing binding of `chord`
+    pub fn bind(&mut self, mode: KeyMode, chord: KeyChord, action: KeyAction) {
+        let bindings = self.bindings.entry(mode).or_default();
+        match bindings.iter_mut().find(|(bound, _)| *bound == chord) {
+            Some(binding) => binding.1 = action,
+            None => bindings.push((chord, action)),
+        }
+    }
+
+    /// Remove the binding of `chord` in `mode`, if any
+    pub fn unbind(&mut self, mode: KeyMode, chord: KeyChord) {
+        if let Some(bindings) = self.bindings.get_mut(&mode) {
+            bindings.retain(|(bound, _)| *bound != chord);
+        }
+    }
+
+    /// The action bound to `key` in `mode`, if any
+    pub fn lookup(&self, mode: KeyMode, key: KeyEvent) -> Option<KeyAction> {
+        let chord = KeyChord::from(key);
+        self.iter(mode).find(|(bound, _)| *bound == chord).map(|(_, action)| action)
+    }
+
+    /// The action bound to `key` in the first of `modes` which binds it, if any
+    pub fn lookup_in(&self, modes: &[KeyMode], key: KeyEvent) -> Option<KeyAction> {
+        modes.iter().find_map(|mode| self.lookup(*mode, key))
+    }
+
+    /// The chords bound to `action`, in the mode in which it is available
+    pub fn chords(&self, action: KeyAction) -> impl Iterator<Item = KeyChord> + '_ {
+        self.iter(action.mode())
+            .filter(move |(_, bound)| *bound == action)
+            .map(|(chord, _)| chord)
+    }
+
+    /// The key to show the user for `action`, i.e. the first character bound to it, if any, as
+    /// those are the quickest to type, otherwise the first chord bound to it
+    pub fn describe(&self, action: KeyAction) -> String {
+        let is_char = |chord: &KeyChord| {
+            chord.modifiers.is_empty() && matches!(chord.code, KeyCode::Char(c) if c != ' ')
+        };
+        self.chords(action)
+            .find(is_char)
+            .or_else(|| self.chords(action).next())
+            .map(|chord| chord.to_string())
+            .unwrap_or_else(|| "unbound".into())
+    }
+
+    /// The bindings of `mode`, in the order they are shown to the user
+    pub fn iter(&self, mode: KeyMode) -> impl Iterator<Item = (KeyChord, KeyAction)> + '_ {
+        self.bindings.get(&mode).into_iter().flatten().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn key_chord_parsing() {
+        let chord = |s: &str| s.parse::<KeyChord>().unwrap();
+        assert_eq!(chord("j"), KeyChord::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(chord("shift-j"), chord("J"));
+        assert_eq!(chord("ctrl-c"), KeyChord::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!(chord("Ctrl-Alt-x").modifiers, KeyModifiers::CONTROL | KeyModifiers::ALT);
+        assert_eq!(chord("-"), KeyChord::new(KeyCode::Char('-'), KeyModifiers::NONE));
+        assert_eq!(chord("ctrl--"), KeyChord::new(KeyCode::Char('-'), KeyModifiers::CONTROL));
+        assert_eq!(chord("PageDown").code, KeyCode::PageDown);
+        assert_eq!(chord("shift-tab"), chord("backtab"));
+        assert_eq!(chord("f5").code, KeyCode::F(5));
+        assert_eq!(chord("space").code, KeyCode::Char(' '));
+
+        // Chords are displayed as they are parsed
+        for s in ["j", "J", "ctrl-c", "ctrl-alt-x", "pagedown", "f12", "space", "shift-up"] {
+            assert_eq!(chord(s).to_string(), s);
+        }
+
+        for invalid in ["", "jj", "hyper-j", "f13", "ctrl-"] {
+            assert!(invalid.parse::<KeyChord>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn default_keybindings() {
+        let bindings = KeyBindings::default();
+        let lookup = |mode, code, modifiers| bindings.lookup(mode, key(code, modifiers));
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('j'), KeyModifiers::NONE),
+            Some(KeyAction::Down)
+        );
+        // Terminals report uppercase characters with the shift modifier
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('E'), KeyModifiers::SHIFT),
+            Some(KeyAction::ShowError)
+        );
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('3'), KeyModifiers::NONE),
+            Some(KeyAction::FocusPane(2))
+        );
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('3'), KeyModifiers::ALT),
+            Some(KeyAction::Tab(2))
+        );
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Tab, KeyModifiers::NONE),
+            Some(KeyAction::FocusLast)
+        );
+        assert_eq!(
+            lookup(KeyMode::Source, KeyCode::Char('N'), KeyModifiers::SHIFT),
+            Some(KeyAction::PrevMatch)
+        );
+        // The source pane shadows the global bindings of the same keys
+        assert_eq!(
+            lookup(KeyMode::Source, KeyCode::Char('f'), KeyModifiers::NONE),
+            Some(KeyAction::ToggleFollow)
+        );
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('f'), KeyModifiers::NONE),
+            Some(KeyAction::FullScreen)
+        );
+        assert_eq!(
+            lookup(KeyMode::Footer, KeyCode::BackTab, KeyModifiers::SHIFT),
+            Some(KeyAction::CompletePrev)
+        );
+        assert_eq!(lookup(KeyMode::Global, KeyCode::Char('z'), KeyModifiers::NONE), None);
+
+        // Every action is available in some mode, and can be referred to by name
+        for mode in KeyMode::ALL {
+            for (_, action) in bindings.iter(mode) {
+                assert_eq!(action.mode(), mode);
+                assert_eq!(action.to_string().parse::<KeyAction>(), Ok(action));
+                assert!(!action.summary().is_empty());
+            }
+        }
+        assert_eq!(
+            bindings
+                .chords(KeyAction::Down)
+                .map(|chord| chord.to_string())
+                .collect::<Vec<_>>(),
+            ["down", "j", "J"]
+        );
+        assert_eq!(bindings.describe(KeyAction::Down), "j");
+        assert_eq!(bindings.describe(KeyAction::PageDown), "pagedown");
+    }
+
+    #[test]
+    fn keybindings_file() {
+        let bindings = KeyBindings::parse_str(
+            r#"
+[global]
+ctrl-n = "next"
+n = "none"
+alt-j = "down"
+
+[source]
+t = "toggle-breakpoint"
+
+[footer]
+ctrl-p = "history-prev"
+"#,
+        )
+        .unwrap();
+        let lookup = |mode, code, modifiers| bindings.lookup(mode, key(code, modifiers));
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('n'), KeyModifiers::CONTROL),
+            Some(KeyAction::Next)
+        );
+        assert_eq!(lookup(KeyMode::Global, KeyCode::Char('n'), KeyModifiers::NONE), None);
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('j'), KeyModifiers::ALT),
+            Some(KeyAction::Down)
+        );
+        // Default bindings which are not overridden remain
+        assert_eq!(
+            lookup(KeyMode::Global, KeyCode::Char('j'), KeyModifiers::NONE),
+            Some(KeyAction::Down)
+        );
+        assert_eq!(
+            lookup(KeyMode::Source, KeyCode::Char('t'), KeyModifiers::NONE),
+            Some(KeyAction::ToggleBreakpoint)
+        );
+        assert_eq!(
+            lookup(KeyMode::Footer, KeyCode::Char('p'), KeyModifiers::CONTROL),
+            Some(KeyAction::HistoryPrev)
+        );
+    }
+
+    #[test]
+    fn keybindings_file_errors() {
+        let error = |content: &str| KeyBindings::parse_str(content).unwrap_err();
+        assert_eq!(
+            error("[global]\nj = \"downwards\""),
+            "[global] j = \"downwards\": unknown action 'downwards'"
+        );
+        assert_eq!(
+            error("[global]\nhyper-j = \"down\""),
+            "[global] hyper-j = \"down\": unknown key 'hyper-j'"
+        );
+        assert_eq!(
+            error("[global]\nt = \"search\""),
+            "[global] t = \"search\": 'search' is only available in [source]"
+        );
+        assert_eq!(
+            error("[global]\nJ = \"down\"\nshift-j = \"up\""),
+            "[global] shift-j = \"up\": 'shift-j' is the same key as 'J'"
+        );
+        assert_eq!(
+            error("[footer]\nq = \"cancel\""),
+            "[footer] q = \"cancel\": 'q' is typed into the command line, so it cannot be bound \
+             there"
+        );
+        assert_eq!(
+            error("[pane]\nj = \"down\""),
+            "unknown table [pane], expected one of [global], [source], [timeline], [popup], [footer]"
+        );
+        assert!(error("[global]\nj = 1").contains("invalid type"));
+    }
+}
@@ -0,0 +1,407 @@
+use std::fmt;
+
+use ratatui::layout::Constraint;
+
+/// A pane of the home page, whose visibility and size are part of the [PaneLayout]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PaneId {
+    Source,
+    Disassembly,
+    CallStack,
+    Memory,
+    Stack,
+    Breakpoints,
+    Watch,
+    Advice,
+    Timeline,
+}
+
+impl PaneId {
+    /// The panes, in the order they are focused in
+    pub const ALL: [Self; 9] = [
+        Self::Source,
+        Self::Disassembly,
+        Self::CallStack,
+        Self::Memory,
+        Self::Stack,
+        Self::Breakpoints,
+        Self::Watch,
+        Self::Advice,
+        Self::Timeline,
+    ];
+
+    /// The name of the pane, by which it is configured in the settings file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Source => "source",
+            Self::Disassembly => "disassembly",
+            Self::CallStack => "callstack",
+            Self::Memory => "memory",
+            Self::Stack => "stack",
+            Self::Breakpoints => "breakpoints",
+            Self::Watch => "watch",
+            Self::Advice => "advice",
+            Self::Timeline => "timeline",
+        }
+    }
+
+    /// The index of the pane in [PaneId::ALL]
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|id| id == self).expect("every pane is listed")
+    }
+
+    /// The `title` of the pane, prefixed by its number, i.e. its index in [PaneId::ALL] plus one,
+    /// by which it is focused, see [crate::ui::keybindings::KeyAction::FocusPane]
+    pub fn numbered(&self, title: &str) -> String {
+        format!("[{}] {title}", self.index() + 1)
+    }
+
+    /// Whether the pane is shown in the left column, rather than the right
+    pub fn is_left(&self) -> bool {
+        matches!(
+            self,
+            Self::Source | Self::Disassembly | Self::CallStack | Self::Memory | Self::Timeline
+        )
+    }
+
+    /// The size of the pane unless configured otherwise
+    fn default_size(&self) -> PaneSize {
+        match self {
+            Self::Source => PaneSize::Weight(3),
+            Self::Disassembly => PaneSize::Lines(7),
+            Self::CallStack => PaneSize::Lines(15),
+            Self::Memory => PaneSize::Weight(1),
+            Self::Stack => PaneSize::Lines(30),
+            Self::Breakpoints => PaneSize::Weight(5),
+            Self::Watch => PaneSize::Weight(3),
+            Self::Advice => PaneSize::Lines(12),
+            Self::Timeline => PaneSize::Lines(4),
+        }
+    }
+}
+
+impl fmt::Display for PaneId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The height of a pane within its column
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PaneSize {
+    /// A share of the lines left over by the panes of a fixed number of lines, in proportion to
+    /// the weights of the other panes of the column
+    Weight(u16),
+    /// At most the given number of lines
+    Lines(u16),
+}
+
+impl PaneSize {
+    /// The smallest weight of a pane
+    const MIN_WEIGHT: u16 = 1;
+    /// The largest weight of a pane
+    const MAX_WEIGHT: u16 = 20;
+    /// The fewest lines of a pane, i.e. its borders, and a single line of content
+    const MIN_LINES: u16 = 3;
+    /// The number of lines by which a pane of a fixed number of lines is grown or shrunk
+    const LINES_STEP: u16 = 2;
+
+    pub fn constraint(&self) -> Constraint {
+        match *self {
+            Self::Weight(weight) => Constraint::Fill(weight),
+            Self::Lines(lines) => Constraint::Max(lines),
+        }
+    }
+
+    /// The next larger size of the same kind
+    pub fn grow(&self) -> Self {
+        match *self {
+            Self::Weight(weight) => Self::Weight(weight.saturating_add(1).min(Self::MAX_WEIGHT)),
+            Self::Lines(lines) => Self::Lines(lines.saturating_add(Self::LINES_STEP)),
+        }
+    }
+
+    /// The next smaller size of the same kind
+    pub fn shrink(&self) -> Self {
+        match *self {
+            Self::Weight(weight) => Self::Weight(weight.saturating_sub(1).max(Self::MIN_WEIGHT)),
+            Self::Lines(lines) => {
+                Self::Lines(lines.saturating_sub(Self::LINES_STEP).max(Self::MIN_LINES))
+            }
+        }
+    }
+}
+
+impl fmt::Display for PaneSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Weight(weight) => write!(f, "weight {weight}"),
+            Self::Lines(lines) => write!(f, "{lines} lines"),
+        }
+    }
+}
+
+/// The visibility and size of a single pane
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PaneConfig {
+    pub visible: bool,
+    pub size: PaneSize,
+}
+
+/// The visibility and size of each of the panes of the home page, which are adjusted at runtime,
+/// and persisted to the settings file, see [super::settings::Settings::layout].
+///
+/// Each pane is a table of the `[layout]` table of the settings file, named after the pane, which
+/// gives its size either as a `weight`, or a number of `lines`, e.g.:
+///
+/// ```toml
+/// [layout.memory]
+/// visible = false
+///
+/// [layout.source]
+/// weight = 5
+///
+/// [layout.stack]
+/// lines = 12
+/// ```
+///
+/// Panes which are not configured, or are configured invalidly, are shown at their default size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneLayout {
+    panes: [PaneConfig; PaneId::ALL.len()],
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            panes: PaneId::ALL.map(|id| PaneConfig {
+                visible: true,
+                size: id.default_size(),
+            }),
+        }
+    }
+}
+
+impl PaneLayout {
+    pub fn get(&self, id: PaneId) -> &PaneConfig {
+        &self.panes[id.index()]
+    }
+
+    pub fn is_visible(&self, id: PaneId) -> bool {
+        self.get(id).visible
+    }
+
+    /// The visible panes of the left or right column, top to bottom, along with their heights.
+    ///
+    /// If each of them has a fixed number of lines, the last one takes any lines left over.
+    pub fn column(&self, left: bool) -> Vec<(PaneId, Constraint)> {
+        let mut column = PaneId::ALL
+            .into_iter()
+            .filter(|id| id.is_left() == left && self.is_visible(*id))
+            .map(|id| (id, self.get(id).size.constraint()))
+            .collect::<Vec<_>>();
+        if column.iter().all(|(_, height)| !matches!(height, Constraint::Fill(_)))
+            && let Some((_, height)) = column.last_mut()
+        {
+            *height = Constraint::Fill(1);
+        }
+        column
+    }
+
+    /// Hide the pane `id`, unless it is the only one shown
+    pub fn hide(&mut self, id: PaneId) -> Result<(), String> {
+        if PaneId::ALL.iter().all(|other| *other == id || !self.is_visible(*other)) {
+            return Err(format!("the {id} pane is the only one shown"));
+        }
+        self.panes[id.index()].visible = false;
+        Ok(())
+    }
+
+    /// Show all of the panes which were hidden
+    pub fn show_all(&mut self) {
+        self.panes.iter_mut().for_each(|pane| pane.visible = true);
+    }
+
+    /// Grow the pane `id`, returning its new size
+    pub fn grow(&mut self, id: PaneId) -> PaneSize {
+        let pane = &mut self.panes[id.index()];
+        pane.size = pane.size.grow();
+        pane.size
+    }
+
+    /// Shrink the pane `id`, returning its new size
+    pub fn shrink(&mut self, id: PaneId) -> PaneSize {
+        let pane = &mut self.panes[id.index()];
+        pane.size = pane.size.shrink();
+        pane.size
+    }
+
+    /// Read the layout from the `[layout]` table of the settings file
+    pub fn from_toml(table: &toml::Table) -> Self {
+        let mut layout = Self::default();
+        for (name, value) in table {
+            let Some(id) = PaneId::ALL.into_iter().find(|id| id.name() == name) else {
+                log::warn!("ignoring the layout of unknown pane '{name}'");
+                continue;
+            };
+            let Some(config) = value.as_table() else {
+                log::warn!("ignoring the layout of the {id} pane, which is not a table");
+                continue;
+            };
+            let pane = &mut layout.panes[id.index()];
+            if let Some(visible) = config.get("visible").and_then(toml::Value::as_bool) {
+                pane.visible = visible;
+            }
+            let positive = |key: &str| {
+                config
+                    .get(key)
+                    .and_then(toml::Value::as_integer)
+                    .and_then(|n| u16::try_from(n).ok())
+                    .filter(|n| *n > 0)
+            };
+            if let Some(weight) = positive("weight") {
+                pane.size =
+                    PaneSize::Weight(weight.clamp(PaneSize::MIN_WEIGHT, PaneSize::MAX_WEIGHT));
+            } else if let Some(lines) = positive("lines") {
+                pane.size = PaneSize::Lines(lines.max(PaneSize::MIN_LINES));
+            }
+        }
+        // At least one pane is always shown
+        if layout.panes.iter().all(|pane| !pane.visible) {
+            layout.show_all();
+        }
+        layout
+    }
+
+    /// Write the layout as the `[layout]` table of the settings file
+    pub fn to_toml(&self) -> toml::Table {
+        PaneId::ALL
+            .into_iter()
+            .map(|id| {
+                let pane = self.get(id);
+                let mut config = toml::Table::new();
+                config.insert("visible".into(), pane.visible.into());
+                match pane.size {
+                    PaneSize::Weight(weight) => {
+                        config.insert("weight".into(), i64::from(weight).into())
+                    }
+                    PaneSize::Lines(lines) => {
+                        config.insert("lines".into(), i64::from(lines).into())
+                    }
+                };
+                (id.name().to_string(), config.into())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_round_trip() {
+        let mut layout = PaneLayout::default();
+        assert_eq!(PaneLayout::from_toml(&layout.to_toml()), layout);
+
+        layout.hide(PaneId::Memory).unwrap();
+        layout.grow(PaneId::Source);
+        layout.shrink(PaneId::Stack);
+        let table = layout.to_toml();
+        assert_eq!(table["memory"]["visible"].as_bool(), Some(false));
+        assert_eq!(table["source"]["weight"].as_integer(), Some(4));
+        assert_eq!(table["stack"]["lines"].as_integer(), Some(28));
+        assert_eq!(PaneLayout::from_toml(&table), layout);
+
+        // Unknown panes and invalid settings are ignored, and sizes are kept within bounds
+        let table = toml::from_str::<toml::Table>(
+            "
+[bogus]
+visible = false
+
+[memory]
+visible = 'no'
+weight = 0
+
+[source]
+weight = 1000
+
+[stack]
+lines = 1
+
+[watch]
+lines = 5
+",
+        )
+        .unwrap();
+        let parsed = PaneLayout::from_toml(&table);
+        assert_eq!(*parsed.get(PaneId::Memory), *PaneLayout::default().get(PaneId::Memory));
+        assert_eq!(parsed.get(PaneId::Source).size, PaneSize::Weight(PaneSize::MAX_WEIGHT));
+        assert_eq!(parsed.get(PaneId::Stack).size, PaneSize::Lines(PaneSize::MIN_LINES));
+        assert_eq!(parsed.get(PaneId::Watch).size, PaneSize::Lines(5));
+
+        // A layout hiding every pane shows them all instead
+        let hidden = PaneId::ALL.map(|id| format!("[{id}]\nvisible = false\n")).join("\n");
+        let table = toml::from_str::<toml::Table>(&hidden).unwrap();
+        assert_eq!(PaneLayout::from_toml(&table), PaneLayout::default());
+    }
+
+    #[test]
+    fn layout_resize_and_visibility() {
+        // Weights grow and shrink by one, within bounds
+        assert_eq!(PaneSize::Weight(3).grow(), PaneSize::Weight(4));
+        assert_eq!(PaneSize::Weight(3).shrink(), PaneSize::Weight(2));
+        assert_eq!(PaneSize::Weight(1).shrink(), PaneSize::Weight(1));
+        assert_eq!(PaneSize::Weight(20).grow(), PaneSize::Weight(20));
+        // Panes of a fixed number of lines grow and shrink by two, keeping a line of content
+        assert_eq!(PaneSize::Lines(7).grow(), PaneSize::Lines(9));
+        assert_eq!(PaneSize::Lines(7).shrink(), PaneSize::Lines(5));
+        assert_eq!(PaneSize::Lines(4).shrink(), PaneSize::Lines(3));
+        assert_eq!(PaneSize::Lines(u16::MAX).grow(), PaneSize::Lines(u16::MAX));
+        assert_eq!(PaneSize::Weight(2).constraint(), Constraint::Fill(2));
+        assert_eq!(PaneSize::Lines(9).constraint(), Constraint::Max(9));
+
+        let mut layout = PaneLayout::default();
+        assert_eq!(layout.grow(PaneId::Disassembly), PaneSize::Lines(9));
+        assert_eq!(layout.shrink(PaneId::Memory), PaneSize::Weight(1));
+        assert_eq!(
+            layout.column(true),
+            [
+                (PaneId::Source, Constraint::Fill(3)),
+                (PaneId::Disassembly, Constraint::Max(9)),
+                (PaneId::CallStack, Constraint::Max(15)),
+                (PaneId::Memory, Constraint::Fill(1)),
+                (PaneId::Timeline, Constraint::Max(4)),
+            ]
+        );
+
+        // Hidden panes are left out of their column, but the last pane shown cannot be hidden
+        for id in PaneId::ALL.into_iter().filter(|id| !id.is_left()) {
+            layout.hide(id).unwrap();
+        }
+        assert!(layout.column(false).is_empty());
+
+        // Without a weighted pane, the last pane of a column fills it
+        layout.hide(PaneId::Source).unwrap();
+        layout.hide(PaneId::Memory).unwrap();
+        assert_eq!(
+            layout.column(true),
+            [
+                (PaneId::Disassembly, Constraint::Max(9)),
+                (PaneId::CallStack, Constraint::Max(15)),
+                (PaneId::Timeline, Constraint::Fill(1)),
+            ]
+        );
+        layout.show_all();
+        for id in PaneId::ALL.into_iter().filter(|id| *id != PaneId::Source) {
+            layout.hide(id).unwrap();
+        }
+        assert_eq!(layout.column(true), [(PaneId::Source, Constraint::Fill(3))]);
+        assert_eq!(
+            layout.hide(PaneId::Source),
+            Err("the source pane is the only one shown".into())
+        );
+        layout.show_all();
+        assert_eq!(layout.column(false).len(), 4);
+    }
+}
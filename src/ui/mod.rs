@@ -1,11 +1,29 @@
 mod action;
+mod aliases;
 mod app;
+mod clipboard;
+pub(crate) mod commands;
+mod completion;
+pub(crate) mod count;
 mod duration;
+mod help;
+mod history;
+mod interrupt;
+mod keybindings;
+mod layout;
 mod pages;
 mod panes;
-mod state;
+mod procedures;
+mod session_log;
+mod settings;
+pub(crate) mod state;
+pub(crate) mod style;
 mod syntax_highlighting;
+#[cfg(test)]
+mod testing;
+mod theme;
 mod tui;
+mod watch;
 
 use miden_assembly_syntax::diagnostics::{IntoDiagnostic, Report};
 
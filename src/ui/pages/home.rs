@@ -1,20 +1,26 @@
+use std::time::{Duration, Instant};
+
 use miden_assembly_syntax::diagnostics::{IntoDiagnostic, Report};
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent},
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
 };
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
-    debug::{BreakpointType, ReadMemoryExpr},
+    debug::{BreakpointType, FormatType},
     ui::{
         action::Action,
+        commands,
+        keybindings::{KeyAction, KeyMode},
+        layout::PaneId,
         pages::Page,
         panes::{
-            Pane, breakpoints::BreakpointsPane, disasm::DisassemblyPane,
-            source_code::SourceCodePane, stack::OperandStackPane, stacktrace::StackTracePane,
+            Pane, advice::AdvicePane, breakpoints::BreakpointsPane, disasm::DisassemblyPane,
+            memory::MemoryPane, source_code::SourceCodePane, stack::OperandStackPane,
+            stacktrace::StackTracePane, timeline::TimelinePane, watch::WatchPane,
         },
-        state::{InputMode, State},
+        state::{INTERRUPT_CHECK_INTERVAL, InputMode, State},
         tui::EventResponse,
     },
 };
@@ -24,27 +30,150 @@ pub struct Home {
     command_tx: Option<UnboundedSender<Action>>,
     panes: Vec<Box<dyn Pane>>,
     focused_pane_index: usize,
+    /// The index of the pane focused before the focused one, to which [Action::FocusLast] returns
+    last_focused_pane_index: Option<usize>,
     fullscreen_pane_index: Option<usize>,
+    /// The area in which each of the panes was last drawn, by which mouse events are directed to
+    /// the pane they occurred in. Panes which were not shown have an empty area.
+    pane_areas: Vec<Rect>,
 }
 
 impl Home {
     pub fn new() -> Result<Self, Report> {
         let focused_border_style = Style::default().fg(Color::LightGreen);
 
+        // The panes are listed in the order of [PaneId::ALL]
         Ok(Self {
             command_tx: None,
             panes: vec![
                 Box::new(SourceCodePane::new(true, focused_border_style)),
                 Box::new(DisassemblyPane::new(false, focused_border_style)),
                 Box::new(StackTracePane::new(false, focused_border_style)),
+                Box::new(MemoryPane::new(false, focused_border_style)),
                 Box::new(OperandStackPane::new(false, focused_border_style)),
                 Box::new(BreakpointsPane::new(false, focused_border_style)),
+                Box::new(WatchPane::new(false, focused_border_style)),
+                Box::new(AdvicePane::new(false, focused_border_style)),
+                Box::new(TimelinePane::new(false, focused_border_style)),
             ],
 
             focused_pane_index: 0,
+            last_focused_pane_index: None,
             fullscreen_pane_index: None,
+            pane_areas: vec![],
         })
     }
+
+    /// Move the focus to the pane at `index`
+    fn focus_pane(
+        &mut self,
+        index: usize,
+        state: &mut State,
+        actions: &mut Vec<Option<Action>>,
+    ) -> Result<(), Report> {
+        if index == self.focused_pane_index {
+            return Ok(());
+        }
+        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+            actions.push(pane.update(Action::UnFocus, state)?);
+        }
+        self.last_focused_pane_index = Some(self.focused_pane_index);
+        self.focused_pane_index = index;
+        if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+            actions.push(pane.update(Action::Focus, state)?);
+        }
+        Ok(())
+    }
+
+    /// Move the focus to the next pane shown after the focused one, or before it if `forward` is
+    /// false
+    fn focus_next_shown(
+        &mut self,
+        forward: bool,
+        state: &mut State,
+        actions: &mut Vec<Option<Action>>,
+    ) -> Result<(), Report> {
+        let len = self.panes.len();
+        let step = if forward { 1 } else { len - 1 };
+        let next = (1..=len)
+            .map(|n| (self.focused_pane_index + n * step) % len)
+            .find(|index| state.layout.is_visible(PaneId::ALL[*index]));
+        if let Some(index) = next {
+            self.focus_pane(index, state, actions)?;
+        }
+        Ok(())
+    }
+
+    /// Move the focus to the pane at `index`, if it is shown
+    fn focus_shown(
+        &mut self,
+        index: usize,
+        state: &mut State,
+        actions: &mut Vec<Option<Action>>,
+    ) -> Result<(), Report> {
+        let Some(id) = PaneId::ALL.get(index).copied() else {
+            return Ok(());
+        };
+        if state.layout.is_visible(id) {
+            self.focus_pane(index, state, actions)?;
+        } else {
+            let status = format!("the {id} pane is hidden");
+            actions.push(Some(Action::TimedStatusLine(status, 3)));
+        }
+        Ok(())
+    }
+
+    /// The index of the pane drawn at `column` and `row` of the terminal, if any, and the area in
+    /// which it was drawn
+    fn pane_at(&self, column: u16, row: u16) -> Option<(usize, Rect)> {
+        let position = Position::new(column, row);
+        self.pane_areas
+            .iter()
+            .position(|area| area.contains(position))
+            .map(|index| (index, self.pane_areas[index]))
+    }
+
+    /// Advance the current run for a short period of time, reporting the outcome if it stopped
+    fn resume_run(
+        &mut self,
+        state: &mut State,
+        actions: &mut Vec<Option<Action>>,
+    ) -> Result<(), Report> {
+        const SLICE: Duration = Duration::from_millis(20);
+
+        let deadline = Instant::now() + SLICE;
+        let mut stopped = false;
+        while !stopped && Instant::now() < deadline {
+            stopped = state.continue_run(INTERRUPT_CHECK_INTERVAL);
+        }
+        if !stopped {
+            return Ok(());
+        }
+
+        // Report program termination, or interruption, to the user
+        if state.executor.stopped {
+            if state.execution_failed.is_some() {
+                actions.push(Some(Action::StatusLine(commands::describe_stop(state))));
+                actions.push(Some(Action::ShowError));
+            } else {
+                let mut status = commands::describe_stop(state);
+                if let Some(warning) =
+                    commands::cycle_budget_warning(&state.config, state.executor.cycle)
+                {
+                    status.push_str(&format!(" (warning: {warning})"));
+                }
+                actions.push(Some(Action::StatusLine(status)));
+            }
+        } else if state.interrupted {
+            actions.push(Some(Action::StatusLine(commands::describe_stop(state))));
+        }
+
+        // Update the UI with latest state
+        for pane in self.panes.iter_mut() {
+            actions.push(pane.update(Action::Update, state)?);
+        }
+        Ok(())
+    }
 }
 
 impl Page for Home {
@@ -52,15 +181,29 @@ impl Page for Home {
         for pane in self.panes.iter_mut() {
             pane.init(state)?;
         }
+        // The pane focused initially may have been hidden in an earlier session
+        if !state.layout.is_visible(PaneId::ALL[self.focused_pane_index])
+            && let Some(command_tx) = &self.command_tx
+        {
+            command_tx.send(Action::FocusNext).into_diagnostic()?;
+        }
         Ok(())
     }
 
-    fn focus(&mut self) -> Result<(), Report> {
+    fn focus(&mut self, state: &State) -> Result<(), Report> {
         if let Some(command_tx) = &self.command_tx {
             const ARROW: &str = symbols::scrollbar::HORIZONTAL.end;
-            let status_line =
-                format!("[l,h {ARROW} pane movement] [: {ARROW} commands] [q {ARROW} quit]");
+            let keys = |action| state.keybindings.describe(action);
+            let status_line = format!(
+                "[{},{} {ARROW} pane movement] [{} {ARROW} commands] [{} {ARROW} quit]",
+                keys(KeyAction::FocusNext),
+                keys(KeyAction::FocusPrev),
+                keys(KeyAction::CommandLine),
+                keys(KeyAction::Quit),
+            );
             command_tx.send(Action::StatusLine(status_line)).into_diagnostic()?;
+            // Some panes can only be populated with mutable access to the program state
+            command_tx.send(Action::Update).into_diagnostic()?;
         }
         Ok(())
     }
@@ -74,36 +217,96 @@ impl Page for Home {
         let mut actions: Vec<Option<Action>> = vec![];
         match action {
             Action::Tick => {}
-            Action::FocusNext => {
-                let next_index = self.focused_pane_index.saturating_add(1) % self.panes.len();
-                if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-                    actions.push(pane.update(Action::UnFocus, state)?);
+            Action::FocusNext => self.focus_next_shown(true, state, &mut actions)?,
+            Action::FocusPrev => self.focus_next_shown(false, state, &mut actions)?,
+            Action::FocusPane(index) => self.focus_shown(index, state, &mut actions)?,
+            Action::FocusLast => match self.last_focused_pane_index {
+                Some(index) => self.focus_shown(index, state, &mut actions)?,
+                None => actions.push(Some(Action::TimedStatusLine(
+                    "no other pane has been focused".into(),
+                    3,
+                ))),
+            },
+            Action::ToggleBreakpoint(source_id, line) => {
+                match state.toggle_line_breakpoint(source_id, line) {
+                    Ok(created) => {
+                        let status = match created {
+                            true => format!("breakpoint created on line {line}"),
+                            false => format!("breakpoints on line {line} removed"),
+                        };
+                        actions.push(Some(Action::TimedStatusLine(status, 1)));
+                    }
+                    Err(err) => actions.push(Some(Action::TimedStatusLine(err, 5))),
                 }
-                self.focused_pane_index = next_index;
-                if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-                    actions.push(pane.update(Action::Focus, state)?);
+                for pane in self.panes.iter_mut() {
+                    actions.push(pane.update(Action::Update, state)?);
                 }
             }
-            Action::FocusPrev => {
-                let prev_index =
-                    self.focused_pane_index.saturating_add(self.panes.len() - 1) % self.panes.len();
-                if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-                    actions.push(pane.update(Action::UnFocus, state)?);
-                }
-                self.focused_pane_index = prev_index;
-                if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
-                    actions.push(pane.update(Action::Focus, state)?);
+            Action::SelectFrame(index) => {
+                state.selected_frame = index;
+                for pane in self.panes.iter_mut() {
+                    actions.push(pane.update(action.clone(), state)?);
                 }
             }
-            Action::Update => {
+            Action::GotoCycle(cycle) => match state.goto_cycle(cycle) {
+                // Going back restarts the program, so the panes start over too
+                Ok(_) => {
+                    actions.push(Some(Action::StatusLine(commands::describe_stop(state))));
+                    for pane in self.panes.iter_mut() {
+                        actions.push(pane.update(Action::Reload, state)?);
+                    }
+                }
+                Err(err) => {
+                    actions.push(Some(Action::TimedStatusLine(err.to_string(), 5)));
+                }
+            },
+            Action::Update
+            | Action::ShowMemory(_)
+            | Action::ShowLocation(_)
+            | Action::GotoLine(_)
+            | Action::SyncSource
+            | Action::ThemeChanged => {
                 for pane in self.panes.iter_mut() {
                     actions.push(pane.update(action.clone(), state)?);
                 }
             }
+            Action::ToggleFormat => {
+                state.display_format = match state.display_format {
+                    FormatType::Hex => FormatType::Decimal,
+                    _ => FormatType::Hex,
+                };
+                actions.push(Some(Action::TimedStatusLine(
+                    format!("values are shown in {}", state.display_format),
+                    3,
+                )));
+            }
             Action::ToggleFullScreen => {
                 self.fullscreen_pane_index =
                     self.fullscreen_pane_index.map_or(Some(self.focused_pane_index), |_| None);
             }
+            Action::HidePane => {
+                let id = PaneId::ALL[self.focused_pane_index];
+                match state.layout.hide(id) {
+                    Ok(()) => {
+                        if self.fullscreen_pane_index == Some(self.focused_pane_index) {
+                            self.fullscreen_pane_index = None;
+                        }
+                        self.focus_next_shown(true, state, &mut actions)?;
+                        let status = format!("the {id} pane is hidden");
+                        actions.push(Some(Action::TimedStatusLine(status, 3)));
+                    }
+                    Err(err) => actions.push(Some(Action::TimedStatusLine(err, 3))),
+                }
+            }
+            Action::ShowPanes => state.layout.show_all(),
+            Action::GrowPane | Action::ShrinkPane => {
+                let id = PaneId::ALL[self.focused_pane_index];
+                let size = match action {
+                    Action::GrowPane => state.layout.grow(id),
+                    _ => state.layout.shrink(id),
+                };
+                actions.push(Some(Action::TimedStatusLine(format!("the {id} pane is {size}"), 3)));
+            }
             Action::FocusFooter(..) => {
                 if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
                     actions.push(pane.update(Action::UnFocus, state)?);
@@ -113,46 +316,39 @@ impl Page for Home {
                 if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
                     pane.update(Action::Focus, state)?;
                 }
-                // Dispatch commands of the form: CMD [ARGS..]
-                match args.split_once(' ') {
-                    Some((cmd, rest)) => match cmd.trim() {
-                        "b" | "break" | "breakpoint" => match rest.parse::<BreakpointType>() {
-                            Ok(ty) => {
-                                state.create_breakpoint(ty);
-                                actions.push(Some(Action::TimedStatusLine(
-                                    "breakpoint created".to_string(),
-                                    1,
-                                )));
-                            }
-                            Err(err) => {
-                                actions.push(Some(Action::TimedStatusLine(err, 5)));
-                            }
-                        },
-                        "r" | "read" => match rest.parse::<ReadMemoryExpr>() {
-                            Ok(expr) => match state.read_memory(&expr) {
-                                Ok(result) => actions.push(Some(Action::StatusLine(result))),
-                                Err(err) => actions.push(Some(Action::TimedStatusLine(err, 5))),
-                            },
-                            Err(err) => actions.push(Some(Action::TimedStatusLine(err, 5))),
-                        },
-                        _ => {
-                            log::debug!("unknown command with arguments: '{cmd} {args}'");
-                            actions.push(Some(Action::TimedStatusLine("unknown command".into(), 1)))
-                        }
-                    },
-                    None => match args.trim() {
-                        "q" | "quit" => actions.push(Some(Action::Quit)),
-                        "reload" => {
-                            actions.push(Some(Action::Reload));
-                        }
-                        "debug" => {
-                            actions.push(Some(Action::ShowDebug));
-                        }
-                        invalid => {
-                            log::debug!("unknown command: '{invalid}'");
-                            actions.push(Some(Action::TimedStatusLine("unknown command".into(), 1)))
-                        }
-                    },
+                // Dispatch commands of the form: CMD [ARGS..], resolving the command invoked before
+                // it runs, as it may change the aliases
+                let command = commands::command_name(state, &args);
+                let mut output = vec![];
+                let result = commands::execute(state, &args, &mut |line| output.push(line));
+                match output.len() {
+                    0 => (),
+                    1 => actions.push(output.pop().map(Action::StatusLine)),
+                    _ => actions.push(Some(Action::ShowText(args.clone(), output))),
+                }
+                match result {
+                    Ok(action) => actions.push(action),
+                    Err(err) => actions.push(Some(Action::TimedStatusLine(err, 5))),
+                }
+                // Scripts may have changed the program state arbitrarily, and `run` reloads the
+                // program, so rebuild all views
+                if matches!(command, Some("source" | "run")) {
+                    for pane in self.panes.iter_mut() {
+                        actions.push(pane.update(Action::Reload, state)?);
+                    }
+                }
+            }
+            Action::FooterResult(cmd, Some(query)) if cmd.eq("/") => {
+                if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+                    actions.push(pane.update(Action::Focus, state)?);
+                    actions.push(pane.update(Action::Search(query), state)?);
+                    actions.push(pane.update(Action::NextMatch, state)?);
+                }
+            }
+            Action::FooterResult(cmd, None) if cmd.eq("/") => {
+                if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
+                    actions.push(pane.update(Action::Focus, state)?);
+                    actions.push(pane.update(Action::ClearSearch, state)?);
                 }
             }
             Action::FooterResult(_cmd, None) => {
@@ -161,159 +357,38 @@ impl Page for Home {
                 }
             }
             Action::Continue => {
-                let start_cycle = state.executor.cycle;
-                let mut breakpoints = core::mem::take(&mut state.breakpoints);
-                state.stopped = false;
-                let stopped = loop {
-                    // If stepping the program results in the program terminating succesfully, stop
-                    if state.executor.stopped {
-                        break true;
-                    }
-
-                    let mut consume_most_recent_finish = false;
-                    match state.executor.step() {
-                        Ok(Some(exited)) if exited.should_break_on_exit() => {
-                            consume_most_recent_finish = true;
-                        }
-                        Ok(_) => (),
-                        Err(err) => {
-                            // Execution terminated with an error
-                            state.execution_failed = Some(err);
-                            break true;
-                        }
-                    }
-
-                    if breakpoints.is_empty() {
-                        // No breakpoint management needed, keep executing
-                        continue;
-                    }
-
-                    let (_op, is_op_boundary, proc, loc) = {
-                        let op = state.executor.current_op;
-                        let is_boundary = state
-                            .executor
-                            .current_asmop
-                            .as_ref()
-                            .map(|_info| true)
-                            .unwrap_or(false);
-                        let (proc, loc) = match state.executor.callstack.current_frame() {
-                            Some(frame) => {
-                                let loc = frame
-                                    .recent()
-                                    .back()
-                                    .and_then(|detail| detail.resolve(&state.source_manager))
-                                    .cloned();
-                                (frame.procedure(""), loc)
-                            }
-                            None => (None, None),
-                        };
-                        (op, is_boundary, proc, loc)
-                    };
-
-                    // Remove all breakpoints triggered at this cycle
-                    let current_cycle = state.executor.cycle;
-                    let cycles_stepped = current_cycle - start_cycle;
-                    breakpoints.retain_mut(|bp| {
-                        if let Some(n) = bp.cycles_to_skip(current_cycle) {
-                            if cycles_stepped >= n {
-                                let retained = !bp.is_one_shot();
-                                if retained {
-                                    state.breakpoints_hit.push(bp.clone());
-                                } else {
-                                    state.breakpoints_hit.push(core::mem::take(bp));
-                                }
-                                return retained;
-                            } else {
-                                return true;
-                            }
-                        }
-
-                        if cycles_stepped > 0
-                            && is_op_boundary
-                            && matches!(&bp.ty, BreakpointType::Next)
-                        {
-                            state.breakpoints_hit.push(core::mem::take(bp));
-                            return false;
-                        }
-
-                        if let Some(loc) = loc.as_ref()
-                            && bp.should_break_at(loc)
-                        {
-                            let retained = !bp.is_one_shot();
-                            if retained {
-                                state.breakpoints_hit.push(bp.clone());
-                            } else {
-                                state.breakpoints_hit.push(core::mem::take(bp));
-                            }
-                            return retained;
-                        }
-
-                        if let Some(proc) = proc.as_deref()
-                            && bp.should_break_in(proc)
-                        {
-                            let retained = !bp.is_one_shot();
-                            if retained {
-                                state.breakpoints_hit.push(bp.clone());
-                            } else {
-                                state.breakpoints_hit.push(core::mem::take(bp));
-                            }
-                            return retained;
-                        }
-
-                        true
-                    });
-
-                    if consume_most_recent_finish
-                        && let Some(id) = breakpoints.iter().rev().find_map(|bp| {
-                            if matches!(bp.ty, BreakpointType::Finish) {
-                                Some(bp.id)
-                            } else {
-                                None
-                            }
-                        })
-                    {
-                        breakpoints.retain(|bp| bp.id != id);
-                        break true;
-                    }
-
-                    if !state.breakpoints_hit.is_empty() {
-                        break true;
-                    }
-                };
-
-                // Restore the breakpoints state
-                state.breakpoints = breakpoints;
-
-                // Ensure that if we yield to the runtime, that we resume executing when
-                // resumed, unless we specifically stopped for a breakpoint or other condition
-                state.stopped = stopped;
-
-                // Report program termination to the user
-                if stopped && state.executor.stopped {
-                    if let Some(err) = state.execution_failed.as_ref() {
-                        actions.push(Some(Action::StatusLine(err.to_string())));
-                    } else {
-                        actions.push(Some(Action::StatusLine(
-                            "program terminated successfully".to_string(),
-                        )));
-                    }
+                if !state.is_running() {
+                    state.start_run();
                 }
-
-                // Update the UI with latest state
-                for pane in self.panes.iter_mut() {
-                    actions.push(pane.update(Action::Update, state)?);
+                self.resume_run(state, &mut actions)?;
+                if state.is_running() {
+                    actions.push(Some(Action::StatusLine(
+                        "running, press Ctrl-C to interrupt".to_string(),
+                    )));
                 }
             }
-            Action::Reload => match state.reload() {
-                Ok(_) => {
-                    for pane in self.panes.iter_mut() {
-                        actions.push(pane.update(Action::Reload, state)?);
+            // Long-running executions are advanced a slice at a time between frames, so that the
+            // UI remains responsive, and the run can be interrupted
+            Action::Render if state.is_running() => {
+                self.resume_run(state, &mut actions)?;
+            }
+            Action::Reload | Action::Reset => {
+                let result = match action {
+                    Action::Reload => state.reload(),
+                    _ => state.reset(),
+                };
+                match result {
+                    // Either way, the panes start over from the beginning of the program
+                    Ok(_) => {
+                        for pane in self.panes.iter_mut() {
+                            actions.push(pane.update(Action::Reload, state)?);
+                        }
+                    }
+                    Err(err) => {
+                        actions.push(Some(Action::TimedStatusLine(err.to_string(), 5)));
                     }
                 }
-                Err(err) => {
-                    actions.push(Some(Action::TimedStatusLine(err.to_string(), 5)));
-                }
-            },
+            }
             _ => {
                 if let Some(pane) = self.panes.get_mut(self.focused_pane_index) {
                     actions.push(pane.update(action, state)?);
@@ -336,65 +411,98 @@ impl Page for Home {
     ) -> Result<Option<EventResponse<Action>>, Report> {
         match state.input_mode {
             InputMode::Normal => {
-                let response = match key.code {
-                    KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('L') => {
-                        EventResponse::Stop(Action::FocusNext)
-                    }
-                    KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => {
-                        EventResponse::Stop(Action::FocusPrev)
-                    }
-                    KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
-                        EventResponse::Stop(Action::Down)
-                    }
-                    KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
-                        EventResponse::Stop(Action::Up)
-                    }
-                    KeyCode::Char('g') | KeyCode::Char('G') => EventResponse::Stop(Action::Go),
-                    KeyCode::Backspace | KeyCode::Char('b') | KeyCode::Char('B') => {
-                        EventResponse::Stop(Action::Back)
-                    }
-                    KeyCode::Char('f') | KeyCode::Char('F') => {
-                        EventResponse::Stop(Action::ToggleFullScreen)
+                // Keys specific to the focused pane take precedence
+                if let Some(pane) = self.panes.get_mut(self.focused_pane_index)
+                    && let Some(response) = pane.handle_key_events(key, state)?
+                {
+                    return Ok(Some(response));
+                }
+                let Some(action) = state.keybindings.lookup(KeyMode::Global, key) else {
+                    return Ok(None);
+                };
+                let response = match action {
+                    // Interrupt the current run, if one is in progress
+                    KeyAction::Interrupt if state.is_running() => {
+                        state.interrupt.raise();
+                        EventResponse::Stop(Action::Noop)
                     }
-                    KeyCode::Char(c) if ('1'..='9').contains(&c) => {
-                        EventResponse::Stop(Action::Tab(c.to_digit(10).unwrap_or(0) - 1))
+                    KeyAction::FocusNext => EventResponse::Stop(Action::FocusNext),
+                    KeyAction::FocusPrev => EventResponse::Stop(Action::FocusPrev),
+                    KeyAction::FocusPane(index) => {
+                        EventResponse::Stop(Action::FocusPane(index as usize))
                     }
-                    KeyCode::Char(']') => EventResponse::Stop(Action::TabNext),
-                    KeyCode::Char('[') => EventResponse::Stop(Action::TabPrev),
-                    KeyCode::Char(':') => {
+                    KeyAction::FocusLast => EventResponse::Stop(Action::FocusLast),
+                    KeyAction::Down => EventResponse::Stop(Action::Down),
+                    KeyAction::Up => EventResponse::Stop(Action::Up),
+                    KeyAction::PageDown => EventResponse::Stop(Action::PageDown),
+                    KeyAction::PageUp => EventResponse::Stop(Action::PageUp),
+                    KeyAction::Go => EventResponse::Stop(Action::Go),
+                    KeyAction::Back => EventResponse::Stop(Action::Back),
+                    KeyAction::FullScreen => EventResponse::Stop(Action::ToggleFullScreen),
+                    KeyAction::HidePane => EventResponse::Stop(Action::HidePane),
+                    KeyAction::ShowPanes => EventResponse::Stop(Action::ShowPanes),
+                    KeyAction::GrowPane => EventResponse::Stop(Action::GrowPane),
+                    KeyAction::ShrinkPane => EventResponse::Stop(Action::ShrinkPane),
+                    KeyAction::Tab(index) => EventResponse::Stop(Action::Tab(index)),
+                    KeyAction::TabNext => EventResponse::Stop(Action::TabNext),
+                    KeyAction::TabPrev => EventResponse::Stop(Action::TabPrev),
+                    KeyAction::CommandLine => {
                         EventResponse::Stop(Action::FocusFooter(":".into(), None))
                     }
-                    KeyCode::Char('q') => EventResponse::Stop(Action::Quit),
-                    KeyCode::Char('e') => {
+                    KeyAction::Quit => EventResponse::Stop(Action::Quit),
+                    KeyAction::ToggleFormat => EventResponse::Stop(Action::ToggleFormat),
+                    KeyAction::CycleMemoryView => EventResponse::Stop(Action::CycleMemoryView),
+                    KeyAction::ShowLog => EventResponse::Stop(Action::ShowDebug),
+                    KeyAction::ShowError => EventResponse::Stop(Action::ShowError),
+                    KeyAction::ShowSymbols => EventResponse::Stop(Action::ShowSymbols),
+                    KeyAction::Help => EventResponse::Stop(Action::Help),
+                    KeyAction::Finish => {
                         state.create_breakpoint(BreakpointType::Finish);
                         state.stopped = false;
                         EventResponse::Stop(Action::Continue)
                     }
                     // Only step if we're stopped, and execution has not terminated
-                    KeyCode::Char('s') if state.stopped && !state.executor.stopped => {
+                    KeyAction::Step if state.stopped && !state.executor.stopped => {
                         state.create_breakpoint(BreakpointType::Step);
                         state.stopped = false;
                         EventResponse::Stop(Action::Continue)
                     }
                     // Only step-next if we're stopped, and execution has not terminated
-                    KeyCode::Char('n') if state.stopped && !state.executor.stopped => {
+                    KeyAction::Next if state.stopped && !state.executor.stopped => {
                         state.create_breakpoint(BreakpointType::Next);
                         state.stopped = false;
                         EventResponse::Stop(Action::Continue)
                     }
                     // Only resume execution if we're stopped, and execution has not terminated
-                    KeyCode::Char('c') if state.stopped && !state.executor.stopped => {
+                    KeyAction::Continue if state.stopped && !state.executor.stopped => {
                         state.stopped = false;
                         EventResponse::Stop(Action::Continue)
                     }
                     // Do not try to continue if execution has terminated, but warn user
-                    KeyCode::Char('c' | 's' | 'n') if state.stopped && state.executor.stopped => {
+                    KeyAction::Continue | KeyAction::Step | KeyAction::Next
+                        if state.stopped && state.executor.stopped =>
+                    {
                         EventResponse::Stop(Action::TimedStatusLine(
                             "program has terminated, cannot continue".to_string(),
                             3,
                         ))
                     }
-                    KeyCode::Char('d') => EventResponse::Stop(Action::Delete),
+                    KeyAction::Delete => EventResponse::Stop(Action::Delete),
+                    KeyAction::ToggleEnabled => {
+                        EventResponse::Stop(Action::ToggleBreakpointEnabled)
+                    }
+                    KeyAction::Submit => EventResponse::Stop(Action::Submit),
+                    KeyAction::Yank => EventResponse::Stop(Action::Yank),
+                    KeyAction::ToggleStackPairs => EventResponse::Stop(Action::ToggleStackPairs),
+                    KeyAction::Paste => match state.clipboard.register() {
+                        Some(text) => {
+                            EventResponse::Stop(Action::FocusFooter(":".into(), Some(text.into())))
+                        }
+                        None => EventResponse::Stop(Action::TimedStatusLine(
+                            "nothing has been copied".into(),
+                            3,
+                        )),
+                    },
                     _ => {
                         return Ok(None);
                     }
@@ -406,36 +514,71 @@ impl Page for Home {
         }
     }
 
+    fn handle_mouse_events(
+        &mut self,
+        mouse: MouseEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        if state.input_mode != InputMode::Normal {
+            return Ok(None);
+        }
+        let response = match mouse.kind {
+            MouseEventKind::ScrollDown => EventResponse::Stop(Action::Down),
+            MouseEventKind::ScrollUp => EventResponse::Stop(Action::Up),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some((index, area)) = self.pane_at(mouse.column, mouse.row) else {
+                    return Ok(None);
+                };
+                if index != self.focused_pane_index {
+                    let mut actions = vec![];
+                    self.focus_pane(index, state, &mut actions)?;
+                    if let Some(tx) = &self.command_tx {
+                        actions.into_iter().flatten().for_each(|action| {
+                            tx.send(action).ok();
+                        });
+                    }
+                }
+                // The pane is given the position of the click within the area it was drawn in
+                let mouse = MouseEvent {
+                    column: mouse.column - area.x,
+                    row: mouse.row - area.y,
+                    ..mouse
+                };
+                self.panes[index]
+                    .handle_mouse_events(mouse, state)?
+                    .unwrap_or(EventResponse::Stop(Action::Noop))
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(response))
+    }
+
     fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        self.pane_areas = vec![Rect::default(); self.panes.len()];
         if let Some(fullscreen_pane_index) = self.fullscreen_pane_index {
+            self.pane_areas[fullscreen_pane_index] = area;
             self.panes[fullscreen_pane_index].draw(frame, area, state)?;
         } else {
-            let outer_layout = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(vec![Constraint::Fill(3), Constraint::Fill(1)])
-                .split(area);
-
-            let left_panes = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(vec![
-                    self.panes[0].height_constraint(),
-                    self.panes[1].height_constraint(),
-                    self.panes[2].height_constraint(),
-                ])
-                .split(outer_layout[0]);
+            // A column of which every pane is hidden gives its width to the other
+            let columns = [state.layout.column(true), state.layout.column(false)]
+                .into_iter()
+                .filter(|column| !column.is_empty())
+                .collect::<Vec<_>>();
+            let widths = match columns.len() {
+                1 => vec![Constraint::Fill(1)],
+                _ => vec![Constraint::Fill(3), Constraint::Fill(1)],
+            };
+            let outer_layout = Layout::horizontal(widths).split(area);
 
-            let right_panes = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(vec![
-                    self.panes[3].height_constraint(),
-                    self.panes[4].height_constraint(),
-                ])
-                .split(outer_layout[1]);
-            self.panes[0].draw(frame, left_panes[0], state)?;
-            self.panes[1].draw(frame, left_panes[1], state)?;
-            self.panes[2].draw(frame, left_panes[2], state)?;
-            self.panes[3].draw(frame, right_panes[0], state)?;
-            self.panes[4].draw(frame, right_panes[1], state)?;
+            for (column, column_area) in columns.iter().zip(outer_layout.iter()) {
+                let areas =
+                    Layout::vertical(column.iter().map(|(_, height)| *height)).split(*column_area);
+                for ((id, _), area) in column.iter().zip(areas.iter()) {
+                    let index = id.index();
+                    self.pane_areas[index] = *area;
+                    self.panes[index].draw(frame, *area, state)?;
+                }
+            }
         }
         Ok(())
     }
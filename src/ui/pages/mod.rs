@@ -20,7 +20,7 @@ pub trait Page {
         Ok(())
     }
 
-    fn focus(&mut self) -> Result<(), Report> {
+    fn focus(&mut self, _state: &State) -> Result<(), Report> {
         Ok(())
     }
 
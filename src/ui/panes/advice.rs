@@ -0,0 +1,102 @@
+use miden_assembly_syntax::diagnostics::Report;
+use miden_core::Word;
+use miden_processor::Felt;
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::ui::{action::Action, layout::PaneId, panes::Pane, state::State, tui::Frame};
+
+/// A read-only view of the advice stack and advice map
+pub struct AdvicePane {
+    focused: bool,
+    focused_border_style: Style,
+    /// The advice stack, as of the last update
+    stack: Vec<Felt>,
+    /// The keys of the advice map, and the number of elements mapped to each, as of the last
+    /// update
+    map: Vec<(Word, usize)>,
+}
+
+impl AdvicePane {
+    pub fn new(focused: bool, focused_border_style: Style) -> Self {
+        Self {
+            focused,
+            focused_border_style,
+            stack: vec![],
+            map: vec![],
+        }
+    }
+
+    fn border_style(&self) -> Style {
+        match self.focused {
+            true => self.focused_border_style,
+            false => Style::default(),
+        }
+    }
+
+    fn border_type(&self) -> BorderType {
+        match self.focused {
+            true => BorderType::Thick,
+            false => BorderType::Plain,
+        }
+    }
+}
+
+impl Pane for AdvicePane {
+    fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Focus => {
+                self.focused = true;
+            }
+            Action::UnFocus => {
+                self.focused = false;
+            }
+            Action::Update | Action::Reload => {
+                (self.stack, self.map) = state.executor.with_advice(|advice| {
+                    let map = advice.map().map(|(key, values)| (*key, values.len())).collect();
+                    (advice.stack().to_vec(), map)
+                });
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        let stack = self.stack.iter().map(|felt| {
+            Line::from(Span::styled(format!(" {}", state.format_felt(*felt)), Color::White))
+        });
+        let map = self.map.iter().map(|(key, len)| {
+            Line::from(vec![
+                Span::styled(format!(" {key}"), Color::Cyan),
+                Span::styled(format!(" ({len} elements)"), Color::DarkGray),
+            ])
+        });
+        let lines = stack.chain(map).collect::<Vec<_>>();
+
+        frame.render_widget(List::new(lines).block(Block::default().borders(Borders::ALL)), area);
+        frame.render_widget(
+            Block::default()
+                .title(PaneId::Advice.numbered("Advice"))
+                .borders(Borders::ALL)
+                .border_style(self.border_style())
+                .border_type(self.border_type())
+                .title_bottom(
+                    Line::styled(
+                        format!(
+                            "stack depth is {}, {} map entries",
+                            self.stack.len(),
+                            self.map.len()
+                        ),
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    )
+                    .right_aligned(),
+                ),
+            area,
+        );
+        Ok(())
+    }
+}
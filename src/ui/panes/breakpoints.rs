@@ -1,14 +1,22 @@
-use miden_assembly_syntax::diagnostics::Report;
+use std::{path::Path, sync::Arc};
+
+use glob::Pattern;
+use miden_assembly_syntax::{
+    debuginfo::{SourceFile, SourceSpan},
+    diagnostics::Report,
+};
+use miden_debug_types::{LineIndex, SourceManager, SourceManagerExt};
 use ratatui::{
     prelude::*,
     widgets::{block::*, *},
 };
 
 use crate::{
-    debug::{Breakpoint, BreakpointType},
-    ui::{action::Action, panes::Pane, state::State, tui::Frame},
+    debug::{Breakpoint, BreakpointType, ResolvedLocation},
+    ui::{action::Action, layout::PaneId, panes::Pane, state::State, tui::Frame},
 };
 
+/// The breakpoints created by the user, with those hit at the current cycle highlighted
 pub struct BreakpointsPane {
     focused: bool,
     focused_border_style: Style,
@@ -41,16 +49,53 @@ impl BreakpointsPane {
             false => BorderType::Plain,
         }
     }
+
+    fn selected<'a>(&self, state: &'a mut State) -> Option<&'a mut Breakpoint> {
+        let id = self.breakpoint_selected?;
+        state.breakpoints.iter_mut().find(|bp| bp.id == id)
+    }
 }
 
-impl Pane for BreakpointsPane {
-    fn height_constraint(&self) -> Constraint {
-        match self.focused {
-            true => Constraint::Fill(5),
-            false => Constraint::Fill(5),
-        }
+/// Resolve the location at which `bp` is set, i.e. the first line of the file for file breakpoints,
+/// or the line for line breakpoints
+fn breakpoint_location(bp: &Breakpoint, state: &State) -> Result<ResolvedLocation, String> {
+    let (pattern, line) = match &bp.ty {
+        BreakpointType::File(pattern) => (pattern, 1),
+        BreakpointType::Line { pattern, line } => (pattern, *line),
+        _ => return Err(format!("breakpoint {} is not set on a source location", bp.id)),
+    };
+    let source_file = find_source_file(pattern, state)
+        .ok_or_else(|| format!("no source file matches '{}'", pattern.as_str()))?;
+    let range = source_file
+        .content()
+        .line_range(LineIndex::from(line - 1))
+        .ok_or_else(|| format!("{} has no line {line}", source_file.uri().as_str()))?;
+    Ok(ResolvedLocation {
+        line,
+        col: 1,
+        span: SourceSpan::at(source_file.id(), range.start),
+        source_file,
+    })
+}
+
+/// Find the first source file whose URI matches `pattern`, loading it from disk if the pattern
+/// names a file, relative to the working directory, which is not yet known to the source manager
+fn find_source_file(pattern: &Pattern, state: &State) -> Option<Arc<SourceFile>> {
+    if let Some(file) = state
+        .source_files()
+        .into_iter()
+        .find(|file| pattern.matches_path(Path::new(file.uri().as_str())))
+    {
+        return Some(file);
+    }
+    let path = state.config.working_dir().join(pattern.as_str());
+    if path.is_file() {
+        return state.source_manager.load_file(&path).ok();
     }
+    None
+}
 
+impl Pane for BreakpointsPane {
     fn init(&mut self, state: &State) -> Result<(), Report> {
         self.breakpoint_cycle = state.executor.cycle;
         self.breakpoints_hit.clear();
@@ -62,6 +107,8 @@ impl Pane for BreakpointsPane {
         match action {
             Action::Focus => {
                 self.focused = true;
+                static STATUS_LINE: &str = "[space → toggle] [d → delete] [enter → show location]";
+                return Ok(Some(Action::TimedStatusLine(STATUS_LINE.into(), 3)));
             }
             Action::UnFocus => {
                 self.focused = false;
@@ -102,20 +149,43 @@ impl Pane for BreakpointsPane {
                     self.breakpoint_selected = select_next;
                 }
             }
+            Action::ToggleBreakpointEnabled => {
+                if let Some(bp) = self.selected(state) {
+                    bp.enabled = !bp.enabled;
+                    let status = match bp.enabled {
+                        true => format!("breakpoint {} enabled", bp.id),
+                        false => format!("breakpoint {} disabled", bp.id),
+                    };
+                    return Ok(Some(Action::TimedStatusLine(status, 1)));
+                }
+            }
+            Action::Submit => {
+                if let Some(id) = self.breakpoint_selected
+                    && let Some(bp) = state.breakpoints.iter().find(|bp| bp.id == id)
+                {
+                    return Ok(Some(match breakpoint_location(bp, state) {
+                        Ok(loc) => Action::ShowLocation(loc),
+                        Err(err) => Action::TimedStatusLine(err, 3),
+                    }));
+                }
+            }
             Action::Reload => {
+                // Breakpoints are recreated on reload, so the ids of those selected or hit are
+                // no longer meaningful
                 self.init(state)?;
             }
             Action::Update => {
                 if self.breakpoint_cycle < state.executor.cycle {
                     self.breakpoints_hit.clear();
                     self.breakpoints_hit.append(&mut state.breakpoints_hit);
-                    if let Some(prev) = self.breakpoint_selected
-                        && self.breakpoints_hit.iter().any(|bp| bp.id == prev && bp.is_one_shot())
-                    {
-                        self.breakpoint_selected = None;
-                    }
                 }
                 self.breakpoint_cycle = state.executor.cycle;
+                // The selected breakpoint may have been consumed when hit, or removed via a command
+                if let Some(prev) = self.breakpoint_selected
+                    && !state.breakpoints.iter().any(|bp| bp.id == prev)
+                {
+                    self.breakpoint_selected = None;
+                }
             }
             _ => {}
         }
@@ -124,17 +194,18 @@ impl Pane for BreakpointsPane {
     }
 
     fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
-        let mut breakpoints = self
-            .breakpoints_hit
+        // Breakpoints which are retained when hit are shown as they are now, e.g. if toggled since,
+        // while those consumed by being hit are shown until the program advances
+        let mut breakpoints = state
+            .breakpoints
             .iter()
-            .map(|bp| (true, bp))
-            .chain(state.breakpoints.iter().filter_map(|bp| {
-                if self.breakpoints_hit.iter().any(|hit| hit.id == bp.id) {
-                    None
-                } else {
-                    Some((false, bp))
-                }
-            }))
+            .map(|bp| (self.breakpoints_hit.iter().any(|hit| hit.id == bp.id), bp))
+            .chain(
+                self.breakpoints_hit
+                    .iter()
+                    .filter(|hit| !state.breakpoints.iter().any(|bp| bp.id == hit.id))
+                    .map(|bp| (true, bp)),
+            )
             .filter(|(_, bp)| !bp.is_internal())
             .collect::<Vec<_>>();
         breakpoints.sort_by_key(|(_, bp)| bp.id);
@@ -170,44 +241,86 @@ impl Pane for BreakpointsPane {
                 } else {
                     Span::styled("", Style::default())
                 };
+                let id = Span::styled(format!("{:>2} ", bp.id), Color::Cyan);
+                let enabled = match bp.enabled {
+                    true => Span::styled("[x] ", Color::Green),
+                    false => Span::styled("[ ] ", Color::DarkGray),
+                };
+                let hits = match bp.is_located() {
+                    true => format!(" hits:{} passes:{}", bp.hits, bp.passes),
+                    false => format!(" hits:{}", bp.hits),
+                };
+                let hits = Span::styled(hits, Color::DarkGray);
                 let line = match &bp.ty {
-                    BreakpointType::Next | BreakpointType::Step | BreakpointType::Finish => {
-                        unreachable!()
-                    }
+                    BreakpointType::Next
+                    | BreakpointType::Step
+                    | BreakpointType::StepInstructions { .. }
+                    | BreakpointType::Finish
+                    | BreakpointType::RelativeLine(_)
+                    | BreakpointType::CurrentFileLine(_) => unreachable!(),
                     BreakpointType::StepN(n) => Line::from(vec![
                         gutter,
+                        id,
+                        enabled,
                         Span::styled("cycle:", yellow),
                         Span::styled(format!("{}", bp.creation_cycle + *n), gray),
                     ]),
+                    BreakpointType::StepOps { n, started } => Line::from(vec![
+                        gutter,
+                        id,
+                        enabled,
+                        Span::styled("ops:", yellow),
+                        Span::styled(format!("{started}/{n}"), gray),
+                    ]),
                     BreakpointType::StepTo(cycle) => Line::from(vec![
                         gutter,
+                        id,
+                        enabled,
                         Span::styled("cycle:", yellow),
                         Span::styled(format!("{cycle}"), gray),
                     ]),
                     BreakpointType::File(pattern) => Line::from(vec![
                         gutter,
+                        id,
+                        enabled,
                         Span::styled("file:", yellow),
                         Span::styled(pattern.as_str(), gray),
                     ]),
                     BreakpointType::Line { pattern, line } => Line::from(vec![
                         gutter,
+                        id,
+                        enabled,
                         Span::styled("file:", yellow),
                         Span::styled(pattern.as_str(), gray),
                         Span::styled(format!(":{line}"), yellow),
                     ]),
                     BreakpointType::Called(pattern) => Line::from(vec![
                         gutter,
+                        id,
+                        enabled,
                         Span::styled("proc:", yellow),
                         Span::styled(pattern.as_str(), gray),
                     ]),
+                    BreakpointType::ContextSwitch => Line::from(vec![
+                        gutter,
+                        id,
+                        enabled,
+                        Span::styled("context:", yellow),
+                        Span::styled("any", gray),
+                    ]),
                     BreakpointType::Opcode(op) => Line::from(vec![
                         gutter,
+                        id,
+                        enabled,
                         Span::styled("opcode:", yellow),
                         Span::styled(format!("{op}"), gray),
                     ]),
                 };
+                let line = line + hits;
                 if is_hit {
                     line.patch_style(Style::default().add_modifier(Modifier::BOLD))
+                } else if !bp.enabled {
+                    line.patch_style(Style::default().add_modifier(Modifier::DIM))
                 } else {
                     line
                 }
@@ -222,7 +335,7 @@ impl Pane for BreakpointsPane {
         let mut list_state = ListState::default().with_selected(selected_index);
 
         let pane = Block::default()
-            .title("Breakpoints")
+            .title(PaneId::Breakpoints.numbered("Breakpoints"))
             .borders(Borders::ALL)
             .border_style(self.border_style())
             .border_type(self.border_type());
@@ -245,3 +358,145 @@ impl Pane for BreakpointsPane {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::main_masm;
+
+    const PROGRAM: &str = "
+proc foo
+    push.1
+    drop
+end
+
+begin
+    exec.foo
+    exec.foo
+end
+";
+
+    fn pattern(pattern: &str) -> Pattern {
+        Pattern::new(pattern).unwrap()
+    }
+
+    #[test]
+    fn breakpoints_pane_selection_toggle_and_delete() {
+        let mut state = State::for_source(PROGRAM);
+        state.create_breakpoint(BreakpointType::Called(pattern("*foo")));
+        state.create_breakpoint(BreakpointType::StepTo(100));
+        state.create_breakpoint(BreakpointType::ContextSwitch);
+        let mut pane = BreakpointsPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+
+        // Toggling or deleting does nothing until a breakpoint is selected
+        assert_eq!(pane.update(Action::ToggleBreakpointEnabled, &mut state).unwrap(), None);
+        pane.update(Action::Delete, &mut state).unwrap();
+        assert_eq!(state.breakpoints.len(), 3);
+
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.breakpoint_selected, Some(0));
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.breakpoint_selected, Some(1));
+        pane.update(Action::Up, &mut state).unwrap();
+        pane.update(Action::Up, &mut state).unwrap();
+        assert_eq!(pane.breakpoint_selected, Some(2));
+
+        assert_eq!(
+            pane.update(Action::ToggleBreakpointEnabled, &mut state).unwrap(),
+            Some(Action::TimedStatusLine("breakpoint 2 disabled".into(), 1))
+        );
+        assert!(!state.breakpoints[2].enabled);
+        assert_eq!(
+            pane.update(Action::ToggleBreakpointEnabled, &mut state).unwrap(),
+            Some(Action::TimedStatusLine("breakpoint 2 enabled".into(), 1))
+        );
+        assert!(state.breakpoints[2].enabled);
+
+        // Deleting the last breakpoint selects the first
+        pane.update(Action::Delete, &mut state).unwrap();
+        assert_eq!(state.breakpoints.iter().map(|bp| bp.id).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(pane.breakpoint_selected, Some(0));
+    }
+
+    #[test]
+    fn breakpoints_pane_stays_in_sync() {
+        let mut state = State::for_source(PROGRAM);
+        state.create_breakpoint(BreakpointType::Called(pattern("*foo")));
+        let mut pane = BreakpointsPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        pane.update(Action::Down, &mut state).unwrap();
+        pane.update(Action::ToggleBreakpointEnabled, &mut state).unwrap();
+
+        // Disabled breakpoints are never hit, and stay disabled across reloads
+        state.run_until_stopped();
+        assert!(state.executor.stopped);
+        assert_eq!(state.breakpoints[0].hits, 0);
+        state.reload().unwrap();
+        pane.update(Action::Reload, &mut state).unwrap();
+        assert_eq!(pane.breakpoint_selected, None);
+        assert!(!state.breakpoints[0].enabled);
+
+        pane.update(Action::Down, &mut state).unwrap();
+        pane.update(Action::ToggleBreakpointEnabled, &mut state).unwrap();
+        state.run_until_stopped();
+        assert!(!state.executor.stopped);
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.breakpoints_hit.iter().map(|bp| bp.id).collect::<Vec<_>>(), [0]);
+        assert_eq!(state.breakpoints[0].hits, 1);
+        assert_eq!(pane.breakpoint_selected, Some(0));
+
+        // Selecting a breakpoint which is consumed when hit clears the selection once it is hit
+        state.create_breakpoint(BreakpointType::StepN(1));
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.breakpoint_selected, Some(1));
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        // The first breakpoint is hit again, as the program is still executing `foo`
+        assert_eq!(pane.breakpoints_hit.iter().map(|bp| bp.id).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(state.breakpoints[0].hits, 2);
+        assert_eq!(pane.breakpoint_selected, None);
+    }
+
+    #[test]
+    fn breakpoints_pane_show_location() {
+        let (_dir, path) = main_masm("begin\n    push.1\n    push.2\n    add\n    drop\nend\n");
+
+        let mut state = State::for_source_file(&path);
+        let file = Pattern::escape(path.to_str().unwrap());
+        state.create_breakpoint(BreakpointType::Line {
+            pattern: pattern(&file),
+            line: 3,
+        });
+        state.create_breakpoint(BreakpointType::Called(pattern("*foo")));
+        state.create_breakpoint(BreakpointType::Line {
+            pattern: pattern(&file),
+            line: 99,
+        });
+        let mut pane = BreakpointsPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+
+        pane.update(Action::Down, &mut state).unwrap();
+        let action = pane.update(Action::Submit, &mut state).unwrap();
+        let Some(Action::ShowLocation(loc)) = action else {
+            panic!("expected the location of the breakpoint, got {action:?}");
+        };
+        assert_eq!(loc.source_file.uri().as_str(), path.to_str().unwrap());
+        assert_eq!(loc.line, 3);
+        assert_eq!(loc.span.start().to_usize(), "begin\n    push.1\n".len());
+
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(
+            pane.update(Action::Submit, &mut state).unwrap(),
+            Some(Action::TimedStatusLine(
+                "breakpoint 1 is not set on a source location".into(),
+                3
+            ))
+        );
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(
+            pane.update(Action::Submit, &mut state).unwrap(),
+            Some(Action::TimedStatusLine(format!("{} has no line 99", path.display()), 3))
+        );
+    }
+}
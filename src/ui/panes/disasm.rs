@@ -1,14 +1,42 @@
+use std::path::Path;
+
 use miden_assembly_syntax::diagnostics::Report;
 use ratatui::{
     prelude::*,
     widgets::{block::*, *},
 };
 
-use crate::ui::{action::Action, panes::Pane, state::State, tui::Frame};
+use crate::{
+    debug::ResolvedLocation,
+    ui::{action::Action, layout::PaneId, panes::Pane, state::State, tui::Frame},
+};
 
+/// A row of the [DisassemblyPane]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Row {
+    /// A line of source code, shown before the first operation emitted for it
+    Source {
+        file: String,
+        line: u32,
+        text: String,
+    },
+    /// An operation of the block, which completes `cycles` cycles after the start of the block
+    Op { op: String, cycles: usize },
+}
+
+/// The operations of the basic block being executed, interleaved with the source lines they were
+/// emitted for, if known
 pub struct DisassemblyPane {
     focused: bool,
     focused_border_style: Style,
+    /// The rows for the current block, as of the last update
+    rows: Vec<Row>,
+    /// The index of the row of the operation about to execute, if it belongs to the block
+    next_row: Option<usize>,
+    /// The first row shown, if scrolled by the user, otherwise the view follows execution
+    scroll: Option<usize>,
+    /// The number of rows shown as of the last draw, by which the view is paged
+    page_size: usize,
 }
 
 impl DisassemblyPane {
@@ -16,6 +44,10 @@ impl DisassemblyPane {
         Self {
             focused,
             focused_border_style,
+            rows: vec![],
+            next_row: None,
+            scroll: None,
+            page_size: 1,
         }
     }
 
@@ -32,17 +64,76 @@ impl DisassemblyPane {
             false => BorderType::Plain,
         }
     }
+
+    /// Read the operations of the current block, and follow execution again
+    fn refresh(&mut self, state: &State) {
+        self.rows.clear();
+        self.next_row = None;
+        self.scroll = None;
+        let Some(block) = state.executor.current_block_ops() else {
+            return;
+        };
+
+        let mut current_line = None;
+        for (index, op) in block.ops.iter().enumerate() {
+            let loc = op
+                .asmop
+                .as_ref()
+                .and_then(|asmop| asmop.location())
+                .and_then(|loc| ResolvedLocation::resolve(loc, &*state.source_manager));
+            if let Some(loc) = loc {
+                let line = (loc.source_file.id(), loc.line);
+                if current_line != Some(line) {
+                    current_line = Some(line);
+                    let file = Path::new(loc.source_file.uri().as_str());
+                    let file = file.file_name().map(Path::new).unwrap_or(file);
+                    let text = loc
+                        .source_file
+                        .as_str()
+                        .lines()
+                        .nth(loc.line.saturating_sub(1) as usize)
+                        .unwrap_or_default()
+                        .trim();
+                    self.rows.push(Row::Source {
+                        file: file.display().to_string(),
+                        line: loc.line,
+                        text: text.to_string(),
+                    });
+                }
+            }
+            if block.next == Some(index) {
+                self.next_row = Some(self.rows.len());
+            }
+            self.rows.push(Row::Op {
+                op: op.op.to_string(),
+                cycles: op.cycles,
+            });
+        }
+    }
+
+    /// The first row shown, i.e. that chosen by the user, or one which keeps the operation about
+    /// to execute in the middle of the view
+    fn first_row(&self) -> usize {
+        let last = self.rows.len().saturating_sub(self.page_size);
+        let first = self.scroll.unwrap_or_else(|| {
+            self.next_row.unwrap_or_default().saturating_sub(self.page_size / 2)
+        });
+        first.min(last)
+    }
+
+    fn scroll_by(&mut self, rows: isize) {
+        let last = self.rows.len().saturating_sub(self.page_size);
+        self.scroll = Some(self.first_row().saturating_add_signed(rows).min(last));
+    }
 }
 
 impl Pane for DisassemblyPane {
-    fn height_constraint(&self) -> Constraint {
-        match self.focused {
-            true => Constraint::Max(7),
-            false => Constraint::Max(7),
-        }
+    fn init(&mut self, state: &State) -> Result<(), Report> {
+        self.refresh(state);
+        Ok(())
     }
 
-    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+    fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
         match action {
             Action::Focus => {
                 self.focused = true;
@@ -50,6 +141,11 @@ impl Pane for DisassemblyPane {
             Action::UnFocus => {
                 self.focused = false;
             }
+            Action::Down => self.scroll_by(1),
+            Action::Up => self.scroll_by(-1),
+            Action::PageDown => self.scroll_by(self.page_size as isize),
+            Action::PageUp => self.scroll_by(-(self.page_size as isize)),
+            Action::Update | Action::Reload => self.refresh(state),
             _ => {}
         }
 
@@ -57,57 +153,60 @@ impl Pane for DisassemblyPane {
     }
 
     fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
-        let (current_proc, lines) = match state.executor.callstack.current_frame() {
-            None => {
-                let proc = Line::from("in <unknown>").right_aligned();
-                (
-                    proc,
-                    state
-                        .executor
-                        .recent
-                        .iter()
-                        .map(|op| Line::from(vec![Span::styled(format!(" | {op}"), Color::White)]))
-                        .collect::<Vec<_>>(),
-                )
-            }
-            Some(frame) => {
-                let proc = frame
-                    .procedure("")
-                    .map(|proc| Line::from(format!("in {proc}")))
-                    .unwrap_or_else(|| Line::from("in <unknown>"))
-                    .right_aligned();
-                (
-                    proc,
-                    frame
-                        .recent()
-                        .iter()
-                        .map(|op| {
-                            Line::from(vec![Span::styled(
-                                format!(" | {}", &op.display()),
-                                Color::White,
-                            )])
-                        })
-                        .collect::<Vec<_>>(),
-                )
-            }
-        };
-        let selected_line = lines.len().saturating_sub(1);
+        self.page_size = area.height.saturating_sub(2).max(1) as usize;
 
-        let list = List::new(lines)
-            .block(Block::default().borders(Borders::ALL))
-            .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
-            .highlight_spacing(HighlightSpacing::Always)
-            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
-        let mut list_state = ListState::default().with_selected(Some(selected_line));
+        let width = self
+            .rows
+            .iter()
+            .filter_map(|row| match row {
+                Row::Op { cycles, .. } => Some(cycles.to_string().len() + 1),
+                Row::Source { .. } => None,
+            })
+            .max()
+            .unwrap_or_default();
+        let lines = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.first_row())
+            .take(self.page_size)
+            .map(|(index, row)| match row {
+                Row::Source { file, line, text } => Line::styled(
+                    format!("  ; {file}:{line} {text}"),
+                    Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+                ),
+                Row::Op { op, cycles } if self.next_row == Some(index) => Line::from(vec![
+                    Span::styled(
+                        format!("{} ", symbols::scrollbar::HORIZONTAL.end),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("{:>width$}  ", format!("+{cycles}")), Color::Cyan),
+                    Span::styled(op.as_str(), Style::default().add_modifier(Modifier::BOLD)),
+                ]),
+                Row::Op { op, cycles } => Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(format!("{:>width$}  ", format!("+{cycles}")), Color::Cyan),
+                    Span::styled(op.as_str(), Color::White),
+                ]),
+            })
+            .collect::<Vec<_>>();
 
-        frame.render_stateful_widget(list, area, &mut list_state);
+        let current_proc = match state.executor.callstack.current_frame() {
+            Some(frame) => frame
+                .procedure("")
+                .map(|proc| Line::from(format!("in {proc}")))
+                .unwrap_or_else(|| Line::from("in <unknown>")),
+            None => Line::from("in <unknown>"),
+        };
+
+        frame.render_widget(List::new(lines).block(Block::default().borders(Borders::ALL)), area);
         frame.render_widget(
             Block::default()
-                .title("Disassembly")
+                .title(PaneId::Disassembly.numbered("Disassembly"))
                 .borders(Borders::ALL)
                 .border_style(self.border_style())
                 .border_type(self.border_type())
-                .title_bottom(current_proc)
+                .title_bottom(current_proc.right_aligned())
                 .title(
                     Line::styled(
                         format!(" at cycle {}", state.executor.cycle),
@@ -120,3 +219,96 @@ impl Pane for DisassemblyPane {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        debug::BreakpointType,
+        ui::testing::{main_masm, render_pane},
+    };
+
+    const PROGRAM: &str = "begin\n    push.1 push.2\n    add\n    push.3 mul\n    drop\nend\n";
+
+    fn render(pane: &mut DisassemblyPane, state: &State, height: u16) -> Vec<String> {
+        render_pane(pane, state, 40, height)
+    }
+
+    #[test]
+    fn disasm_pane_render() {
+        let (_dir, path) = main_masm(PROGRAM);
+        let mut state = State::for_source_file(&path);
+        let mut pane = DisassemblyPane::new(false, Style::default());
+        pane.init(&state).unwrap();
+        state.create_breakpoint(BreakpointType::StepN(4));
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        let lines = render(&mut pane, &state, 14);
+
+        // The block begins with the initialization of the frame pointer, which has no source
+        assert_eq!(
+            lines,
+            [
+                "┌[2] Disassembly──────────── at cycle 4┐",
+                "│   +2  push(2147483648)               │",
+                "│   +3  push(4294967294)               │",
+                "│   +4  mstore                         │",
+                "│→  +5  drop                           │",
+                "│  ; main.masm:2 push.1 push.2         │",
+                "│   +6  pad                            │",
+                "│   +7  incr                           │",
+                "│   +8  push(2)                        │",
+                "│  ; main.masm:3 add                   │",
+                "│   +9  add                            │",
+                "│  ; main.masm:4 push.3 mul            │",
+                "│  +10  push(3)                        │",
+                "└──────────────────────────in <unknown>┘",
+            ]
+        );
+
+        // The view follows the next operation when it does not fit
+        assert_eq!(
+            render(&mut pane, &state, 6),
+            [
+                "┌[2] Disassembly──────────── at cycle 4┐",
+                "│   +3  push(4294967294)               │",
+                "│   +4  mstore                         │",
+                "│→  +5  drop                           │",
+                "│  ; main.masm:2 push.1 push.2         │",
+                "└──────────────────────────in <unknown>┘",
+            ]
+        );
+    }
+
+    #[test]
+    fn disasm_pane_scrolling() {
+        let mut state = State::for_source(PROGRAM);
+        let mut pane = DisassemblyPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        assert!(pane.rows.is_empty());
+
+        state.create_breakpoint(BreakpointType::StepN(6));
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        pane.page_size = 4;
+        let ops = pane.rows.len();
+        assert_eq!(pane.next_row, Some(5));
+        assert_eq!(pane.first_row(), 3);
+
+        pane.update(Action::Up, &mut state).unwrap();
+        assert_eq!(pane.first_row(), 2);
+        pane.update(Action::PageUp, &mut state).unwrap();
+        assert_eq!(pane.first_row(), 0);
+        pane.update(Action::PageDown, &mut state).unwrap();
+        pane.update(Action::PageDown, &mut state).unwrap();
+        pane.update(Action::PageDown, &mut state).unwrap();
+        assert_eq!(pane.first_row(), ops - 4);
+
+        // Stepping follows execution again
+        state.create_breakpoint(BreakpointType::Step);
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.next_row, Some(6));
+        assert_eq!(pane.first_row(), 4);
+    }
+}
@@ -0,0 +1,267 @@
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::{
+    debug::ResolvedLocation,
+    ui::{
+        action::Action,
+        count::HumanCount,
+        keybindings::{KeyAction, KeyMode},
+        panes::Pane,
+        state::{InputMode, State},
+        tui::{EventResponse, Frame},
+    },
+};
+
+/// The number of source lines shown either side of the line at which the program failed
+const SNIPPET_CONTEXT: u32 = 2;
+
+/// The number of stack frames shown, innermost first
+const MAX_FRAMES: usize = 5;
+
+/// A popup describing the error with which the program failed: the error itself, the source code
+/// at which it was raised, if known, and the innermost frames of the stack trace
+pub struct ErrorPane {
+    text: Text<'static>,
+    scroll: usize,
+}
+
+impl ErrorPane {
+    /// Describe the error with which the program failed, or `None` if it has not
+    pub fn new(state: &State) -> Option<Self> {
+        Some(Self {
+            text: error_report(state)?,
+            scroll: 0,
+        })
+    }
+}
+
+/// Render the error with which the program failed, if it has, in the manner of a diagnostic
+pub fn error_report(state: &State) -> Option<Text<'static>> {
+    let err = state.execution_failed.as_ref()?;
+    let executor = &state.executor;
+    let source_manager = &*state.source_manager;
+    let error_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let description = format!(
+        "cycle {}: {}",
+        HumanCount(executor.cycle),
+        executor.describe_error(err, source_manager)
+    );
+    let mut lines = description
+        .lines()
+        .map(|line| Line::styled(line.to_string(), error_style))
+        .collect::<Vec<_>>();
+
+    let stacktrace = executor.callstack.stacktrace(&executor.recent, source_manager);
+    if let Some(loc) = stacktrace.current_location() {
+        lines.push(Line::default());
+        lines.extend(snippet(loc));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Stack Trace:", Style::default().add_modifier(Modifier::BOLD)));
+    let frames = executor.callstack.frames();
+    for (depth, frame) in frames.iter().rev().take(MAX_FRAMES).enumerate() {
+        let name = frame.procedure("");
+        let location = match frame.last_resolved(source_manager) {
+            Some(loc) => loc.to_string(),
+            None => "<unavailable>".into(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{depth:>3}: "), dim),
+            Span::styled(name.as_deref().unwrap_or("<unknown>").to_string(), Color::Cyan),
+            Span::styled(format!(" in {location}"), dim),
+        ]));
+    }
+    if frames.len() > MAX_FRAMES {
+        lines.push(Line::styled(
+            format!("     ... {} more frame(s)", frames.len() - MAX_FRAMES),
+            dim,
+        ));
+    }
+
+    if let Some(op) = executor.current_op {
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::styled("Failed Operation: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(op.to_string()),
+        ]));
+    }
+    Some(Text::from(lines))
+}
+
+/// Render the source lines around `loc`, underlining the code it spans, and marking where it starts
+fn snippet(loc: &ResolvedLocation) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let label = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let source = loc.source_file.as_str();
+    let span = loc.span.start().to_usize()..loc.span.end().to_usize();
+    let first = loc.line.saturating_sub(SNIPPET_CONTEXT).max(1);
+    let last = loc.line + SNIPPET_CONTEXT;
+    let width = last.to_string().len();
+
+    let mut lines = vec![Line::styled(format!("{:width$} ┌─ {loc}", ""), dim)];
+    let mut offset = 0;
+    for (number, text) in (1..).zip(source.split_inclusive('\n')) {
+        let start = offset;
+        offset += text.len();
+        if number < first {
+            continue;
+        }
+        if number > last {
+            break;
+        }
+        let text = text.trim_end_matches(['\n', '\r']);
+        let gutter = Span::styled(format!("{number:>width$} │ "), dim);
+        // The part of the span on this line, relative to the start of the line
+        let labeled_start = span.start.clamp(start, start + text.len()) - start;
+        let labeled_end = span.end.clamp(start, start + text.len()) - start;
+        if labeled_start == labeled_end && number != loc.line {
+            lines.push(Line::from(vec![gutter, Span::raw(text.to_string())]));
+            continue;
+        }
+        lines.push(Line::from(vec![
+            gutter,
+            Span::raw(text[..labeled_start].to_string()),
+            Span::styled(
+                text[labeled_start..labeled_end].to_string(),
+                label.add_modifier(Modifier::UNDERLINED),
+            ),
+            Span::raw(text[labeled_end..].to_string()),
+        ]));
+        if number == loc.line {
+            let indent = text[..labeled_start].chars().count();
+            let carets = text[labeled_start..labeled_end].chars().count().max(1);
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:width$} │ ", ""), dim),
+                Span::raw(" ".repeat(indent)),
+                Span::styled("^".repeat(carets), label),
+            ]));
+        }
+    }
+    lines
+}
+
+impl Pane for ErrorPane {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        match state.input_mode {
+            InputMode::Normal => {
+                let bound = state.keybindings.lookup_in(&[KeyMode::Popup, KeyMode::Global], key);
+                let response = match bound {
+                    Some(KeyAction::Down) => EventResponse::Stop(Action::Down),
+                    Some(KeyAction::Up) => EventResponse::Stop(Action::Up),
+                    Some(KeyAction::ClosePopup | KeyAction::Submit) => {
+                        EventResponse::Stop(Action::ClosePopup)
+                    }
+                    _ => {
+                        return Ok(Some(EventResponse::Stop(Action::Noop)));
+                    }
+                };
+                Ok(Some(response))
+            }
+            InputMode::Insert => Ok(Some(EventResponse::Stop(Action::Noop))),
+            InputMode::Command => Ok(Some(EventResponse::Stop(Action::Noop))),
+        }
+    }
+
+    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Down => {
+                self.scroll = (self.scroll + 1).min(self.text.lines.len().saturating_sub(1));
+            }
+            Action::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        frame.render_widget(Clear, area);
+        let keys = |action| state.keybindings.describe(action);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title("Execution Error")
+            .title_bottom(
+                Line::styled(
+                    format!(
+                        "{} to close, {} to reopen",
+                        keys(KeyAction::ClosePopup),
+                        keys(KeyAction::ShowError)
+                    ),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                )
+                .right_aligned(),
+            );
+        let text = Paragraph::new(self.text.clone()).scroll((self.scroll as u16, 0)).block(block);
+        frame.render_widget(text, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::{draw_pane, main_masm, render_pane};
+
+    const PROGRAM: &str = "begin\n    push.1\n    push.2\n    assert_eq\nend\n";
+
+    #[test]
+    fn error_pane_render() {
+        let (_dir, path) = main_masm(PROGRAM);
+        let mut state = State::for_source_file(&path);
+        assert!(ErrorPane::new(&state).is_none());
+        state.run_until_stopped();
+        let mut pane = ErrorPane::new(&state).unwrap();
+
+        let lines = render_pane(&mut pane, &state, 84, 15);
+        // The source file is in a temporary directory, so its path is replaced, keeping the
+        // right-hand border in place
+        let lines = lines
+            .into_iter()
+            .map(|line| {
+                let line = line.replace(path.to_str().unwrap(), "main.masm");
+                let (line, border) = line.split_at(line.len() - '│'.len_utf8());
+                format!("{:<83}{border}", line.trim_end())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "┌Execution Error───────────────────────────────────────────────────────────────────┐",
+                "│cycle 9: assertion failed with error code: 0                                      │",
+                "│                                                                                  │",
+                "│  ┌─ main.masm:4:5                                                                │",
+                "│2 │     push.1                                                                    │",
+                "│3 │     push.2                                                                    │",
+                "│4 │     assert_eq                                                                 │",
+                "│  │     ^^^^^^^^^                                                                 │",
+                "│5 │ end                                                                           │",
+                "│                                                                                  │",
+                "│Stack Trace:                                                                      │",
+                "│  0: $exec::$main in main.masm:4:5                                                │",
+                "│                                                                                  │",
+                "│Failed Operation: assert(0)                                                       │",
+                "└───────────────────────────────────────────────────────────q to close, E to reopen┘",
+            ]
+        );
+
+        // The source code is labeled in red, and the popup can be scrolled
+        let label = draw_pane(&mut pane, &state, 84, 15)[(9, 6)].style();
+        assert_eq!(label.fg, Some(Color::Red));
+        assert!(label.add_modifier.contains(Modifier::UNDERLINED));
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.scroll, 1);
+    }
+}
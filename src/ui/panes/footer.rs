@@ -1,8 +1,8 @@
-use std::{collections::VecDeque, time::Instant};
+use std::time::Instant;
 
 use miden_assembly_syntax::diagnostics::Report;
 use ratatui::{
-    crossterm::event::{Event, KeyCode, KeyEvent},
+    crossterm::event::{Event, KeyEvent},
     prelude::*,
     widgets::Paragraph,
 };
@@ -10,8 +10,11 @@ use tui_input::{Input, backend::crossterm::EventHandler};
 
 use crate::ui::{
     action::Action,
+    commands,
+    completion::{Completer, Completion},
+    keybindings::{KeyAction, KeyMode},
     panes::Pane,
-    state::{InputMode, State},
+    state::{ExecutionStatus, InputMode, State},
     tui::{EventResponse, Frame},
 };
 
@@ -21,14 +24,6 @@ struct TimedStatusLine {
     status_line: String,
 }
 
-struct Config {
-    max_command_history: usize,
-}
-
-static CONFIG: Config = Config {
-    max_command_history: 20,
-};
-
 #[derive(Default)]
 pub struct FooterPane {
     focused: bool,
@@ -36,8 +31,10 @@ pub struct FooterPane {
     command: String,
     status_line: String,
     timed_status_line: Option<TimedStatusLine>,
-    command_history: VecDeque<String>,
     command_history_index: Option<usize>,
+    completer: Completer,
+    /// The completion being cycled through with Tab/Shift-Tab, and the selected candidate
+    completion: Option<(Completion, usize)>,
 }
 
 impl FooterPane {
@@ -48,22 +45,67 @@ impl FooterPane {
         }
     }
 
-    fn get_status_line(&mut self) -> &String {
-        if self
-            .timed_status_line
+    fn timed_status_line_active(&self) -> bool {
+        self.timed_status_line
             .as_ref()
             .is_some_and(|tsl| tsl.created.elapsed().as_secs() < tsl.show_time)
-        {
+    }
+
+    fn get_status_line(&mut self) -> &String {
+        if self.timed_status_line_active() {
             return &self.timed_status_line.as_ref().unwrap().status_line;
         }
         self.timed_status_line = None;
         &self.status_line
     }
+
+    /// Replace the word being completed with the next (or previous) candidate, computing the set
+    /// of candidates first if we are not already completing.
+    fn cycle_completion(&mut self, forward: bool) {
+        let (completion, index) = match self.completion.take() {
+            Some((completion, index)) => {
+                let len = completion.candidates.len();
+                let index = if forward {
+                    (index + 1) % len
+                } else {
+                    (index + len - 1) % len
+                };
+                (completion, index)
+            }
+            None => match self.completer.complete(self.input.value()) {
+                Some(completion) => {
+                    let index = if forward {
+                        0
+                    } else {
+                        completion.candidates.len() - 1
+                    };
+                    (completion, index)
+                }
+                None => return,
+            },
+        };
+        let mut value = self.input.value()[..completion.start].to_string();
+        value.push_str(&completion.candidates[index]);
+        self.input = self.input.clone().with_value(value);
+        self.completion = Some((completion, index));
+    }
+
+    /// Returns the hint for the current input, if the cursor is at the end of the input
+    fn hint(&self) -> Option<String> {
+        if self.command != ":"
+            || self.completion.is_some()
+            || self.input.cursor() != self.input.value().chars().count()
+        {
+            return None;
+        }
+        self.completer.hint(self.input.value())
+    }
 }
 
 impl Pane for FooterPane {
-    fn height_constraint(&self) -> Constraint {
-        Constraint::Max(1)
+    fn init(&mut self, state: &State) -> Result<(), Report> {
+        self.completer = Completer::new(state);
+        Ok(())
     }
 
     fn handle_key_events(
@@ -73,48 +115,73 @@ impl Pane for FooterPane {
     ) -> Result<Option<EventResponse<Action>>, Report> {
         match state.input_mode {
             InputMode::Command => {
+                // Searches are neither completed, nor recorded in the command history
+                let searching = self.command == "/";
+                let action = state.keybindings.lookup(KeyMode::Footer, key);
+                match action {
+                    _ if searching => (),
+                    Some(KeyAction::Complete) => {
+                        self.cycle_completion(true);
+                        return Ok(None);
+                    }
+                    Some(KeyAction::CompletePrev) => {
+                        self.cycle_completion(false);
+                        return Ok(None);
+                    }
+                    // Accept the current hint, if there is one
+                    Some(KeyAction::AcceptHint) => {
+                        if let Some(hint) = self.hint() {
+                            let value = format!("{}{hint}", self.input.value());
+                            self.input = self.input.clone().with_value(value);
+                            return Ok(None);
+                        }
+                    }
+                    _ => self.completion = None,
+                }
                 self.input.handle_event(&Event::Key(key));
-                let response = match key.code {
-                    KeyCode::Enter => {
+                let response = match action {
+                    Some(KeyAction::Execute) => {
                         let command = self.input.to_string();
-                        if !command.is_empty() {
-                            self.command_history.push_front(self.input.to_string());
-                            self.command_history.truncate(CONFIG.max_command_history);
-                            self.command_history_index = None;
+                        // History expansions are recorded once expanded
+                        if !searching
+                            && !command.is_empty()
+                            && !commands::is_history_expansion(&command)
+                        {
+                            state.history.push(command.clone());
                         }
+                        self.command_history_index = None;
                         Some(EventResponse::Stop(Action::FooterResult(
                             self.command.clone(),
                             Some(command),
                         )))
                     }
-                    KeyCode::Esc => {
+                    Some(KeyAction::Cancel) => {
                         self.command_history_index = None;
                         Some(EventResponse::Stop(Action::FooterResult(self.command.clone(), None)))
                     }
-                    KeyCode::Up if !self.command_history.is_empty() => {
+                    // Matches are highlighted as the search is typed
+                    _ if searching => {
+                        Some(EventResponse::Stop(Action::Search(self.input.value().to_string())))
+                    }
+                    Some(KeyAction::HistoryPrev) if !state.history.is_empty() => {
                         let history_index = self
                             .command_history_index
-                            .map(|idx| idx.saturating_add(1) % self.command_history.len())
+                            .map(|idx| idx.saturating_add(1) % state.history.len())
                             .unwrap_or(0);
-                        self.input = self
-                            .input
-                            .clone()
-                            .with_value(self.command_history[history_index].clone());
+                        let entry = state.history.get_recent(history_index).unwrap_or_default();
+                        self.input = self.input.clone().with_value(entry.to_string());
                         self.command_history_index = Some(history_index);
                         None
                     }
-                    KeyCode::Down if !self.command_history.is_empty() => {
+                    Some(KeyAction::HistoryNext) if !state.history.is_empty() => {
                         let history_index = self
                             .command_history_index
                             .map(|idx| {
-                                idx.saturating_add(self.command_history.len() - 1)
-                                    % self.command_history.len()
+                                idx.saturating_add(state.history.len() - 1) % state.history.len()
                             })
-                            .unwrap_or(self.command_history.len() - 1);
-                        self.input = self
-                            .input
-                            .clone()
-                            .with_value(self.command_history[history_index].clone());
+                            .unwrap_or(state.history.len() - 1);
+                        let entry = state.history.get_recent(history_index).unwrap_or_default();
+                        self.input = self.input.clone().with_value(entry.to_string());
                         self.command_history_index = Some(history_index);
                         None
                     }
@@ -130,6 +197,7 @@ impl Pane for FooterPane {
         match action {
             Action::FocusFooter(cmd, args) => {
                 self.focused = true;
+                self.completer.set_aliases(state);
                 state.input_mode = InputMode::Command;
                 if let Some(args) = args {
                     self.input = self.input.clone().with_value(args);
@@ -137,8 +205,13 @@ impl Pane for FooterPane {
                     self.input = self.input.clone().with_value("".into());
                 }
                 self.command = cmd;
+                self.completion = None;
                 Ok(Some(Action::Update))
             }
+            Action::Reload => {
+                self.completer = Completer::new(state);
+                Ok(None)
+            }
             Action::FooterResult(..) => {
                 state.input_mode = InputMode::Normal;
                 self.focused = false;
@@ -167,9 +240,11 @@ impl Pane for FooterPane {
 
             let width = area.width.max(3);
             let scroll = self.input.visual_scroll(width as usize - self.command.len());
+            let hint = self.hint().unwrap_or_default();
             let input = Paragraph::new(Line::from(vec![
                 Span::styled(&self.command, Style::default().fg(Color::LightBlue)),
                 Span::styled(self.input.value(), Style::default()),
+                Span::styled(hint, Style::default().fg(Color::DarkGray)),
             ]))
             .scroll((0, scroll as u16));
             frame.render_widget(input, area);
@@ -181,11 +256,50 @@ impl Pane for FooterPane {
                 area.y + 1,
             ));
         } else {
-            frame.render_widget(
-                Line::from(vec![Span::styled(self.get_status_line(), Style::default())])
+            let status = state.status();
+            let (badge, badge_color) = match status {
+                ExecutionStatus::Running => ("RUNNING", Color::LightYellow),
+                ExecutionStatus::Paused => ("PAUSED", Color::LightBlue),
+                ExecutionStatus::Halted => ("HALTED", Color::LightGreen),
+                ExecutionStatus::Failed => ("ERROR", Color::LightRed),
+            };
+            let mode = match state.input_mode {
+                InputMode::Normal => "[N]",
+                InputMode::Insert => "[I]",
+                InputMode::Command => "[C]",
+            };
+            let indicators = Line::from(vec![
+                Span::styled(format!("[{badge}]"), Style::default().fg(badge_color)),
+                Span::from(" "),
+                Span::from(mode),
+            ])
+            .right_aligned();
+            let available = (area.width as usize).saturating_sub(indicators.width() + 1);
+
+            // Timed status lines are transient, and are shown as-is, otherwise the status line
+            // reflects whether the program has halted, or failed
+            let timed = self.timed_status_line_active();
+            let status_line = self.get_status_line();
+            let line = match status {
+                ExecutionStatus::Failed if !timed => {
+                    const HINT: &str = " (:error for details)";
+                    let width = available.saturating_sub(HINT.len());
+                    Line::from(vec![
+                        Span::styled(
+                            truncate(status_line, width),
+                            Style::default().fg(Color::LightRed),
+                        ),
+                        Span::styled(HINT, Style::default().fg(Color::DarkGray)),
+                    ])
+                }
+                ExecutionStatus::Halted if !timed => Line::from(truncate(status_line, available))
+                    .style(Style::default().fg(Color::LightGreen)),
+                _ => Line::from(truncate(status_line, available))
                     .style(Style::default().fg(Color::DarkGray)),
-                area,
-            );
+            };
+            frame.render_widget(line, area);
+            frame.render_widget(indicators, area);
+            return Ok(());
         }
         frame.render_widget(
             Line::from(vec![match state.input_mode {
@@ -200,3 +314,28 @@ impl Pane for FooterPane {
         Ok(())
     }
 }
+
+/// Returns the first line of `text`, truncated to at most `width` characters
+fn truncate(text: &str, width: usize) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    let multiline = text.trim_end().contains('\n');
+    if line.chars().count() <= width && !multiline {
+        return line.to_string();
+    }
+    let mut truncated = line.chars().take(width.saturating_sub(1)).collect::<String>();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_status_line() {
+        assert_eq!(truncate("stopped at cycle 6", 20), "stopped at cycle 6");
+        assert_eq!(truncate("stopped at cycle 6", 8), "stopped…");
+        assert_eq!(truncate("assertion failed\n  at foo.masm:1", 80), "assertion failed…");
+        assert_eq!(truncate("", 0), "");
+    }
+}
@@ -13,10 +13,6 @@ impl HeaderPane {
 }
 
 impl Pane for HeaderPane {
-    fn height_constraint(&self) -> Constraint {
-        Constraint::Max(1)
-    }
-
     fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, _state: &State) -> Result<(), Report> {
         frame.render_widget(
             Line::from(vec![
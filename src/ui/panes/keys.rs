@@ -0,0 +1,217 @@
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::ui::{
+    action::Action,
+    keybindings::{KeyAction, KeyBindings, KeyCategory, KeyChord, KeyMode},
+    panes::Pane,
+    state::State,
+    tui::{EventResponse, Frame},
+};
+
+/// A popup listing the key bindings by category, built from the key bindings in effect, so that
+/// it reflects those loaded from the key bindings file
+pub struct KeyHelpPane {
+    lines: Vec<Line<'static>>,
+    scroll: usize,
+    /// The number of lines shown as of the last draw, by which the view is paged
+    page_size: usize,
+}
+
+impl KeyHelpPane {
+    pub fn new(keybindings: &KeyBindings) -> Self {
+        Self {
+            lines: help_lines(keybindings),
+            scroll: 0,
+            page_size: 1,
+        }
+    }
+
+    fn scroll_by(&mut self, lines: isize) {
+        let last = self.lines.len().saturating_sub(self.page_size);
+        self.scroll = self.scroll.saturating_add_signed(lines).min(last);
+    }
+}
+
+/// The bound actions, each with the chords bound to it, grouped by category, and in the order in
+/// which they are first bound within each category
+fn help_rows(keybindings: &KeyBindings) -> Vec<(KeyCategory, KeyAction, Vec<KeyChord>)> {
+    let mut rows = Vec::<(KeyCategory, KeyAction, Vec<KeyChord>)>::new();
+    for (chord, action) in KeyMode::ALL.into_iter().flat_map(|mode| keybindings.iter(mode)) {
+        match rows.iter_mut().find(|(_, bound, _)| *bound == action) {
+            Some((.., chords)) => chords.push(chord),
+            None => rows.push((action.category(), action, vec![chord])),
+        }
+    }
+    rows.sort_by_key(|(category, ..)| KeyCategory::ALL.iter().position(|c| c == category));
+    rows
+}
+
+fn help_lines(keybindings: &KeyBindings) -> Vec<Line<'static>> {
+    let rows = help_rows(keybindings)
+        .into_iter()
+        .map(|(category, action, chords)| {
+            let keys = chords.iter().map(|chord| chord.to_string()).collect::<Vec<_>>().join(", ");
+            let summary = match action.mode() {
+                KeyMode::Global => action.summary(),
+                KeyMode::Source => format!("{} (source pane)", action.summary()),
+                KeyMode::Timeline => format!("{} (timeline pane)", action.summary()),
+                KeyMode::Popup => format!("{} (popups)", action.summary()),
+                KeyMode::Footer => format!("{} (command line)", action.summary()),
+            };
+            (category, keys, summary)
+        })
+        .collect::<Vec<_>>();
+    let width = rows.iter().map(|(_, keys, _)| keys.chars().count()).max().unwrap_or(0);
+
+    let heading = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![];
+    let mut current = None;
+    for (category, keys, summary) in rows {
+        if current != Some(category) {
+            if current.is_some() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(category.to_string(), heading));
+            current = Some(category);
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {keys:<width$}  "), Color::Cyan),
+            Span::raw(summary),
+        ]));
+    }
+    lines
+}
+
+impl Pane for KeyHelpPane {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        // The popup is also closed by the keys which opened it
+        let response = match state.keybindings.lookup_in(&[KeyMode::Popup, KeyMode::Global], key) {
+            Some(KeyAction::ClosePopup | KeyAction::Help) => {
+                EventResponse::Stop(Action::ClosePopup)
+            }
+            Some(KeyAction::Down) => EventResponse::Stop(Action::Down),
+            Some(KeyAction::Up) => EventResponse::Stop(Action::Up),
+            Some(KeyAction::PageDown) => EventResponse::Stop(Action::PageDown),
+            Some(KeyAction::PageUp) => EventResponse::Stop(Action::PageUp),
+            _ => EventResponse::Stop(Action::Noop),
+        };
+        Ok(Some(response))
+    }
+
+    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Down => self.scroll_by(1),
+            Action::Up => self.scroll_by(-1),
+            Action::PageDown => self.scroll_by(self.page_size as isize),
+            Action::PageUp => self.scroll_by(-(self.page_size as isize)),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        frame.render_widget(Clear, area);
+        self.page_size = area.height.saturating_sub(2).max(1) as usize;
+        self.scroll_by(0);
+        let keys = |action| state.keybindings.describe(action);
+        let mut status = format!("{} to close", keys(KeyAction::ClosePopup));
+        if self.lines.len() > self.page_size {
+            status.insert_str(
+                0,
+                &format!("{}/{} to scroll, ", keys(KeyAction::Down), keys(KeyAction::Up)),
+            );
+        }
+        let block = Block::default().borders(Borders::ALL).title("Key Bindings").title_bottom(
+            Line::styled(status, Style::default().add_modifier(Modifier::ITALIC)).right_aligned(),
+        );
+        let text = Paragraph::new(self.lines.clone()).scroll((self.scroll as u16, 0)).block(block);
+        frame.render_widget(text, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::ui::testing::render_pane;
+
+    #[test]
+    fn key_help_render() {
+        let mut state = State::for_source("begin push.1 drop end");
+        let mut pane = KeyHelpPane::new(&KeyBindings::default());
+        assert_eq!(
+            render_pane(&mut pane, &state, 60, 10),
+            [
+                "┌Key Bindings──────────────────────────────────────────────┐",
+                "│Execution                                                 │",
+                "│  ctrl-c           interrupt the program while it is runni│",
+                "│  e                run until the current procedure returns│",
+                "│  s                step to the next cycle                 │",
+                "│  n                step to the next instruction           │",
+                "│  c                run until a breakpoint is hit          │",
+                "│                                                          │",
+                "│Navigation                                                │",
+                "└─────────────────────────────────j/k to scroll, q to close┘",
+            ]
+        );
+
+        // The popup scrolls, but not past its last line, and other keys are swallowed
+        let key = |pane: &mut KeyHelpPane, state: &mut State, code| {
+            let event = KeyEvent::new(code, KeyModifiers::NONE);
+            match pane.handle_key_events(event, state).unwrap() {
+                Some(EventResponse::Stop(action)) => {
+                    pane.update(action.clone(), state).unwrap();
+                    action
+                }
+                _ => panic!("expected the popup to handle {code:?}"),
+            }
+        };
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Char('j')), Action::Down);
+        assert_eq!(pane.scroll, 1);
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Char('x')), Action::Noop);
+        for _ in 0..pane.lines.len() {
+            key(&mut pane, &mut state, KeyCode::PageDown);
+        }
+        assert_eq!(pane.scroll, pane.lines.len() - 8);
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Char('?')), Action::ClosePopup);
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Esc), Action::ClosePopup);
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Char('q')), Action::ClosePopup);
+    }
+
+    #[test]
+    fn key_help_lists_every_binding() {
+        let mut keybindings = KeyBindings::default();
+        keybindings.bind(KeyMode::Global, "f5".parse().unwrap(), KeyAction::Continue);
+        let text = KeyHelpPane::new(&keybindings)
+            .lines
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect::<String>())
+            .collect::<Vec<_>>();
+        for mode in KeyMode::ALL {
+            for (chord, action) in keybindings.iter(mode) {
+                let row = text
+                    .iter()
+                    .find(|line| line.contains(&action.summary()))
+                    .unwrap_or_else(|| panic!("{action} is not listed"));
+                assert!(
+                    row.split("  ")
+                        .any(|keys| keys.split(", ").any(|key| key == chord.to_string())),
+                    "{chord} is not listed for {action}: {row}"
+                );
+            }
+        }
+        for category in KeyCategory::ALL {
+            assert!(text.contains(&category.to_string()));
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crossterm::event::KeyCode;
+use log::LevelFilter;
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::{
+    logger::{DebugLogger, next_level_filter},
+    ui::{
+        action::Action,
+        keybindings::{KeyAction, KeyMode},
+        panes::Pane,
+        state::{InputMode, State},
+        tui::{EventResponse, Frame},
+    },
+};
+
+/// A popup tailing the log records captured by the [DebugLogger]
+///
+/// The pane follows the newest record until the user scrolls, after which the selected record
+/// stays put until the user returns to following the log with `G`.
+pub struct LogPane {
+    logger: &'static DebugLogger,
+    /// The least severe level of the records shown
+    filter: LevelFilter,
+    /// The index of the selected record among those shown, or `None` to follow the newest
+    selected_entry: Option<usize>,
+    /// The number of records shown as of the last draw, by which the view is paged
+    page_size: usize,
+}
+impl Default for LogPane {
+    fn default() -> Self {
+        Self::new(DebugLogger::get())
+    }
+}
+
+impl LogPane {
+    pub fn new(logger: &'static DebugLogger) -> Self {
+        Self {
+            logger,
+            filter: LevelFilter::Trace,
+            selected_entry: None,
+            page_size: 1,
+        }
+    }
+
+    fn level_color(level: log::Level) -> Color {
+        use log::Level;
+        match level {
+            Level::Trace => Color::LightCyan,
+            Level::Debug => Color::LightMagenta,
+            Level::Info => Color::LightGreen,
+            Level::Warn => Color::LightYellow,
+            Level::Error => Color::LightRed,
+        }
+    }
+
+    /// The number of records which pass the current filter
+    fn len(&self) -> usize {
+        self.logger.with_captured(|captured| captured.filtered(self.filter).count())
+    }
+
+    /// The index of the selected record, i.e. the newest one when following the log
+    fn selected(&self, len: usize) -> Option<usize> {
+        let last = len.checked_sub(1)?;
+        Some(self.selected_entry.unwrap_or(last).min(last))
+    }
+
+    /// Move the selection by `entries`, which stops following the log, unless the selection moves
+    /// past the newest record
+    fn scroll_by(&mut self, entries: isize) {
+        let len = self.len();
+        let Some(selected) = self.selected(len) else {
+            return;
+        };
+        let selected = selected.saturating_add_signed(entries);
+        self.selected_entry = (selected < len).then_some(selected);
+    }
+}
+
+impl Pane for LogPane {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        match state.input_mode {
+            InputMode::Normal => {
+                // The popup is also closed by the keys which opened it
+                let bound = state.keybindings.lookup_in(&[KeyMode::Popup, KeyMode::Global], key);
+                let response = match bound {
+                    Some(KeyAction::Down) => EventResponse::Stop(Action::Down),
+                    Some(KeyAction::Up) => EventResponse::Stop(Action::Up),
+                    Some(KeyAction::PageDown) => EventResponse::Stop(Action::PageDown),
+                    Some(KeyAction::PageUp) => EventResponse::Stop(Action::PageUp),
+                    Some(KeyAction::ClosePopup | KeyAction::ShowLog) => {
+                        EventResponse::Stop(Action::ClosePopup)
+                    }
+                    _ => match key.code {
+                        KeyCode::Char('f') | KeyCode::Char('F') => {
+                            self.filter = next_level_filter(self.filter);
+                            self.selected_entry = None;
+                            EventResponse::Stop(Action::Noop)
+                        }
+                        KeyCode::End | KeyCode::Char('G') => {
+                            self.selected_entry = None;
+                            EventResponse::Stop(Action::Noop)
+                        }
+                        _ => {
+                            return Ok(Some(EventResponse::Stop(Action::Noop)));
+                        }
+                    },
+                };
+                Ok(Some(response))
+            }
+            InputMode::Insert => Ok(Some(EventResponse::Stop(Action::Noop))),
+            InputMode::Command => Ok(Some(EventResponse::Stop(Action::Noop))),
+        }
+    }
+
+    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Down => self.scroll_by(1),
+            Action::Up => self.scroll_by(-1),
+            Action::PageDown => self.scroll_by(self.page_size as isize),
+            Action::PageUp => self.scroll_by(-(self.page_size as isize)),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        frame.render_widget(Clear, area);
+        self.page_size = area.height.saturating_sub(2).max(1) as usize;
+        let (items, evicted) = self.logger.with_captured(|captured| {
+            let items = captured
+                .filtered(self.filter)
+                .map(|entry| {
+                    let color = Self::level_color(entry.level);
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:>8.3}s ", entry.timestamp.as_secs_f64()),
+                            Color::DarkGray,
+                        ),
+                        Span::styled(format!("{:5} ", entry.level), color),
+                        Span::styled(format!("{}: ", entry.target), Color::Gray),
+                        Span::styled(entry.message.clone(), color),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            (items, captured.evicted())
+        });
+        let selected = self.selected(items.len());
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL))
+            .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+        let mut list_state = ListState::default().with_selected(selected);
+
+        let mode = match self.selected_entry {
+            None => "following",
+            Some(_) => "scrolled, G to follow",
+        };
+        let mut status = format!(" showing {} and above, {mode}", self.filter);
+        if evicted > 0 {
+            status.push_str(&format!(", {evicted} older discarded"));
+        }
+        frame.render_stateful_widget(list, area, &mut list_state);
+        frame.render_widget(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Log")
+                .title(
+                    Line::styled(status, Style::default().add_modifier(Modifier::ITALIC))
+                        .right_aligned(),
+                )
+                .title_bottom(
+                    Line::styled(
+                        format!(
+                            "f to filter, {} to close",
+                            state.keybindings.describe(KeyAction::ClosePopup)
+                        ),
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    )
+                    .right_aligned(),
+                )
+                .style(Style::default()),
+            area,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::{Level, Log, Record};
+    use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn log(logger: &DebugLogger, level: Level, message: &str) {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target("miden_debug::test")
+                .args(format_args!("{message}"))
+                .build(),
+        );
+    }
+
+    fn key(state: &mut State, pane: &mut LogPane, code: KeyCode) {
+        let event = KeyEvent::new(code, KeyModifiers::NONE);
+        if let Some(EventResponse::Stop(action)) = pane.handle_key_events(event, state).unwrap() {
+            pane.update(action, state).unwrap();
+        }
+    }
+
+    #[test]
+    fn log_pane_follows_and_scrolls() {
+        let logger = Box::leak(Box::new(DebugLogger::default()));
+        let mut state = State::for_source("begin push.1 drop end");
+        let mut pane = LogPane::new(logger);
+        for i in 0..3 {
+            log(logger, Level::Debug, &format!("debug {i}"));
+            log(logger, Level::Warn, &format!("warn {i}"));
+        }
+        assert_eq!(pane.selected(pane.len()), Some(5));
+
+        // Scrolling stops following the log, so new records do not move the selection
+        key(&mut state, &mut pane, KeyCode::Char('k'));
+        key(&mut state, &mut pane, KeyCode::Char('k'));
+        assert_eq!(pane.selected_entry, Some(3));
+        log(logger, Level::Warn, "warn 3");
+        assert_eq!(pane.selected(pane.len()), Some(3));
+        key(&mut state, &mut pane, KeyCode::Char('G'));
+        assert_eq!(pane.selected(pane.len()), Some(6));
+
+        // Moving past the newest record follows the log again
+        key(&mut state, &mut pane, KeyCode::Char('k'));
+        key(&mut state, &mut pane, KeyCode::Char('j'));
+        key(&mut state, &mut pane, KeyCode::Char('j'));
+        assert_eq!(pane.selected_entry, None);
+
+        // Filtering hides the less severe records
+        key(&mut state, &mut pane, KeyCode::Char('f'));
+        assert_eq!(pane.filter, LevelFilter::Debug);
+        assert_eq!(pane.len(), 7);
+        key(&mut state, &mut pane, KeyCode::Char('f'));
+        key(&mut state, &mut pane, KeyCode::Char('f'));
+        assert_eq!(pane.filter, LevelFilter::Warn);
+        assert_eq!(pane.len(), 4);
+    }
+}
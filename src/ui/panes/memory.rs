@@ -0,0 +1,341 @@
+use std::collections::BTreeSet;
+
+use miden_assembly_syntax::diagnostics::Report;
+use miden_processor::{ContextId, Felt};
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::{
+    debug::FormatType,
+    ui::{action::Action, clipboard, layout::PaneId, panes::Pane, state::State, tui::Frame},
+};
+
+/// The number of words read on each update, i.e. the most rows that can be shown at once
+const WINDOW: u32 = 64;
+
+/// How the elements of each word in the [MemoryPane] are interpreted
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryView {
+    /// Field elements, shown in decimal or hexadecimal per [State::display_format]
+    #[default]
+    Felt,
+    /// 32-bit unsigned integers, elements which are not valid `u32` values are shown as `-`
+    U32,
+    /// The four bytes of each element, in big-endian order
+    Bytes,
+}
+
+impl MemoryView {
+    fn next(self) -> Self {
+        match self {
+            Self::Felt => Self::U32,
+            Self::U32 => Self::Bytes,
+            Self::Bytes => Self::Felt,
+        }
+    }
+}
+
+impl core::fmt::Display for MemoryView {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Felt => f.write_str("felt"),
+            Self::U32 => f.write_str("u32"),
+            Self::Bytes => f.write_str("bytes"),
+        }
+    }
+}
+
+/// A scrollable view of the memory of the current context, one word per row
+pub struct MemoryPane {
+    focused: bool,
+    focused_border_style: Style,
+    /// The element address of the first word shown, always word-aligned
+    addr: u32,
+    view: MemoryView,
+    /// The number of rows shown as of the last draw, by which the view is paged
+    page_size: u32,
+    /// The words from `addr` onwards, as of the last update
+    words: Vec<[Felt; 4]>,
+    /// The addresses of the words of the current context written during the most recent run
+    changed: BTreeSet<u32>,
+    /// The context and cycle at which `words` were read
+    context: Option<ContextId>,
+    cycle: usize,
+}
+
+impl MemoryPane {
+    pub fn new(focused: bool, focused_border_style: Style) -> Self {
+        Self {
+            focused,
+            focused_border_style,
+            addr: 0,
+            view: MemoryView::default(),
+            page_size: 1,
+            words: vec![],
+            changed: BTreeSet::new(),
+            context: None,
+            cycle: 0,
+        }
+    }
+
+    fn border_style(&self) -> Style {
+        match self.focused {
+            true => self.focused_border_style,
+            false => Style::default(),
+        }
+    }
+
+    fn border_type(&self) -> BorderType {
+        match self.focused {
+            true => BorderType::Thick,
+            false => BorderType::Plain,
+        }
+    }
+
+    /// Show the words from `addr` onwards, rounded down to a word boundary
+    fn scroll_to(&mut self, addr: u32, state: &State) {
+        self.addr = addr & !3;
+        self.refresh(state);
+    }
+
+    /// Scroll by `rows` words, staying within the bounds of memory
+    fn scroll_by(&mut self, rows: i64, state: &State) {
+        let last = (u32::MAX & !3) as i64;
+        let addr = (self.addr as i64 + rows * 4).clamp(0, last);
+        self.scroll_to(addr as u32, state);
+    }
+
+    /// Read the words shown from the current context, as of the current cycle.
+    ///
+    /// The words written during the most recent run are highlighted, see [State::changes].
+    fn refresh(&mut self, state: &State) {
+        let context = state.executor.current_context;
+        let words = (0..WINDOW)
+            .map_while(|row| self.addr.checked_add(row * 4))
+            .map(|addr| {
+                core::array::from_fn(|i| {
+                    state
+                        .executor
+                        .read_memory_element_in_context(addr + i as u32, context)
+                        .unwrap_or(Felt::ZERO)
+                })
+            })
+            .collect::<Vec<[Felt; 4]>>();
+
+        self.changed = state
+            .changes
+            .memory
+            .iter()
+            .filter(|(ctx, _)| *ctx == context)
+            .map(|(_, addr)| *addr)
+            .collect();
+        self.words = words;
+        self.context = Some(context);
+        self.cycle = state.executor.cycle;
+    }
+
+    /// Render `word` per the current view
+    fn format_word(&self, word: &[Felt; 4], state: &State) -> Vec<String> {
+        word.iter()
+            .map(|felt| match self.view {
+                MemoryView::Felt => state.format_felt(*felt),
+                MemoryView::U32 => match u32::try_from(felt.as_canonical_u64()) {
+                    Ok(value) => match state.display_format {
+                        FormatType::Decimal => value.to_string(),
+                        _ => format!("{value:#x}"),
+                    },
+                    Err(_) => "-".to_string(),
+                },
+                MemoryView::Bytes => (felt.as_canonical_u64() as u32)
+                    .to_be_bytes()
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            })
+            .collect()
+    }
+}
+
+impl Pane for MemoryPane {
+    fn init(&mut self, state: &State) -> Result<(), Report> {
+        self.words.clear();
+        self.refresh(state);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Focus => {
+                self.focused = true;
+            }
+            Action::UnFocus => {
+                self.focused = false;
+            }
+            Action::Down => self.scroll_by(1, state),
+            Action::Up => self.scroll_by(-1, state),
+            Action::PageDown => self.scroll_by(self.page_size as i64, state),
+            Action::PageUp => self.scroll_by(-(self.page_size as i64), state),
+            Action::CycleMemoryView => {
+                self.view = self.view.next();
+                return Ok(Some(Action::TimedStatusLine(
+                    format!("memory is shown as {}", self.view),
+                    3,
+                )));
+            }
+            Action::ShowMemory(addr) => self.scroll_to(addr, state),
+            // The word at the top of the view is copied
+            Action::Yank => {
+                if let Some(word) = self.words.first() {
+                    let text = format!("[{}]", self.format_word(word, state).join(", "));
+                    let what = format!("the word at {:#010x}", self.addr);
+                    return Ok(Some(clipboard::yank(state, &what, text)));
+                }
+            }
+            Action::Update => self.refresh(state),
+            Action::Reload => self.init(state)?,
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        self.page_size = area.height.saturating_sub(2).max(1) as u32;
+
+        let rows = self
+            .words
+            .iter()
+            .take(self.page_size as usize)
+            .map(|word| self.format_word(word, state))
+            .collect::<Vec<_>>();
+        let width = rows.iter().flatten().map(|value| value.len()).max().unwrap_or_default();
+        let lines = rows
+            .into_iter()
+            .enumerate()
+            .map(|(row, values)| {
+                let addr = self.addr + row as u32 * 4;
+                let style = match self.changed.contains(&addr) {
+                    true => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    false => state.theme.styles.value,
+                };
+                let values = values
+                    .iter()
+                    .map(|value| format!("{value:>width$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                Line::from(vec![
+                    Span::styled(format!(" {addr:#010x} "), Color::Cyan),
+                    Span::styled(values, style),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        frame.render_widget(List::new(lines).block(Block::default().borders(Borders::ALL)), area);
+        let context = self.context.map(u32::from).unwrap_or_default();
+        frame.render_widget(
+            Block::default()
+                .title(PaneId::Memory.numbered("Memory"))
+                .borders(Borders::ALL)
+                .border_style(self.border_style())
+                .border_type(self.border_type())
+                .title(
+                    Line::styled(
+                        format!(" context {context} at cycle {}", self.cycle),
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    )
+                    .right_aligned(),
+                )
+                .title_bottom(
+                    Line::styled(
+                        format!("shown as {}", self.view),
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    )
+                    .right_aligned(),
+                ),
+            area,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::render_pane;
+
+    const PROGRAM: &str = "
+begin
+    push.1 mem_store.0
+    push.2 mem_store.5
+end
+";
+
+    #[test]
+    fn memory_pane_scrolling() {
+        let mut state = State::for_source(PROGRAM);
+        let mut pane = MemoryPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        pane.page_size = 3;
+
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.addr, 4);
+        pane.update(Action::Up, &mut state).unwrap();
+        pane.update(Action::Up, &mut state).unwrap();
+        assert_eq!(pane.addr, 0);
+        pane.update(Action::PageDown, &mut state).unwrap();
+        assert_eq!(pane.addr, 12);
+        pane.update(Action::PageUp, &mut state).unwrap();
+        assert_eq!(pane.addr, 0);
+
+        pane.update(Action::ShowMemory(0x103), &mut state).unwrap();
+        assert_eq!(pane.addr, 0x100);
+        pane.update(Action::ShowMemory(u32::MAX), &mut state).unwrap();
+        pane.update(Action::PageDown, &mut state).unwrap();
+        assert_eq!(pane.addr, u32::MAX - 3);
+        assert_eq!(pane.words.len(), 1);
+
+        pane.update(Action::CycleMemoryView, &mut state).unwrap();
+        assert_eq!(pane.view, MemoryView::U32);
+        pane.update(Action::CycleMemoryView, &mut state).unwrap();
+        pane.update(Action::CycleMemoryView, &mut state).unwrap();
+        assert_eq!(pane.view, MemoryView::Felt);
+    }
+
+    #[test]
+    fn memory_pane_highlights_changed_words() {
+        let mut state = State::for_source(PROGRAM);
+        let mut pane = MemoryPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        assert!(pane.changed.is_empty());
+
+        // The frame pointer is written at the top of memory as the program starts
+        let written = BTreeSet::from([0, 4, u32::MAX - 3]);
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.changed, written);
+        assert_eq!(pane.words[1], [Felt::ZERO, Felt::new(2), Felt::ZERO, Felt::ZERO]);
+
+        // Scrolling does not change which words are highlighted
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.changed, written);
+    }
+
+    #[test]
+    fn memory_pane_render() {
+        let mut state = State::for_source(PROGRAM);
+        state.run_until_stopped();
+        let mut pane = MemoryPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        pane.view = MemoryView::Bytes;
+
+        let lines = render_pane(&mut pane, &state, 64, 4);
+        assert!(lines[0].starts_with("┏[4] Memory"), "unexpected output: {lines:#?}");
+        assert!(lines[0].contains("context 0 at cycle"), "unexpected output: {lines:#?}");
+        assert_eq!(lines[1], "┃ 0x00000000 00 00 00 01  00 00 00 00  00 00 00 00  00 00 00 00┃");
+        assert!(lines[3].contains("shown as bytes"), "unexpected output: {lines:#?}");
+        assert_eq!(pane.page_size, 2);
+    }
+}
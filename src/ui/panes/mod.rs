@@ -1,7 +1,7 @@
 use miden_assembly_syntax::diagnostics::Report;
 use ratatui::{
     crossterm::event::{KeyEvent, MouseEvent},
-    layout::{Constraint, Rect},
+    layout::Rect,
 };
 
 use super::{
@@ -10,22 +10,29 @@ use super::{
     tui::{Event, EventResponse, Frame},
 };
 
+pub mod advice;
 pub mod breakpoints;
-pub mod debug;
 pub mod disasm;
+pub mod error;
 pub mod footer;
 pub mod header;
+pub mod keys;
+pub mod log;
+pub mod memory;
 pub mod source_code;
 pub mod stack;
 pub mod stacktrace;
+pub mod status_bar;
+pub mod symbols;
+pub mod text;
+pub mod timeline;
+pub mod watch;
 
 pub trait Pane {
     fn init(&mut self, _state: &State) -> Result<(), Report> {
         Ok(())
     }
 
-    fn height_constraint(&self) -> Constraint;
-
     fn handle_events(
         &mut self,
         event: Event,
@@ -1,22 +1,33 @@
-use std::{collections::BTreeMap, ops::Deref, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    ops::{Deref, Range},
+    sync::Arc,
+};
 
 use miden_assembly_syntax::{
     debuginfo::{SourceFile, SourceId, SourceSpan},
     diagnostics::{Report, SourceCode},
 };
 use ratatui::{
+    crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
     widgets::{block::*, *},
 };
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    debug::ResolvedLocation,
+    debug::{Breakpoint, BreakpointType, ResolvedLocation, char_range},
     ui::{
         action::Action,
+        clipboard,
+        keybindings::{KeyAction, KeyMode},
+        layout::PaneId,
         panes::Pane,
         state::State,
         syntax_highlighting::{Highlighter, HighlighterState, NoopHighlighter, SyntectHighlighter},
-        tui::Frame,
+        tui::{EventResponse, Frame},
     },
 };
 
@@ -29,88 +40,261 @@ pub struct SourceCodePane {
     num_lines: u32,
     selected_line: u32,
     syntax_highlighter: Box<dyn Highlighter>,
-    syntax_highlighting_states: BTreeMap<SourceId, Box<dyn HighlighterState>>,
+    /// The lines highlighted so far of each file shown, keyed by [cache_key], so that they survive
+    /// reloading the program, as long as the file is unchanged
+    highlight_cache: BTreeMap<u64, HighlightedLines>,
+    /// The highlighted lines of the current file covered by the current location of the program
+    span_lines: Option<SpanLines>,
     current_file: Option<HighlightedFile>,
+    /// The lines of the current file on which breakpoints are set, and whether any of those set on
+    /// each line are enabled
+    breakpoint_markers: BTreeMap<u32, bool>,
+    /// The search within the current file, if any
+    search: Option<Search>,
+    /// Whether the selected line follows the program as it executes, rather than only the marker
+    /// of the line on which it is stopped
+    following: bool,
+    /// The line last selected in each of the files which were shown before, and so the position
+    /// to which the view of each is scrolled when it is shown again
+    scroll_positions: BTreeMap<SourceId, u32>,
+    /// The indices of the lines of the current file shown as of the last draw, from the top of the
+    /// pane, and the width of the pane, by which mouse clicks are resolved to lines
+    lines_in_view: Range<usize>,
+    view_width: u16,
     theme: Theme,
 }
 
+/// The number of lines following those in view which are highlighted along with them, so that
+/// scrolling down does not highlight a few lines at a time
+const HIGHLIGHT_AHEAD: usize = 100;
+
+/// The number of lines kept in view above and below the selected line, where possible
+const SCROLL_PADDING: usize = 15;
+
 struct HighlightedFile {
     source_file: Arc<SourceFile>,
-    /// The syntax highlighted lines of `source_file`, cached so that patching
-    /// them with the current selected line can be done efficiently
-    lines: Vec<Vec<Span<'static>>>,
-    selected_span: SourceSpan,
+    /// The key of the highlighted lines of `source_file` in the highlight cache
+    key: u64,
     gutter_width: u8,
 }
 
+/// The lines of a file covered by the current location of the program, with the location
+/// highlighted, each of which is computed when it first comes into view
+struct SpanLines {
+    /// The key of the file in the highlight cache
+    key: u64,
+    span: SourceSpan,
+    lines: BTreeMap<usize, Vec<Span<'static>>>,
+}
+
+/// The syntax highlighted lines of a file, highlighted on demand as they come into view.
+///
+/// The highlighting of each line depends on that of the lines before it, so lines are highlighted
+/// in order, from the start of the file, and `lines` holds those highlighted so far.
+struct HighlightedLines {
+    state: Box<dyn HighlighterState>,
+    lines: Vec<Vec<Span<'static>>>,
+}
+
 impl SourceCodePane {
-    fn highlight_file(&mut self, resolved: &ResolvedLocation) -> HighlightedFile {
-        let highlighter_state = self
-            .syntax_highlighting_states
-            .entry(resolved.source_file.id())
-            .or_insert_with(|| {
-                let span_contents = resolved
-                    .source_file
-                    .read_span(&resolved.source_file.source_span().into(), 0, 0)
-                    .expect("failed to read span of file");
-                self.syntax_highlighter.start_highlighter_state(span_contents.as_ref())
+    /// Prepare to show `source_file`, reusing the lines highlighted when it was last shown, if its
+    /// content is unchanged since
+    fn highlight_file(&mut self, source_file: &Arc<SourceFile>) -> HighlightedFile {
+        let key = cache_key(source_file);
+        self.highlight_cache.entry(key).or_insert_with(|| {
+            let span_contents = source_file
+                .read_span(&source_file.source_span().into(), 0, 0)
+                .expect("failed to read span of file");
+            HighlightedLines {
+                state: self.syntax_highlighter.start_highlighter_state(span_contents.as_ref()),
+                lines: vec![],
+            }
+        });
+        HighlightedFile {
+            source_file: source_file.clone(),
+            key,
+            gutter_width: line_count(source_file).ilog10() as u8 + 1,
+        }
+    }
+
+    /// Highlight the lines of the current file before `end`, if they have not been already
+    fn highlight_lines(&mut self, end: usize) {
+        let Some(file) = self.current_file.as_ref() else {
+            return;
+        };
+        let Some(highlighted) = self.highlight_cache.get_mut(&file.key) else {
+            return;
+        };
+        while highlighted.lines.len() < end {
+            let text = line_text(&file.source_file, highlighted.lines.len() as u32);
+            let line = highlighted.state.highlight_line(Cow::Owned(text));
+            highlighted.lines.push(line);
+        }
+    }
+
+    /// Get the highlighted line of the current file at `index`, along with the current location of
+    /// the program, if it covers the line, or `None` if the line has not been highlighted
+    fn span_line(&mut self, index: usize) -> Option<Vec<Span<'static>>> {
+        let file = self.current_file.as_ref()?;
+        let highlighted = self.highlight_cache.get(&file.key)?.lines.get(index)?;
+        let span = self.current_span;
+        if span.source_id() != file.source_file.id() {
+            return Some(highlighted.clone());
+        }
+        let content = file.source_file.content();
+        let first = content.line_index(span.start()).to_usize();
+        let last = content.line_index(span.end()).to_usize();
+        if !(first..=last).contains(&index) {
+            return Some(highlighted.clone());
+        }
+
+        // The highlighting of the current location only changes when the program moves on
+        if self
+            .span_lines
+            .as_ref()
+            .is_none_or(|lines| lines.key != file.key || lines.span != span)
+        {
+            self.span_lines = Some(SpanLines {
+                key: file.key,
+                span,
+                lines: BTreeMap::new(),
             });
-        let resolved_span = resolved.span.into_slice_index();
-        let content = resolved.source_file.content();
-        let last_line = content.last_line_index();
-        let max_line_no = last_line.number().to_usize();
-        let gutter_width = max_line_no.ilog10() as u8;
-        let lines = (0..(max_line_no - 1))
-            .map(|line_index| {
-                let line_index = miden_debug_types::LineIndex::from(line_index as u32);
-                let span = content.line_range(line_index).expect("invalid line index");
-                let span = span.start.to_usize()..span.end.to_usize();
-
-                // Only highlight a portion of the line if the full span fits on that line
-                let is_highlighted = span.contains(&resolved_span.start)
-                    && span.contains(&resolved_span.end)
-                    && span != resolved_span;
-
-                let line_content =
-                    strip_newline(&content.as_bytes()[span.start..span.end]).into_owned();
-                if is_highlighted {
-                    let selection = if resolved.span.is_empty() {
-                        // Select the closest character to the span
-                        //let start = core::cmp::max(span.start, resolved_span.start);
-                        //let end = core::cmp::min(span.end, resolved_span.end.saturating_add(1));
-                        //(start - span.start)..(end - span.start)
-                        0..(span.end - span.start)
-                    } else {
-                        (resolved_span.start - span.start)..(resolved_span.end - span.start)
-                    };
-                    highlighter_state.highlight_line_with_selection(
-                        line_content.into(),
-                        selection,
-                        self.theme.current_span,
-                    )
-                } else {
-                    highlighter_state.highlight_line(line_content.into())
-                }
+        }
+        let span_lines = self.span_lines.as_mut().unwrap();
+        let line = span_lines
+            .lines
+            .entry(index)
+            .or_insert_with(|| {
+                let range = content.line_range((index as u32).into()).expect("invalid line index");
+                let range = range.start.to_usize()..range.end.to_usize();
+                let text = line_text(&file.source_file, index as u32);
+                let selection = match line_selection(span.into_slice_index(), range, &text) {
+                    // An empty span selects the whole of the line it is on
+                    Some(_) if span.is_empty() => 0..text.len(),
+                    Some(selection) => selection,
+                    None => return highlighted.clone(),
+                };
+                overlay_ranges(highlighted.clone(), &[selection], self.theme.current_span)
             })
-            .collect::<Vec<_>>();
+            .clone();
+        Some(line)
+    }
 
-        HighlightedFile {
-            source_file: resolved.source_file.clone(),
-            lines,
-            selected_span: resolved.span,
-            gutter_width,
+    /// Forget the highlighting of files which are no longer known to the source manager, or whose
+    /// content has changed
+    fn prune_highlight_cache(&mut self, state: &State) {
+        let keys = state.source_files().iter().map(|file| cache_key(file)).collect::<BTreeSet<_>>();
+        self.highlight_cache.retain(|key, _| keys.contains(key));
+    }
+
+    /// Show `loc` in this pane, switching to its source file if it is not the one shown. If the
+    /// line of `loc` is 0, the line last selected in its file is selected.
+    fn show_location(&mut self, loc: &ResolvedLocation) {
+        let source_id = loc.source_file.id();
+        let switching = self.current_file.is_none() || source_id != self.current_source_id;
+        if switching {
+            if self.current_file.is_some() {
+                self.scroll_positions.insert(self.current_source_id, self.selected_line);
+            }
+            self.current_file = Some(self.highlight_file(&loc.source_file));
+            self.current_source_id = source_id;
+            self.num_lines = line_count(&loc.source_file);
+            if let Some(search) = self.search.take() {
+                self.search = Some(Search::new(search.query, &loc.source_file));
+            }
         }
+        self.selected_line = match loc.line {
+            0 if switching => self
+                .scroll_positions
+                .get(&source_id)
+                .map_or(1, |line| (*line).clamp(1, self.num_lines.max(1))),
+            0 => self.selected_line,
+            line => line,
+        };
+    }
+
+    /// Follow the program to `loc`, its current location
+    fn follow(&mut self, loc: &ResolvedLocation) {
+        self.mark(loc);
+        self.show_location(loc);
+    }
+
+    /// Mark `loc` as the current location of the program, without moving the view to it
+    fn mark(&mut self, loc: &ResolvedLocation) {
+        self.current_span = loc.span;
+        self.current_line = loc.line;
+        self.current_col = loc.col;
+    }
+
+    /// Start or stop following the program as it executes, returning to its current location when
+    /// following it again
+    fn toggle_follow(&mut self, state: &State) -> Action {
+        self.following = !self.following;
+        if !self.following {
+            let keys = state.keybindings.describe(KeyAction::ToggleFollow);
+            return Action::TimedStatusLine(
+                format!("no longer following execution, press {keys} to follow it again"),
+                3,
+            );
+        }
+        if let Some(loc) = self.current_location(state) {
+            self.follow(&loc);
+            self.refresh_breakpoint_markers(state);
+        }
+        Action::TimedStatusLine("following execution".into(), 3)
+    }
+
+    /// Returns true if the file shown is not the one in which the program is stopped, e.g. after
+    /// the `file` command
+    fn is_detached(&self) -> bool {
+        self.current_file.is_some() && self.current_source_id != self.current_span.source_id()
+    }
+
+    /// Search the current file for `query`, highlighting the matches, or stop searching if it is
+    /// empty
+    fn search(&mut self, query: String) {
+        self.search = match self.current_file.as_ref() {
+            Some(file) if !query.is_empty() => Some(Search::new(query, &file.source_file)),
+            _ => None,
+        };
+    }
+
+    /// Select the next (or previous) line with a match for the current search, if there is one
+    fn select_match(&mut self, forward: bool) -> Option<Action> {
+        let search = self.search.as_ref()?;
+        let line = match forward {
+            true => search.next_line(self.selected_line),
+            false => search.prev_line(self.selected_line),
+        };
+        match line {
+            Some(line) => {
+                self.selected_line = line;
+                None
+            }
+            None => {
+                Some(Action::TimedStatusLine(format!("pattern not found: {}", search.query), 3))
+            }
+        }
+    }
+
+    /// Recompute the breakpoint markers shown in the gutter of the current file
+    fn refresh_breakpoint_markers(&mut self, state: &State) {
+        self.breakpoint_markers = match self.current_file.as_ref() {
+            Some(file) => breakpoint_markers(file.source_file.uri().as_str(), &state.breakpoints),
+            None => BTreeMap::new(),
+        };
     }
 
     /// Get the [ResolvedLocation] for the current state
     fn current_location(&self, state: &State) -> Option<ResolvedLocation> {
-        match state.executor.callstack.current_frame() {
+        match state.selected_frame() {
             Some(frame) => {
                 let resolved = frame.last_resolved(&state.source_manager);
                 resolved.cloned()
             }
-            None if !self.current_source_id.is_unknown() => {
-                let source_file = state.source_manager.get(self.current_source_id).ok();
+            // The file shown may not be that of the current location, so it is found via the span
+            None if !self.current_span.source_id().is_unknown() => {
+                let source_file = state.source_manager.get(self.current_span.source_id()).ok();
                 source_file.map(|src| ResolvedLocation {
                     source_file: src,
                     line: self.current_line,
@@ -126,12 +310,173 @@ impl SourceCodePane {
     }
 }
 
+/// Get the part of the line spanning the byte range `line` of a file covered by `span`, as a byte
+/// range of `text`, the content of that line, or `None` if `span` is not on the line.
+///
+/// A span covering several lines is split between them: its first line is selected from the start
+/// of the span, its last line up to the end of the span, and any lines in between in full.
+fn line_selection(span: Range<usize>, line: Range<usize>, text: &str) -> Option<Range<usize>> {
+    let on_line = match span.is_empty() {
+        true => line.contains(&span.start),
+        false => span.start < line.end && span.end > line.start,
+    };
+    if !on_line {
+        return None;
+    }
+    let start = span.start.max(line.start) - line.start;
+    let end = span.end.min(line.end) - line.start;
+    Some(grapheme_range(text, start..end))
+}
+
+/// Clamp `range` to the bounds of `text`, widening it as necessary so that it starts and ends on
+/// the boundaries of grapheme clusters, i.e. so that a character is never highlighted apart from
+/// the combining marks which follow it
+fn grapheme_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let range = char_range(text, range);
+    let mut start = 0;
+    let mut end = text.len();
+    for (offset, _) in text.grapheme_indices(true) {
+        if offset <= range.start {
+            start = offset;
+        }
+        if offset >= range.end {
+            end = offset;
+            break;
+        }
+    }
+    start..end
+}
+
+/// Replace the tabs of `spans`, the parts of a line, by spaces up to the next tab stop, where tab
+/// stops are `tab_width` columns apart, accounting for the width of wide characters before them
+fn expand_tabs(spans: Vec<Span<'static>>, tab_width: usize) -> Vec<Span<'static>> {
+    if spans.iter().all(|span| !span.content.contains('\t')) {
+        return spans;
+    }
+    let mut column = 0;
+    spans
+        .into_iter()
+        .map(|span| {
+            let mut content = String::with_capacity(span.content.len());
+            for grapheme in span.content.graphemes(true) {
+                if grapheme == "\t" {
+                    let spaces = tab_width - column % tab_width;
+                    content.extend(core::iter::repeat_n(' ', spaces));
+                    column += spaces;
+                } else {
+                    content.push_str(grapheme);
+                    column += grapheme.width();
+                }
+            }
+            Span::styled(content, span.style)
+        })
+        .collect()
+}
+
+/// Compute the lines of the file whose URI is `uri` on which any of `breakpoints` are set, and
+/// whether any of those set on each line are enabled
+fn breakpoint_markers(uri: &str, breakpoints: &[Breakpoint]) -> BTreeMap<u32, bool> {
+    let mut markers = BTreeMap::new();
+    for bp in breakpoints {
+        if let BreakpointType::Line { line, .. } = &bp.ty
+            && bp.is_set_on_line(uri, *line)
+        {
+            *markers.entry(*line).or_default() |= bp.enabled;
+        }
+    }
+    markers
+}
+
+/// The matches of a search within a source file.
+///
+/// Matches are found in the raw text of each line, and overlaid on its syntax highlighting.
+#[derive(Debug, Default)]
+struct Search {
+    query: String,
+    /// The byte ranges of the matches on each line with any, keyed by line number
+    matches: BTreeMap<u32, Vec<Range<usize>>>,
+}
+
+impl Search {
+    fn new(query: String, source_file: &SourceFile) -> Self {
+        let content = source_file.content();
+        let mut matches = BTreeMap::new();
+        for index in 0..line_count(source_file) {
+            let Some(range) = content.line_range(index.into()) else {
+                break;
+            };
+            let text =
+                strip_newline(&content.as_bytes()[range.start.to_usize()..range.end.to_usize()])
+                    .into_owned();
+            let ranges = text
+                .match_indices(query.as_str())
+                .map(|(start, found)| start..(start + found.len()))
+                .collect::<Vec<_>>();
+            if !ranges.is_empty() {
+                matches.insert(index + 1, ranges);
+            }
+        }
+        Self { query, matches }
+    }
+
+    /// The first line with a match after `line`, wrapping around to the start of the file
+    fn next_line(&self, line: u32) -> Option<u32> {
+        self.matches
+            .range((line + 1)..)
+            .next()
+            .or_else(|| self.matches.first_key_value())
+            .map(|(line, _)| *line)
+    }
+
+    /// The last line with a match before `line`, wrapping around to the end of the file
+    fn prev_line(&self, line: u32) -> Option<u32> {
+        self.matches
+            .range(..line)
+            .next_back()
+            .or_else(|| self.matches.last_key_value())
+            .map(|(line, _)| *line)
+    }
+}
+
+/// Apply `style` to the byte ranges of `spans` given by `ranges`, which are relative to the start
+/// of the first span, splitting spans as necessary
+fn overlay_ranges(
+    spans: Vec<Span<'static>>,
+    ranges: &[Range<usize>],
+    style: Style,
+) -> Vec<Span<'static>> {
+    let mut overlaid = Vec::with_capacity(spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in spans {
+        let content = span.content.as_ref();
+        let end = offset + content.len();
+        let mut pos = 0;
+        for range in ranges.iter().filter(|range| range.start < end && range.end > offset) {
+            let start = range.start.max(offset) - offset;
+            let stop = range.end.min(end) - offset;
+            if start > pos {
+                overlaid.push(Span::styled(content[pos..start].to_string(), span.style));
+            }
+            overlaid.push(Span::styled(content[start..stop].to_string(), span.style.patch(style)));
+            pos = stop;
+        }
+        if pos == 0 {
+            overlaid.push(span);
+        } else if pos < content.len() {
+            overlaid.push(Span::styled(content[pos..].to_string(), span.style));
+        }
+        offset = end;
+    }
+    overlaid
+}
+
 struct Theme {
     focused_border_style: Style,
     current_line: Style,
     current_span: Style,
     line_number: Style,
     gutter_border: Style,
+    search_match: Style,
 }
 impl Default for Theme {
     fn default() -> Self {
@@ -147,6 +492,7 @@ impl Default for Theme {
                 .add_modifier(Modifier::BOLD),
             line_number: Style::default(),
             gutter_border: Style::default(),
+            search_match: Style::default().fg(Color::Black).bg(Color::Yellow),
         }
     }
 }
@@ -188,12 +534,37 @@ impl SourceCodePane {
             current_col: 0,
             current_span: SourceSpan::default(),
             syntax_highlighter: Box::new(NoopHighlighter),
-            syntax_highlighting_states: Default::default(),
+            highlight_cache: BTreeMap::new(),
+            span_lines: None,
             current_file: None,
+            breakpoint_markers: BTreeMap::new(),
+            search: None,
+            following: true,
+            scroll_positions: BTreeMap::new(),
+            lines_in_view: 0..0,
+            view_width: 0,
             theme,
         }
     }
 
+    /// The line shown at `column` and `row` of the pane, relative to the area in which it was last
+    /// drawn, and whether the position is within the gutter, i.e. on the breakpoint marker or the
+    /// line number, rather than the text of the line
+    fn line_at(&self, column: u16, row: u16) -> Option<(u32, bool)> {
+        let current_file = self.current_file.as_ref()?;
+        // The first and last columns, and the first row, are the border of the pane
+        if column == 0 || column + 1 >= self.view_width {
+            return None;
+        }
+        let index = self.lines_in_view.start + (row as usize).checked_sub(1)?;
+        if !self.lines_in_view.contains(&index) {
+            return None;
+        }
+        // The selection and breakpoint markers, and the line number, precede the separator
+        let gutter_end = 3 + current_file.gutter_width as u16;
+        Some((index as u32 + 1, column < gutter_end))
+    }
+
     fn reload(&mut self, state: &State) {
         self.current_source_id = SourceId::UNKNOWN;
         self.current_span = SourceSpan::default();
@@ -202,16 +573,19 @@ impl SourceCodePane {
         self.num_lines = 0;
         self.selected_line = 0;
         self.current_file = None;
+        self.span_lines = None;
+
+        self.prune_highlight_cache(state);
 
         if let Some(frame) = state.executor.callstack.current_frame()
             && let Some(loc) = frame.last_resolved(&state.source_manager)
         {
-            self.current_file = Some(self.highlight_file(loc));
+            self.current_file = Some(self.highlight_file(&loc.source_file));
             self.current_source_id = loc.source_file.id();
             self.current_span = loc.span;
             self.current_line = loc.line;
             self.current_col = loc.col;
-            self.num_lines = loc.source_file.line_count() as u32;
+            self.num_lines = line_count(&loc.source_file);
             self.selected_line = loc.line;
         }
     }
@@ -237,8 +611,11 @@ impl SourceCodePane {
         }
 
         let syntax_set = syntect::parsing::SyntaxSet::load_defaults_nonewlines();
-        let theme_set = syntect::highlighting::ThemeSet::load_defaults();
-        let theme = theme_set.themes["base16-eighties.dark"].clone();
+        let theme = (*state.theme.syntect).clone();
+        self.theme = Theme {
+            focused_border_style: self.theme.focused_border_style,
+            ..Default::default()
+        };
         self.theme.patch_from_syntect(&theme);
         self.syntax_highlighter = Box::new(SyntectHighlighter::new(syntax_set, theme, false));
     }
@@ -251,25 +628,70 @@ impl Pane for SourceCodePane {
         if let Some(frame) = state.executor.callstack.current_frame()
             && let Some(loc) = frame.last_resolved(&state.source_manager)
         {
-            self.current_file = Some(self.highlight_file(loc));
+            self.current_file = Some(self.highlight_file(&loc.source_file));
             self.current_source_id = loc.source_file.id();
             self.current_span = loc.span;
             self.current_line = loc.line;
             self.current_col = loc.col;
-            self.num_lines = loc.source_file.line_count() as u32;
+            self.num_lines = line_count(&loc.source_file);
             self.selected_line = loc.line;
         }
+        self.refresh_breakpoint_markers(state);
 
         Ok(())
     }
 
-    fn height_constraint(&self) -> Constraint {
-        match self.focused {
-            true => Constraint::Fill(3),
-            false => Constraint::Fill(3),
+    fn handle_key_events(
+        &mut self,
+        key: KeyEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        match state.keybindings.lookup(KeyMode::Source, key) {
+            Some(KeyAction::ToggleBreakpoint)
+                if self.current_file.is_some() && self.selected_line > 0 =>
+            {
+                Ok(Some(EventResponse::Stop(Action::ToggleBreakpoint(
+                    self.current_source_id,
+                    self.selected_line,
+                ))))
+            }
+            Some(KeyAction::Search) if self.current_file.is_some() => {
+                Ok(Some(EventResponse::Stop(Action::FocusFooter("/".into(), None))))
+            }
+            // While searching, these take precedence over stepping to the next instruction
+            Some(KeyAction::NextMatch) if self.search.is_some() => {
+                Ok(Some(EventResponse::Stop(Action::NextMatch)))
+            }
+            Some(KeyAction::PrevMatch) if self.search.is_some() => {
+                Ok(Some(EventResponse::Stop(Action::PrevMatch)))
+            }
+            Some(KeyAction::ClearSearch) if self.search.is_some() => {
+                Ok(Some(EventResponse::Stop(Action::ClearSearch)))
+            }
+            Some(KeyAction::ToggleFollow) => Ok(Some(EventResponse::Stop(Action::ToggleFollow))),
+            _ => Ok(None),
         }
     }
 
+    fn handle_mouse_events(
+        &mut self,
+        mouse: MouseEvent,
+        _state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Ok(None);
+        }
+        let Some((line, in_gutter)) = self.line_at(mouse.column, mouse.row) else {
+            return Ok(None);
+        };
+        self.selected_line = line;
+        let action = match in_gutter {
+            true => Action::ToggleBreakpoint(self.current_source_id, line),
+            false => Action::Update,
+        };
+        Ok(Some(EventResponse::Stop(action)))
+    }
+
     fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
         match action {
             Action::Down => {
@@ -288,32 +710,84 @@ impl Pane for SourceCodePane {
             }
             Action::Focus => {
                 self.focused = true;
-                static STATUS_LINE: &str = "[j,k → movement]";
-                return Ok(Some(Action::TimedStatusLine(STATUS_LINE.into(), 3)));
+                let keys = |action| state.keybindings.describe(action);
+                let status_line = format!(
+                    "[{},{} → movement] [{} → toggle breakpoint] [{} → search] [{} → follow]",
+                    keys(KeyAction::Down),
+                    keys(KeyAction::Up),
+                    keys(KeyAction::ToggleBreakpoint),
+                    keys(KeyAction::Search),
+                    keys(KeyAction::ToggleFollow)
+                );
+                return Ok(Some(Action::TimedStatusLine(status_line, 3)));
             }
             Action::UnFocus => {
                 self.focused = false;
             }
             Action::Submit => {}
+            Action::Yank => {
+                let Some(file) = self.current_file.as_ref().filter(|_| self.selected_line > 0)
+                else {
+                    return Ok(Some(Action::TimedStatusLine("no source file is shown".into(), 3)));
+                };
+                let text = line_text(&file.source_file, self.selected_line - 1);
+                let what = format!("line {}", self.selected_line);
+                return Ok(Some(clipboard::yank(state, &what, text)));
+            }
+            Action::Search(query) => self.search(query),
+            Action::NextMatch => return Ok(self.select_match(true)),
+            Action::PrevMatch => return Ok(self.select_match(false)),
+            Action::ClearSearch => self.search = None,
+            Action::ToggleFollow => return Ok(Some(self.toggle_follow(state))),
+            Action::ShowLocation(loc) => {
+                self.show_location(&loc);
+                self.refresh_breakpoint_markers(state);
+            }
+            Action::GotoLine(line) => {
+                if self.current_file.is_none() {
+                    return Ok(Some(Action::TimedStatusLine("no source file is shown".into(), 3)));
+                }
+                self.selected_line = line.clamp(1, self.num_lines.max(1));
+            }
+            Action::SyncSource | Action::SelectFrame(_) => {
+                let Some(loc) = self.current_location(state) else {
+                    return Ok(Some(Action::TimedStatusLine(
+                        "there is no current source location".into(),
+                        3,
+                    )));
+                };
+                self.follow(&loc);
+                self.refresh_breakpoint_markers(state);
+            }
+            Action::ThemeChanged => {
+                // The files shown so far are highlighted again, with the new theme
+                self.enable_syntax_highlighting(state);
+                self.highlight_cache.clear();
+                self.span_lines = None;
+                if let Some(file) = self.current_file.take() {
+                    self.current_file = Some(self.highlight_file(&file.source_file));
+                }
+            }
             Action::Update | Action::Reload => {
                 if action == Action::Reload {
                     self.reload(state);
+                    if let Some(search) = self.search.take() {
+                        self.search(search.query);
+                    }
                 }
 
-                if let Some(loc) = self.current_location(state) {
-                    let source_id = loc.source_file.id();
-                    if source_id != self.current_source_id {
-                        self.highlight_file(&loc);
-                        self.current_source_id = source_id;
-                        self.num_lines = loc.source_file.line_count() as u32;
-                        self.selected_line = loc.line;
-                    } else if self.selected_line != loc.line {
-                        self.selected_line = loc.line;
+                // Only follow the current location once the program has moved on from it, so that
+                // a location shown via [Action::ShowLocation] stays in view until then. When not
+                // following, only the marker of the current line moves.
+                if let Some(loc) = self.current_location(state)
+                    && loc.span != self.current_span
+                {
+                    match self.following {
+                        true => self.follow(&loc),
+                        false => self.mark(&loc),
                     }
-                    self.current_span = loc.span;
-                    self.current_line = loc.line;
-                    self.current_col = loc.col;
                 }
+                self.refresh_breakpoint_markers(state);
             }
             _ => {}
         }
@@ -321,12 +795,14 @@ impl Pane for SourceCodePane {
         Ok(None)
     }
 
-    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, _state: &State) -> Result<(), Report> {
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        self.view_width = area.width;
         let current_file = self.current_file.as_ref();
         if current_file.is_none() {
+            self.lines_in_view = 0..0;
             frame.render_widget(
                 Block::default()
-                    .title("Source Code")
+                    .title(PaneId::Source.numbered("Source Code"))
                     .borders(Borders::ALL)
                     .border_style(self.border_style())
                     .border_type(self.border_type())
@@ -343,86 +819,58 @@ impl Pane for SourceCodePane {
             return Ok(());
         }
 
-        let current_file = unsafe { current_file.unwrap_unchecked() };
-
-        // Get the cached (highlighted) lines for the current source file
-        let mut lines = current_file.lines.clone();
-        // Extract the current selected line as a vector of raw syntect parts
+        // Only the lines which may be in view are highlighted, the view being scrolled no further
+        // than needed to show the selected line
         let selected_line = self.selected_line.saturating_sub(1) as usize;
-        let selected_line_deconstructed = lines[selected_line]
-            .iter()
-            .map(|span| {
-                (
-                    crate::ui::syntax_highlighting::convert_to_syntect_style(span.style, false),
-                    span.content.as_ref(),
-                )
-            })
+        let num_lines = self.num_lines as usize;
+        self.highlight_lines(
+            (selected_line + area.height as usize + HIGHLIGHT_AHEAD).min(num_lines),
+        );
+        // Likewise, only the lines which may be in view are rendered, i.e. those within a page of
+        // the selected line, and those within the scroll padding below that
+        let view_height = area.height.saturating_sub(2) as usize;
+        let window = selected_line.saturating_sub(view_height)
+            ..(selected_line + view_height + SCROLL_PADDING + 1).min(num_lines);
+        let mut lines = window
+            .clone()
+            .map(|index| self.span_line(index).unwrap_or_default())
             .collect::<Vec<_>>();
 
-        // Modify the selected line's highlighting style to reflect the selection
-        let syntect_style = syntect::highlighting::StyleModifier {
-            foreground: self
-                .theme
-                .current_span
-                .fg
-                .map(crate::ui::syntax_highlighting::convert_to_syntect_color),
-            background: self
-                .theme
-                .current_span
-                .bg
-                .map(crate::ui::syntax_highlighting::convert_to_syntect_color),
-            font_style: if self.theme.current_span.add_modifier.is_empty() {
-                None
-            } else {
-                Some(crate::ui::syntax_highlighting::convert_to_font_style(
-                    self.theme.current_span.add_modifier,
-                ))
-            },
+        // Overlay the matches of the current search, if any, on the highlighted lines
+        if let Some(search) = self.search.as_ref() {
+            let first = window.start as u32 + 1;
+            for (line, ranges) in search.matches.range(first..(window.end as u32 + 1)) {
+                let parts = &mut lines[(line - first) as usize];
+                *parts = overlay_ranges(core::mem::take(parts), ranges, self.theme.search_match);
+            }
+        }
+
+        let current_file = self.current_file.as_ref().unwrap();
+        let gutter_width = current_file.gutter_width as usize;
+        let tab_width = state.config.tab_width();
+        // The line on which the program is stopped is marked even when it is not selected
+        let execution_line = match self.is_detached() {
+            true => None,
+            false => Some(self.current_line.saturating_sub(1) as usize),
         };
-        let span = current_file.selected_span;
-        let line_span = current_file
-            .source_file
-            .content()
-            .line_range((selected_line as u32).into())
-            .unwrap();
-        let selection_start = core::cmp::max(span.start(), line_span.start);
-        let selection_end = core::cmp::min(span.end(), line_span.end);
-        let selected_span = SourceSpan::new(span.source_id(), selection_start..selection_end);
-        let selected = selected_span.into_slice_index();
-        let selected = if selected_span.is_empty() {
-            // Select the closest character to the span
-            let start = selected.start - line_span.start.to_usize();
-            start..start
-        } else {
-            (selected.start - line_span.start.to_usize())..(selected.end - line_span.end.to_usize())
-        };
-        let mut parts = syntect::util::modify_range(
-            selected_line_deconstructed.as_slice(),
-            selected,
-            syntect_style,
-        )
-        .into_iter()
-        .map(|(style, str)| {
-            Span::styled(
-                str.to_string(),
-                crate::ui::syntax_highlighting::convert_style(style, true),
-            )
-        })
-        .collect();
-        lines[selected_line].clear();
-        lines[selected_line].append(&mut parts);
-
-        let gutter_width = self.current_file.as_ref().unwrap().gutter_width as usize;
-        let lines = lines.into_iter().enumerate().map(|(line_index, highlighted_parts)| {
-            let line_number_style = if line_index == selected_line {
-                self.theme.current_line
-            } else {
-                self.theme.line_number
+        let lines = window.clone().zip(lines).map(|(line_index, highlighted_parts)| {
+            let highlighted_parts = expand_tabs(highlighted_parts, tab_width);
+            let line_number_style =
+                if line_index == selected_line || Some(line_index) == execution_line {
+                    self.theme.current_line
+                } else {
+                    self.theme.line_number
+                };
+            let marker = match self.breakpoint_markers.get(&(line_index as u32 + 1)) {
+                Some(true) => Span::styled("●", Color::Red),
+                Some(false) => Span::styled("○", Color::Red),
+                None => Span::styled(" ", line_number_style),
             };
             Line::from_iter(
                 [
+                    marker,
                     Span::styled(
-                        format!("{line_no:gutter_width$}", line_no = line_index + 1),
+                        format!("{line_no:>gutter_width$}", line_no = line_index + 1),
                         line_number_style,
                     ),
                     Span::styled(" | ", line_number_style),
@@ -437,13 +885,15 @@ impl Pane for SourceCodePane {
             .block(Block::default().borders(Borders::ALL))
             .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
             .highlight_spacing(HighlightSpacing::Always)
-            .scroll_padding(15);
-        let mut list_state = ListState::default().with_selected(Some(selected_line));
+            .scroll_padding(SCROLL_PADDING);
+        let mut list_state = ListState::default().with_selected(Some(selected_line - window.start));
 
         frame.render_stateful_widget(list, area, &mut list_state);
+        let first = window.start + list_state.offset();
+        self.lines_in_view = first..(first + view_height).min(window.end);
         frame.render_widget(
             Block::default()
-                .title("Source Code")
+                .title(PaneId::Source.numbered("Source Code"))
                 .borders(Borders::ALL)
                 .border_style(self.border_style())
                 .border_type(self.border_type())
@@ -451,6 +901,27 @@ impl Pane for SourceCodePane {
                     Line::from(format!("{} of {}", self.selected_line, self.num_lines,))
                         .right_aligned(),
                 )
+                .title_bottom(match self.search.as_ref() {
+                    Some(search) => Line::styled(
+                        format!("/{}: {} line(s) match", search.query, search.matches.len()),
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    ),
+                    None => Line::default(),
+                })
+                .title(match (self.is_detached(), self.following) {
+                    (true, _) => Line::styled(
+                        " detached from execution (:sync) ",
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    ),
+                    (false, false) => Line::styled(
+                        format!(
+                            " not following execution ({}) ",
+                            state.keybindings.describe(KeyAction::ToggleFollow)
+                        ),
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    ),
+                    (false, true) => Line::default(),
+                })
                 .title(
                     Line::styled(
                         current_file.source_file.deref().uri().as_str(),
@@ -464,10 +935,767 @@ impl Pane for SourceCodePane {
     }
 }
 
+/// The key of the highlighted lines of `source_file` in the highlight cache, which identifies both
+/// the file and its content
+fn cache_key(source_file: &SourceFile) -> u64 {
+    use core::hash::{Hash, Hasher};
+
+    let mut hasher = std::hash::DefaultHasher::new();
+    source_file.uri().as_str().hash(&mut hasher);
+    source_file.as_str().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The text of the line of `source_file` at `index`, without its newline
+fn line_text(source_file: &SourceFile, index: u32) -> String {
+    let content = source_file.content();
+    match content.line_range(index.into()) {
+        Some(range) => {
+            strip_newline(&content.as_bytes()[range.start.to_usize()..range.end.to_usize()])
+                .into_owned()
+        }
+        None => String::new(),
+    }
+}
+
+/// The number of lines of `source_file`, which does not count the empty line following a newline
+/// at the end of the file as a line
+fn line_count(source_file: &SourceFile) -> u32 {
+    let lines = source_file.line_count() as u32;
+    match source_file.as_str().ends_with('\n') {
+        true => lines - 1,
+        false => lines,
+    }
+}
+
 fn strip_newline(s: &[u8]) -> std::borrow::Cow<'_, str> {
-    if let Some(sans_newline) = s.strip_suffix(b"\n") {
-        String::from_utf8_lossy(sans_newline)
-    } else {
-        String::from_utf8_lossy(s)
+    let s = s.strip_suffix(b"\n").unwrap_or(s);
+    String::from_utf8_lossy(s.strip_suffix(b"\r").unwrap_or(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use glob::Pattern;
+    use miden_debug_types::SourceManagerExt;
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::ui::testing::{draw_pane, main_masm, render_pane};
+
+    fn line_breakpoint(pattern: &str, line: u32, enabled: bool) -> Breakpoint {
+        Breakpoint {
+            ty: BreakpointType::Line {
+                pattern: Pattern::new(pattern).unwrap(),
+                line,
+            },
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn breakpoint_markers_for_file() {
+        let breakpoints = [
+            line_breakpoint("*main.masm", 3, false),
+            line_breakpoint("src/main.masm", 3, true),
+            line_breakpoint("*.masm", 5, false),
+            line_breakpoint("*lib.masm", 7, true),
+            Breakpoint {
+                ty: BreakpointType::File(Pattern::new("*main.masm").unwrap()),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            breakpoint_markers("src/main.masm", &breakpoints),
+            BTreeMap::from([(3, true), (5, false)])
+        );
+        assert!(breakpoint_markers("src/other.rs", &breakpoints).is_empty());
+    }
+
+    #[test]
+    fn toggle_breakpoint_round_trip() {
+        let (_dir, path) = main_masm("begin\n    push.1\n    drop\nend\n");
+
+        let mut state = State::for_source_file(&path);
+        let source_file = state.source_manager.load_file(&path).unwrap();
+        let source_id = source_file.id();
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+
+        // There is nothing to set a breakpoint in until a file is shown
+        let b = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE);
+        assert!(pane.handle_key_events(b, &mut state).unwrap().is_none());
+
+        let loc = ResolvedLocation {
+            span: SourceSpan::at(source_id, 6u32),
+            source_file,
+            line: 2,
+            col: 1,
+        };
+        pane.update(Action::ShowLocation(loc), &mut state).unwrap();
+        let Some(EventResponse::Stop(action)) = pane.handle_key_events(b, &mut state).unwrap()
+        else {
+            panic!("expected `b` to toggle a breakpoint");
+        };
+        assert_eq!(action, Action::ToggleBreakpoint(source_id, 2));
+
+        assert_eq!(state.toggle_line_breakpoint(source_id, 2), Ok(true));
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.breakpoint_markers, BTreeMap::from([(2, true)]));
+        assert!(state.breakpoints[0].is_set_on_line(path.to_str().unwrap(), 2));
+
+        state.breakpoints[0].enabled = false;
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.breakpoint_markers, BTreeMap::from([(2, false)]));
+
+        assert_eq!(state.toggle_line_breakpoint(source_id, 2), Ok(false));
+        pane.update(Action::Update, &mut state).unwrap();
+        assert!(pane.breakpoint_markers.is_empty());
+        assert!(state.breakpoints.is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn search_match_navigation() {
+        let search = Search {
+            query: "drop".into(),
+            matches: BTreeMap::from([(3, vec![4..8]), (5, vec![0..4, 5..9]), (9, vec![2..6])]),
+        };
+        assert_eq!(search.next_line(0), Some(3));
+        assert_eq!(search.next_line(3), Some(5));
+        assert_eq!(search.next_line(4), Some(5));
+        assert_eq!(search.next_line(9), Some(3));
+        assert_eq!(search.next_line(12), Some(3));
+        assert_eq!(search.prev_line(5), Some(3));
+        assert_eq!(search.prev_line(6), Some(5));
+        assert_eq!(search.prev_line(3), Some(9));
+        assert_eq!(search.prev_line(0), Some(9));
+
+        let empty = Search::default();
+        assert_eq!(empty.next_line(1), None);
+        assert_eq!(empty.prev_line(1), None);
+    }
+
+    #[test]
+    fn search_matches_overlay_highlighting() {
+        let plain = Style::default();
+        let keyword = Style::default().fg(Color::Magenta);
+        let found = Style::default().bg(Color::Yellow);
+        // A match spanning two highlighted spans is split between them
+        let spans = vec![
+            Span::styled("    ", plain),
+            Span::styled("push", keyword),
+            Span::styled(".1 push.2", plain),
+        ];
+        let overlaid = overlay_ranges(spans, &[6..10, 11..15], found);
+        assert_eq!(
+            overlaid,
+            vec![
+                Span::styled("    ", plain),
+                Span::styled("pu", keyword),
+                Span::styled("sh", keyword.patch(found)),
+                Span::styled(".1", plain.patch(found)),
+                Span::styled(" ", plain),
+                Span::styled("push", plain.patch(found)),
+                Span::styled(".2", plain),
+            ]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn search_source_file() {
+        let (_dir, path) = main_masm("begin\n    push.1 drop\n    push.2\n    drop\nend\n");
+
+        let mut state = State::for_source_file(&path);
+        let source_file = state.source_manager.load_file(&path).unwrap();
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let loc = ResolvedLocation {
+            span: SourceSpan::at(source_file.id(), 6u32),
+            source_file,
+            line: 2,
+            col: 1,
+        };
+        pane.update(Action::ShowLocation(loc), &mut state).unwrap();
+
+        // `n` steps the program unless there is a search
+        let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert!(pane.handle_key_events(n, &mut state).unwrap().is_none());
+
+        // Typing a search highlights the matches, without moving the selection
+        pane.update(Action::Search("drop".into()), &mut state).unwrap();
+        let search = pane.search.as_ref().unwrap();
+        assert_eq!(search.matches, BTreeMap::from([(2, vec![11..15]), (4, vec![4..8])]));
+        assert_eq!(pane.selected_line, 2);
+        assert!(matches!(
+            pane.handle_key_events(n, &mut state).unwrap(),
+            Some(EventResponse::Stop(Action::NextMatch))
+        ));
+
+        pane.update(Action::NextMatch, &mut state).unwrap();
+        assert_eq!(pane.selected_line, 4);
+        pane.update(Action::NextMatch, &mut state).unwrap();
+        assert_eq!(pane.selected_line, 2);
+        pane.update(Action::PrevMatch, &mut state).unwrap();
+        assert_eq!(pane.selected_line, 4);
+
+        pane.update(Action::Search("swap".into()), &mut state).unwrap();
+        let result = pane.update(Action::NextMatch, &mut state).unwrap();
+        assert!(
+            matches!(result, Some(Action::TimedStatusLine(ref msg, _)) if msg.contains("swap"))
+        );
+        assert_eq!(pane.selected_line, 4);
+
+        pane.update(Action::ClearSearch, &mut state).unwrap();
+        assert!(pane.search.is_none());
+    }
+
+    #[test]
+    fn goto_line_and_file() {
+        let (dir, path) = main_masm("begin\n    push.1\n    push.2\n    add\n    drop\nend\n");
+        let other_path = dir.path().join("other.masm");
+        std::fs::write(&other_path, "proc foo\n    nop\nend\n").unwrap();
+
+        let mut state = State::for_source_file(&path);
+        let other = state.source_manager.load_file(&other_path).unwrap();
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let line = |line| BreakpointType::Line {
+            pattern: Pattern::new(&Pattern::escape(path.to_str().unwrap())).unwrap(),
+            line,
+        };
+        state.create_breakpoint(line(3));
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        let main_id = pane.current_source_id;
+        assert!(!main_id.is_unknown());
+        assert_eq!(pane.current_line, 3);
+        assert!(!pane.is_detached());
+
+        // Lines are clamped to those of the file
+        pane.update(Action::GotoLine(4), &mut state).unwrap();
+        assert_eq!(pane.selected_line, 4);
+        pane.update(Action::GotoLine(1000), &mut state).unwrap();
+        assert_eq!(pane.selected_line, pane.num_lines);
+        assert!(!pane.is_detached());
+
+        // Showing another file detaches the pane from execution, until it is re-synced
+        let loc = ResolvedLocation {
+            source_file: other.clone(),
+            line: 1,
+            col: 1,
+            span: SourceSpan::UNKNOWN,
+        };
+        pane.update(Action::ShowLocation(loc.clone()), &mut state).unwrap();
+        assert_eq!(pane.current_source_id, other.id());
+        assert_eq!(pane.num_lines, 3);
+        assert!(pane.is_detached());
+        pane.update(Action::GotoLine(2), &mut state).unwrap();
+        assert_eq!(pane.selected_line, 2);
+        // Nothing changes until the program moves on
+        pane.update(Action::Update, &mut state).unwrap();
+        assert!(pane.is_detached());
+
+        pane.update(Action::SyncSource, &mut state).unwrap();
+        assert_eq!(pane.current_source_id, main_id);
+        assert_eq!(pane.selected_line, 3);
+        assert!(!pane.is_detached());
+
+        // Stepping re-syncs too
+        pane.update(Action::ShowLocation(loc), &mut state).unwrap();
+        assert!(pane.is_detached());
+        state.create_breakpoint(line(4));
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.current_source_id, main_id);
+        assert_eq!(pane.selected_line, 4);
+        assert!(!pane.is_detached());
+    }
+
+    #[test]
+    fn theme_change_keeps_file_shown() {
+        let (_dir, path) = main_masm("begin\n    push.1\n    drop\nend\n");
+
+        let mut state = State::for_source_file(&path);
+        let source_file = state.source_manager.load_file(&path).unwrap();
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+
+        // A file shown via `file`, before the program has a current location
+        let loc = ResolvedLocation {
+            source_file,
+            line: 0,
+            col: 1,
+            span: SourceSpan::UNKNOWN,
+        };
+        pane.update(Action::ShowLocation(loc), &mut state).unwrap();
+        pane.update(Action::ThemeChanged, &mut state).unwrap();
+        let rows = render_pane(&mut pane, &state, 40, 6);
+        assert!(rows[1].contains("begin"), "{rows:?}");
+        assert!(rows[2].contains("push.1"), "{rows:?}");
+    }
+
+    #[test]
+    fn follow_mode_update() {
+        let (_dir, path) = main_masm("begin\n    push.1\n    push.2\n    add\n    drop\nend\n");
+
+        let mut state = State::for_source_file(&path);
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let mut stop_at = |state: &mut State, line| {
+            state.create_breakpoint(BreakpointType::Line {
+                pattern: Pattern::new(&Pattern::escape(path.to_str().unwrap())).unwrap(),
+                line,
+            });
+            state.run_until_stopped();
+        };
+        stop_at(&mut state, 3);
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!((pane.current_line, pane.selected_line), (3, 3));
+
+        // While not following, stepping only moves the marker of the current line
+        let key = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE);
+        let Some(EventResponse::Stop(action)) = pane.handle_key_events(key, &mut state).unwrap()
+        else {
+            panic!("expected the source pane to handle 'f'");
+        };
+        assert_eq!(action, Action::ToggleFollow);
+        pane.update(action, &mut state).unwrap();
+        assert!(!pane.following);
+        pane.update(Action::GotoLine(1), &mut state).unwrap();
+        stop_at(&mut state, 4);
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!((pane.current_line, pane.selected_line), (4, 1));
+        assert!(!pane.is_detached());
+
+        // Following again returns to the current line, and stepping moves the selection again
+        pane.update(Action::ToggleFollow, &mut state).unwrap();
+        assert!(pane.following);
+        assert_eq!((pane.current_line, pane.selected_line), (4, 4));
+        pane.update(Action::GotoLine(1), &mut state).unwrap();
+        stop_at(&mut state, 5);
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!((pane.current_line, pane.selected_line), (5, 5));
+    }
+
+    #[test]
+    fn scroll_positions_per_file() {
+        let (dir, path) = main_masm("begin\n    push.1\n    push.2\n    add\n    drop\nend\n");
+        let other_path = dir.path().join("other.masm");
+        std::fs::write(&other_path, "proc foo\n    nop\n    nop\nend\n").unwrap();
+
+        let mut state = State::for_source_file(&path);
+        let other = state.source_manager.load_file(&other_path).unwrap();
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        state.create_breakpoint(BreakpointType::Line {
+            pattern: Pattern::new(&Pattern::escape(path.to_str().unwrap())).unwrap(),
+            line: 3,
+        });
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        let main = state.source_manager.get(pane.current_source_id).unwrap();
+        let show = |source_file: &Arc<SourceFile>| {
+            Action::ShowLocation(ResolvedLocation {
+                source_file: source_file.clone(),
+                line: 0,
+                col: 0,
+                span: SourceSpan::UNKNOWN,
+            })
+        };
+
+        // A file shown for the first time is shown from its first line
+        pane.update(Action::GotoLine(5), &mut state).unwrap();
+        pane.update(show(&other), &mut state).unwrap();
+        assert_eq!(pane.selected_line, 1);
+        pane.update(Action::GotoLine(3), &mut state).unwrap();
+
+        // Switching back to a file selects the line last selected in it
+        pane.update(show(&main), &mut state).unwrap();
+        assert_eq!(pane.selected_line, 5);
+        pane.update(show(&main), &mut state).unwrap();
+        assert_eq!(pane.selected_line, 5);
+        pane.update(show(&other), &mut state).unwrap();
+        assert_eq!(pane.selected_line, 3);
+        assert_eq!(pane.scroll_positions, BTreeMap::from([(main.id(), 5), (other.id(), 3)]));
+
+        // Unless a specific line is shown
+        pane.update(Action::SyncSource, &mut state).unwrap();
+        assert_eq!((pane.current_source_id, pane.selected_line), (main.id(), 3));
+    }
+
+    #[test]
+    fn highlight_cache_survives_reload() {
+        let (_dir, path) = main_masm("begin\n    push.1\n    drop\nend\n");
+
+        let mut state = State::for_source_file(&path);
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        state.create_breakpoint(BreakpointType::Line {
+            pattern: Pattern::new(&Pattern::escape(path.to_str().unwrap())).unwrap(),
+            line: 2,
+        });
+        let render = |pane: &mut SourceCodePane, state: &mut State| {
+            state.run_until_stopped();
+            pane.update(Action::Update, state).unwrap();
+            render_pane(pane, state, 40, 9).swap_remove(2)
+        };
+        assert!(render(&mut pane, &mut state).contains("    push.1"));
+        assert_eq!(pane.highlight_cache.len(), 1);
+
+        // Lines highlighted before the program is reloaded are reused, as the file is unchanged,
+        // which is observed by tampering with them
+        let cached = pane.highlight_cache.values_mut().next().unwrap();
+        cached.lines[1] = vec![Span::raw("    cached")];
+        state.reload().unwrap();
+        pane.update(Action::Reload, &mut state).unwrap();
+        assert!(render(&mut pane, &mut state).contains("    cached"));
+
+        // The highlighting of a file is not reused once its content changes
+        let file = |content: &str| {
+            use miden_debug_types::{SourceLanguage, Uri};
+            SourceFile::new(SourceId::UNKNOWN, SourceLanguage::Masm, Uri::from("a.masm"), content)
+        };
+        assert_eq!(cache_key(&file("begin nop end")), cache_key(&file("begin nop end")));
+        assert_ne!(cache_key(&file("begin nop end")), cache_key(&file("begin add end")));
+    }
+
+    #[test]
+    fn highlight_lines_in_view() {
+        use miden_debug_types::{SourceLanguage, Uri};
+
+        let mut state = State::for_source("begin push.1 drop end");
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let text = (1..=10_000).map(|line| format!("nop # {line}\n")).collect::<String>();
+        let source_file =
+            state.source_manager.load(SourceLanguage::Masm, Uri::from("large.masm"), text);
+        let loc = ResolvedLocation {
+            span: SourceSpan::at(source_file.id(), 0u32),
+            source_file,
+            line: 1,
+            col: 1,
+        };
+        pane.follow(&loc);
+        let highlighted = |pane: &SourceCodePane| {
+            let key = pane.current_file.as_ref().unwrap().key;
+            pane.highlight_cache[&key].lines.len()
+        };
+
+        // Only the lines in view, and those just below them, are highlighted
+        assert_eq!(render_highlighted(&mut pane, &state)[0], "nop # 1");
+        assert_eq!(highlighted(&pane), 9 + HIGHLIGHT_AHEAD);
+        pane.update(Action::GotoLine(5_000), &mut state).unwrap();
+        render_highlighted(&mut pane, &state);
+        assert_eq!(highlighted(&pane), 4_999 + 9 + HIGHLIGHT_AHEAD);
+        pane.update(Action::GotoLine(1), &mut state).unwrap();
+        render_highlighted(&mut pane, &state);
+        assert_eq!(highlighted(&pane), 4_999 + 9 + HIGHLIGHT_AHEAD);
+    }
+
+    #[test]
+    fn draw_lines_in_view() {
+        use miden_debug_types::{SourceLanguage, Uri};
+
+        let mut state = State::for_source("begin push.1 drop end");
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let text = (1..=10_000).map(|line| format!("nop # {line}\n")).collect::<String>();
+        let start = text.find("nop # 5000\n").unwrap() as u32;
+        let source_file =
+            state.source_manager.load(SourceLanguage::Masm, Uri::from("large.masm"), text);
+        let loc = ResolvedLocation {
+            span: SourceSpan::new(source_file.id(), start..(start + 14)),
+            source_file,
+            line: 5_000,
+            col: 1,
+        };
+        pane.follow(&loc);
+        let render = |pane: &mut SourceCodePane, state: &State| {
+            let buffer = draw_pane(pane, state, 24, 6);
+            (1..(buffer.area.height - 1))
+                .map(|y| (1..(buffer.area.width - 1)).map(|x| buffer[(x, y)].symbol()).collect())
+                .collect::<Vec<String>>()
+        };
+
+        // The view is the same as if every line of the file were rendered
+        assert_eq!(
+            render(&mut pane, &state),
+            [
+                "   4998 | nop # 4998  ",
+                "   4999 | nop # 4999  ",
+                "→  5000 | nop # 5000  ",
+                "   5001 | nop # 5001  ",
+            ]
+        );
+        pane.update(Action::GotoLine(2), &mut state).unwrap();
+        assert_eq!(render(&mut pane, &state)[0], "      1 | nop # 1     ");
+        pane.update(Action::GotoLine(10_000), &mut state).unwrap();
+        assert_eq!(render(&mut pane, &state)[3], "→ 10000 | nop # 10000 ");
+
+        // Only the lines covered by the current location are highlighted with it, which is done
+        // once, rather than each time they are drawn
+        pane.update(Action::GotoLine(5_000), &mut state).unwrap();
+        render(&mut pane, &state);
+        let span_lines = pane.span_lines.as_mut().unwrap();
+        assert_eq!(span_lines.lines.keys().copied().collect::<Vec<_>>(), [4_999, 5_000]);
+        span_lines.lines.insert(4_999, vec![Span::raw("cached")]);
+        assert!(render(&mut pane, &state)[2].contains("| cached"));
+
+        // Once the program moves on, they are highlighted anew
+        let mut loc = loc;
+        loc.span = SourceSpan::at(loc.source_file.id(), start);
+        pane.follow(&loc);
+        assert!(render(&mut pane, &state)[2].contains("| nop # 5000"));
+        assert_eq!(pane.span_lines.as_ref().unwrap().lines.len(), 1);
+    }
+
+    #[test]
+    fn mouse_selects_lines() {
+        use miden_debug_types::{SourceLanguage, Uri};
+
+        let mut state = State::for_source("begin push.1 drop end");
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let text = (1..=100).map(|line| format!("nop # {line}\n")).collect::<String>();
+        let start = text.find("nop # 50\n").unwrap() as u32;
+        let source_file =
+            state.source_manager.load(SourceLanguage::Masm, Uri::from("large.masm"), text);
+        let source_id = source_file.id();
+        pane.follow(&ResolvedLocation {
+            span: SourceSpan::new(source_id, start..(start + 8)),
+            source_file,
+            line: 50,
+            col: 1,
+        });
+        draw_pane(&mut pane, &state, 24, 6);
+
+        // Lines 48 to 51 are in view, below the border, followed by the border
+        assert_eq!(pane.lines_in_view, 47..51);
+        assert_eq!(pane.line_at(10, 0), None);
+        assert_eq!(pane.line_at(10, 1), Some((48, false)));
+        assert_eq!(pane.line_at(10, 4), Some((51, false)));
+        assert_eq!(pane.line_at(10, 5), None);
+        // The gutter spans the markers and the line number, but not the borders
+        assert_eq!(pane.line_at(0, 2), None);
+        assert_eq!(pane.line_at(1, 2), Some((49, true)));
+        assert_eq!(pane.line_at(5, 2), Some((49, true)));
+        assert_eq!(pane.line_at(6, 2), Some((49, false)));
+        assert_eq!(pane.line_at(23, 2), None);
+
+        // Clicking a line selects it, and clicking its number toggles a breakpoint on it
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let response = pane.handle_mouse_events(click(10, 4), &mut state).unwrap();
+        assert!(matches!(response, Some(EventResponse::Stop(Action::Update))));
+        assert_eq!(pane.selected_line, 51);
+        let response = pane.handle_mouse_events(click(3, 1), &mut state).unwrap();
+        assert!(matches!(
+            response,
+            Some(EventResponse::Stop(Action::ToggleBreakpoint(id, 48))) if id == source_id
+        ));
+        assert_eq!(pane.selected_line, 48);
+        assert!(pane.handle_mouse_events(click(10, 5), &mut state).unwrap().is_none());
+    }
+
+    #[test]
+    fn highlight_span_crossing_multibyte_characters() {
+        use miden_debug_types::{SourceLanguage, Uri};
+
+        let mut state = State::for_source("begin push.1 drop end");
+        let text = "begin\n    push.1 # é → x\nend\n";
+        let source_file =
+            state
+                .source_manager
+                .load(SourceLanguage::Masm, Uri::from("unicode.masm"), text.into());
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+
+        // A span which starts and ends in the middle of multi-byte characters
+        let start = text.find('é').unwrap() as u32 + 1;
+        let loc = ResolvedLocation {
+            span: SourceSpan::new(source_file.id(), start..(start + 3)),
+            source_file,
+            line: 2,
+            col: 14,
+        };
+        pane.follow(&loc);
+        assert_eq!(render_highlighted(&mut pane, &state)[1], "é →");
+
+        let line = render_pane(&mut pane, &state, 40, 6).swap_remove(2);
+        assert!(line.contains("push.1 # é → x"), "unexpected output: {line:?}");
+        pane.update(Action::Down, &mut state).unwrap();
+        draw_pane(&mut pane, &state, 40, 6);
+    }
+
+    #[test]
+    fn highlight_wide_characters_and_tabs() {
+        use miden_debug_types::{SourceLanguage, Uri};
+
+        let mut state = State::for_source("begin push.1 drop end");
+        let text = "begin\n\tpush.1 # 漢字 🎉 cafe\u{301}\r\n\t\tdrop # 字\tx\nend\n";
+        let source_file =
+            state
+                .source_manager
+                .load(SourceLanguage::Masm, Uri::from("wide.masm"), text.into());
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let mut highlight = |state: &State, from: &str, len: usize| {
+            let start = text.find(from).unwrap() as u32;
+            let loc = ResolvedLocation {
+                span: SourceSpan::new(source_file.id(), start..(start + len as u32)),
+                source_file: source_file.clone(),
+                line: 2,
+                col: 1,
+            };
+            pane.follow(&loc);
+            let highlighted = render_highlighted(&mut pane, state);
+            let buffer = draw_pane(&mut pane, state, 40, 9);
+            // The cells following those of wide characters are skipped
+            let rows = (2..4)
+                .map(|y| {
+                    let mut row = String::new();
+                    let mut x = 0;
+                    while x < buffer.area.width {
+                        let symbol = buffer[(x, y)].symbol();
+                        row.push_str(symbol);
+                        x += symbol.width().max(1) as u16;
+                    }
+                    row
+                })
+                .collect::<Vec<_>>();
+            (highlighted[1].clone(), rows)
+        };
+
+        // Wide characters both before and inside the span, which ends within a wide character
+        let (highlighted, rows) = highlight(&state, "字 🎉", "字 🎉".len() - 1);
+        assert_eq!(highlighted, "字 🎉");
+        // Tabs are expanded to the next tab stop, taking the width of wide characters into account
+        assert!(rows[0].contains("|     push.1 # 漢字 🎉 cafe\u{301} "), "{rows:?}");
+        assert!(rows[1].contains("|         drop # 字   x "), "{rows:?}");
+
+        // A span starting on a combining mark covers the character it applies to
+        let (highlighted, _) = highlight(&state, "\u{301}", 2);
+        assert_eq!(highlighted, "e\u{301}");
+
+        state.config.tab_width = Some(2);
+        let (_, rows) = highlight(&state, "push", 4);
+        assert!(rows[0].contains("|   push.1 # 漢字"), "{rows:?}");
+        assert!(rows[1].contains("|     drop # 字 x "), "{rows:?}");
+    }
+
+    /// Render `pane`, returning the text of each line of the file shown which is highlighted as
+    /// part of the current span
+    fn render_highlighted(pane: &mut SourceCodePane, state: &State) -> Vec<String> {
+        let selected = pane.theme.current_span.bg;
+        let buffer = draw_pane(pane, state, 40, 9);
+        (1..(buffer.area.height - 1))
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| &buffer[(x, y)])
+                    .filter(|cell| Some(cell.bg) == selected)
+                    .map(|cell| cell.symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn highlight_spans_covering_several_lines() {
+        use miden_debug_types::{SourceLanguage, Uri};
+
+        let mut state = State::for_source("begin push.1 drop end");
+        let text = "begin\n    push.1\n    push.2\n    add\n    push.3\n    drop\nend\n";
+        let source_file =
+            state
+                .source_manager
+                .load(SourceLanguage::Masm, Uri::from("lines.masm"), text.into());
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let mut highlight = |from: &str, to: &str, line: u32| {
+            let start = text.find(from).unwrap();
+            let end = start + text[start..].find(to).unwrap() + to.len();
+            let loc = ResolvedLocation {
+                span: SourceSpan::new(source_file.id(), (start as u32)..(end as u32)),
+                source_file: source_file.clone(),
+                line,
+                col: 1,
+            };
+            // Force the file to be highlighted again
+            pane.current_file = None;
+            pane.follow(&loc);
+            render_highlighted(&mut pane, &state)
+        };
+
+        let highlighted = highlight("push.2", "push.2", 3);
+        assert_eq!(highlighted, ["", "", "push.2", "", "", "", ""]);
+
+        let highlighted = highlight("1\n", "    push", 2);
+        assert_eq!(highlighted, ["", "1", "    push", "", "", "", ""]);
+
+        let highlighted = highlight("push.1", "drop", 2);
+        assert_eq!(
+            highlighted,
+            ["", "push.1", "    push.2", "    add", "    push.3", "    drop", ""]
+        );
+
+        // The current span is highlighted on the selected line, wherever it is within the span
+        pane.update(Action::GotoLine(4), &mut state).unwrap();
+        assert_eq!(render_highlighted(&mut pane, &state)[3], "    add");
+        pane.update(Action::GotoLine(7), &mut state).unwrap();
+        assert_eq!(render_highlighted(&mut pane, &state)[6], "");
+    }
+
+    #[test]
+    fn last_line_and_gutter_width() {
+        use miden_debug_types::{SourceLanguage, Uri};
+
+        let mut state = State::for_source("begin push.1 drop end");
+        let mut pane = SourceCodePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+
+        // The last line of a file without a trailing newline can be the current location
+        let text = "begin\n    push.1\nend";
+        let source_file =
+            state
+                .source_manager
+                .load(SourceLanguage::Masm, Uri::from("last.masm"), text.into());
+        let loc = ResolvedLocation {
+            span: SourceSpan::new(source_file.id(), 17u32..20),
+            source_file,
+            line: 3,
+            col: 1,
+        };
+        pane.follow(&loc);
+        assert_eq!(pane.num_lines, 3);
+        assert_eq!(render_highlighted(&mut pane, &state), ["", "", "end", "", "", "", ""]);
+
+        for (num_lines, width) in [(9, 1), (10, 2), (99, 2), (100, 3)] {
+            let text = (1..=num_lines).map(|line| format!("nop # {line}\n")).collect::<String>();
+            let uri = Uri::from(format!("lines{num_lines}.masm"));
+            let source_file = state.source_manager.load(SourceLanguage::Masm, uri, text);
+            let loc = ResolvedLocation {
+                span: SourceSpan::at(source_file.id(), 0u32),
+                source_file,
+                line: 1,
+                col: 1,
+            };
+            pane.follow(&loc);
+            assert_eq!(pane.num_lines, num_lines);
+            assert_eq!(pane.current_file.as_ref().unwrap().gutter_width, width);
+
+            pane.update(Action::GotoLine(num_lines), &mut state).unwrap();
+            let rows = render_pane(&mut pane, &state, 30, 4)[1..3].to_vec();
+            let last = format!(" {num_lines} | nop # {num_lines}");
+            let previous =
+                format!(" {:>w$} | nop # {}", num_lines - 1, num_lines - 1, w = width as usize);
+            assert!(rows[1].contains(&last), "unexpected output: {rows:#?}");
+            assert!(rows[0].contains(&previous), "unexpected output: {rows:#?}");
+        }
     }
 }
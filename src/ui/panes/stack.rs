@@ -1,15 +1,38 @@
+use std::collections::BTreeSet;
+
 use miden_assembly_syntax::diagnostics::Report;
-use miden_core::field::PrimeField64;
+use miden_processor::Felt;
 use ratatui::{
     prelude::*,
     widgets::{block::*, *},
 };
 
-use crate::ui::{action::Action, panes::Pane, state::State, tui::Frame};
+use crate::{
+    debug::FormatType,
+    exec::StackProvenance,
+    ui::{action::Action, clipboard, layout::PaneId, panes::Pane, state::State, tui::Frame},
+};
 
+/// The operand stack, from the top, with each element shown in both decimal and hexadecimal.
+///
+/// Elements pushed by the most recent operation are shown in green, and those which changed during
+/// the most recent run in yellow, see [State::changes]. Once the program terminates successfully, its stack outputs are
+/// shown instead.
 pub struct OperandStackPane {
     focused: bool,
     focused_border_style: Style,
+    /// Whether adjacent elements which look like the limbs of a `u64` are shown combined
+    show_u64: bool,
+    /// The elements shown, from the top, as of the last update
+    stack: Vec<Felt>,
+    /// The index of the selected element, from the top
+    selected: usize,
+    /// The indices of the elements which changed in the most recent run
+    changed: BTreeSet<usize>,
+    /// The indices of the elements pushed by the most recent operation
+    pushed: BTreeSet<usize>,
+    /// The cycle at which `stack` was read
+    cycle: usize,
 }
 
 impl OperandStackPane {
@@ -17,6 +40,12 @@ impl OperandStackPane {
         Self {
             focused,
             focused_border_style,
+            show_u64: false,
+            stack: vec![],
+            selected: 0,
+            changed: BTreeSet::new(),
+            pushed: BTreeSet::new(),
+            cycle: 0,
         }
     }
 
@@ -33,17 +62,78 @@ impl OperandStackPane {
             false => BorderType::Plain,
         }
     }
+
+    /// Read the stack as of the current cycle.
+    ///
+    /// The elements changed by the most recent run are highlighted, and if the program has advanced
+    /// since the last refresh, so are those pushed by the most recent operation, until the next
+    /// time it advances.
+    fn refresh(&mut self, state: &State) {
+        let stack = match has_outputs(state) {
+            true => state.executor.stack_outputs.to_vec(),
+            false => state.executor.current_stack.clone(),
+        };
+        self.changed = state.changes.stack.clone();
+        // Nothing is highlighted until there is a previous stack to compare against
+        if self.stack.is_empty() {
+            self.pushed.clear();
+        } else if self.cycle != state.executor.cycle {
+            self.pushed = match has_outputs(state) {
+                true => BTreeSet::new(),
+                false => state
+                    .executor
+                    .stack_provenance()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, provenance)| *provenance == StackProvenance::Pushed)
+                    .map(|(index, _)| index)
+                    .collect(),
+            };
+        }
+        self.stack = stack;
+        self.selected = self.selected.min(self.stack.len().saturating_sub(1));
+        self.cycle = state.executor.cycle;
+    }
 }
 
-impl Pane for OperandStackPane {
-    fn height_constraint(&self) -> Constraint {
-        match self.focused {
-            true => Constraint::Max(30),
-            false => Constraint::Max(30),
+/// Whether the program has terminated successfully, i.e. its stack outputs are known
+fn has_outputs(state: &State) -> bool {
+    state.executor.stopped && state.execution_failed.is_none()
+}
+
+/// Pair up the elements of `stack`, from the top, which look like the limbs of a `u64`, i.e. a
+/// `u32` low limb followed by a non-zero `u32` high limb.
+///
+/// Returns the combined value at the index of the low limb of each pair. Each element belongs to
+/// at most one pair.
+fn u64_pairs(stack: &[Felt]) -> Vec<Option<u64>> {
+    let mut pairs = vec![None; stack.len()];
+    let mut index = 0;
+    while index + 1 < stack.len() {
+        let lo = stack[index].as_canonical_u64();
+        let hi = stack[index + 1].as_canonical_u64();
+        if lo <= u32::MAX as u64 && hi <= u32::MAX as u64 && hi != 0 {
+            pairs[index] = Some(lo | (hi << 32));
+            index += 2;
+        } else {
+            index += 1;
         }
     }
+    pairs
+}
 
-    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+impl Pane for OperandStackPane {
+    fn init(&mut self, state: &State) -> Result<(), Report> {
+        self.stack.clear();
+        self.selected = 0;
+        self.changed.clear();
+        self.pushed.clear();
+        self.cycle = state.executor.cycle;
+        self.refresh(state);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
         match action {
             Action::Focus => {
                 self.focused = true;
@@ -51,6 +141,27 @@ impl Pane for OperandStackPane {
             Action::UnFocus => {
                 self.focused = false;
             }
+            Action::ToggleStackPairs => {
+                self.show_u64 = !self.show_u64;
+                let status = match self.show_u64 {
+                    true => "u64 values are shown",
+                    false => "u64 values are hidden",
+                };
+                return Ok(Some(Action::TimedStatusLine(status.into(), 3)));
+            }
+            Action::Down => {
+                self.selected = (self.selected + 1).min(self.stack.len().saturating_sub(1));
+            }
+            Action::Up => self.selected = self.selected.saturating_sub(1),
+            Action::Yank => {
+                if let Some(felt) = self.stack.get(self.selected) {
+                    let what = format!("element {} of the stack", self.selected);
+                    let text = state.format_felt(*felt);
+                    return Ok(Some(clipboard::yank(state, &what, text)));
+                }
+            }
+            Action::Update => self.refresh(state),
+            Action::Reload => self.init(state)?,
             _ => {}
         }
 
@@ -58,33 +169,67 @@ impl Pane for OperandStackPane {
     }
 
     fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
-        let lines: Vec<Line<'_>> = if state.executor.current_stack.is_empty() {
-            vec![]
-        } else {
-            state
-                .executor
-                .current_stack
-                .iter()
-                .rev()
-                .map(|item| {
-                    Line::from(Span::styled(format!(" {}", item.as_canonical_u64()), Color::White))
-                })
-                .collect()
+        let alternate = |felt: &Felt| match state.display_format {
+            FormatType::Decimal => format!("{:#x}", felt.as_canonical_u64()),
+            _ => felt.as_canonical_u64().to_string(),
+        };
+        let rows = self
+            .stack
+            .iter()
+            .map(|felt| (state.format_felt(*felt), alternate(felt)))
+            .collect::<Vec<_>>();
+        let width = rows.iter().map(|(value, _)| value.len()).max().unwrap_or_default();
+        let alternate_width = rows.iter().map(|(_, value)| value.len()).max().unwrap_or_default();
+        let index_width = self.stack.len().saturating_sub(1).to_string().len();
+        let pairs = match self.show_u64 {
+            true => u64_pairs(&self.stack),
+            false => vec![],
         };
 
+        let lines = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, (value, alternate))| {
+                let style = if self.pushed.contains(&index) {
+                    Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+                } else if self.changed.contains(&index) {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    state.theme.styles.value
+                };
+                let mut spans = vec![
+                    Span::styled(format!("{index:>index_width$} "), Color::Cyan),
+                    Span::styled(format!("{value:>width$}"), style),
+                    Span::styled(format!("  {alternate:>alternate_width$}"), Color::DarkGray),
+                ];
+                if let Some(Some(value)) = pairs.get(index) {
+                    let value = match state.display_format {
+                        FormatType::Decimal => value.to_string(),
+                        _ => format!("{value:#x}"),
+                    };
+                    spans.push(Span::styled(format!("  u64:{value}"), Color::Magenta));
+                }
+                Line::from(spans)
+            })
+            .collect::<Vec<_>>();
+
         let depth = lines.len();
-        let selected_line = depth.saturating_sub(1);
         let list = List::new(lines)
             .block(Block::default().borders(Borders::ALL))
             .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
             .highlight_spacing(HighlightSpacing::Always)
             .highlight_style(Style::default().add_modifier(Modifier::BOLD));
-        let mut list_state = ListState::default().with_selected(Some(selected_line));
+        let mut list_state =
+            ListState::default().with_selected((depth > 0).then_some(self.selected));
 
+        let title = match has_outputs(state) {
+            true => "Stack Outputs",
+            false => "Operand Stack",
+        };
         frame.render_stateful_widget(list, area, &mut list_state);
         frame.render_widget(
             Block::default()
-                .title("Operand Stack")
+                .title(PaneId::Stack.numbered(title))
                 .borders(Borders::ALL)
                 .border_style(self.border_style())
                 .border_type(self.border_type())
@@ -100,3 +245,74 @@ impl Pane for OperandStackPane {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debug::BreakpointType;
+
+    fn felts(values: &[u64]) -> Vec<Felt> {
+        values.iter().copied().map(Felt::new).collect()
+    }
+
+    #[test]
+    fn stack_u64_pairs() {
+        let u32_max = u32::MAX as u64;
+        // The low limb is on top, i.e. at the lower index
+        assert_eq!(u64_pairs(&felts(&[2, 1])), [Some((1 << 32) | 2), None]);
+        // A zero high limb does not make a pair, as the low limb alone has the same value
+        assert_eq!(u64_pairs(&felts(&[2, 0, 0])), [None, None, None]);
+        // Elements which are not valid u32 values cannot be limbs
+        assert_eq!(u64_pairs(&felts(&[u32_max + 1, 1, 1])), [None, Some((1 << 32) | 1), None]);
+        assert_eq!(u64_pairs(&felts(&[1, u32_max + 1])), [None, None]);
+        // Each element belongs to at most one pair, from the top
+        assert_eq!(
+            u64_pairs(&felts(&[u32_max, u32_max, 3, 4])),
+            [Some(u64::MAX), None, Some((4 << 32) | 3), None]
+        );
+        assert_eq!(u64_pairs(&felts(&[5])), [None]);
+    }
+
+    #[test]
+    fn stack_pane_highlights_pushed_elements() {
+        let mut state = State::for_source("begin push.1 push.2 swap drop drop end");
+        let mut pane = OperandStackPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        let mut step = |state: &mut State, pane: &mut OperandStackPane| {
+            state.create_breakpoint(BreakpointType::Step);
+            state.run_until_stopped();
+            pane.update(Action::Update, state).unwrap();
+            state.executor.current_op.map(|op| op.to_string())
+        };
+        while step(&mut state, &mut pane).as_deref() != Some("push(2)") {}
+        assert_eq!(pane.pushed, BTreeSet::from([0]));
+        assert!(pane.changed.contains(&0));
+
+        assert_eq!(step(&mut state, &mut pane).as_deref(), Some("swap"));
+        assert!(pane.pushed.is_empty());
+        assert_eq!(pane.changed, BTreeSet::from([0, 1]));
+    }
+
+    #[test]
+    fn stack_pane_shows_outputs_once_terminated() {
+        let mut state = State::for_source("begin push.2 push.1 movup.2 drop movup.2 drop end");
+        let mut pane = OperandStackPane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        state.create_breakpoint(BreakpointType::StepN(2));
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        assert!(pane.changed.is_empty());
+
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        assert!(state.executor.stopped);
+        assert_eq!(pane.stack, state.executor.stack_outputs.to_vec());
+        assert_eq!(pane.stack[..2], felts(&[1, 2]));
+        assert_eq!(pane.changed, BTreeSet::from([0, 1]));
+        assert!(pane.pushed.is_empty());
+
+        pane.update(Action::ToggleStackPairs, &mut state).unwrap();
+        assert!(pane.show_u64);
+        assert_eq!(u64_pairs(&pane.stack)[0], Some((2 << 32) | 1));
+    }
+}
@@ -4,11 +4,37 @@ use ratatui::{
     widgets::{block::*, *},
 };
 
-use crate::ui::{action::Action, panes::Pane, state::State, tui::Frame};
+use crate::ui::{action::Action, clipboard, layout::PaneId, panes::Pane, state::State, tui::Frame};
 
+/// The call stack, innermost frame first, from which a frame can be selected to show its location
+/// in the source code pane
 pub struct StackTracePane {
     focused: bool,
     focused_border_style: Style,
+    /// The index of the selected row, see [frame_rows]
+    selected: usize,
+}
+
+/// A row of the [StackTracePane], i.e. a run of consecutive frames of the same procedure, such as
+/// those of a recursive procedure, which are shown once
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct FrameRow {
+    /// The index of the innermost frame of the run, innermost first
+    frame: usize,
+    /// The number of frames in the run
+    count: usize,
+}
+
+/// Collapse the frames named by `names`, innermost first, into rows
+fn frame_rows(names: &[Option<String>]) -> Vec<FrameRow> {
+    let mut rows = Vec::<FrameRow>::new();
+    for (frame, name) in names.iter().enumerate() {
+        match rows.last_mut() {
+            Some(row) if name.is_some() && names[row.frame] == *name => row.count += 1,
+            _ => rows.push(FrameRow { frame, count: 1 }),
+        }
+    }
+    rows
 }
 
 impl StackTracePane {
@@ -16,9 +42,37 @@ impl StackTracePane {
         Self {
             focused,
             focused_border_style,
+            selected: 0,
         }
     }
 
+    fn rows(state: &State) -> Vec<FrameRow> {
+        let names = state
+            .executor
+            .callstack
+            .frames()
+            .iter()
+            .rev()
+            .map(|frame| frame.procedure("").map(|name| name.to_string()))
+            .collect::<Vec<_>>();
+        frame_rows(&names)
+    }
+
+    /// The name of the frame at `index`, innermost first, and its location if known, e.g.
+    /// `$exec::$main at add.masm:4:5`
+    fn describe_frame(index: usize, state: &State) -> String {
+        let frames = state.executor.callstack.frames();
+        let frame = &frames[frames.len() - 1 - index];
+        let name = frame.procedure("");
+        let mut text = name.as_deref().unwrap_or("<unknown>").to_string();
+        if let Some(resolved) = frame.last_resolved(&state.source_manager) {
+            let path = std::path::Path::new(resolved.source_file.as_ref().uri().as_str());
+            let path = path.strip_prefix(state.config.working_dir()).ok().unwrap_or(path);
+            text.push_str(&format!(" at {}:{}:{}", path.display(), resolved.line, resolved.col));
+        }
+        text
+    }
+
     fn border_style(&self) -> Style {
         match self.focused {
             true => self.focused_border_style,
@@ -35,14 +89,8 @@ impl StackTracePane {
 }
 
 impl Pane for StackTracePane {
-    fn height_constraint(&self) -> Constraint {
-        match self.focused {
-            true => Constraint::Max(15),
-            false => Constraint::Max(15),
-        }
-    }
-
-    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+    fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
+        let rows = Self::rows(state);
         match action {
             Action::Focus => {
                 self.focused = true;
@@ -50,6 +98,31 @@ impl Pane for StackTracePane {
             Action::UnFocus => {
                 self.focused = false;
             }
+            Action::Down if !rows.is_empty() => {
+                self.selected = (self.selected + 1) % rows.len();
+            }
+            Action::Up if !rows.is_empty() => {
+                self.selected = (self.selected + rows.len() - 1) % rows.len();
+            }
+            Action::Submit => {
+                if let Some(row) = rows.get(self.selected) {
+                    return Ok(Some(Action::SelectFrame(row.frame)));
+                }
+            }
+            Action::Yank => {
+                if let Some(row) = rows.get(self.selected) {
+                    let text = Self::describe_frame(row.frame, state);
+                    let what = format!("frame #{}", row.frame);
+                    return Ok(Some(clipboard::yank(state, &what, text)));
+                }
+            }
+            // The selected frame is reset whenever the program advances
+            Action::Update | Action::Reload | Action::SelectFrame(_) => {
+                self.selected = rows
+                    .iter()
+                    .rposition(|row| row.frame <= state.selected_frame)
+                    .unwrap_or_default();
+            }
             _ => {}
         }
 
@@ -58,9 +131,11 @@ impl Pane for StackTracePane {
 
     fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
         let mut lines = Vec::default();
-        let num_frames = state.executor.callstack.frames().len();
-        for (i, frame) in state.executor.callstack.frames().iter().enumerate() {
-            let is_top = i + 1 == num_frames;
+        let frames = state.executor.callstack.frames();
+        let rows = Self::rows(state);
+        for row in rows.iter() {
+            let frame = &frames[frames.len() - 1 - row.frame];
+            let is_top = row.frame == 0;
             let mut parts = vec![];
             /*
             let gutter = if is_top {
@@ -76,12 +151,12 @@ impl Pane for StackTracePane {
             let name = if is_top {
                 Span::styled(name, Color::Gray)
             } else {
-                Span::styled(
-                    name,
-                    Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD),
-                )
+                Span::styled(name, state.theme.styles.frame)
             };
             parts.push(name);
+            if row.count > 1 {
+                parts.push(Span::styled(format!(" (x{})", row.count), Color::Magenta));
+            }
             if let Some(resolved) = frame.last_resolved(&state.source_manager) {
                 parts.push(Span::styled(" in ", Color::DarkGray));
                 let path = std::path::Path::new(resolved.source_file.as_ref().uri().as_str());
@@ -125,24 +200,101 @@ impl Pane for StackTracePane {
             lines.push(Line::from(parts));
         }
 
-        let selected_line = lines.len().saturating_sub(1);
+        let selected_line = (!lines.is_empty()).then(|| self.selected.min(lines.len() - 1));
 
         let list = List::new(lines)
             .block(Block::default().borders(Borders::ALL))
             .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
             .highlight_spacing(HighlightSpacing::Always)
             .highlight_style(Style::default().add_modifier(Modifier::BOLD));
-        let mut list_state = ListState::default().with_selected(Some(selected_line));
+        let mut list_state = ListState::default().with_selected(selected_line);
 
         frame.render_stateful_widget(list, area, &mut list_state);
         frame.render_widget(
             Block::default()
-                .title("Stack Trace")
+                .title(PaneId::CallStack.numbered("Stack Trace"))
                 .borders(Borders::ALL)
                 .border_style(self.border_style())
-                .border_type(self.border_type()),
+                .border_type(self.border_type())
+                .title_bottom(
+                    Line::styled(
+                        "enter shows frame",
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    )
+                    .right_aligned(),
+                ),
             area,
         );
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debug::BreakpointType;
+
+    #[test]
+    fn collapse_recursive_frames() {
+        let names = ["fib", "fib", "fib", "main"].map(|name| Some(name.to_string()));
+        assert_eq!(
+            frame_rows(&names),
+            [FrameRow { frame: 0, count: 3 }, FrameRow { frame: 3, count: 1 }]
+        );
+        // Frames whose procedure is unknown are never collapsed
+        assert_eq!(
+            frame_rows(&[None, None]),
+            [FrameRow { frame: 0, count: 1 }, FrameRow { frame: 1, count: 1 }]
+        );
+        assert!(frame_rows(&[]).is_empty());
+    }
+
+    #[test]
+    fn select_frame_with_wrap_around() {
+        // Frames are delimited by the frame start and end trace events emitted by the compiler
+        let mut state = State::for_source(
+            "
+proc inner
+    trace.240 push.1 drop trace.252
+end
+
+proc outer
+    trace.240 nop exec.inner trace.252
+end
+
+begin
+    exec.outer
+end
+",
+        );
+        state.create_breakpoint(BreakpointType::Called(glob::Pattern::new("*inner").unwrap()));
+        state.run_until_stopped();
+        assert_eq!(state.executor.callstack.frames().len(), 3);
+
+        let mut pane = StackTracePane::new(true, Style::default());
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.selected, 0);
+        pane.update(Action::Up, &mut state).unwrap();
+        assert_eq!(pane.selected, 2);
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.selected, 0);
+        pane.update(Action::Down, &mut state).unwrap();
+        assert_eq!(pane.update(Action::Submit, &mut state).unwrap(), Some(Action::SelectFrame(1)));
+
+        state.selected_frame = 1;
+        let frame = state.selected_frame().and_then(|frame| frame.procedure(""));
+        assert!(
+            frame.as_ref().is_some_and(|name| name.ends_with("outer")),
+            "unexpected frame: {frame:?}"
+        );
+        pane.update(Action::SelectFrame(1), &mut state).unwrap();
+        assert_eq!(pane.selected, 1);
+
+        // The selection is reset to the innermost frame once the program advances
+        state.create_breakpoint(BreakpointType::Step);
+        state.run_until_stopped();
+        assert_eq!(state.selected_frame, 0);
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.selected, 0);
+    }
+}
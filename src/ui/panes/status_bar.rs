@@ -0,0 +1,228 @@
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::prelude::*;
+
+use crate::ui::{
+    panes::Pane,
+    state::{ExecutionStatus, State},
+    tui::Frame,
+};
+
+/// The text separating the fields of the status bar
+const SEPARATOR: &str = " │ ";
+
+/// The fewest characters to which the name of the procedure is shortened before it is dropped
+const MIN_PROCEDURE_WIDTH: usize = 8;
+
+/// A single line summarizing where execution is: the status of the program, the current cycle,
+/// the innermost procedure, the current context, and the number of enabled breakpoints.
+///
+/// The status bar is drawn from the program state each frame, so it is always up to date. Fields
+/// which do not fit are dropped, see [layout].
+#[derive(Default)]
+pub struct StatusBarPane;
+
+impl StatusBarPane {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// A field of the status bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Field {
+    text: String,
+    style: Style,
+    /// Fields are dropped in ascending order of priority when they do not fit, except those of
+    /// priority 0, which are always shown
+    priority: u8,
+    /// Whether the field is the name of a procedure, which is shortened before it is dropped
+    procedure: bool,
+}
+
+impl Field {
+    fn new(text: impl Into<String>, style: Style, priority: u8) -> Self {
+        Self {
+            text: text.into(),
+            style,
+            priority,
+            procedure: false,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.text.chars().count()
+    }
+}
+
+/// The fields of the status bar for `state`, in the order they are shown
+fn fields(state: &State) -> Vec<Field> {
+    let (status, color) = match state.status() {
+        ExecutionStatus::Running => ("running", Color::LightYellow),
+        ExecutionStatus::Paused => ("stopped", Color::LightBlue),
+        ExecutionStatus::Halted => ("terminated", Color::LightGreen),
+        ExecutionStatus::Failed => ("error", Color::LightRed),
+    };
+    let cycle = state.executor.cycle;
+    let cycle = match state.execution_trace.as_ref() {
+        Some(trace) => format!("cycle {cycle}/{}", trace.last_cycle().as_usize()),
+        None => format!("cycle {cycle}"),
+    };
+    let breakpoints =
+        state.breakpoints.iter().filter(|bp| bp.enabled && !bp.ty.is_internal()).count();
+    let breakpoints = match breakpoints {
+        1 => "1 breakpoint".to_string(),
+        n => format!("{n} breakpoints"),
+    };
+
+    let mut fields = vec![
+        Field::new(status, Style::default().fg(color).add_modifier(Modifier::BOLD), 0),
+        Field::new(cycle, Style::default(), 0),
+    ];
+    let procedure = state.executor.callstack.frames().last().and_then(|frame| frame.procedure(""));
+    if let Some(procedure) = procedure {
+        fields.push(Field {
+            procedure: true,
+            ..Field::new(procedure.as_ref(), state.theme.styles.value, 3)
+        });
+    }
+    fields.push(Field::new(
+        format!("context {}", state.executor.current_context),
+        Style::default(),
+        2,
+    ));
+    fields.push(Field::new(breakpoints, Style::default(), 1));
+    fields
+}
+
+/// The width of `fields` when shown separated by [SEPARATOR]
+fn total_width(fields: &[Field]) -> usize {
+    let separators = fields.len().saturating_sub(1) * SEPARATOR.chars().count();
+    fields.iter().map(Field::width).sum::<usize>() + separators
+}
+
+/// Fit `fields` in `width` columns by dropping those of least priority, first the number of
+/// breakpoints, then the context, and then the procedure, whose name is shortened instead if it
+/// can be to at least [MIN_PROCEDURE_WIDTH] characters.
+///
+/// The status and cycle are always shown, and are truncated by the terminal if even they do not
+/// fit.
+fn layout(mut fields: Vec<Field>, width: usize) -> Vec<Field> {
+    while total_width(&fields) > width {
+        let Some(index) = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.priority > 0)
+            .min_by_key(|(_, field)| field.priority)
+            .map(|(index, _)| index)
+        else {
+            break;
+        };
+        if fields[index].procedure {
+            let others = total_width(&fields) - fields[index].width();
+            let available = width.saturating_sub(others);
+            if available >= MIN_PROCEDURE_WIDTH {
+                fields[index].text = shorten_procedure(&fields[index].text, available);
+                break;
+            }
+        }
+        fields.remove(index);
+    }
+    fields
+}
+
+/// Shorten the procedure `name` to at most `width` characters, by eliding the leading components
+/// of its path, e.g. `…::smt::get` for `miden::core::collections::smt::get`, and then truncating
+/// what remains of it
+fn shorten_procedure(name: &str, width: usize) -> String {
+    if name.chars().count() <= width {
+        return name.to_string();
+    }
+    const ELLIPSIS: &str = "…::";
+    let mut components = name.split("::").collect::<Vec<_>>();
+    while components.len() > 1 {
+        components.remove(0);
+        let shortened = format!("{ELLIPSIS}{}", components.join("::"));
+        if shortened.chars().count() <= width {
+            return shortened;
+        }
+    }
+    let mut truncated = name.chars().take(width.saturating_sub(1)).collect::<String>();
+    truncated.push('…');
+    truncated
+}
+
+impl Pane for StatusBarPane {
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        let fields = layout(fields(state), area.width as usize);
+        let separator = Span::styled(SEPARATOR, Style::default().fg(Color::DarkGray));
+        let mut spans = vec![];
+        for (i, field) in fields.into_iter().enumerate() {
+            if i > 0 {
+                spans.push(separator.clone());
+            }
+            spans.push(Span::styled(field.text, field.style));
+        }
+        frame.render_widget(Line::from(spans), area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debug::BreakpointType;
+
+    const SOURCE: &str = "proc helper
+    trace.240 push.1 drop trace.252
+end
+
+begin
+    push.0 drop
+    exec.helper
+    push.2 drop
+end
+";
+
+    fn render(state: &State, width: u16) -> String {
+        let rows = crate::ui::testing::render_pane(&mut StatusBarPane::new(), state, width, 1);
+        rows[0].trim_end().to_string()
+    }
+
+    #[test]
+    fn status_bar_widths() {
+        let mut state = State::for_source(SOURCE);
+        state.create_breakpoint(BreakpointType::Called(glob::Pattern::new("*helper").unwrap()));
+        state.create_breakpoint(BreakpointType::ContextSwitch);
+        crate::ui::commands::run_script(&mut state, "c", &mut |_| ());
+
+        assert_eq!(
+            render(&state, 120),
+            "stopped │ cycle 8/16 │ $exec::helper │ context 0 │ 2 breakpoints"
+        );
+        assert_eq!(render(&state, 80), render(&state, 120));
+        // Fields are dropped starting from the number of breakpoints, then the context
+        assert_eq!(render(&state, 40), "stopped │ cycle 8/16 │ $exec::helper");
+        assert_eq!(render(&state, 50), "stopped │ cycle 8/16 │ $exec::helper │ context 0");
+        // The procedure is shortened before it is dropped
+        assert_eq!(render(&state, 32), "stopped │ cycle 8/16 │ …::helper");
+        assert_eq!(render(&state, 25), "stopped │ cycle 8/16");
+
+        // Disabled breakpoints are not counted
+        state.breakpoints[0].enabled = false;
+        crate::ui::commands::run_script(&mut state, "c", &mut |_| ());
+        assert_eq!(
+            render(&state, 120),
+            "terminated │ cycle 16/16 │ $exec::$main │ context 0 │ 1 breakpoint"
+        );
+    }
+
+    #[test]
+    fn shorten_procedure_names() {
+        let name = "miden::core::collections::smt::get";
+        assert_eq!(shorten_procedure(name, 80), name);
+        assert_eq!(shorten_procedure(name, 24), "…::collections::smt::get");
+        assert_eq!(shorten_procedure(name, 20), "…::smt::get");
+        assert_eq!(shorten_procedure(name, 7), "…::get");
+        assert_eq!(shorten_procedure("a_very_long_name", 8), "a_very_…");
+    }
+}
@@ -0,0 +1,363 @@
+use std::sync::Arc;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use miden_assembly_syntax::{DisplayHex, diagnostics::Report};
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::{
+    debug::{BreakpointType, ResolvedLocation},
+    ui::{
+        action::Action,
+        keybindings::{KeyAction, KeyMode},
+        panes::Pane,
+        procedures::Procedure,
+        state::State,
+        tui::{EventResponse, Frame},
+    },
+};
+
+/// A popup listing the procedures of the program and its libraries, from which the source code
+/// of a procedure can be shown, or a breakpoint set in it
+pub struct SymbolsPane {
+    procedures: Arc<[Procedure]>,
+    /// The prefix by which the procedures are filtered
+    filter: String,
+    /// Whether keys pressed are typed into the filter
+    filtering: bool,
+    /// The indices of the procedures matching the filter
+    matches: Vec<usize>,
+    /// The index of the selected procedure among those matching the filter
+    selected: usize,
+    /// The number of procedures shown as of the last draw, by which the selection is paged
+    page_size: usize,
+}
+
+impl SymbolsPane {
+    pub fn new(state: &State) -> Self {
+        let procedures = state.procedures();
+        let matches = (0..procedures.len()).collect();
+        Self {
+            procedures,
+            filter: String::new(),
+            filtering: false,
+            matches,
+            selected: 0,
+            page_size: 1,
+        }
+    }
+
+    /// Select the procedures whose name, or the last component of their path, starts with the
+    /// filter
+    fn apply_filter(&mut self) {
+        let filter = self.filter.as_str();
+        self.matches = self
+            .procedures
+            .iter()
+            .enumerate()
+            .filter(|(_, procedure)| {
+                let name = procedure.name.as_str();
+                let last = name.rsplit("::").next().unwrap_or(name);
+                name.starts_with(filter) || last.starts_with(filter)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        self.selected = 0;
+    }
+
+    fn select_by(&mut self, offset: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(offset).min(last);
+    }
+
+    fn selected_procedure(&self) -> Option<&Procedure> {
+        self.matches.get(self.selected).map(|index| &self.procedures[*index])
+    }
+
+    /// Show the source code of the selected procedure
+    fn show_selected(&self, state: &State) -> Action {
+        let Some(procedure) = self.selected_procedure() else {
+            return Action::Noop;
+        };
+        let location = procedure
+            .location
+            .as_ref()
+            .and_then(|loc| ResolvedLocation::resolve(loc, &*state.source_manager));
+        match location {
+            Some(location) => Action::ShowLocation(location),
+            None => Action::TimedStatusLine(
+                format!("no source code is available for {}", procedure.name),
+                3,
+            ),
+        }
+    }
+
+    /// Break whenever the selected procedure is entered
+    fn break_in_selected(&self, state: &mut State) -> Action {
+        let Some(procedure) = self.selected_procedure() else {
+            return Action::Noop;
+        };
+        let pattern = glob::Pattern::new(&glob::Pattern::escape(&procedure.name))
+            .expect("escaped patterns are valid");
+        state.create_breakpoint(BreakpointType::Called(pattern));
+        Action::TimedStatusLine(format!("breakpoint created in {}", procedure.name), 3)
+    }
+}
+
+impl Pane for SymbolsPane {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        // The filter is edited like a command, so it is applied and cleared as commands are
+        // executed and cancelled
+        if self.filtering {
+            match state.keybindings.lookup(KeyMode::Footer, key) {
+                Some(KeyAction::Execute) => self.filtering = false,
+                Some(KeyAction::Cancel) => {
+                    self.filtering = false;
+                    self.filter.clear();
+                    self.apply_filter();
+                }
+                _ => match key.code {
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.filter.push(c);
+                        self.apply_filter();
+                    }
+                    KeyCode::Backspace => {
+                        self.filter.pop();
+                        self.apply_filter();
+                    }
+                    _ => (),
+                },
+            }
+            return Ok(Some(EventResponse::Stop(Action::Noop)));
+        }
+
+        let response = match state.keybindings.lookup_in(&[KeyMode::Popup, KeyMode::Global], key) {
+            Some(KeyAction::ClosePopup) => EventResponse::Stop(Action::ClosePopup),
+            Some(KeyAction::Down) => EventResponse::Stop(Action::Down),
+            Some(KeyAction::Up) => EventResponse::Stop(Action::Up),
+            Some(KeyAction::PageDown) => EventResponse::Stop(Action::PageDown),
+            Some(KeyAction::PageUp) => EventResponse::Stop(Action::PageUp),
+            Some(KeyAction::Filter) => {
+                self.filtering = true;
+                EventResponse::Stop(Action::Noop)
+            }
+            Some(KeyAction::Submit) => EventResponse::Stop(self.show_selected(state)),
+            Some(KeyAction::BreakIn) => EventResponse::Stop(self.break_in_selected(state)),
+            _ => EventResponse::Stop(Action::Noop),
+        };
+        Ok(Some(response))
+    }
+
+    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Down => self.select_by(1),
+            Action::Up => self.select_by(-1),
+            Action::PageDown => self.select_by(self.page_size as isize),
+            Action::PageUp => self.select_by(-(self.page_size as isize)),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        /// The number of hexadecimal digits of each digest which are shown
+        const DIGEST_DIGITS: usize = 16;
+
+        frame.render_widget(Clear, area);
+        self.page_size = area.height.saturating_sub(2).max(1) as usize;
+
+        let width = self
+            .matches
+            .iter()
+            .map(|index| self.procedures[*index].name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let items = self.matches.iter().map(|index| {
+            let procedure = &self.procedures[*index];
+            let visibility = if procedure.exported { "pub" } else { "   " };
+            let digest = match procedure.digest.as_ref() {
+                Some(digest) => {
+                    let digest = DisplayHex::new(&digest.as_bytes()).to_string();
+                    format!("0x{}..", &digest[..DIGEST_DIGITS])
+                }
+                None => "-".to_string(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{visibility} "), Color::Cyan),
+                Span::styled(format!("{:<width$}  ", procedure.name), state.theme.styles.value),
+                Span::styled(digest, Color::Gray),
+            ]))
+        });
+
+        let filter = match (self.filtering, self.filter.is_empty()) {
+            (false, true) => String::new(),
+            _ => format!("/{}", self.filter),
+        };
+        let keys = |action| state.keybindings.describe(action);
+        let help = if self.filtering {
+            format!("{} to apply, {} to clear", keys(KeyAction::Execute), keys(KeyAction::Cancel))
+        } else {
+            format!(
+                "{} to filter, {} to show, {} to break, {} to close",
+                keys(KeyAction::Filter),
+                keys(KeyAction::Submit),
+                keys(KeyAction::BreakIn),
+                keys(KeyAction::ClosePopup)
+            )
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Procedures ({} of {})", self.matches.len(), self.procedures.len()))
+            .title_bottom(Line::from(filter))
+            .title_bottom(
+                Line::styled(help, Style::default().add_modifier(Modifier::ITALIC)).right_aligned(),
+            );
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+        let selected = (!self.matches.is_empty()).then_some(self.selected);
+        let mut list_state = ListState::default().with_selected(selected);
+        frame.render_stateful_widget(list, area, &mut list_state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyEvent;
+
+    use super::*;
+    use crate::ui::testing::main_masm;
+
+    const SOURCE: &str = "proc helper
+    push.1 drop
+end
+
+proc other
+    push.2 drop
+end
+
+begin
+    exec.helper
+    call.other
+end
+";
+
+    fn key(pane: &mut SymbolsPane, state: &mut State, code: KeyCode) -> Action {
+        let event = KeyEvent::new(code, KeyModifiers::NONE);
+        match pane.handle_key_events(event, state).unwrap() {
+            Some(EventResponse::Stop(action)) => {
+                pane.update(action.clone(), state).unwrap();
+                action
+            }
+            _ => panic!("expected the popup to handle {code:?}"),
+        }
+    }
+
+    fn render(pane: &mut SymbolsPane, state: &State) -> Vec<String> {
+        crate::ui::testing::render_pane(pane, state, 56, 6)
+    }
+
+    fn names(pane: &SymbolsPane) -> Vec<&str> {
+        pane.matches.iter().map(|index| pane.procedures[*index].name.as_str()).collect()
+    }
+
+    /// A popup listing the procedures of [SOURCE], which is written to `path`, so that their
+    /// locations can be resolved
+    fn symbols(path: &std::path::Path) -> (State, SymbolsPane) {
+        let state = State::for_source_file(path);
+        let pane = SymbolsPane::new(&state);
+        (state, pane)
+    }
+
+    #[test]
+    fn symbols_filter() {
+        let (_dir, path) = main_masm(SOURCE);
+        let (mut state, mut pane) = symbols(&path);
+        assert_eq!(names(&pane), ["$exec::$main", "$exec::helper", "$exec::other"]);
+        assert!(pane.procedures.iter().all(|procedure| !procedure.exported));
+        // The root of `other` is its own, whereas that of the entrypoint begins with the inlined
+        // `helper`, so neither of the latter can be attributed a digest
+        let digests = pane.procedures.iter().map(|procedure| procedure.digest.is_some());
+        assert_eq!(digests.collect::<Vec<_>>(), [false, false, true]);
+        let rows = render(&mut pane, &state);
+        assert_eq!(rows[0], "┌Procedures (3 of 3)───────────────────────────────────┐");
+        assert_eq!(rows[1], "│→    $exec::$main   -                                 │");
+        assert!(rows[3].starts_with("│     $exec::other   0x"), "{}", rows[3]);
+        assert_eq!(rows[5], "└────/ to filter, enter to show, b to break, q to close┘");
+
+        // Keys are typed into the filter once it is focused, which matches the start of either the
+        // full name, or its last component
+        key(&mut pane, &mut state, KeyCode::Char('/'));
+        for c in "he".chars() {
+            key(&mut pane, &mut state, KeyCode::Char(c));
+        }
+        assert_eq!(names(&pane), ["$exec::helper"]);
+        key(&mut pane, &mut state, KeyCode::Backspace);
+        key(&mut pane, &mut state, KeyCode::Backspace);
+        key(&mut pane, &mut state, KeyCode::Char('$'));
+        assert_eq!(names(&pane), ["$exec::$main", "$exec::helper", "$exec::other"]);
+        for c in "exec::o".chars() {
+            key(&mut pane, &mut state, KeyCode::Char(c));
+        }
+        assert_eq!(names(&pane), ["$exec::other"]);
+        assert_eq!(pane.filter, "$exec::o");
+
+        // The filter is kept when applied, and discarded when cleared
+        key(&mut pane, &mut state, KeyCode::Enter);
+        assert!(!pane.filtering);
+        assert_eq!(names(&pane), ["$exec::other"]);
+        key(&mut pane, &mut state, KeyCode::Char('/'));
+        key(&mut pane, &mut state, KeyCode::Char('x'));
+        assert!(names(&pane).is_empty());
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Enter), Action::Noop);
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Char('b')), Action::Noop);
+        key(&mut pane, &mut state, KeyCode::Char('/'));
+        key(&mut pane, &mut state, KeyCode::Esc);
+        assert_eq!(names(&pane).len(), 3);
+        assert_eq!(key(&mut pane, &mut state, KeyCode::Esc), Action::ClosePopup);
+    }
+
+    #[test]
+    fn symbols_jump_and_break() {
+        let (_dir, path) = main_masm(SOURCE);
+        let (mut state, mut pane) = symbols(&path);
+
+        // Enter shows the first line of the selected procedure
+        key(&mut pane, &mut state, KeyCode::Down);
+        match key(&mut pane, &mut state, KeyCode::Enter) {
+            Action::ShowLocation(location) => {
+                assert_eq!(location.source_file.uri().as_str(), path.to_str().unwrap());
+                assert_eq!(location.line, 2);
+            }
+            action => panic!("expected the location of helper to be shown, got {action:?}"),
+        }
+        key(&mut pane, &mut state, KeyCode::Down);
+        match key(&mut pane, &mut state, KeyCode::Enter) {
+            Action::ShowLocation(location) => assert_eq!(location.line, 6),
+            action => panic!("expected the location of other to be shown, got {action:?}"),
+        }
+
+        // b breaks whenever the selected procedure is entered
+        let action = key(&mut pane, &mut state, KeyCode::Char('b'));
+        assert_eq!(action, Action::TimedStatusLine("breakpoint created in $exec::other".into(), 3));
+        assert!(matches!(
+            &state.breakpoints[..],
+            [breakpoint] if breakpoint.ty.should_break_in("$exec::other")
+                && !breakpoint.ty.should_break_in("$exec::helper")
+        ));
+
+        // The listing is kept until the program is reloaded
+        assert!(Arc::ptr_eq(&pane.procedures, &SymbolsPane::new(&state).procedures));
+        state.reload().unwrap();
+        assert!(!Arc::ptr_eq(&pane.procedures, &SymbolsPane::new(&state).procedures));
+    }
+}
@@ -0,0 +1,81 @@
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::ui::{
+    action::Action,
+    keybindings::{KeyAction, KeyMode},
+    panes::Pane,
+    state::{InputMode, State},
+    tui::{EventResponse, Frame},
+};
+
+/// A popup which displays the multi-line output of a command
+pub struct TextPane {
+    title: String,
+    lines: Vec<String>,
+    scroll: usize,
+}
+
+impl TextPane {
+    pub fn new(title: String, lines: Vec<String>) -> Self {
+        Self {
+            title,
+            lines,
+            scroll: 0,
+        }
+    }
+}
+
+impl Pane for TextPane {
+    fn handle_key_events(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        match state.input_mode {
+            InputMode::Normal => {
+                let bound = state.keybindings.lookup_in(&[KeyMode::Popup, KeyMode::Global], key);
+                let response = match bound {
+                    Some(KeyAction::Down) => EventResponse::Stop(Action::Down),
+                    Some(KeyAction::Up) => EventResponse::Stop(Action::Up),
+                    Some(KeyAction::ClosePopup | KeyAction::Submit) => {
+                        EventResponse::Stop(Action::ClosePopup)
+                    }
+                    _ => {
+                        return Ok(Some(EventResponse::Stop(Action::Noop)));
+                    }
+                };
+                Ok(Some(response))
+            }
+            InputMode::Insert => Ok(Some(EventResponse::Stop(Action::Noop))),
+            InputMode::Command => Ok(Some(EventResponse::Stop(Action::Noop))),
+        }
+    }
+
+    fn update(&mut self, action: Action, _state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Down => {
+                self.scroll = core::cmp::min(self.scroll + 1, self.lines.len().saturating_sub(1));
+                Ok(Some(Action::Update))
+            }
+            Action::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+                Ok(Some(Action::Update))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, _state: &State) -> Result<(), Report> {
+        frame.render_widget(Clear, area);
+        let lines = self.lines.iter().map(|line| Line::from(line.as_str())).collect::<Vec<_>>();
+        let text = Paragraph::new(lines)
+            .scroll((self.scroll as u16, 0))
+            .block(Block::default().borders(Borders::ALL).title(self.title.as_str()));
+        frame.render_widget(text, area);
+        Ok(())
+    }
+}
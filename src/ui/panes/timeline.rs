@@ -0,0 +1,299 @@
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::{
+    crossterm::event::KeyEvent,
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::ui::{
+    action::Action,
+    keybindings::{KeyAction, KeyMode},
+    layout::PaneId,
+    panes::Pane,
+    state::State,
+    tui::{EventResponse, Frame},
+};
+
+/// A bar spanning every cycle of the program, on which a cursor can be moved to go to any cycle,
+/// including earlier ones.
+///
+/// The bar marks the cycles at which call frames were entered, at which breakpoints were hit, and
+/// at which the program failed, if it did. It requires the execution trace, as the total number of
+/// cycles is otherwise unknown.
+pub struct TimelinePane {
+    focused: bool,
+    focused_border_style: Style,
+    /// The cycle under the cursor
+    cursor: usize,
+    /// The number of cycles by which the cursor moves, or if `None`, the number of cycles covered
+    /// by a single column of the bar
+    increment: Option<usize>,
+    /// The number of columns of the bar as of the last draw
+    width: usize,
+}
+
+impl TimelinePane {
+    pub fn new(focused: bool, focused_border_style: Style) -> Self {
+        Self {
+            focused,
+            focused_border_style,
+            cursor: 0,
+            increment: None,
+            width: 1,
+        }
+    }
+
+    fn border_style(&self) -> Style {
+        match self.focused {
+            true => self.focused_border_style,
+            false => Style::default(),
+        }
+    }
+
+    fn border_type(&self) -> BorderType {
+        match self.focused {
+            true => BorderType::Thick,
+            false => BorderType::Plain,
+        }
+    }
+
+    /// The number of cycles by which the cursor moves, given `total` cycles
+    fn increment(&self, total: usize) -> usize {
+        self.increment
+            .unwrap_or_else(|| total / self.width.saturating_sub(1).max(1))
+            .max(1)
+    }
+
+    /// Move the cursor forwards by `increments` increments, or backwards if negative, staying
+    /// within the `total` cycles of the program
+    fn move_cursor(&mut self, increments: isize, total: usize) {
+        let cycles = increments.saturating_mul(self.increment(total) as isize);
+        self.cursor = self.cursor.saturating_add_signed(cycles).min(total);
+    }
+}
+
+/// The total number of cycles executed by the program, if known
+fn total_cycles(state: &State) -> Option<usize> {
+    state.execution_trace.as_ref().map(|trace| trace.last_cycle().as_usize())
+}
+
+/// The column of a bar of `width` columns at which `cycle` of `total` cycles is shown, such that
+/// the first and last cycles are shown in the first and last columns
+fn cycle_to_column(cycle: usize, total: usize, width: usize) -> usize {
+    if total == 0 || width < 2 {
+        return 0;
+    }
+    (cycle.min(total) * (width - 1) + total / 2) / total
+}
+
+/// The cycle shown at `column` of a bar of `width` columns, spanning `total` cycles, i.e. the
+/// inverse of [cycle_to_column]
+fn column_to_cycle(column: usize, total: usize, width: usize) -> usize {
+    if width < 2 {
+        return 0;
+    }
+    (column.min(width - 1) * total + (width - 1) / 2) / (width - 1)
+}
+
+impl Pane for TimelinePane {
+    fn init(&mut self, state: &State) -> Result<(), Report> {
+        self.cursor = state.executor.cycle;
+        Ok(())
+    }
+
+    fn handle_key_events(
+        &mut self,
+        key: KeyEvent,
+        state: &mut State,
+    ) -> Result<Option<EventResponse<Action>>, Report> {
+        let Some(total) = total_cycles(state) else {
+            return Ok(None);
+        };
+        match state.keybindings.lookup(KeyMode::Timeline, key) {
+            Some(KeyAction::CursorBack) => self.move_cursor(-1, total),
+            Some(KeyAction::CursorForward) => self.move_cursor(1, total),
+            Some(KeyAction::LargerSteps) => {
+                self.increment = Some(self.increment(total).saturating_mul(10));
+            }
+            Some(KeyAction::SmallerSteps) => {
+                self.increment = Some((self.increment(total) / 10).max(1));
+            }
+            _ => return Ok(None),
+        }
+        Ok(Some(EventResponse::Stop(Action::Noop)))
+    }
+
+    fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
+        match action {
+            Action::Focus => {
+                self.focused = true;
+            }
+            Action::UnFocus => {
+                self.focused = false;
+            }
+            Action::Submit if total_cycles(state).is_some() => {
+                return Ok(Some(Action::GotoCycle(self.cursor)));
+            }
+            Action::Update | Action::Reload => self.init(state)?,
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        let block = Block::default()
+            .title(PaneId::Timeline.numbered("Timeline"))
+            .borders(Borders::ALL)
+            .border_style(self.border_style())
+            .border_type(self.border_type());
+        let (Some(trace), Some(total)) = (state.execution_trace.as_ref(), total_cycles(state))
+        else {
+            let message = Line::styled(
+                "the timeline requires the execution trace, see :capture",
+                Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+            );
+            frame.render_widget(Paragraph::new(message).block(block), area);
+            return Ok(());
+        };
+
+        self.width = area.width.saturating_sub(2).max(1) as usize;
+        let column = |cycle: usize| cycle_to_column(cycle, total, self.width);
+        let mut bar = vec![('─', Style::default().fg(Color::DarkGray)); self.width];
+        for clk in trace.frame_starts() {
+            bar[column(clk.as_usize())] = ('│', Style::default().fg(Color::Blue));
+        }
+        for cycle in state.hit_cycles.iter() {
+            bar[column(*cycle)] = ('●', Style::default().fg(Color::Magenta));
+        }
+        if trace.failed() {
+            bar[column(total)] =
+                ('✗', Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        }
+        bar[column(state.executor.cycle)] =
+            ('█', Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let bar = bar
+            .into_iter()
+            .map(|(symbol, style)| Span::styled(symbol.to_string(), style))
+            .collect::<Vec<_>>();
+        let cursor = Line::from(vec![
+            Span::raw(" ".repeat(column(self.cursor))),
+            Span::styled("▲", Style::default().fg(Color::Yellow)),
+        ]);
+
+        let increment = self.increment(total);
+        let keys = |action| state.keybindings.describe(action);
+        let block = block
+            .title(
+                Line::styled(
+                    format!(" at cycle {} of {total}", state.executor.cycle),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                )
+                .right_aligned(),
+            )
+            .title_bottom(
+                Line::styled(
+                    format!(
+                        "cursor at cycle {} ({}/{} by {increment}, {}/{} to change), {} goes there",
+                        self.cursor,
+                        keys(KeyAction::CursorBack),
+                        keys(KeyAction::CursorForward),
+                        keys(KeyAction::LargerSteps),
+                        keys(KeyAction::SmallerSteps),
+                        keys(KeyAction::Submit),
+                    ),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                )
+                .right_aligned(),
+            );
+        frame.render_widget(Paragraph::new(vec![Line::from(bar), cursor]).block(block), area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::{debug::BreakpointType, ui::keybindings::KeyBindings};
+
+    fn key(state: &mut State, pane: &mut TimelinePane, c: char) {
+        let event = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let response = pane.handle_key_events(event, state).unwrap();
+        assert!(matches!(response, Some(EventResponse::Stop(Action::Noop))));
+    }
+
+    #[test]
+    fn timeline_cycle_column_mapping() {
+        // The first and last cycles are at either end of the bar
+        assert_eq!(cycle_to_column(0, 1000, 11), 0);
+        assert_eq!(cycle_to_column(1000, 1000, 11), 10);
+        assert_eq!(cycle_to_column(2000, 1000, 11), 10);
+        assert_eq!(cycle_to_column(449, 1000, 11), 4);
+        assert_eq!(cycle_to_column(450, 1000, 11), 5);
+        assert_eq!(column_to_cycle(5, 1000, 11), 500);
+        assert_eq!(column_to_cycle(20, 1000, 11), 1000);
+
+        // Every column maps back to itself, even when there are more columns than cycles
+        for (total, width) in [(1000, 11), (7, 20), (1, 2), (123_456, 77)] {
+            for column in 0..width {
+                let cycle = column_to_cycle(column, total, width);
+                assert!(cycle <= total);
+                if total >= width - 1 {
+                    assert_eq!(cycle_to_column(cycle, total, width), column, "{total} {width}");
+                }
+            }
+        }
+
+        // Degenerate bars show everything in the first column
+        assert_eq!(cycle_to_column(5, 0, 10), 0);
+        assert_eq!(cycle_to_column(5, 10, 1), 0);
+        assert_eq!(column_to_cycle(3, 10, 1), 0);
+    }
+
+    #[test]
+    fn timeline_cursor_actions() {
+        let mut state = State::for_source("begin push.1 push.2 add drop end");
+        let total = state.execution_trace().unwrap().last_cycle().as_usize();
+        let mut pane = TimelinePane::new(true, Style::default());
+        pane.init(&state).unwrap();
+        pane.width = total + 1;
+
+        key(&mut state, &mut pane, 'l');
+        key(&mut state, &mut pane, 'l');
+        assert_eq!(pane.cursor, 2);
+        assert_eq!(pane.update(Action::Submit, &mut state).unwrap(), Some(Action::GotoCycle(2)));
+        key(&mut state, &mut pane, '+');
+        key(&mut state, &mut pane, 'l');
+        assert_eq!(pane.cursor, total);
+        key(&mut state, &mut pane, '-');
+        key(&mut state, &mut pane, 'h');
+        assert_eq!(pane.cursor, total - 1);
+        for _ in 0..=total {
+            key(&mut state, &mut pane, 'h');
+        }
+        assert_eq!(pane.cursor, 0);
+
+        // The cursor follows the program when it is stepped
+        state.create_breakpoint(BreakpointType::StepN(3));
+        state.run_until_stopped();
+        pane.update(Action::Update, &mut state).unwrap();
+        assert_eq!(pane.cursor, 3);
+
+        // The keys are those bound in the timeline mode
+        state.keybindings =
+            KeyBindings::parse_str("[timeline]\nl = \"none\"\nw = \"cursor-forward\"").unwrap();
+        let event = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(pane.handle_key_events(event, &mut state).unwrap().is_none());
+        key(&mut state, &mut pane, 'w');
+        assert_eq!(pane.cursor, 4);
+
+        // Other keys are left to the page, and the timeline is inert without a trace
+        let event = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert!(pane.handle_key_events(event, &mut state).unwrap().is_none());
+        state.execution_trace = None;
+        assert!(pane.handle_key_events(event, &mut state).unwrap().is_none());
+        assert_eq!(pane.update(Action::Submit, &mut state).unwrap(), None);
+    }
+}
@@ -0,0 +1,132 @@
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::{
+    prelude::*,
+    widgets::{block::*, *},
+};
+
+use crate::ui::{action::Action, layout::PaneId, panes::Pane, state::State, tui::Frame};
+
+/// The expressions watched via the `watch` command, with their values as of the current cycle
+pub struct WatchPane {
+    focused: bool,
+    focused_border_style: Style,
+    /// The index of the selected watch, if any
+    selected: Option<usize>,
+}
+
+impl WatchPane {
+    pub fn new(focused: bool, focused_border_style: Style) -> Self {
+        Self {
+            focused,
+            focused_border_style,
+            selected: None,
+        }
+    }
+
+    fn border_style(&self) -> Style {
+        match self.focused {
+            true => self.focused_border_style,
+            false => Style::default(),
+        }
+    }
+
+    fn border_type(&self) -> BorderType {
+        match self.focused {
+            true => BorderType::Thick,
+            false => BorderType::Plain,
+        }
+    }
+}
+
+impl Pane for WatchPane {
+    fn update(&mut self, action: Action, state: &mut State) -> Result<Option<Action>, Report> {
+        let len = state.watches.len();
+        match action {
+            Action::Focus => {
+                self.focused = true;
+            }
+            Action::UnFocus => {
+                self.focused = false;
+            }
+            Action::Down if len > 0 => {
+                self.selected = Some(self.selected.map_or(0, |selected| (selected + 1) % len));
+            }
+            Action::Up if len > 0 => {
+                self.selected =
+                    Some(self.selected.map_or(len - 1, |selected| (selected + len - 1) % len));
+            }
+            Action::Delete => {
+                if let Some(watch) =
+                    self.selected.and_then(|selected| state.watches.remove(selected))
+                {
+                    let len = state.watches.len();
+                    self.selected = self.selected.filter(|_| len > 0).map(|s| s.min(len - 1));
+                    return Ok(Some(Action::TimedStatusLine(
+                        format!("stopped watching {}", watch.text),
+                        1,
+                    )));
+                }
+            }
+            Action::Update | Action::Reload => {
+                state.evaluate_watches();
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, area: Rect, state: &State) -> Result<(), Report> {
+        // Watches may have been removed via the `unwatch` command
+        self.selected = self.selected.filter(|selected| *selected < state.watches.len());
+
+        let lines = state
+            .watches
+            .iter()
+            .map(|watch| {
+                let marker = match watch.changed {
+                    true => Span::styled("* ", Style::default().fg(Color::Yellow)),
+                    false => Span::raw("  "),
+                };
+                let expr = Span::styled(watch.text.as_str(), Color::Cyan);
+                let value = match watch.value.as_ref() {
+                    Some(Ok(value)) => {
+                        let style = match watch.changed {
+                            true => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                            false => Style::default().fg(Color::White),
+                        };
+                        Span::styled(format!(" = {value}"), style)
+                    }
+                    Some(Err(err)) => Span::styled(format!(" ! {err}"), Color::Red),
+                    None => Span::styled(" = ?", Color::DarkGray),
+                };
+                Line::from(vec![marker, expr, value])
+            })
+            .collect::<Vec<_>>();
+
+        let list = List::new(lines)
+            .block(Block::default().borders(Borders::ALL))
+            .highlight_symbol(symbols::scrollbar::HORIZONTAL.end)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+        let mut list_state = ListState::default().with_selected(self.selected);
+
+        frame.render_stateful_widget(list, area, &mut list_state);
+        frame.render_widget(
+            Block::default()
+                .title(PaneId::Watch.numbered("Watches"))
+                .borders(Borders::ALL)
+                .border_style(self.border_style())
+                .border_type(self.border_type())
+                .title_bottom(
+                    Line::styled(
+                        "add with :watch EXPR",
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    )
+                    .right_aligned(),
+                ),
+            area,
+        );
+        Ok(())
+    }
+}
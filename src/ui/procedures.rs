@@ -0,0 +1,134 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use miden_assembly_syntax::Library;
+use miden_core::{
+    Word,
+    mast::{MastForest, MastNode, MastNodeExt, MastNodeId},
+    operations::AssemblyOp,
+};
+use miden_debug_types::Location;
+use miden_mast_package::{Package, PackageExport};
+
+/// A procedure of the program being debugged, or of one of the libraries it was loaded with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Procedure {
+    /// The name of the procedure, as shown in the call stack
+    pub name: String,
+    /// The digest of the MAST root of the procedure, unless it has none, i.e. it is only ever
+    /// inlined, or its root cannot be told apart from those of the procedures it inlines
+    pub digest: Option<Word>,
+    /// Whether the procedure is exported by the package, or by one of the libraries
+    pub exported: bool,
+    /// The location of the first instruction of the procedure in its source file, if known
+    pub location: Option<Location>,
+}
+
+/// List the procedures of `package` and `libraries`, in lexicographic order of their names.
+///
+/// Procedures are named after the assembly operations recorded in the debug info of each MAST
+/// forest, so procedures which are inlined are listed along with those having a MAST root. The
+/// procedures exported by the package or the libraries are listed even without debug info.
+pub fn list_procedures(package: &Package, libraries: &[std::sync::Arc<Library>]) -> Vec<Procedure> {
+    let mut procedures = BTreeMap::<String, Procedure>::new();
+    let forests = core::iter::once(package.mast.mast_forest())
+        .chain(libraries.iter().map(|library| library.mast_forest().as_ref()));
+    for forest in forests {
+        for index in 0..forest.num_nodes() {
+            let id = MastNodeId::new_unchecked(index);
+            for asmop in assembly_ops(forest, id) {
+                let procedure = procedure(&mut procedures, asmop.context_name());
+                // The first instruction of a procedure is the one which comes first in its file
+                if let Some(location) = asmop.location()
+                    && procedure.location.as_ref().is_none_or(|first| {
+                        first.uri == location.uri && first.start > location.start
+                    })
+                {
+                    procedure.location = Some(location.clone());
+                }
+            }
+        }
+        for root in forest.procedure_roots() {
+            if let Some(name) = root_procedure_name(forest, *root) {
+                procedure(&mut procedures, name).digest = Some(forest[*root].digest());
+            }
+        }
+        for (digest, name) in forest.procedure_names() {
+            procedure(&mut procedures, name).digest = Some(digest);
+        }
+    }
+
+    let mut exports = BTreeSet::new();
+    for export in package.manifest.exports() {
+        if let PackageExport::Procedure(export) = export {
+            exports.insert(export.path.to_string());
+            procedure(&mut procedures, &export.path.to_string()).digest = Some(export.digest);
+        }
+    }
+    for library in libraries {
+        for export in library.exports().filter_map(|export| export.as_procedure()) {
+            exports.insert(export.path.to_string());
+            procedure(&mut procedures, &export.path.to_string()).digest =
+                library.get_procedure_root_by_path(&export.path);
+        }
+    }
+    for export in exports {
+        procedure(&mut procedures, &export).exported = true;
+    }
+
+    procedures.into_values().collect()
+}
+
+/// The entry of `procedures` for the procedure named `name`, which is added if there is none
+fn procedure<'a>(procedures: &'a mut BTreeMap<String, Procedure>, name: &str) -> &'a mut Procedure {
+    let name = display_name(name);
+    procedures.entry(name.clone()).or_insert_with(|| Procedure {
+        name,
+        digest: None,
+        exported: false,
+        location: None,
+    })
+}
+
+/// The assembly operations of the node `id` of `forest`, i.e. those of each operation of a basic
+/// block, or that of the instruction from which any other node was assembled, e.g. a `call`
+fn assembly_ops(forest: &MastForest, id: MastNodeId) -> Vec<&AssemblyOp> {
+    match &forest[id] {
+        MastNode::Block(block) => (0..(block.num_operations() as usize))
+            .filter_map(|index| forest.get_assembly_op(id, Some(index)))
+            .collect(),
+        _ => forest.get_assembly_op(id, None).into_iter().collect(),
+    }
+}
+
+/// The name of the procedure rooted at `root`, as recorded in the debug info of `forest`, if the
+/// assembly operations of the root, other than those of the procedures it calls, all belong to a
+/// single procedure.
+///
+/// Otherwise the root cannot be attributed to any one procedure, as the operations of procedures
+/// which are inlined via `exec` are indistinguishable from those of the procedure executing them.
+fn root_procedure_name(forest: &MastForest, root: MastNodeId) -> Option<&str> {
+    let mut name = None;
+    let mut unvisited = vec![root];
+    while let Some(id) = unvisited.pop() {
+        for asmop in assembly_ops(forest, id) {
+            match name {
+                None => name = Some(asmop.context_name()),
+                Some(name) if name == asmop.context_name() => (),
+                Some(_) => return None,
+            }
+        }
+        match &forest[id] {
+            MastNode::Join(join) => unvisited.extend([join.second(), join.first()]),
+            MastNode::Split(split) => unvisited.extend([split.on_false(), split.on_true()]),
+            MastNode::Loop(node) => unvisited.push(node.body()),
+            MastNode::Block(_) | MastNode::Call(_) | MastNode::Dyn(_) | MastNode::External(_) => (),
+        }
+    }
+    name
+}
+
+/// The name of a procedure as shown in the call stack, i.e. without the leading `::` of its fully
+/// qualified path, and demangled
+fn display_name(name: &str) -> String {
+    format!("{:#}", rustc_demangle::demangle(name.trim_start_matches("::")))
+}
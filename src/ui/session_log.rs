@@ -0,0 +1,140 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{action::Action, style::strip_ansi};
+
+/// A transcript of a debugging session, i.e. the commands entered, and the output they produced.
+///
+/// Each line of the transcript is prefixed with the time at which it was written, and the cycle
+/// at which the program was stopped at that time. Output is stripped of ANSI escape sequences.
+///
+/// Failing to write the transcript is never fatal, instead a warning is emitted and logging is
+/// disabled.
+#[derive(Debug, Default)]
+pub struct SessionLog {
+    file: Option<(PathBuf, File)>,
+}
+
+impl SessionLog {
+    /// Start appending the transcript to the file at `path`, creating it if it does not exist.
+    ///
+    /// If a transcript was already being written to another file, it is closed first.
+    pub fn open(&mut self, path: &Path, cycle: usize) -> Result<(), String> {
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| format!("unable to open session log {}: {err}", path.display()))?;
+        self.close(cycle);
+        self.file = Some((path.to_path_buf(), file));
+        self.write(cycle, "# session log started");
+        Ok(())
+    }
+
+    /// Stop writing the transcript, returning the path of the file it was written to, if any
+    pub fn close(&mut self, cycle: usize) -> Option<PathBuf> {
+        self.write(cycle, "# session log stopped");
+        self.file.take().map(|(path, _)| path)
+    }
+
+    /// The path of the file the transcript is written to, if logging is enabled
+    pub fn path(&self) -> Option<&Path> {
+        self.file.as_ref().map(|(path, _)| path.as_path())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Record `command` as having been entered at `cycle`
+    pub fn command(&mut self, cycle: usize, command: &str) {
+        self.write(cycle, &format!(":{command}"));
+    }
+
+    /// Record `text` as having been output at `cycle`
+    pub fn output(&mut self, cycle: usize, text: &str) {
+        for line in text.lines() {
+            self.write(cycle, &format!("  {line}"));
+        }
+    }
+
+    /// Record `action`, as handled by the TUI, at `cycle`.
+    ///
+    /// Commands entered on the `:` command line, status line messages, and text shown in popups
+    /// are recorded in full, while other actions are recorded by name.
+    pub fn action(&mut self, cycle: usize, action: &Action) {
+        if !self.is_enabled() {
+            return;
+        }
+        match action {
+            Action::Tick | Action::Render | Action::Update | Action::Noop => (),
+            Action::FooterResult(cmd, Some(command)) if cmd == ":" => self.command(cycle, command),
+            Action::StatusLine(text) | Action::TimedStatusLine(text, _) => {
+                self.write(cycle, &format!("status: {text}"));
+            }
+            Action::Error(text) => self.write(cycle, &format!("error: {text}")),
+            Action::ShowText(title, lines) => {
+                self.write(cycle, &format!("{title}:"));
+                lines.iter().for_each(|line| self.output(cycle, line));
+            }
+            action => self.write(cycle, &format!("action: {action:?}")),
+        }
+    }
+
+    fn write(&mut self, cycle: usize, line: &str) {
+        let Some((path, file)) = self.file.as_mut() else {
+            return;
+        };
+        let line = format!("{} [cycle {cycle}] {}\n", timestamp(), strip_ansi(line));
+        if let Err(err) = file.write_all(line.as_bytes()) {
+            log::warn!(
+                "unable to write session log to {}: {err}, session logging has been disabled",
+                path.display()
+            );
+            self.file = None;
+        }
+    }
+}
+
+/// The current time, in UTC, formatted as per RFC 3339, e.g. `2024-01-31T12:00:00.000Z`
+fn timestamp() -> String {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = elapsed.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Convert days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3600,
+        (secs_of_day / 60) % 60,
+        secs_of_day % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_format() {
+        let timestamp = timestamp();
+        assert_eq!(timestamp.len(), "2024-01-31T12:00:00.000Z".len());
+        assert!(timestamp.ends_with('Z'));
+        assert_eq!(&timestamp[10..11], "T");
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use super::layout::PaneLayout;
+
+/// The settings changed from within the debugger, e.g. the theme selected via `set theme`, which
+/// are persisted to the settings file so that they carry over to later sessions.
+///
+/// Like [super::aliases::Aliases], failing to read the file is never fatal, and settings which
+/// are not known to this version of the debugger are preserved when it is written.
+#[derive(Debug, Default)]
+pub struct Settings {
+    path: Option<PathBuf>,
+    table: toml::Table,
+}
+
+impl Settings {
+    /// Load the settings stored at `path`
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let mut table = toml::Table::new();
+        if let Some(path) = path.as_deref() {
+            match std::fs::read_to_string(path) {
+                Ok(content) => match content.parse::<toml::Table>() {
+                    Ok(content) => table = content,
+                    Err(err) => {
+                        log::warn!("ignoring invalid settings in {}: {err}", path.display())
+                    }
+                },
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => (),
+                Err(err) => {
+                    log::warn!("unable to read settings from {}: {err}", path.display());
+                }
+            }
+        }
+
+        Self { path, table }
+    }
+
+    /// The name of the theme selected in an earlier session, if any
+    pub fn theme(&self) -> Option<&str> {
+        self.table.get("theme").and_then(toml::Value::as_str)
+    }
+
+    /// Select the theme named `name` in later sessions, and persist the updated settings
+    pub fn set_theme(&mut self, name: &str) -> Result<(), String> {
+        self.table.insert("theme".into(), name.into());
+        self.save()
+    }
+
+    /// The layout of the panes as of the end of an earlier session, or the default layout
+    pub fn layout(&self) -> PaneLayout {
+        match self.table.get("layout").and_then(toml::Value::as_table) {
+            Some(table) => PaneLayout::from_toml(table),
+            None => PaneLayout::default(),
+        }
+    }
+
+    /// Restore `layout` in later sessions, and persist the updated settings
+    pub fn set_layout(&mut self, layout: &PaneLayout) -> Result<(), String> {
+        self.table.insert("layout".into(), layout.to_toml().into());
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = self.path.as_deref() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                format!("unable to create settings directory {}: {err}", parent.display())
+            })?;
+        }
+        let content = toml::to_string(&self.table).map_err(|err| err.to_string())?;
+        std::fs::write(path, content)
+            .map_err(|err| format!("unable to save settings to {}: {err}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::layout::PaneId;
+
+    #[test]
+    fn settings_persist_theme() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.toml");
+        assert_eq!(Settings::load(Some(path.clone())).theme(), None);
+
+        // Unknown settings are preserved
+        std::fs::write(&path, "future = 1\n").unwrap();
+        let mut settings = Settings::load(Some(path.clone()));
+        settings.set_theme("InspiredGitHub").unwrap();
+        let settings = Settings::load(Some(path.clone()));
+        assert_eq!(settings.theme(), Some("InspiredGitHub"));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("future = 1"));
+
+        // Invalid settings are ignored
+        std::fs::write(&path, "theme = ").unwrap();
+        assert_eq!(Settings::load(Some(path.clone())).theme(), None);
+
+        // The layout is kept alongside the other settings
+        let mut settings = Settings::load(Some(path.clone()));
+        assert_eq!(settings.layout(), PaneLayout::default());
+        let mut layout = PaneLayout::default();
+        layout.hide(PaneId::Advice).unwrap();
+        settings.set_theme("InspiredGitHub").unwrap();
+        settings.set_layout(&layout).unwrap();
+        let settings = Settings::load(Some(path));
+        assert_eq!(settings.layout(), layout);
+        assert_eq!(settings.theme(), Some("InspiredGitHub"));
+    }
+}
@@ -1,30 +1,144 @@
-use std::sync::Arc;
+use std::{cell::OnceCell, collections::BTreeSet, sync::Arc};
 
 use miden_assembly::{DefaultSourceManager, SourceManager};
-use miden_assembly_syntax::diagnostics::{IntoDiagnostic, Report};
+use miden_assembly_syntax::{
+    debuginfo::{SourceFile, SourceId},
+    diagnostics::{IntoDiagnostic, Report},
+};
 use miden_core::field::{PrimeCharacteristicRing, PrimeField64};
 use miden_core::serde::Deserializable;
-use miden_processor::{Felt, StackInputs};
+use miden_processor::{ContextId, Felt, StackInputs, trace::RowIndex};
 
 use crate::{
     config::DebuggerConfig,
-    debug::{Breakpoint, BreakpointType, ReadMemoryExpr},
-    exec::{DebugExecutor, ExecutionTrace, Executor},
+    debug::{
+        Breakpoint, BreakpointType, CallFrame, CycleMeasurement, FieldNames, FormatType, NativePtr,
+        ReadMemoryExpr, Symbols,
+    },
+    exec::{DebugExecutor, ErrorCodes, ExecutionConfig, ExecutionTrace, Executor},
     input::InputFile,
+    ui::{
+        aliases::Aliases,
+        clipboard::Clipboard,
+        commands::SourceListing,
+        history::History,
+        interrupt::Interrupt,
+        keybindings::KeyBindings,
+        layout::PaneLayout,
+        procedures::{Procedure, list_procedures},
+        session_log::SessionLog,
+        settings::Settings,
+        theme::{DEFAULT_THEME, Theme, Themes},
+        watch::WatchList,
+    },
 };
 
 pub struct State {
     pub package: Arc<miden_mast_package::Package>,
+    pub libraries: Vec<Arc<miden_assembly_syntax::Library>>,
     pub source_manager: Arc<dyn SourceManager>,
     pub config: Box<DebuggerConfig>,
     pub executor: DebugExecutor,
-    pub execution_trace: ExecutionTrace,
+    /// The trace of the program executed to completion, unless its capture was deferred, see
+    /// [DebuggerConfig::no_capture_trace]
+    pub execution_trace: Option<ExecutionTrace>,
     pub execution_failed: Option<miden_processor::ExecutionError>,
     pub input_mode: InputMode,
     pub breakpoints: Vec<Breakpoint>,
     pub breakpoints_hit: Vec<Breakpoint>,
     pub next_breakpoint_id: u8,
     pub stopped: bool,
+    pub history: History,
+    pub aliases: Aliases,
+    pub keybindings: KeyBindings,
+    /// The settings changed from within the debugger, which are persisted across sessions
+    pub settings: Settings,
+    /// The visibility and size of the panes, which are restored in later sessions
+    pub layout: PaneLayout,
+    /// The themes which may be selected via `set theme`
+    pub themes: Themes,
+    /// The theme used to color the panes of the TUI
+    pub theme: Theme,
+    /// The transcript of the session, if enabled
+    pub session_log: SessionLog,
+    /// The source most recently shown by the `list` command, so that it can be paged through
+    pub listing: Option<SourceListing>,
+    /// Whether user-defined trace events should be reported as they fire
+    pub trace_user_events: bool,
+    /// The user-defined trace events fired during the most recent run, if they are being traced
+    pub user_trace_events: Vec<(RowIndex, u32)>,
+    /// Raised to interrupt the current run
+    pub interrupt: Interrupt,
+    /// Whether the most recent run was stopped by an interruption
+    pub interrupted: bool,
+    /// How values are rendered by the panes of the TUI, kept for the rest of the session
+    pub display_format: FormatType,
+    /// The expressions shown in the watch pane, kept across reloads
+    pub watches: WatchList,
+    /// The addresses of the named data of the program, e.g. globals, if it has a symbol table
+    pub symbols: Symbols,
+    /// The contexts moved from and to by the most recent context switch of the current run, if
+    /// any
+    pub context_switch: Option<(ContextId, ContextId)>,
+    /// The index of the call frame whose location is shown in the source pane, innermost first,
+    /// as selected in the stack trace pane. This is reset to the innermost frame on each run.
+    pub selected_frame: usize,
+    /// The cycles at which a breakpoint was hit so far, which are kept when going back to an
+    /// earlier cycle, but not when the program is restarted
+    pub hit_cycles: BTreeSet<usize>,
+    /// The changes to the operand stack and memory made by the most recent run
+    pub changes: StepChanges,
+    /// The text yanked from the panes, kept for the rest of the session
+    pub clipboard: Clipboard,
+    /// The number of breakpoint hits on which the next run stops, as requested by `continue N`
+    pub continue_count: Option<usize>,
+    /// The breakpoint hits counted during the most recent run, if it was begun by `continue N`
+    pub hit_count: Option<HitCount>,
+    /// The cycle at which the current run started, if a run is in progress
+    run_start_cycle: Option<usize>,
+    /// The operand stack as of the start of the current run, see [StepChanges::stack]
+    run_start_stack: Vec<Felt>,
+    /// The procedures of the program and libraries, listed when first needed, and again after
+    /// the program is reloaded
+    procedures: OnceCell<Arc<[Procedure]>>,
+}
+
+/// The number of cycles executed between checks for an interruption of the current run
+pub const INTERRUPT_CHECK_INTERVAL: usize = 1000;
+
+/// The status of the program being debugged
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExecutionStatus {
+    /// The program is executing
+    Running,
+    /// The program is paused in the debugger, e.g. at a breakpoint
+    Paused,
+    /// The program terminated successfully
+    Halted,
+    /// The program terminated with an error
+    Failed,
+}
+
+/// The changes made by a run of the program, which are highlighted by the panes of the TUI until
+/// the next run
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StepChanges {
+    /// The indices of the elements of the operand stack whose value differs from that of the
+    /// element at the same depth before the run, see [changed_elements]
+    pub stack: BTreeSet<usize>,
+    /// The words of memory written during the run, as `(context, addr)`, where `addr` is the
+    /// element address of the first element of the word
+    pub memory: BTreeSet<(ContextId, u32)>,
+}
+
+/// The breakpoint hits counted by a run begun by `continue N`, which runs through the hits of
+/// any breakpoint until the Nth
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HitCount {
+    /// The hit on which the run stops
+    pub target: usize,
+    /// The number of hits so far
+    pub hits: usize,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
@@ -39,56 +153,44 @@ pub enum InputMode {
 impl State {
     pub fn new(config: Box<DebuggerConfig>) -> Result<Self, Report> {
         let source_manager = Arc::new(DefaultSourceManager::default());
-        let mut inputs = config.inputs.clone().unwrap_or_default();
-        if !config.args.is_empty() {
-            inputs.inputs = StackInputs::new(&config.args.iter().map(|n| n.0).collect::<Vec<_>>())
-                .into_diagnostic()?;
-        }
-        let args = inputs.inputs.iter().copied().rev().collect::<Vec<_>>();
         let package = load_package(&config)?;
-
-        // Load libraries from link_libraries and sysroot BEFORE resolving dependencies
-        let mut libs = Vec::with_capacity(config.link_libraries.len());
-        for link_library in config.link_libraries.iter() {
-            log::debug!(target: "state", "loading link library {}", link_library.name());
-            let lib = link_library.load(&config, source_manager.clone())?;
-            libs.push(lib.clone());
-        }
-
-        // Load std and base libraries from sysroot if available
-        if let Some(toolchain_dir) = config.toolchain_dir() {
-            libs.extend(load_sysroot_libs(&toolchain_dir)?);
-        }
-
-        // Create executor and register libraries with dependency resolver before resolving
-        let mut executor = Executor::new(args.clone());
-        for lib in libs.iter() {
-            executor.register_library_dependency(lib.clone());
-            executor.with_library(lib.clone());
-        }
-
-        // Now resolve package dependencies (they should find the registered libraries)
-        let dependencies = package.manifest.dependencies();
-        executor.with_dependencies(dependencies)?;
-        executor.with_advice_inputs(inputs.advice_inputs.clone());
+        let libs = load_libraries(&config, source_manager.clone())?;
 
         let program = package.unwrap_program();
-        let executor = executor.into_debug(&program, source_manager.clone());
+        let executor =
+            create_executor(&config, &package, &libs)?.into_debug(&program, source_manager.clone());
 
         // Execute the program until it terminates to capture a full trace for use during debugging
-        let mut trace_executor = Executor::new(args);
-        for lib in libs.iter() {
-            trace_executor.register_library_dependency(lib.clone());
-            trace_executor.with_library(lib.clone());
-        }
-        let dependencies = package.manifest.dependencies();
-        trace_executor.with_dependencies(dependencies)?;
-        trace_executor.with_advice_inputs(inputs.advice_inputs.clone());
+        let execution_trace = if config.no_capture_trace {
+            None
+        } else {
+            Some(
+                create_executor(&config, &package, &libs)?
+                    .capture_trace(&program, source_manager.clone()),
+            )
+        };
 
-        let execution_trace = trace_executor.capture_trace(&program, source_manager.clone());
+        let symbols = Symbols::from_package(&package).map_err(Report::msg)?;
+        let history = History::load(config.history_path(), config.history_size);
+        let aliases = Aliases::load(config.aliases_path());
+        let keybindings = load_keybindings(&config)?;
+        let settings = Settings::load(config.settings_path());
+        let layout = settings.layout();
+        let themes = Themes::load(config.themes_dir().as_deref());
+        let theme = match config.theme.as_deref().or(settings.theme()) {
+            Some(name) => themes.resolve_or_default(name),
+            None => themes.resolve_or_default(DEFAULT_THEME),
+        };
+        let mut session_log = SessionLog::default();
+        if let Some(path) = config.session_log.as_deref()
+            && let Err(err) = session_log.open(path, 0)
+        {
+            log::warn!("{err}");
+        }
 
         Ok(Self {
             package,
+            libraries: libs,
             source_manager,
             config,
             executor,
@@ -99,75 +201,431 @@ impl State {
             breakpoints_hit: vec![],
             next_breakpoint_id: 0,
             stopped: true,
+            history,
+            aliases,
+            keybindings,
+            settings,
+            layout,
+            themes,
+            theme,
+            session_log,
+            listing: None,
+            trace_user_events: false,
+            user_trace_events: vec![],
+            interrupt: Interrupt::default(),
+            interrupted: false,
+            display_format: FormatType::Decimal,
+            watches: WatchList::default(),
+            symbols,
+            context_switch: None,
+            selected_frame: 0,
+            hit_cycles: BTreeSet::new(),
+            changes: StepChanges::default(),
+            clipboard: Clipboard::default(),
+            continue_count: None,
+            hit_count: None,
+            run_start_cycle: None,
+            run_start_stack: vec![],
+            procedures: OnceCell::new(),
         })
     }
 
+    /// Reload the program from disk, and restart execution, keeping breakpoints
     pub fn reload(&mut self) -> Result<(), Report> {
         log::debug!("reloading program");
         let package = load_package(&self.config)?;
+        let libs = load_libraries(&self.config, self.source_manager.clone())?;
+        let symbols = Symbols::from_package(&package).map_err(Report::msg)?;
+        self.restart(package, libs)?;
+        self.symbols = symbols;
+        self.procedures = OnceCell::new();
+        Ok(())
+    }
 
-        let mut inputs = self.config.inputs.clone().unwrap_or_default();
-        if !self.config.args.is_empty() {
-            inputs.inputs = StackInputs::new(
-                &self.config.args.iter().copied().map(|n| n.0).collect::<Vec<_>>(),
-            )
-            .into_diagnostic()?;
-        }
-        let args = inputs.inputs.iter().copied().rev().collect::<Vec<_>>();
-
-        // Load libraries from link_libraries and sysroot BEFORE resolving dependencies
-        let mut libs = Vec::with_capacity(self.config.link_libraries.len());
-        for link_library in self.config.link_libraries.iter() {
-            let lib = link_library.load(&self.config, self.source_manager.clone())?;
-            libs.push(lib.clone());
-        }
+    /// The procedures of the program and the libraries it was loaded with, see [list_procedures]
+    pub fn procedures(&self) -> Arc<[Procedure]> {
+        self.procedures
+            .get_or_init(|| list_procedures(&self.package, &self.libraries).into())
+            .clone()
+    }
 
-        // Load std and base libraries from sysroot if available
-        if let Some(toolchain_dir) = self.config.toolchain_dir() {
-            libs.extend(load_sysroot_libs(&toolchain_dir)?);
-        }
+    /// Select the theme named `name`, and remember it for later sessions
+    ///
+    /// Returns an error if there is no such theme. If the theme is selected, but the selection
+    /// cannot be saved, the reason is returned.
+    pub fn set_theme(&mut self, name: &str) -> Result<Option<String>, String> {
+        self.theme = self.themes.resolve(name)?;
+        Ok(self.settings.set_theme(name).err())
+    }
 
-        // Create executor and register libraries with dependency resolver before resolving
-        let mut executor = Executor::new(args.clone());
-        for lib in libs.iter() {
-            executor.register_library_dependency(lib.clone());
-            executor.with_library(lib.clone());
+    /// Persist the layout of the panes for later sessions, if it was changed during this one
+    pub fn save_layout(&mut self) {
+        if self.layout != self.settings.layout()
+            && let Err(err) = self.settings.set_layout(&self.layout)
+        {
+            log::warn!("{err}");
         }
+    }
 
-        // Now resolve package dependencies
-        let dependencies = package.manifest.dependencies();
-        executor.with_dependencies(dependencies)?;
-        executor.with_advice_inputs(inputs.advice_inputs.clone());
+    /// Restart execution of the loaded program with the current arguments, keeping breakpoints.
+    ///
+    /// Unlike [State::reload], the program is not read from disk again.
+    pub fn reset(&mut self) -> Result<(), Report> {
+        log::debug!("restarting program");
+        self.restart(self.package.clone(), self.libraries.clone())
+    }
 
+    /// Start executing `package` from the beginning, discarding the state of the current run
+    fn restart(
+        &mut self,
+        package: Arc<miden_mast_package::Package>,
+        libs: Vec<Arc<miden_assembly_syntax::Library>>,
+    ) -> Result<(), Report> {
         let program = package.unwrap_program();
-        let executor = executor.into_debug(&program, self.source_manager.clone());
+        let executor = create_executor(&self.config, &package, &libs)?
+            .into_debug(&program, self.source_manager.clone());
 
-        // Execute the program until it terminates to capture a full trace for use during debugging
-        let mut trace_executor = Executor::new(args);
-        for lib in libs.iter() {
-            trace_executor.register_library_dependency(lib.clone());
-            trace_executor.with_library(lib.clone());
-        }
-        let dependencies = package.manifest.dependencies();
-        trace_executor.with_dependencies(dependencies)?;
-        trace_executor.with_advice_inputs(core::mem::take(&mut inputs.advice_inputs));
-        let execution_trace = trace_executor.capture_trace(&program, self.source_manager.clone());
+        // Execute the program until it terminates to capture a full trace for use during debugging,
+        // unless its capture was deferred and has not been requested since
+        let execution_trace = if self.config.no_capture_trace && self.execution_trace.is_none() {
+            None
+        } else {
+            Some(
+                create_executor(&self.config, &package, &libs)?
+                    .capture_trace(&program, self.source_manager.clone()),
+            )
+        };
 
         self.package = package;
-        self.executor = executor;
+        self.libraries = libs;
         self.execution_trace = execution_trace;
+        self.hit_cycles.clear();
+        self.start_over(executor);
+        Ok(())
+    }
+
+    /// Replace the executor with `executor`, which is at the start of the program, discarding the
+    /// state of the current run, but keeping breakpoints
+    fn start_over(&mut self, executor: DebugExecutor) {
+        self.executor = executor;
         self.execution_failed = None;
+        self.listing = None;
         self.breakpoints_hit.clear();
         let breakpoints = core::mem::take(&mut self.breakpoints);
         self.breakpoints.reserve(breakpoints.len());
         self.next_breakpoint_id = 0;
         self.stopped = true;
+        self.interrupted = false;
+        self.run_start_cycle = None;
+        self.changes = StepChanges::default();
+        self.hit_count = None;
+        self.context_switch = None;
+        self.selected_frame = 0;
+        self.watches.reset();
         for bp in breakpoints {
-            self.create_breakpoint(bp.ty);
+            // Like cycle counts, which restart from the creation cycle, instructions are counted
+            // from the start of the program again
+            let ty = match bp.ty {
+                BreakpointType::StepOps { n, .. } => BreakpointType::StepOps { n, started: 0 },
+                ty => ty,
+            };
+            self.create_breakpoint(ty);
+            // Breakpoints disabled by the user stay disabled, but their hit counts are reset
+            if let Some(created) = self.breakpoints.last_mut() {
+                created.enabled = bp.enabled;
+            }
+        }
+    }
+
+    /// Replace the executor with `executor`, which is at the start of the program, discarding the
+    /// state of the current run, but unlike [State::start_over], leaving breakpoints and watches
+    /// untouched
+    fn rewind(&mut self, executor: DebugExecutor) {
+        self.executor = executor;
+        self.execution_failed = None;
+        self.breakpoints_hit.clear();
+        self.stopped = true;
+        self.interrupted = false;
+        self.run_start_cycle = None;
+        self.context_switch = None;
+        self.selected_frame = 0;
+    }
+
+    /// Go to `cycle`, rewinding by executing the program from the beginning again if it has
+    /// already been passed.
+    ///
+    /// Breakpoints are not checked along the way, and are left as they were, along with their ids
+    /// and hit counts, as are watches. If execution terminates before `cycle`, the program is left
+    /// at the cycle at which it terminated.
+    pub fn goto_cycle(&mut self, cycle: usize) -> Result<(), Report> {
+        if cycle < self.executor.cycle {
+            let program = self.package.unwrap_program();
+            let executor = create_executor(&self.config, &self.package, &self.libraries)?
+                .into_debug(&program, self.source_manager.clone());
+            self.rewind(executor);
         }
+        // Going to a cycle is not a run, so nothing is shown as having changed
+        self.changes = StepChanges::default();
+        self.hit_count = None;
+        if let Err(err) = self.executor.run_to_cycle(cycle) {
+            self.execution_failed = Some(err);
+        }
+        self.stopped = true;
         Ok(())
     }
 
+    /// Resume execution until a breakpoint is hit, the program terminates, or execution is
+    /// interrupted via [State::interrupt].
+    pub fn run_until_stopped(&mut self) {
+        // Outside of the TUI, Ctrl-C is delivered as SIGINT rather than as a key press
+        let _sigint = self
+            .interrupt
+            .on_sigint()
+            .inspect_err(|err| log::warn!("unable to handle SIGINT during execution: {err}"));
+
+        self.start_run();
+        while !self.continue_run(INTERRUPT_CHECK_INTERVAL) {}
+    }
+
+    /// Returns the current status of the program being debugged
+    pub fn status(&self) -> ExecutionStatus {
+        if self.executor.stopped {
+            if self.execution_failed.is_some() {
+                ExecutionStatus::Failed
+            } else {
+                ExecutionStatus::Halted
+            }
+        } else if self.is_running() {
+            ExecutionStatus::Running
+        } else {
+            ExecutionStatus::Paused
+        }
+    }
+
+    /// Returns true if a run begun by [State::start_run] has not yet stopped
+    pub fn is_running(&self) -> bool {
+        self.run_start_cycle.is_some()
+    }
+
+    /// Begin resuming execution, which must then be driven to completion via
+    /// [State::continue_run].
+    pub fn start_run(&mut self) {
+        // Breakpoints hit during a previous run have been reported by now
+        self.breakpoints_hit.clear();
+        self.user_trace_events.clear();
+        self.executor.user_trace_events.borrow_mut().clear();
+        // Only interruptions requested during this run are relevant to it
+        self.interrupt.take();
+        self.interrupted = false;
+        self.context_switch = None;
+        self.selected_frame = 0;
+
+        self.run_start_cycle = Some(self.executor.cycle);
+        self.run_start_stack = self.executor.current_stack.clone();
+        self.changes = StepChanges::default();
+        self.hit_count = self.continue_count.take().map(|target| HitCount { target, hits: 0 });
+        self.stopped = false;
+    }
+
+    /// Execute at most `max_steps` cycles of the run begun by [State::start_run], returning true
+    /// if the run has stopped, i.e. a breakpoint was hit, the program terminated, or the run was
+    /// interrupted.
+    pub fn continue_run(&mut self, max_steps: usize) -> bool {
+        let Some(start_cycle) = self.run_start_cycle else {
+            return true;
+        };
+        if self.interrupt.take() {
+            self.interrupted = true;
+            self.finish_run();
+            return true;
+        }
+
+        let mut breakpoints = core::mem::take(&mut self.breakpoints);
+        self.stopped = false;
+        let mut stopped = false;
+        for _ in 0..max_steps {
+            // If stepping the program results in the program terminating succesfully, stop
+            if self.executor.stopped {
+                stopped = true;
+                break;
+            }
+
+            let mut consume_most_recent_finish = false;
+            let prev_context = self.executor.current_context;
+            match self.executor.step() {
+                Ok(Some(exited)) if exited.should_break_on_exit() => {
+                    consume_most_recent_finish = true;
+                }
+                Ok(_) => (),
+                Err(err) => {
+                    // Execution terminated with an error
+                    self.execution_failed = Some(err);
+                    stopped = true;
+                    break;
+                }
+            }
+
+            let written = self.executor.memory_written.iter();
+            self.changes
+                .memory
+                .extend(written.map(|addr| (self.executor.current_context, *addr)));
+
+            let switched_context = prev_context != self.executor.current_context;
+            if switched_context {
+                self.context_switch = Some((prev_context, self.executor.current_context));
+            }
+
+            if breakpoints.is_empty() {
+                // No breakpoint management needed, keep executing
+                continue;
+            }
+
+            let (_op, is_op_boundary, proc, loc) = {
+                let op = self.executor.current_op;
+                // The first cycle of an assembly instruction
+                let is_boundary =
+                    self.executor.current_asmop.is_some() && self.executor.current_asmop_cycle == 0;
+                let (proc, loc) = match self.executor.callstack.current_frame() {
+                    Some(frame) => {
+                        let loc = frame
+                            .recent()
+                            .back()
+                            .and_then(|detail| detail.resolve(&self.source_manager))
+                            .cloned();
+                        (frame.procedure(""), loc)
+                    }
+                    None => (None, None),
+                };
+                (op, is_boundary, proc, loc)
+            };
+
+            // Remove all breakpoints triggered at this cycle
+            let current_cycle = self.executor.cycle;
+            let cycles_stepped = current_cycle - start_cycle;
+            let completed_instruction = self.executor.completed_instruction();
+            let counting_hits = self.hit_count.is_some();
+            breakpoints.retain_mut(|bp| {
+                // While counting hits, a breakpoint on a location is hit once per pass through it,
+                // rather than on each of the cycles spent there
+                let repeated = counting_hits && bp.passing;
+                bp.record_pass(loc.as_ref(), proc.as_deref());
+                if !bp.enabled {
+                    return true;
+                }
+
+                if let BreakpointType::StepInstructions { n, completed } = &mut bp.ty {
+                    *completed += completed_instruction as usize;
+                    if *completed >= *n {
+                        self.breakpoints_hit.push(core::mem::take(bp));
+                        return false;
+                    }
+                    return true;
+                }
+
+                if let BreakpointType::StepOps { n, started } = &mut bp.ty {
+                    *started += (cycles_stepped > 0 && is_op_boundary) as usize;
+                    if *started >= *n {
+                        return record_hit(bp, &mut self.breakpoints_hit);
+                    }
+                    return true;
+                }
+
+                if let Some(n) = bp.cycles_to_skip(current_cycle) {
+                    if n == 0 {
+                        return record_hit(bp, &mut self.breakpoints_hit);
+                    } else {
+                        return true;
+                    }
+                }
+
+                if cycles_stepped > 0 && is_op_boundary && matches!(&bp.ty, BreakpointType::Next) {
+                    self.breakpoints_hit.push(core::mem::take(bp));
+                    return false;
+                }
+
+                if let Some(loc) = loc.as_ref()
+                    && bp.should_break_at(loc)
+                    && !repeated
+                {
+                    return record_hit(bp, &mut self.breakpoints_hit);
+                }
+
+                if switched_context && matches!(bp.ty, BreakpointType::ContextSwitch) {
+                    return record_hit(bp, &mut self.breakpoints_hit);
+                }
+
+                if let Some(proc) = proc.as_deref()
+                    && bp.should_break_in(proc)
+                    && !repeated
+                {
+                    return record_hit(bp, &mut self.breakpoints_hit);
+                }
+
+                true
+            });
+
+            if consume_most_recent_finish
+                && let Some(id) = breakpoints.iter().rev().find_map(|bp| {
+                    if matches!(bp.ty, BreakpointType::Finish) {
+                        Some(bp.id)
+                    } else {
+                        None
+                    }
+                })
+            {
+                breakpoints.retain(|bp| bp.id != id);
+                stopped = true;
+                break;
+            }
+
+            // Run through the hits before the one on which `continue N` stops
+            if !self.breakpoints_hit.is_empty()
+                && let Some(count) = self.hit_count.as_mut()
+            {
+                count.hits += 1;
+                if count.hits < count.target {
+                    self.hit_cycles.insert(current_cycle);
+                    self.breakpoints_hit.clear();
+                }
+            }
+
+            if !self.breakpoints_hit.is_empty() {
+                stopped = true;
+                break;
+            }
+        }
+
+        // Restore the breakpoints state
+        self.breakpoints = breakpoints;
+
+        if stopped {
+            self.finish_run();
+        }
+        stopped
+    }
+
+    fn finish_run(&mut self) {
+        if !self.breakpoints_hit.is_empty() {
+            self.hit_cycles.insert(self.executor.cycle);
+        }
+        // Nothing has changed until there is a stack to compare against, i.e. before the first
+        // cycle
+        if !self.run_start_stack.is_empty() {
+            self.changes.stack =
+                changed_elements(&self.run_start_stack, &self.executor.current_stack);
+        }
+        if self.trace_user_events {
+            let fired = core::mem::take(&mut *self.executor.user_trace_events.borrow_mut());
+            for (clk, id) in fired.iter() {
+                log::info!(target: "trace", "trace.{id} at cycle {clk}");
+            }
+            self.user_trace_events = fired;
+        }
+
+        self.run_start_cycle = None;
+        self.stopped = true;
+    }
+
     pub fn create_breakpoint(&mut self, ty: BreakpointType) {
         let id = self.next_breakpoint_id();
         let creation_cycle = self.executor.cycle;
@@ -181,9 +639,35 @@ impl State {
             id,
             creation_cycle,
             ty,
+            ..Default::default()
         });
     }
 
+    /// Create a breakpoint on `line` of the source file identified by `source_id`, or if any
+    /// breakpoints are already set on that line, remove them.
+    ///
+    /// Returns true if a breakpoint was created, or false if breakpoints were removed.
+    pub fn toggle_line_breakpoint(
+        &mut self,
+        source_id: SourceId,
+        line: u32,
+    ) -> Result<bool, String> {
+        let source_file = self
+            .source_manager
+            .get(source_id)
+            .map_err(|_| "cannot set a breakpoint in an unknown source file".to_string())?;
+        let uri = source_file.uri().as_str();
+        let set = self.breakpoints.len();
+        self.breakpoints.retain(|bp| !bp.is_set_on_line(uri, line));
+        if self.breakpoints.len() != set {
+            return Ok(false);
+        }
+        let pattern = glob::Pattern::new(&glob::Pattern::escape(uri))
+            .map_err(|err| format!("invalid breakpoint expression: bad pattern: {err}"))?;
+        self.create_breakpoint(BreakpointType::Line { pattern, line });
+        Ok(true)
+    }
+
     fn next_breakpoint_id(&mut self) -> u8 {
         let mut candidate = self.next_breakpoint_id;
         let initial = candidate;
@@ -207,108 +691,427 @@ impl State {
 }
 
 macro_rules! write_with_format_type {
-    ($out:ident, $read_expr:ident, $value:expr) => {
-        match $read_expr.format {
-            crate::debug::FormatType::Decimal => write!(&mut $out, "{}", $value).unwrap(),
-            crate::debug::FormatType::Hex => write!(&mut $out, "{:0x}", $value).unwrap(),
-            crate::debug::FormatType::Binary => write!(&mut $out, "{:0b}", $value).unwrap(),
+    ($out:expr, $format:expr, $value:expr) => {
+        match $format {
+            crate::debug::FormatType::Decimal => write!($out, "{}", $value).unwrap(),
+            crate::debug::FormatType::Hex => write!($out, "{:0x}", $value).unwrap(),
+            crate::debug::FormatType::Binary => write!($out, "{:0b}", $value).unwrap(),
         }
     };
 }
 
 impl State {
-    pub fn read_memory(&self, expr: &ReadMemoryExpr) -> Result<String, String> {
-        use core::fmt::Write;
+    /// Capture the execution trace, if its capture was deferred at startup
+    ///
+    /// Returns false if the trace had already been captured.
+    pub fn capture_trace(&mut self) -> Result<bool, Report> {
+        if self.execution_trace.is_some() {
+            return Ok(false);
+        }
+        let program = self.package.unwrap_program();
+        let execution_trace = create_executor(&self.config, &self.package, &self.libraries)?
+            .capture_trace(&program, self.source_manager.clone());
+        self.execution_trace = Some(execution_trace);
+        Ok(true)
+    }
 
-        use miden_assembly_syntax::ast::types::Type;
+    /// Run the program from the start until it terminates, measuring the cycles spent executing
+    /// each line of source code
+    pub fn measure_cycles(&self) -> Result<CycleMeasurement, Report> {
+        let program = self.package.unwrap_program();
+        let executor = create_executor(&self.config, &self.package, &self.libraries)?
+            .into_debug(&program, self.source_manager.clone());
+        Ok(CycleMeasurement::run(executor, &*self.source_manager))
+    }
 
-        use crate::debug::FormatType;
+    /// Get the source files known to the source manager, in the order they were loaded
+    pub fn source_files(&self) -> Vec<Arc<SourceFile>> {
+        // Source files are assigned sequential identifiers, so visit them in order until we reach
+        // one which does not exist
+        (0..).map_while(|id| self.source_manager.get(SourceId::new(id)).ok()).collect()
+    }
 
-        let cycle = miden_processor::trace::RowIndex::from(self.executor.cycle);
-        let context = self.executor.current_context;
+    /// The call frame selected via [State::selected_frame], if the call stack is that deep
+    pub fn selected_frame(&self) -> Option<&CallFrame> {
+        self.executor.callstack.frames().iter().rev().nth(self.selected_frame)
+    }
+
+    /// Render `felt` per [State::display_format], i.e. as the panes of the TUI show it
+    pub fn format_felt(&self, felt: Felt) -> String {
+        let value = felt.as_canonical_u64();
+        match self.display_format {
+            FormatType::Decimal => value.to_string(),
+            FormatType::Hex => format!("{value:#x}"),
+            FormatType::Binary => format!("{value:#b}"),
+        }
+    }
+
+    /// Evaluate the expressions of [State::watches] as of the current cycle
+    pub fn evaluate_watches(&mut self) {
+        let mut watches = core::mem::take(&mut self.watches);
+        watches.evaluate(self.executor.cycle, |expr| self.read_memory(expr));
+        self.watches = watches;
+    }
+
+    /// Get the execution trace, or an error if its capture was deferred, and it has not been
+    /// captured since
+    pub fn execution_trace(&self) -> Result<&ExecutionTrace, String> {
+        self.execution_trace
+            .as_ref()
+            .ok_or_else(|| "the execution trace has not been captured, see `capture`".into())
+    }
+
+    pub fn read_memory(&self, expr: &ReadMemoryExpr) -> Result<String, String> {
+        let expr = &expr.resolve(&self.symbols)?;
         let mut output = String::new();
         if expr.count > 1 {
-            return Err("-count with value > 1 is not yet implemented".into());
-        } else if matches!(expr.ty, Type::Felt) {
-            if !expr.addr.is_element_aligned() {
-                return Err(
-                    "read failed: type 'felt' must be aligned to an element boundary".into()
-                );
-            }
-            let felt = self
-                .execution_trace
-                .read_memory_element_in_context(expr.addr.addr, context, cycle)
-                .unwrap_or(Felt::ZERO);
-            write_with_format_type!(output, expr, felt.as_canonical_u64());
-        } else if matches!(
-            expr.ty,
-            Type::Array(ref array_ty) if array_ty.element_type() == &Type::Felt && array_ty.len() == 4
-        ) {
-            if !expr.addr.is_word_aligned() {
-                return Err("read failed: type 'word' must be aligned to a word boundary".into());
-            }
-            let word = self.execution_trace.read_memory_word(expr.addr.addr).unwrap_or_default();
+            let stride = stride_of(&expr.ty);
             output.push('[');
-            for (i, elem) in word.iter().enumerate() {
+            for i in 0..(expr.count as u32) {
                 if i > 0 {
                     output.push_str(", ");
                 }
-                write_with_format_type!(output, expr, elem.as_canonical_u64());
+                let addr = offset_ptr(expr.addr, i * stride)?;
+                self.read_value(&mut output, addr, &expr.ty, &expr.fields, expr.format)?;
             }
             output.push(']');
         } else {
-            let bytes = self
-                .execution_trace
-                .read_bytes_for_type(expr.addr, &expr.ty, context, cycle)
-                .map_err(|err| format!("invalid read: {err}"))?;
-            match &expr.ty {
-                Type::I1 => match expr.format {
-                    FormatType::Decimal => write!(&mut output, "{}", bytes[0] != 0).unwrap(),
-                    FormatType::Hex => {
-                        write!(&mut output, "{:#0x}", (bytes[0] != 0) as u8).unwrap()
+            self.read_value(&mut output, expr.addr, &expr.ty, &expr.fields, expr.format)?;
+        }
+
+        Ok(output)
+    }
+
+    /// Read the value of type `ty` at `addr` in the current context, as of the current cycle, and
+    /// render it to `output`.
+    ///
+    /// Arrays are read element by element, so that each element is read with the alignment, and
+    /// the representation, of its own type.
+    fn read_value(
+        &self,
+        output: &mut String,
+        addr: NativePtr,
+        ty: &miden_assembly_syntax::ast::types::Type,
+        fields: &FieldNames,
+        format: FormatType,
+    ) -> Result<(), String> {
+        use core::fmt::Write;
+
+        use miden_assembly_syntax::ast::types::{ArrayType, Type};
+
+        let execution_trace = self.execution_trace()?;
+
+        let cycle = miden_processor::trace::RowIndex::from(self.executor.cycle);
+        let context = self.executor.current_context;
+        match ty {
+            Type::Felt => {
+                if !addr.is_element_aligned() {
+                    return Err(
+                        "read failed: type 'felt' must be aligned to an element boundary".into()
+                    );
+                }
+                let felt = execution_trace
+                    .read_memory_element_in_context(addr.addr, context, cycle)
+                    .unwrap_or(Felt::ZERO);
+                write_with_format_type!(output, format, felt.as_canonical_u64());
+            }
+            Type::Array(array_ty)
+                if array_ty.element_type() == &Type::Felt && array_ty.len() == 4 =>
+            {
+                if !addr.is_word_aligned() {
+                    return Err(
+                        "read failed: type 'word' must be aligned to a word boundary".into()
+                    );
+                }
+                let word = execution_trace.read_memory_word(addr.addr).unwrap_or_default();
+                output.push('[');
+                for (i, elem) in word.iter().enumerate() {
+                    if i > 0 {
+                        output.push_str(", ");
                     }
-                    FormatType::Binary => {
-                        write!(&mut output, "{:#0b}", (bytes[0] != 0) as u8).unwrap()
+                    write_with_format_type!(output, format, elem.as_canonical_u64());
+                }
+                output.push(']');
+            }
+            Type::Array(array_ty) => {
+                let element_ty = array_ty.element_type();
+                check_alignment(addr, ty)?;
+                let stride = stride_of(element_ty);
+                output.push('[');
+                for i in 0..(array_ty.len() as u32) {
+                    if i > 0 {
+                        output.push_str(", ");
                     }
-                },
-                Type::I8 => write_with_format_type!(output, expr, bytes[0] as i8),
-                Type::U8 => write_with_format_type!(output, expr, bytes[0]),
-                Type::I16 => {
-                    write_with_format_type!(output, expr, i16::from_be_bytes([bytes[0], bytes[1]]))
+                    let addr = offset_ptr(addr, i * stride)?;
+                    self.read_value(output, addr, element_ty, fields, format)?;
                 }
-                Type::U16 => {
-                    write_with_format_type!(output, expr, u16::from_be_bytes([bytes[0], bytes[1]]))
+                output.push(']');
+            }
+            ty => {
+                check_alignment(addr, ty)?;
+                // Values which do not start on an element boundary are read along with the
+                // preceding bytes of the element containing their first byte
+                let offset = addr.offset as usize;
+                let padded = Type::from(ArrayType::new(Type::U8, offset + ty.size_in_bytes()));
+                let bytes = execution_trace
+                    .read_bytes_for_type(NativePtr::new(addr.addr, 0), &padded, context, cycle)
+                    .map_err(|err| format!("invalid read: {err}"))?;
+                write_value(output, &bytes[offset..], ty, fields, format)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The indices of the elements of `next` which differ from the element at the same depth in
+/// `prev`, including those which are deeper than all of the elements of `prev`
+pub fn changed_elements(prev: &[Felt], next: &[Felt]) -> BTreeSet<usize> {
+    next.iter()
+        .enumerate()
+        .filter(|(index, felt)| prev.get(*index) != Some(*felt))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Record that `bp` was hit in `hits`, returning true if it should be kept, i.e. it is not consumed
+/// by being hit
+fn record_hit(bp: &mut Breakpoint, hits: &mut Vec<Breakpoint>) -> bool {
+    bp.hits += 1;
+    let retained = !bp.is_one_shot();
+    if retained {
+        hits.push(bp.clone());
+    } else {
+        hits.push(core::mem::take(bp));
+    }
+    retained
+}
+
+/// The distance in bytes between consecutive values of type `ty`, e.g. the elements of an array
+fn stride_of(ty: &miden_assembly_syntax::ast::types::Type) -> u32 {
+    ty.size_in_bytes().next_multiple_of(ty.min_alignment()) as u32
+}
+
+/// Returns `addr` advanced by `bytes`
+fn offset_ptr(addr: NativePtr, bytes: u32) -> Result<NativePtr, String> {
+    (addr.addr as u64 * 4 + addr.offset as u64 + bytes as u64)
+        .try_into()
+        .map(NativePtr::from_ptr)
+        .map_err(|_| "read failed: address is out of bounds".to_string())
+}
+
+/// Ensure that `addr` is suitably aligned for a value of type `ty`, which for arrays is the
+/// alignment of their elements
+fn check_alignment(
+    addr: NativePtr,
+    ty: &miden_assembly_syntax::ast::types::Type,
+) -> Result<(), String> {
+    let align = ty.min_alignment();
+    if (addr.addr as u64 * 4 + addr.offset as u64).is_multiple_of(align as u64) {
+        Ok(())
+    } else {
+        Err(format!("read failed: type '{ty}' must be aligned to a {align}-byte boundary"))
+    }
+}
+
+/// Render the value of type `ty` whose in-memory representation is `bytes`.
+///
+/// Aggregates are rendered recursively, with each struct field or array element decoded from its
+/// offset in `bytes`, and struct fields labeled using `fields`.
+fn write_value(
+    output: &mut String,
+    bytes: &[u8],
+    ty: &miden_assembly_syntax::ast::types::Type,
+    fields: &FieldNames,
+    format: FormatType,
+) -> Result<(), String> {
+    use core::fmt::Write;
+
+    use miden_assembly_syntax::ast::types::Type;
+
+    match ty {
+        Type::I1 => match format {
+            FormatType::Decimal => write!(output, "{}", bytes[0] != 0).unwrap(),
+            FormatType::Hex => write!(output, "{:#0x}", (bytes[0] != 0) as u8).unwrap(),
+            FormatType::Binary => write!(output, "{:#0b}", (bytes[0] != 0) as u8).unwrap(),
+        },
+        Type::I8 => write_with_format_type!(output, format, bytes[0] as i8),
+        Type::U8 => write_with_format_type!(output, format, bytes[0]),
+        Type::I16 => {
+            write_with_format_type!(output, format, i16::from_be_bytes([bytes[0], bytes[1]]))
+        }
+        Type::U16 => {
+            write_with_format_type!(output, format, u16::from_be_bytes([bytes[0], bytes[1]]))
+        }
+        Type::I32 => write_with_format_type!(
+            output,
+            format,
+            i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        ),
+        Type::U32 => write_with_format_type!(
+            output,
+            format,
+            u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        ),
+        ty @ (Type::I64 | Type::U64) => {
+            let hi = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+            let lo = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+            let val = (hi * 2u64.pow(32)) + lo;
+            if matches!(ty, Type::I64) {
+                write_with_format_type!(output, format, val as i64)
+            } else {
+                write_with_format_type!(output, format, val)
+            }
+        }
+        Type::Array(array_ty) => {
+            let element_ty = array_ty.element_type();
+            let element_size = element_ty.size_in_bytes();
+            let stride = element_size.next_multiple_of(element_ty.min_alignment());
+            output.push('[');
+            for i in 0..array_ty.len() {
+                if i > 0 {
+                    output.push_str(", ");
                 }
-                Type::I32 => write_with_format_type!(
-                    output,
-                    expr,
-                    i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
-                ),
-                Type::U32 => write_with_format_type!(
+                let offset = i * stride;
+                write_value(
                     output,
-                    expr,
-                    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
-                ),
-                ty @ (Type::I64 | Type::U64) => {
-                    let hi = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
-                    let lo = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
-                    let val = (hi * 2u64.pow(32)) + lo;
-                    if matches!(ty, Type::I64) {
-                        write_with_format_type!(output, expr, val as i64)
-                    } else {
-                        write_with_format_type!(output, expr, val)
-                    }
-                }
-                ty => {
-                    return Err(format!(
-                        "support for reads of type '{ty}' are not implemented yet"
-                    ));
+                    &bytes[offset..(offset + element_size)],
+                    element_ty,
+                    fields,
+                    format,
+                )?;
+            }
+            output.push(']');
+        }
+        Type::Struct(struct_ty) => {
+            output.push_str("{ ");
+            for (i, field) in struct_ty.fields().iter().enumerate() {
+                if i > 0 {
+                    output.push_str(", ");
                 }
+                let (name, field_names) = match fields.0.get(i) {
+                    Some((name, field_names)) => (name.clone(), field_names),
+                    None => (i.to_string(), &FieldNames::default()),
+                };
+                write!(output, "{name}: ").unwrap();
+                let offset = field.offset as usize;
+                write_value(
+                    output,
+                    &bytes[offset..(offset + field.ty.size_in_bytes())],
+                    &field.ty,
+                    field_names,
+                    format,
+                )?;
             }
+            output.push_str(" }");
+        }
+        ty => {
+            return Err(format!("support for reads of type '{ty}' are not implemented yet"));
         }
+    }
 
-        Ok(output)
+    Ok(())
+}
+
+/// Load the libraries linked via `config`, followed by those found in the toolchain sysroot.
+///
+/// Libraries must be loaded before resolving the dependencies of a package, as they are used to
+/// satisfy those dependencies.
+pub fn load_libraries(
+    config: &DebuggerConfig,
+    source_manager: Arc<dyn SourceManager>,
+) -> Result<Vec<Arc<miden_assembly_syntax::Library>>, Report> {
+    let mut libs = Vec::with_capacity(config.link_libraries.len());
+    for link_library in config.link_libraries.iter() {
+        log::debug!(target: "state", "loading link library {}", link_library.name());
+        let lib = link_library.load(config, source_manager.clone())?;
+        libs.push(lib.clone());
+    }
+
+    // Load std and base libraries from sysroot if available
+    if let Some(toolchain_dir) = config.toolchain_dir() {
+        libs.extend(load_sysroot_libs(&toolchain_dir)?);
+    }
+
+    Ok(libs)
+}
+
+/// Merge the `--inputs` file and `--args` of `config` into the inputs the program is executed
+/// with, i.e. the operand stack is exactly what the program sees on entry.
+pub fn resolve_inputs(config: &DebuggerConfig) -> Result<ExecutionConfig, Report> {
+    let mut inputs = config.inputs.clone().unwrap_or_default();
+    if !config.args.is_empty() {
+        let args = config.args.iter().flat_map(|arg| arg.felts()).copied().collect::<Vec<_>>();
+        inputs.inputs = StackInputs::new(&args).into_diagnostic()?;
+    }
+    let stack = inputs.inputs.iter().copied().rev().collect::<Vec<_>>();
+    inputs.inputs = StackInputs::new(&stack).into_diagnostic()?;
+    Ok(inputs)
+}
+
+/// Construct an [Executor] for `package`, using the inputs specified by `config`, and resolving
+/// the dependencies of `package` against `libs`.
+pub fn create_executor(
+    config: &DebuggerConfig,
+    package: &miden_mast_package::Package,
+    libs: &[Arc<miden_assembly_syntax::Library>],
+) -> Result<Executor, Report> {
+    // Register libraries with the dependency resolver before resolving
+    let mut executor = Executor::from_config(resolve_inputs(config)?);
+    for lib in libs.iter() {
+        executor.register_library_dependency(lib.clone());
+        executor.with_library(lib.clone());
+    }
+
+    let dependencies = package.manifest.dependencies();
+    executor.with_dependencies(dependencies)?;
+    executor.with_error_codes(load_error_codes(config, package)?);
+    if let Some(max_cycles) = config.max_cycles {
+        executor.with_max_cycles(max_cycles)?;
+    }
+
+    Ok(executor)
+}
+
+/// Load the key bindings configured by the file given by `--keybindings`, or found in the
+/// configuration directory, if any.
+fn load_keybindings(config: &DebuggerConfig) -> Result<KeyBindings, Report> {
+    let Some(path) = config.keybindings_path() else {
+        return Ok(KeyBindings::default());
+    };
+    if config.keybindings.is_none() && !path.is_file() {
+        return Ok(KeyBindings::default());
     }
+    KeyBindings::parse_file(&path).map_err(|err| {
+        Report::msg(format!("failed to load key bindings from '{}': {err}", path.display()))
+    })
+}
+
+/// Load the error codes embedded in `package`, along with those of the file given by
+/// `--error-codes`, or found next to the program file, which take precedence.
+fn load_error_codes(
+    config: &DebuggerConfig,
+    package: &miden_mast_package::Package,
+) -> Result<ErrorCodes, Report> {
+    let mut error_codes = ErrorCodes::from_package(package).map_err(Report::msg)?;
+
+    let path = config.error_codes.clone().or_else(|| {
+        let InputFile::Real(input) = &config.input else {
+            return None;
+        };
+        let stem = input.file_stem()?.to_str()?;
+        ["toml", "json"]
+            .into_iter()
+            .map(|ext| input.with_file_name(format!("{stem}.errors.{ext}")))
+            .find(|path| path.is_file())
+    });
+    if let Some(path) = path {
+        let file = ErrorCodes::parse_file(&path).map_err(|err| {
+            Report::msg(format!("failed to load error codes from '{}': {err}", path.display()))
+        })?;
+        error_codes.extend(file);
+    }
+    Ok(error_codes)
 }
 
 /// Attempts to load the standard library from the sysroot/toolchain directory.
@@ -370,7 +1173,7 @@ fn load_sysroot_libs(
     Ok(libs)
 }
 
-fn load_package(config: &DebuggerConfig) -> Result<Arc<miden_mast_package::Package>, Report> {
+pub fn load_package(config: &DebuggerConfig) -> Result<Arc<miden_mast_package::Package>, Report> {
     let package = match config.input {
         InputFile::Real(ref path) => {
             let bytes = std::fs::read(path).into_diagnostic()?;
@@ -392,13 +1195,439 @@ fn load_package(config: &DebuggerConfig) -> Result<Arc<miden_mast_package::Packa
         // Input must be a library, not a program
         let id = entry
             .parse::<miden_assembly::ast::QualifiedProcedureName>()
-            .map_err(|_| Report::msg(format!("invalid function identifier: '{entry}'")))?;
+            .ok()
+            .filter(|id| !id.namespace().to_relative().is_empty())
+            .ok_or_else(|| Report::msg(format!("invalid function identifier: '{entry}'")))?;
         if !package.is_library() {
             return Err(Report::msg("cannot use --entrypoint with executable packages"));
         }
+        // The modules of a library are named by their absolute paths, whereas the entrypoint is
+        // usually given relative to the root, e.g. `foo::bar` rather than `::foo::bar`
+        let id = miden_assembly::ast::QualifiedProcedureName::from(&*id.to_absolute());
 
         package.make_executable(&id).map(Arc::new)
     } else {
         Ok(package)
     }
 }
+
+#[cfg(test)]
+impl State {
+    /// Construct a [State] for the program assembled from the given Miden Assembly source
+    pub fn for_source(source: &str) -> Self {
+        Self::for_package_bytes(package_bytes(source))
+    }
+
+    /// Construct a [State] for the program assembled from the Miden Assembly source file at
+    /// `path`, such that source locations resolve to that file
+    pub fn for_source_file(path: &std::path::Path) -> Self {
+        use miden_assembly::ast::{Module, ModuleKind};
+        use miden_debug_types::SourceManagerExt;
+
+        let source_manager = Arc::new(DefaultSourceManager::default());
+        let source_file = source_manager.load_file(path).unwrap();
+        let module = Module::parser(ModuleKind::Executable)
+            .parse(miden_assembly::Path::exec_path(), source_file, source_manager.clone())
+            .unwrap();
+        let program =
+            miden_assembly::Assembler::new(source_manager).assemble_program(module).unwrap();
+        Self::for_package_bytes(program_package_bytes(program))
+    }
+
+    fn for_package_bytes(bytes: Vec<u8>) -> Self {
+        let config = DebuggerConfig {
+            input: InputFile::from_bytes(bytes),
+            ..Default::default()
+        };
+        let mut state = Self::new(Box::new(config)).unwrap();
+        // Keep the aliases and history of tests in memory, rather than in the user's files
+        state.aliases = Aliases::default();
+        state.history = History::load(None, state.config.history_size);
+        state.keybindings = KeyBindings::default();
+        state.settings = Settings::default();
+        state.layout = PaneLayout::default();
+        state.themes = Themes::default();
+        state.theme = state.themes.resolve_or_default(DEFAULT_THEME);
+        state
+    }
+}
+
+/// Assemble the given Miden Assembly source into an executable package, and serialize it
+#[cfg(test)]
+fn package_bytes(source: &str) -> Vec<u8> {
+    let program = miden_assembly::Assembler::default().assemble_program(source).unwrap();
+    program_package_bytes(program)
+}
+
+/// Wrap the given program in an executable package, and serialize it
+#[cfg(test)]
+fn program_package_bytes(program: miden_core::program::Program) -> Vec<u8> {
+    use miden_core::serde::Serializable;
+    use miden_mast_package::{MastArtifact, Package, PackageKind, PackageManifest};
+
+    let package = Package {
+        name: "test".into(),
+        version: None,
+        description: None,
+        kind: PackageKind::Executable,
+        mast: MastArtifact::Executable(Arc::new(program)),
+        manifest: PackageManifest::new([]),
+        sections: vec![],
+    };
+    package.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_value_struct_fields() {
+        let expr = "0 -t struct{a:u32,b:u8,c:[u8;4],d:u64}".parse::<ReadMemoryExpr>().unwrap();
+        let mut bytes = vec![0u8; expr.ty.size_in_bytes()];
+        bytes[0..4].copy_from_slice(&1u32.to_be_bytes());
+        bytes[4] = 2;
+        bytes[5..9].copy_from_slice(&[3, 4, 5, 6]);
+        bytes[12..20].copy_from_slice(&7u64.to_be_bytes());
+
+        let mut output = String::new();
+        write_value(&mut output, &bytes, &expr.ty, &expr.fields, FormatType::Decimal).unwrap();
+        assert_eq!(output, "{ a: 1, b: 2, c: [3, 4, 5, 6], d: 7 }");
+    }
+
+    #[test]
+    fn state_from_package_bytes() {
+        let bytes = package_bytes("begin push.1 push.2 add swap drop end");
+        let input = InputFile::from_bytes(bytes.clone());
+        assert_eq!(input.library_kind(), Some(crate::linker::LibraryKind::Masp));
+        assert_eq!(input.bytes().as_deref(), Some(bytes.as_slice()));
+
+        let config = DebuggerConfig {
+            input,
+            ..Default::default()
+        };
+        let mut state = State::new(Box::new(config)).unwrap();
+        state.run_until_stopped();
+        assert!(state.executor.stopped);
+        assert!(state.execution_failed.is_none());
+        assert_eq!(
+            state.executor.stack_outputs.get_element(0).map(|felt| felt.as_canonical_u64()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn deferred_trace_capture() {
+        let config = DebuggerConfig {
+            input: InputFile::from_bytes(package_bytes("begin push.1 push.2 add swap drop end")),
+            no_capture_trace: true,
+            ..Default::default()
+        };
+        let mut state = State::new(Box::new(config)).unwrap();
+        assert_eq!(
+            state.execution_trace().err().as_deref(),
+            Some("the execution trace has not been captured, see `capture`")
+        );
+
+        assert!(state.capture_trace().unwrap());
+        assert!(!state.capture_trace().unwrap());
+        assert!(state.execution_trace().is_ok());
+
+        // Once requested, the trace is captured again when the program is reloaded
+        state.reload().unwrap();
+        assert!(state.execution_trace().is_ok());
+    }
+
+    #[test]
+    fn reset_does_not_reload_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reset.masp");
+        std::fs::write(&path, package_bytes("begin push.1 add end")).unwrap();
+        let config = DebuggerConfig {
+            input: InputFile::from_path(&path),
+            ..Default::default()
+        };
+        let mut state = State::new(Box::new(config)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        state.create_breakpoint(BreakpointType::StepN(3));
+        state.run_until_stopped();
+        state.run_until_stopped();
+        assert!(state.executor.stopped);
+        assert!(state.breakpoints.is_empty());
+
+        // The program can be restarted after it was removed, but not reloaded
+        state.create_breakpoint(BreakpointType::StepTo(5));
+        state.breakpoints[0].enabled = false;
+        assert!(state.reload().is_err());
+        state.config.args = vec![crate::felt::Felt::new(9).into()];
+        state.reset().unwrap();
+        assert_eq!(state.executor.cycle, 0);
+        assert!(!state.executor.stopped);
+        assert_eq!(state.breakpoints.len(), 1);
+        assert!(!state.breakpoints[0].enabled);
+
+        state.run_until_stopped();
+        assert!(state.executor.stopped);
+        assert!(state.execution_failed.is_none());
+        assert_eq!(
+            state.executor.stack_outputs.get_element(15).map(|felt| felt.as_canonical_u64()),
+            Some(9)
+        );
+    }
+
+    #[test]
+    fn stack_changed_elements() {
+        let felts = |values: &[u64]| values.iter().copied().map(Felt::new).collect::<Vec<_>>();
+        let prev = felts(&[1, 2, 3]);
+        assert!(changed_elements(&prev, &prev).is_empty());
+        assert_eq!(changed_elements(&prev, &felts(&[1, 5, 3])), BTreeSet::from([1]));
+        // Pushing an element shifts every element deeper, so all of them change
+        assert_eq!(changed_elements(&prev, &felts(&[0, 1, 2, 3])), BTreeSet::from([0, 1, 2, 3]));
+        assert_eq!(changed_elements(&prev, &felts(&[1, 2])), BTreeSet::new());
+        assert_eq!(changed_elements(&[], &felts(&[7])), BTreeSet::from([0]));
+    }
+
+    #[test]
+    fn changes_of_each_run() {
+        let mut state = State::for_source(
+            "
+begin
+    push.1 push.2 swap
+    push.3 mem_store.9
+    push.4.5.6.7 mem_storew_be.16 dropw
+end
+",
+        );
+        let root = state.executor.root_context;
+        let mut step_to = |state: &mut State, op: &str| {
+            while state.executor.current_op.map(|op| op.to_string()).as_deref() != Some(op) {
+                state.create_breakpoint(BreakpointType::Step);
+                state.run_until_stopped();
+            }
+            state.changes.clone()
+        };
+
+        // Pushing shifts the elements deeper, so those which differ from the element previously at
+        // their depth change, as does the element which grew the stack
+        let changes = step_to(&mut state, "push(2)");
+        assert_eq!(changes.stack, BTreeSet::from([0, 1, miden_processor::MIN_STACK_DEPTH + 1]));
+        assert!(changes.memory.is_empty());
+        let changes = step_to(&mut state, "swap");
+        assert_eq!(changes.stack, BTreeSet::from([0, 1]));
+
+        // Each word written is changed, however many of its elements were written
+        let changes = step_to(&mut state, "mstore");
+        assert_eq!(changes.memory, BTreeSet::from([(root, 8)]));
+        let changes = step_to(&mut state, "mstorew");
+        assert_eq!(changes.memory, BTreeSet::from([(root, 16)]));
+
+        // The changes of each step are cleared by the next
+        let changes = step_to(&mut state, "drop");
+        assert_eq!(changes.stack, BTreeSet::from_iter(0..6));
+        assert!(changes.memory.is_empty());
+
+        // A run which executes several operations accumulates their changes, while elements which
+        // are changed and then restored are unchanged, other than those by which the stack grew
+        state.goto_cycle(0).unwrap();
+        assert_eq!(state.changes, StepChanges::default());
+        state.create_breakpoint(BreakpointType::Step);
+        state.run_until_stopped();
+        state.run_until_stopped();
+        assert!(state.executor.stopped);
+        assert_eq!(state.changes.stack, BTreeSet::from([0, 1, 16, 17]));
+        assert_eq!(
+            state.changes.memory,
+            BTreeSet::from([(root, 8), (root, 16), (root, u32::MAX - 3)])
+        );
+    }
+
+    #[test]
+    fn goto_cycle_rewinds() {
+        let mut state = State::for_source(
+            "
+proc inner
+    trace.240 nop push.1 drop trace.252
+end
+
+begin
+    exec.inner push.1 assertz
+end
+",
+        );
+        let trace = state.execution_trace().unwrap();
+        let total = trace.last_cycle().as_usize();
+        assert!(trace.failed());
+        assert_eq!(trace.frame_starts().len(), 1);
+
+        state.create_breakpoint(BreakpointType::Called(glob::Pattern::new("*inner").unwrap()));
+        state.run_until_stopped();
+        let hit = state.executor.cycle;
+        assert_eq!(state.hit_cycles, BTreeSet::from([hit]));
+
+        state.goto_cycle(hit + 2).unwrap();
+        assert_eq!(state.executor.cycle, hit + 2);
+        assert!(state.stopped);
+        let stack = state.executor.current_stack.clone();
+
+        // Going back starts over, leaving breakpoints, their hit counts and the cycles at which
+        // they were hit untouched
+        let breakpoints = state.breakpoints.clone();
+        state.goto_cycle(1).unwrap();
+        assert_eq!(state.executor.cycle, 1);
+        assert_eq!(state.breakpoints, breakpoints);
+        assert_eq!(state.breakpoints[0].hits, 1);
+        assert_eq!(state.hit_cycles, BTreeSet::from([hit]));
+        state.goto_cycle(hit + 2).unwrap();
+        assert_eq!(state.executor.current_stack, stack);
+
+        // Going beyond the end stops where the program failed
+        state.goto_cycle(total + 10).unwrap();
+        assert_eq!(state.executor.cycle, total);
+        assert!(state.execution_failed.is_some());
+        state.goto_cycle(0).unwrap();
+        assert!(state.execution_failed.is_none());
+
+        state.reset().unwrap();
+        assert!(state.hit_cycles.is_empty());
+    }
+
+    #[test]
+    fn step_ops_counts_instruction_boundaries() {
+        let mut state = State::for_source(
+            "begin push.1 push.2 u32wrapping_add push.3 mem_store.4 push.5 push.6 drop drop end",
+        );
+        let mut stops = vec![];
+        loop {
+            state.create_breakpoint(BreakpointType::StepOps { n: 1, started: 0 });
+            state.run_until_stopped();
+            if state.executor.stopped {
+                break;
+            }
+            assert_eq!(state.executor.current_asmop_cycle, 0);
+            let asmop = state.executor.current_asmop.as_ref().unwrap();
+            stops.push((state.executor.cycle, asmop.op().to_string()));
+        }
+        let ops = stops.iter().map(|(_, op)| op.as_str()).collect::<Vec<_>>();
+        let start = ops.iter().position(|op| *op == "push.2").unwrap();
+        assert_eq!(
+            ops[start..],
+            [
+                "push.2",
+                "u32wrapping_add",
+                "push.3",
+                "mem_store.4",
+                "push.5",
+                "push.6",
+                "drop",
+                "drop"
+            ]
+        );
+        // Instructions which take several cycles are only counted once
+        let (add, store) = (start + 1, start + 3);
+        assert!(stops[add + 1].0 - stops[add].0 > 1);
+        assert!(stops[store + 1].0 - stops[store].0 > 1);
+
+        // Skipping several instructions at once stops where single steps would have
+        state.breakpoints.clear();
+        state.reset().unwrap();
+        state.create_breakpoint(BreakpointType::StepOps {
+            n: store + 1,
+            started: 0,
+        });
+        state.run_until_stopped();
+        assert_eq!(state.executor.cycle, stops[store].0);
+    }
+
+    #[test]
+    fn memory_words_ordered_by_context_and_address() {
+        let mut state = State::for_source(
+            "
+proc write
+    push.7 mem_store.4
+end
+
+begin
+    push.1 mem_store.9 push.2 mem_store.0 call.write
+end
+",
+        );
+        state.run_until_stopped();
+        // The frame pointer, kept at the top of the memory of each context, is ignored
+        let words = state
+            .executor
+            .memory_words()
+            .filter(|(_, addr, _)| *addr < 0x1000)
+            .map(|(context, addr, word)| {
+                (
+                    u32::from(context),
+                    addr,
+                    word.iter().map(|felt| felt.as_canonical_u64()).collect(),
+                )
+            })
+            .collect::<Vec<(u32, u32, Vec<u64>)>>();
+        let callee = words.last().unwrap().0;
+        assert_ne!(callee, 0);
+        assert_eq!(
+            words,
+            [
+                (0, 0, vec![2, 0, 0, 0]),
+                (0, 8, vec![0, 1, 0, 0]),
+                (callee, 4, vec![7, 0, 0, 0])
+            ]
+        );
+
+        let map = state.executor.memory_map();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[0].regions[..2], [0..=3, 8..=11]);
+        assert_eq!(map[1].regions[0], 4..=7);
+        assert!(map[0].is_root && !map[1].is_root);
+    }
+
+    #[test]
+    fn display_format_is_kept_across_reloads() {
+        let mut state = State::for_source("begin push.1 push.2 add swap drop end");
+        assert_eq!(state.format_felt(Felt::new(255)), "255");
+
+        state.display_format = FormatType::Hex;
+        state.reload().unwrap();
+        assert_eq!(state.format_felt(Felt::new(255)), "0xff");
+    }
+
+    #[test]
+    fn interrupt_stops_run() {
+        let config = DebuggerConfig {
+            input: InputFile::from_bytes(package_bytes(
+                "begin push.1 while.true push.1 end push.1 push.2 add swap drop end",
+            )),
+            max_cycles: Some(1 << 16),
+            ..Default::default()
+        };
+        let mut state = State::new(Box::new(config)).unwrap();
+
+        state.start_run();
+        assert!(!state.continue_run(INTERRUPT_CHECK_INTERVAL));
+        assert!(state.is_running());
+        let cycle = state.executor.cycle;
+
+        state.interrupt.raise();
+        assert!(state.continue_run(INTERRUPT_CHECK_INTERVAL));
+        assert!(!state.is_running());
+        assert!(state.stopped);
+        assert!(state.interrupted);
+        assert!(!state.executor.stopped);
+        assert_eq!(state.executor.cycle, cycle);
+        assert!(cycle <= INTERRUPT_CHECK_INTERVAL);
+        assert!(
+            crate::ui::commands::describe_stop(&state).starts_with(&format!(
+                "interrupted at cycle {}",
+                crate::ui::count::HumanCount(cycle)
+            ))
+        );
+
+        // The interrupted run can be resumed from where it left off
+        state.start_run();
+        assert!(!state.continue_run(INTERRUPT_CHECK_INTERVAL));
+        assert!(!state.interrupted);
+        assert!(state.executor.cycle > cycle);
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    borrow::Cow,
+    io::IsTerminal,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::config::ColorChoice;
+
+/// Whether text written outside of the TUI, including diagnostics, is currently colored
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Returns true if text written to stdout, i.e. outside of the TUI, should be colored per `choice`
+///
+/// In addition to what [ColorChoice::should_attempt_color] permits, `auto` only colors output
+/// written to a terminal, and on Windows, only if ANSI escapes are supported.
+pub fn should_color(choice: ColorChoice) -> bool {
+    if !choice.should_attempt_color() {
+        return false;
+    }
+    #[cfg(windows)]
+    if !choice.should_ansi() {
+        return false;
+    }
+    choice != ColorChoice::Auto || std::io::stdout().is_terminal()
+}
+
+/// Set whether text written outside of the TUI, including diagnostics, is colored per `choice`
+pub fn set_color(choice: ColorChoice) {
+    COLOR.store(should_color(choice), Ordering::Relaxed);
+}
+
+/// Formats text written to stdout, i.e. outside of the TUI, emitting ANSI escapes only when
+/// color is enabled.
+#[derive(Debug, Copy, Clone)]
+pub struct Style {
+    color: bool,
+}
+
+impl Style {
+    pub fn new(choice: ColorChoice) -> Self {
+        Self {
+            color: should_color(choice),
+        }
+    }
+
+    /// The style per the most recent call to [set_color]
+    pub fn current() -> Self {
+        Self {
+            color: COLOR.load(Ordering::Relaxed),
+        }
+    }
+
+    #[inline]
+    pub fn is_colored(&self) -> bool {
+        self.color
+    }
+
+    #[cfg(test)]
+    pub fn with_color(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// Format `text` as an error, i.e. bold red
+    pub fn error<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.paint("1;31", text)
+    }
+
+    /// Format `text` as a warning, i.e. bold yellow
+    pub fn warning<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.paint("1;33", text)
+    }
+
+    /// Format a line of command output, highlighting the `error:` prefix of failed commands
+    pub fn output_line<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        match line.strip_prefix("error:") {
+            Some(rest) if self.color => Cow::Owned(format!("{}{rest}", self.error("error:"))),
+            _ => self.plain(line),
+        }
+    }
+
+    /// Remove any ANSI escapes from `text` if color is disabled, e.g. those of a diagnostic
+    pub fn plain<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.color || !text.contains('\x1b') {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(strip_ansi(text))
+        }
+    }
+
+    fn paint<'a>(&self, code: &str, text: &'a str) -> Cow<'a, str> {
+        if self.color {
+            Cow::Owned(format!("\x1b[{code}m{text}\x1b[0m"))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+}
+
+/// Remove ANSI escape sequences, e.g. colors, from `text`
+pub fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // Control sequences are terminated by a byte in the range `@` to `~`
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // Operating system commands are terminated by BEL, or ESC `\`
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next() == Some('\\')) {
+                        break;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_ansi_escapes() {
+        assert_eq!(strip_ansi("\x1b[1;31merror\x1b[0m: failed"), "error: failed");
+        assert_eq!(strip_ansi("\x1b]8;;file:///a\x07link\x1b]8;;\x1b\\"), "link");
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn no_escapes_without_color() {
+        let style = Style::with_color(false);
+        assert_eq!(style.error("error"), "error");
+        assert_eq!(style.warning("warning"), "warning");
+        assert_eq!(style.output_line("error: failed"), "error: failed");
+        assert_eq!(style.plain("\x1b[31m  × failed\x1b[0m"), "  × failed");
+        assert!(!Style::new(ColorChoice::Never).error("error").contains('\x1b'));
+    }
+
+    #[test]
+    fn escapes_with_color() {
+        let style = Style::with_color(true);
+        assert_eq!(style.output_line("error: failed"), "\x1b[1;31merror:\x1b[0m failed");
+        assert_eq!(style.output_line("ok"), "ok");
+        assert_eq!(style.plain("\x1b[31mfailed\x1b[0m"), "\x1b[31mfailed\x1b[0m");
+    }
+}
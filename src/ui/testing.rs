@@ -0,0 +1,169 @@
+//! A harness which drives the [App] headlessly, rendering it to a [TestBackend], so that what is
+//! shown to the user can be checked against snapshots of the terminal.
+//!
+//! Snapshots are taken with `insta`, and kept in `src/ui/snapshots`. When the UI changes on
+//! purpose, run `cargo insta review` (or the tests with `INSTA_UPDATE=always`) to update the
+//! snapshots which no longer match, and review the difference before committing them.
+
+use miden_assembly_syntax::diagnostics::Report;
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use super::{
+    Action,
+    app::App,
+    panes::Pane,
+    state::State,
+    tui::{Event, Screen},
+};
+
+/// The most actions handled in response to a single input, beyond which the app is assumed to
+/// be stuck in a loop
+const MAX_ACTIONS: usize = 10_000;
+
+impl Screen for Terminal<TestBackend> {
+    type Backend = TestBackend;
+
+    fn terminal(&mut self) -> &mut Terminal<TestBackend> {
+        self
+    }
+
+    fn enter(&mut self) -> Result<(), Report> {
+        Ok(())
+    }
+
+    fn exit(&mut self) -> Result<(), Report> {
+        Ok(())
+    }
+}
+
+/// An [App] rendered to an in-memory terminal, which is driven by feeding it events and actions
+pub struct Harness {
+    pub app: App,
+    terminal: Terminal<TestBackend>,
+    action_tx: UnboundedSender<Action>,
+    action_rx: UnboundedReceiver<Action>,
+}
+
+impl Harness {
+    /// Start the app for the program loaded in `state`, on a terminal of `width` by `height` cells
+    pub fn new(state: State, width: u16, height: u16) -> Self {
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
+        let mut app = App::with_state(state).unwrap();
+        app.start(&action_tx).unwrap();
+        let terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        let mut harness = Self {
+            app,
+            terminal,
+            action_tx,
+            action_rx,
+        };
+        harness.settle();
+        harness
+    }
+
+    /// Handle `action`, and then every action which results from it
+    pub fn action(&mut self, action: Action) -> &mut Self {
+        self.action_tx.send(action).unwrap();
+        self.settle();
+        self
+    }
+
+    /// Handle `event`, as if it was received from the terminal, and then every action which
+    /// results from it
+    pub fn event(&mut self, event: Event) -> &mut Self {
+        self.app.handle_event(event, &self.action_tx).unwrap();
+        self.settle();
+        self
+    }
+
+    /// Press the key `code`, without modifiers
+    pub fn key(&mut self, code: KeyCode) -> &mut Self {
+        self.event(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    /// Perform the mouse action `kind` at `column` and `row` of the terminal, without modifiers
+    pub fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) -> &mut Self {
+        self.event(Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }))
+    }
+
+    /// Click the left mouse button at `column` and `row` of the terminal
+    pub fn click(&mut self, column: u16, row: u16) -> &mut Self {
+        self.mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+    }
+
+    /// Type each of the characters of `keys` in turn, where `\n` presses Enter, and `\t` Tab
+    pub fn keys(&mut self, keys: &str) -> &mut Self {
+        for c in keys.chars() {
+            match c {
+                '\n' => self.key(KeyCode::Enter),
+                '\t' => self.key(KeyCode::Tab),
+                c => self.key(KeyCode::Char(c)),
+            };
+        }
+        self
+    }
+
+    /// Render the app, returning the text shown on the terminal, with each row on its own line,
+    /// and trailing whitespace removed
+    pub fn render(&mut self) -> String {
+        self.action(Action::Render);
+        let buffer = self.terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        let symbols = buffer.content().iter().map(|cell| cell.symbol()).collect::<Vec<_>>();
+        let mut text = symbols
+            .chunks(width)
+            .map(|row| row.concat().trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// Handle the actions sent so far, and those they result in, until there are none left
+    fn settle(&mut self) {
+        for _ in 0..MAX_ACTIONS {
+            let Ok(action) = self.action_rx.try_recv() else {
+                return;
+            };
+            self.app.handle_action(action, &self.action_tx, &mut self.terminal).unwrap();
+        }
+        panic!("the app did not settle after handling {MAX_ACTIONS} actions");
+    }
+}
+
+/// Draw `pane` alone on a terminal of `width` by `height` cells, returning what was drawn
+pub fn draw_pane(pane: &mut dyn Pane, state: &State, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| pane.draw(frame, frame.area(), state).unwrap()).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Draw `pane` alone on a terminal of `width` by `height` cells, returning the text of each row.
+///
+/// Unlike [Harness::render], trailing whitespace is kept, so that right-hand borders stay in place.
+pub fn render_pane(pane: &mut dyn Pane, state: &State, width: u16, height: u16) -> Vec<String> {
+    let buffer = draw_pane(pane, state, width, height);
+    (0..buffer.area.height)
+        .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect())
+        .collect()
+}
+
+/// Write `source` to `main.masm` in a new temporary directory, returning the directory, which is
+/// removed when dropped, and the path of the file
+pub fn main_masm(source: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("main.masm");
+    std::fs::write(&path, source).unwrap();
+    (dir, path)
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+use ratatui::style::{Color, Modifier, Style};
+use syntect::highlighting::ThemeSet;
+
+use super::syntax_highlighting::convert_color;
+
+/// The theme used unless another is selected via `--theme` or `set theme`
+pub const DEFAULT_THEME: &str = "base16-eighties.dark";
+
+/// The themes which may be selected: those bundled with syntect, along with any `.tmTheme` files
+/// found in the themes directory, which are named after the file, and take precedence
+#[derive(Debug, Clone)]
+pub struct Themes {
+    themes: BTreeMap<String, Arc<syntect::highlighting::Theme>>,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self::load(None)
+    }
+}
+
+impl Themes {
+    /// Load the bundled themes, and the `.tmTheme` files in `dir`, if given. Files which cannot be
+    /// loaded are skipped with a warning.
+    pub fn load(dir: Option<&Path>) -> Self {
+        // The bundled themes are decompressed on first use, which is relatively expensive
+        static BUNDLED: OnceLock<BTreeMap<String, Arc<syntect::highlighting::Theme>>> =
+            OnceLock::new();
+        let mut themes = BUNDLED
+            .get_or_init(|| {
+                ThemeSet::load_defaults()
+                    .themes
+                    .into_iter()
+                    .map(|(name, theme)| (name, Arc::new(theme)))
+                    .collect()
+            })
+            .clone();
+        let entries = dir.and_then(|dir| std::fs::read_dir(dir).ok()).into_iter().flatten();
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_none_or(|ext| ext != "tmTheme") {
+                continue;
+            }
+            let Some(name) = path.file_stem().map(|stem| stem.to_string_lossy().into_owned())
+            else {
+                continue;
+            };
+            match ThemeSet::get_theme(&path) {
+                Ok(theme) => {
+                    themes.insert(name, Arc::new(theme));
+                }
+                Err(err) => log::warn!("unable to load theme from {}: {err}", path.display()),
+            }
+        }
+        Self { themes }
+    }
+
+    /// The names of the available themes, in lexicographic order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.themes.keys().map(String::as_str)
+    }
+
+    /// Select the theme named `name`
+    pub fn resolve(&self, name: &str) -> Result<Theme, String> {
+        let syntect = self.themes.get(name).ok_or_else(|| {
+            let names = self.names().collect::<Vec<_>>().join(", ");
+            format!("unknown theme '{name}', the available themes are: {names}")
+        })?;
+        Ok(Theme::new(name, syntect.clone()))
+    }
+
+    /// Select the theme named `name`, falling back to [DEFAULT_THEME] with a warning if there is
+    /// no such theme
+    pub fn resolve_or_default(&self, name: &str) -> Theme {
+        self.resolve(name).unwrap_or_else(|err| {
+            log::warn!("{err}, using {DEFAULT_THEME}");
+            self.resolve(DEFAULT_THEME).expect("the default theme is bundled")
+        })
+    }
+}
+
+/// The selected theme, which highlights the source code, and from which the colors of the other
+/// panes are derived
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub syntect: Arc<syntect::highlighting::Theme>,
+    pub styles: PaneStyles,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Themes::default().resolve_or_default(DEFAULT_THEME)
+    }
+}
+
+impl Theme {
+    pub fn new(name: &str, syntect: Arc<syntect::highlighting::Theme>) -> Self {
+        let mut styles = PaneStyles::default();
+        styles.patch_from_syntect(&syntect);
+        Self {
+            name: name.to_string(),
+            syntect,
+            styles,
+        }
+    }
+}
+
+/// The styles of the stack, memory, and call stack panes which are derived from the theme, so
+/// that they remain legible on both light and dark terminals
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PaneStyles {
+    /// The values shown by the stack and memory panes, other than those which changed
+    pub value: Style,
+    /// The names of the procedures of the call stack, other than the innermost
+    pub frame: Style,
+}
+
+impl Default for PaneStyles {
+    fn default() -> Self {
+        Self {
+            value: Style::default().fg(Color::White),
+            frame: Style::default().fg(Color::White).bg(Color::Black).add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+impl PaneStyles {
+    pub fn patch_from_syntect(&mut self, theme: &syntect::highlighting::Theme) {
+        if let Some(fg) = theme.settings.foreground.map(convert_color) {
+            self.value.fg = Some(fg);
+            self.frame.fg = Some(fg);
+        }
+        if let Some(bg) = theme.settings.line_highlight.map(convert_color) {
+            self.frame.bg = Some(bg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THEME: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>name</key>
+    <string>Paper</string>
+    <key>settings</key>
+    <array>
+        <dict>
+            <key>settings</key>
+            <dict>
+                <key>background</key>
+                <string>#FFFFFF</string>
+                <key>foreground</key>
+                <string>#102030</string>
+                <key>lineHighlight</key>
+                <string>#E0E0E0</string>
+            </dict>
+        </dict>
+    </array>
+</dict>
+</plist>
+"#;
+
+    #[test]
+    fn theme_resolution() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("paper.tmTheme"), THEME).unwrap();
+        std::fs::write(dir.path().join("broken.tmTheme"), "not a theme").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a theme either").unwrap();
+        let themes = Themes::load(Some(dir.path()));
+
+        // Both bundled themes and those in the themes directory are available
+        let names = themes.names().collect::<Vec<_>>();
+        assert!(names.contains(&DEFAULT_THEME));
+        assert!(names.contains(&"InspiredGitHub"));
+        assert!(names.contains(&"paper"));
+        assert!(!names.contains(&"broken"));
+        assert!(!names.contains(&"notes"));
+
+        // The pane styles are derived from the theme
+        let paper = themes.resolve("paper").unwrap();
+        assert_eq!(paper.name, "paper");
+        assert_eq!(paper.styles.value.fg, Some(Color::Rgb(0x10, 0x20, 0x30)));
+        assert_eq!(paper.styles.frame.bg, Some(Color::Rgb(0xe0, 0xe0, 0xe0)));
+        assert!(paper.styles.frame.add_modifier.contains(Modifier::BOLD));
+
+        // Unknown themes are an error, listing those available, or fall back to the default
+        let err = themes.resolve("bogus").unwrap_err();
+        assert!(err.starts_with("unknown theme 'bogus', the available themes are: "), "{err}");
+        assert!(err.contains("InspiredGitHub, "), "{err}");
+        assert_eq!(themes.resolve_or_default("bogus").name, DEFAULT_THEME);
+        assert_eq!(Theme::default().name, DEFAULT_THEME);
+    }
+}
@@ -94,7 +94,6 @@ impl Tui {
         self
     }
 
-    #[allow(unused)]
     pub fn mouse(mut self, mouse: bool) -> Self {
         self.mouse = mouse;
         self
@@ -239,6 +238,37 @@ impl Tui {
     }
 }
 
+/// The terminal to which the UI is drawn, which is taken over by [Tui], but may be substituted
+/// by one which is not backed by a real terminal, e.g. to drive the UI headlessly in tests
+pub trait Screen {
+    type Backend: ratatui::backend::Backend;
+
+    /// The terminal to draw to
+    fn terminal(&mut self) -> &mut ratatui::Terminal<Self::Backend>;
+
+    /// Take control of the terminal, see [Tui::enter]
+    fn enter(&mut self) -> Result<(), Report>;
+
+    /// Hand control of the terminal back to the shell, see [Tui::exit]
+    fn exit(&mut self) -> Result<(), Report>;
+}
+
+impl Screen for Tui {
+    type Backend = Backend<std::io::Stdout>;
+
+    fn terminal(&mut self) -> &mut ratatui::Terminal<Self::Backend> {
+        &mut self.terminal
+    }
+
+    fn enter(&mut self) -> Result<(), Report> {
+        Tui::enter(self)
+    }
+
+    fn exit(&mut self) -> Result<(), Report> {
+        Tui::exit(self)
+    }
+}
+
 impl Deref for Tui {
     type Target = ratatui::Terminal<Backend<std::io::Stdout>>;
 
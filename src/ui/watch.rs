@@ -0,0 +1,132 @@
+use crate::debug::ReadMemoryExpr;
+
+/// An expression, in the syntax of the `read` command, whose value is shown in the watch pane
+#[derive(Debug, Clone)]
+pub struct Watch {
+    /// The expression, as given by the user
+    pub text: String,
+    expr: ReadMemoryExpr,
+    /// The result of the most recent evaluation of `expr`, if it has been evaluated
+    pub value: Option<Result<String, String>>,
+    /// Whether `value` differs from the value of the evaluation which preceded it
+    pub changed: bool,
+}
+
+/// The expressions watched by the user, which are re-evaluated whenever the program advances.
+///
+/// Unlike their values, the expressions themselves are kept when the program is reloaded.
+#[derive(Debug, Default)]
+pub struct WatchList {
+    watches: Vec<Watch>,
+    /// The cycle at which the watches were last evaluated
+    cycle: Option<usize>,
+}
+
+impl WatchList {
+    /// Watch the expression `text`, returning its index
+    pub fn add(&mut self, text: &str) -> Result<usize, String> {
+        let text = text.trim();
+        let expr = text.parse::<ReadMemoryExpr>()?;
+        self.watches.push(Watch {
+            text: text.to_string(),
+            expr,
+            value: None,
+            changed: false,
+        });
+        Ok(self.watches.len() - 1)
+    }
+
+    /// Stop watching the expression at `index`
+    pub fn remove(&mut self, index: usize) -> Option<Watch> {
+        (index < self.watches.len()).then(|| self.watches.remove(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.watches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watches.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Watch> {
+        self.watches.iter()
+    }
+
+    /// Evaluate each watch at `cycle` using `eval`.
+    ///
+    /// If `cycle` differs from that of the previous evaluation, i.e. the program has advanced,
+    /// each watch is marked as changed if its value differs from the previous one. Otherwise, e.g.
+    /// when a watch is added, the change markers are left as they are.
+    pub fn evaluate<F>(&mut self, cycle: usize, mut eval: F)
+    where
+        F: FnMut(&ReadMemoryExpr) -> Result<String, String>,
+    {
+        let advanced = self.cycle.is_some_and(|prev| prev != cycle);
+        for watch in self.watches.iter_mut() {
+            let value = eval(&watch.expr);
+            if advanced {
+                watch.changed = watch.value.as_ref().is_some_and(|prev| prev != &value);
+            }
+            watch.value = Some(value);
+        }
+        self.cycle = Some(cycle);
+    }
+
+    /// Forget the values of all watches, e.g. when the program is reloaded
+    pub fn reset(&mut self) {
+        for watch in self.watches.iter_mut() {
+            watch.value = None;
+            watch.changed = false;
+        }
+        self.cycle = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watch_change_markers() {
+        let mut watches = WatchList::default();
+        watches.add("8").unwrap();
+        watches.add("12 -t u8").unwrap();
+        assert!(watches.add("8 -t").is_err());
+
+        // The value of the first watch changes on the second step, and the second always fails
+        let values = ["0", "0", "42", "42"];
+        let eval = |step: usize| {
+            move |expr: &ReadMemoryExpr| match expr.addr.addr {
+                8 => Ok(values[step].to_string()),
+                _ => Err("read failed".to_string()),
+            }
+        };
+        let markers = |watches: &WatchList| watches.iter().map(|w| w.changed).collect::<Vec<_>>();
+
+        watches.evaluate(0, eval(0));
+        assert_eq!(markers(&watches), [false, false]);
+        watches.evaluate(1, eval(1));
+        assert_eq!(markers(&watches), [false, false]);
+        watches.evaluate(2, eval(2));
+        assert_eq!(markers(&watches), [true, false]);
+        assert_eq!(watches.iter().next().unwrap().value, Some(Ok("42".to_string())));
+        assert_eq!(watches.iter().nth(1).unwrap().value, Some(Err("read failed".to_string())));
+
+        // Re-evaluating at the same cycle keeps the markers
+        watches.add("16").unwrap();
+        watches.evaluate(2, eval(2));
+        assert_eq!(markers(&watches), [true, false, false]);
+
+        watches.evaluate(3, eval(3));
+        assert_eq!(markers(&watches), [false, false, false]);
+
+        // Values are forgotten on reload, so nothing is marked as changed on the next evaluation
+        watches.reset();
+        watches.evaluate(0, eval(2));
+        assert_eq!(markers(&watches), [false, false, false]);
+        assert_eq!(watches.remove(1).map(|w| w.text), Some("12 -t u8".to_string()));
+        assert!(watches.remove(2).is_none());
+        assert_eq!(watches.len(), 2);
+    }
+}
@@ -0,0 +1,546 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    sync::Arc,
+};
+
+use miden_core::serde::Serializable;
+use miden_mast_package::{MastArtifact, Package, PackageKind, PackageManifest};
+use tempfile::TempDir;
+
+/// Assemble the program in `testdata/batch/{name}.masm`, and write it as a package to a temporary
+/// directory, returning the directory, which is removed when dropped, and the path of the package.
+fn package_fixture(name: &str) -> (TempDir, PathBuf) {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("batch")
+        .join(format!("{name}.masm"));
+    let source = std::fs::read_to_string(source).unwrap();
+    let program = miden_assembly::Assembler::default().assemble_program(source).unwrap();
+    let package = Package {
+        name: name.into(),
+        version: None,
+        description: None,
+        kind: PackageKind::Executable,
+        mast: MastArtifact::Executable(Arc::new(program)),
+        manifest: PackageManifest::new([]),
+        sections: vec![],
+    };
+
+    write_package(&package)
+}
+
+/// Assemble the module in `testdata/batch/{name}.masm` as a library, in which it is named `name`,
+/// and write it as a package to a temporary directory, returning the directory, which is removed
+/// when dropped, and the path of the package.
+fn library_fixture(name: &str) -> (TempDir, PathBuf) {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("batch")
+        .join(format!("{name}.masm"));
+    let source = std::fs::read_to_string(source).unwrap();
+    let source_manager = Arc::new(miden_assembly::DefaultSourceManager::default());
+    let module = miden_assembly::ast::Module::parser(miden_assembly::ast::ModuleKind::Library)
+        .parse_str(name, source, source_manager.clone())
+        .unwrap();
+    let library = miden_assembly::Assembler::new(source_manager)
+        .assemble_library([module])
+        .unwrap();
+    let package = Package {
+        name: name.into(),
+        version: None,
+        description: None,
+        kind: PackageKind::Library,
+        mast: MastArtifact::Library(Arc::new(library)),
+        manifest: PackageManifest::new([]),
+        sections: vec![],
+    };
+
+    write_package(&package)
+}
+
+fn write_package(package: &Package) -> (TempDir, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(format!("{}.masp", package.name));
+    std::fs::write(&path, package.to_bytes()).unwrap();
+    (dir, path)
+}
+
+fn miden_debug(args: &[&str]) -> (Output, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_miden-debug"))
+        .args(args)
+        .env_remove("MIDEN_SYSROOT")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    (output, stdout)
+}
+
+#[test]
+fn batch_success() {
+    let (_dir, package) = package_fixture("add");
+    let (output, stdout) =
+        miden_debug(&["--batch", "--result-type", "u32", package.to_str().unwrap()]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("outputs: [3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]"));
+    assert_eq!(lines.next(), Some("result: 3"));
+    assert!(lines.next().is_some_and(|line| line.starts_with("cycles: ")));
+}
+
+#[test]
+fn batch_library_entrypoint() {
+    let (_dir, package) = library_fixture("math");
+    let package = package.to_str().unwrap();
+    // The arguments fill the operand stack from the bottom, so they are preceded by zeros to place
+    // the operands of the procedure on top
+    fn with_operands<'a>(args: &[&'a str], operands: &[&'a str]) -> Vec<&'a str> {
+        let mut args = args.to_vec();
+        args.push("--");
+        args.extend(std::iter::repeat_n("0", 16 - operands.len()));
+        args.extend(operands);
+        args
+    }
+
+    let args =
+        with_operands(&[package, "--entrypoint", "math::mul_add", "--run"], &["4", "2", "3"]);
+    let (output, stdout) = miden_debug(&args);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("outputs: [10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]"));
+    assert!(lines.next().is_some_and(|line| line.starts_with("cycles: ")));
+
+    let args = with_operands(
+        &["--batch", "--entrypoint", "::math::sum", "--expect", "3", package],
+        &["1", "2"],
+    );
+    let (output, stdout) = miden_debug(&args);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+
+    // The entrypoint must be a procedure exported by the library
+    for (entrypoint, error) in [
+        ("math::bogus", "library does not export '::math::bogus'"),
+        ("bogus::sum", "library does not contain a module named '::bogus'"),
+        ("sum", "invalid function identifier: 'sum'"),
+    ] {
+        let (output, _) = miden_debug(&["--batch", "--entrypoint", entrypoint, package]);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(error), "unexpected error: {stderr}");
+    }
+}
+
+#[test]
+fn batch_failure() {
+    let (_dir, package) = package_fixture("assert");
+    let (output, stdout) = miden_debug(&["--batch", package.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("Stack Trace:"), "missing stack trace: {stdout}");
+    assert!(stdout.contains("program execution failed"), "missing error: {stdout}");
+    assert!(!stdout.contains("outputs:"));
+}
+
+#[test]
+fn batch_error_codes() {
+    let (_dir, package) = package_fixture("assert");
+    let (_, stdout) = miden_debug(&["--batch", package.to_str().unwrap()]);
+    assert!(
+        stdout.contains("assertion failed with error code: 0"),
+        "unexpected output: {stdout}"
+    );
+
+    // Error codes next to the package are found automatically
+    std::fs::write(
+        package.with_file_name("assert.errors.toml"),
+        "[0]\nname = \"ERR_MISMATCH\"\ndescription = \"the values differ\"\n",
+    )
+    .unwrap();
+    let (output, stdout) = miden_debug(&["--batch", package.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.contains("assertion failed with error ERR_MISMATCH (0x0): the values differ"),
+        "unexpected output: {stdout}"
+    );
+
+    // An explicit --error-codes file takes precedence
+    let json = package.with_file_name("codes.json");
+    std::fs::write(&json, r#"{"0x0": {"name": "ERR_OTHER"}}"#).unwrap();
+    let (_, stdout) = miden_debug(&[
+        "--batch",
+        "--error-codes",
+        json.to_str().unwrap(),
+        package.to_str().unwrap(),
+    ]);
+    assert!(
+        stdout.contains("assertion failed with error ERR_OTHER (0x0)"),
+        "unexpected output: {stdout}"
+    );
+}
+
+#[test]
+fn batch_color() {
+    let (_dir, package) = package_fixture("assert");
+    let package = package.to_str().unwrap();
+
+    let (_, stdout) = miden_debug(&["--batch", "--color", "never", package]);
+    assert!(stdout.contains("program execution failed"), "missing error: {stdout}");
+    assert!(!stdout.contains('\x1b'), "unexpected escapes: {stdout:?}");
+
+    let (_, stdout) = miden_debug(&["--batch", "--color", "always", package]);
+    assert!(stdout.contains('\x1b'), "missing escapes: {stdout:?}");
+
+    let (_, stdout) = Command::new(env!("CARGO_BIN_EXE_miden-debug"))
+        .args(["--batch", package])
+        .env("NO_COLOR", "1")
+        .output()
+        .map(|output| (output.status, String::from_utf8(output.stdout).unwrap()))
+        .unwrap();
+    assert!(!stdout.contains('\x1b'), "unexpected escapes: {stdout:?}");
+}
+
+#[test]
+fn batch_script_failure() {
+    let (_dir, package) = package_fixture("add");
+    let script = package.with_extension("script");
+    std::fs::write(&script, "c\nbogus\n").unwrap();
+    let (output, stdout) =
+        miden_debug(&["--batch", "--script", script.to_str().unwrap(), package.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.contains("program terminated successfully"),
+        "unexpected output: {stdout}"
+    );
+    assert!(stdout.ends_with("error: unknown command\n"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn batch_script_continue_on_error() {
+    let (_dir, package) = package_fixture("add");
+    let script = package.with_extension("script");
+    std::fs::write(&script, "bogus\nc\n").unwrap();
+    let (output, stdout) = miden_debug(&[
+        "--batch",
+        "--script",
+        script.to_str().unwrap(),
+        "--continue-on-error",
+        package.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("error: unknown command\n:c\n"), "unexpected output: {stdout}");
+    assert!(
+        stdout.contains("program terminated successfully"),
+        "unexpected output: {stdout}"
+    );
+}
+
+#[test]
+fn batch_script_tui_only_commands() {
+    let (_dir, package) = package_fixture("add");
+    let script = package.with_extension("script");
+    std::fs::write(&script, "mem 0x100\ngoto 3\ndebug\nread 0x100\n").unwrap();
+    let (output, stdout) =
+        miden_debug(&["--batch", "--script", script.to_str().unwrap(), package.to_str().unwrap()]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+    assert!(
+        stdout.contains(
+            ":mem 0x100\nwarning: the memory pane is only shown in the TUI, see 'read'\n:goto 3\n\
+             warning: the source code pane is only shown in the TUI, see 'list'\n:debug\n\
+             warning: the debug log is only shown in the TUI, see 'log-save'\n:read 0x100\n"
+        ),
+        "unexpected output: {stdout}"
+    );
+}
+
+#[test]
+fn batch_expect_pass() {
+    let (_dir, package) = package_fixture("add");
+    let (output, stdout) = miden_debug(&[
+        "--batch",
+        "--expect",
+        "3,0",
+        "--expect",
+        "u64:0",
+        package.to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+    assert!(!stdout.contains("do not match"));
+}
+
+#[test]
+fn batch_expect_mismatch() {
+    let (_dir, package) = package_fixture("add");
+    let (output, stdout) = miden_debug(&[
+        "--batch",
+        "--expect",
+        "3,2",
+        "--expect",
+        "u64:7",
+        package.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let report = stdout
+        .lines()
+        .skip_while(|line| !line.contains("do not match"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        report,
+        vec![
+            "outputs do not match expectations:",
+            "  position 1: expected 2, got 0",
+            "  position 2: expected u64:7, got u64:0",
+        ]
+    );
+}
+
+#[test]
+fn batch_expect_with_script() {
+    // Expectations cannot be checked against a program stopped wherever a script left it, so they
+    // are rejected rather than silently ignored
+    let (_dir, package) = package_fixture("add");
+    let script = package.with_extension("script");
+    std::fs::write(&script, "c\n").unwrap();
+    let expectations = package.with_extension("expected");
+    std::fs::write(&expectations, "999\n").unwrap();
+    for option in [
+        ["--expect", "999"],
+        ["--expect-file", expectations.to_str().unwrap()],
+        ["--result-type", "u32"],
+    ] {
+        let (output, stdout) = miden_debug(&[
+            "--batch",
+            "--script",
+            script.to_str().unwrap(),
+            option[0],
+            option[1],
+            package.to_str().unwrap(),
+        ]);
+        assert_eq!(output.status.code(), Some(2), "unexpected success: {stdout}");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("cannot be used with"), "unexpected error: {stderr}");
+    }
+}
+
+#[test]
+fn batch_assert_output() {
+    let (_dir, package) = package_fixture("add");
+    let run = |assertions: &[&str]| {
+        let mut args = vec!["--batch"];
+        for assertion in assertions {
+            args.extend(["--assert-output", assertion]);
+        }
+        args.push(package.to_str().unwrap());
+        miden_debug(&args)
+    };
+    let (output, stdout) = run(&["felt=3", "u64=0"]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+
+    let (output, stdout) = run(&["felt=3", "u32=4"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.ends_with(
+            "outputs do not match expectations:\n  position 1: expected u32:4, got u32:0\n"
+        ),
+        "unexpected output: {stdout}"
+    );
+
+    // A failing assertion is not skipped when a script is given, the combination is an error
+    let script = package.with_extension("script");
+    std::fs::write(&script, "c\n").unwrap();
+    let (output, stdout) = miden_debug(&[
+        "--batch",
+        "--script",
+        script.to_str().unwrap(),
+        "--assert-output",
+        "u32=4",
+        package.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(2), "unexpected success: {stdout}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("cannot be used with '--expect <VALUES>'"),
+        "unexpected error: {stderr}"
+    );
+}
+
+#[test]
+fn batch_expect_short_output() {
+    let (_dir, package) = package_fixture("add");
+    let expectations = package.with_extension("expected");
+    std::fs::write(
+        &expectations,
+        "# the sum, then padding\n3\n0,0,0,0,0,0,0,0,0,0,0,0,0,0,0\n\nu64:5\n",
+    )
+    .unwrap();
+    let (output, stdout) = miden_debug(&[
+        "--batch",
+        "--expect-file",
+        expectations.to_str().unwrap(),
+        package.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.ends_with("  position 16: expected u64:5, got nothing\n"),
+        "unexpected output: {stdout}"
+    );
+}
+
+#[test]
+fn batch_dump_inputs() {
+    let (_dir, package) = package_fixture("add");
+    let inputs = package.with_file_name("inputs.toml");
+    std::fs::write(
+        &inputs,
+        "[inputs]\nstack = [7]\n\n[inputs.advice]\nstack = [5, 6]\n\n[[inputs.advice.map]]\n\
+         digest = \"0x3cff5b58a573dc9d25fd3c57130cc57e5b1b381dc58b5ae3594b390c59835e63\"\n\
+         values = [1, 2]\n",
+    )
+    .unwrap();
+    let (inputs, package) = (inputs.to_str().unwrap(), package.to_str().unwrap());
+
+    // Without --batch, the program is not executed
+    let (output, stdout) =
+        miden_debug(&["--dump-inputs", "--inputs", inputs, package, "--", "1", "2"]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "operand stack (bottom to top):");
+    assert_eq!(&lines[1..3], &["  [15] 1", "  [14] 2"]);
+    assert_eq!(lines[16], "  [0] 0");
+    assert_eq!(
+        &lines[17..],
+        &[
+            "advice stack (bottom to top, 2 elements):",
+            "  [1] 5",
+            "  [0] 6",
+            "advice map (1 entries):",
+            "  0x3cff5b58a573dc9d25fd3c57130cc57e5b1b381dc58b5ae3594b390c59835e63 => [1, 2]",
+        ]
+    );
+
+    let (output, stdout) = miden_debug(&["--batch", "--dump-inputs", package]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+    assert!(
+        stdout.starts_with("operand stack (bottom to top):\n"),
+        "unexpected output: {stdout}"
+    );
+    assert!(stdout.contains("\noutputs: [3, "), "missing outputs: {stdout}");
+}
+
+#[test]
+fn batch_preloaded_memory() {
+    let (_dir, package) = package_fixture("load");
+    std::fs::write(package.with_file_name("image.bin"), [0, 0, 0, 9]).unwrap();
+    let inputs = package.with_file_name("inputs.toml");
+    std::fs::write(&inputs, "[[memory]]\naddr = 256\npath = \"image.bin\"\n").unwrap();
+
+    let (output, stdout) = miden_debug(&[
+        "--batch",
+        "--result-type",
+        "u32",
+        "--inputs",
+        inputs.to_str().unwrap(),
+        package.to_str().unwrap(),
+    ]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+    assert!(stdout.contains("\nresult: 9\n"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn batch_max_cycles() {
+    let (_dir, package) = package_fixture("loop");
+    let (output, stdout) =
+        miden_debug(&["--batch", "--max-cycles", "5000", package.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.contains("exceeded the allowed number of max cycles 5000"),
+        "unexpected output: {stdout}"
+    );
+}
+
+#[test]
+fn batch_cycle_budget_warning() {
+    let (_dir, package) = package_fixture("add");
+    let (output, _) =
+        miden_debug(&["--batch", "--cycle-budget-warning", "5", package.to_str().unwrap()]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("warning: execution took "), "unexpected output: {stderr}");
+    assert!(stderr.contains("exceeding the budget of 5"), "unexpected output: {stderr}");
+}
+
+#[test]
+fn print_search_paths() {
+    let (_dir, package) = package_fixture("add");
+    let dir = package.parent().unwrap();
+    let lib_dir = dir.join("lib");
+    let (output, stdout) = miden_debug(&[
+        "--print-search-paths",
+        "-L",
+        lib_dir.to_str().unwrap(),
+        "--working-dir",
+        dir.to_str().unwrap(),
+        package.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+    let expected = format!("{}\n{}\n", lib_dir.display(), dir.display());
+    assert!(stdout.ends_with(&expected), "unexpected output: {stdout}");
+}
+
+#[test]
+fn missing_library_lists_search_paths() {
+    let (_dir, package) = package_fixture("add");
+    let dir = package.parent().unwrap();
+    let (output, _) = miden_debug(&[
+        "--batch",
+        "-l",
+        "masp=nosuchlib",
+        "--working-dir",
+        dir.to_str().unwrap(),
+        package.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("unable to locate library 'nosuchlib'"),
+        "unexpected output: {stderr}"
+    );
+    assert!(stderr.contains(dir.to_str().unwrap()), "unexpected output: {stderr}");
+}
+
+#[test]
+fn batch_measure() {
+    // Packages assembled from strings have no source files, so no cycles can be attributed to
+    // a line of source code
+    let (_dir, package) = package_fixture("add");
+    let (output, stdout) = miden_debug(&["--batch", "--measure", package.to_str().unwrap()]);
+    assert!(output.status.success(), "unexpected failure: {stdout}");
+
+    let cycles = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("cycles: "))
+        .expect("missing cycle count");
+    let measurement = stdout.lines().skip_while(|line| !line.starts_with("cycles by line"));
+    assert_eq!(
+        measurement.collect::<Vec<_>>(),
+        vec![
+            "cycles by line (top 0 of 0):".to_string(),
+            "cycles by file:".to_string(),
+            format!("  {cycles}  <unknown>"),
+            format!("total: {cycles} cycles"),
+        ]
+    );
+
+    // Scripts measure cycles with the `measure` command instead
+    let script = package.with_extension("script");
+    std::fs::write(&script, "c\n").unwrap();
+    let (output, stdout) = miden_debug(&[
+        "--batch",
+        "--script",
+        script.to_str().unwrap(),
+        "--measure",
+        package.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(2), "unexpected success: {stdout}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot be used with '--measure"), "unexpected error: {stderr}");
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use miden_core::serde::Serializable;
+use miden_mast_package::{MastArtifact, Package, PackageKind, PackageManifest};
+use miden_processor::Felt;
+
+/// Assemble `source` into an executable package, and serialize it
+fn package_bytes(source: &str) -> Vec<u8> {
+    let program = miden_assembly::Assembler::default().assemble_program(source).unwrap();
+    let package = Package {
+        name: "test".into(),
+        version: None,
+        description: None,
+        kind: PackageKind::Executable,
+        mast: MastArtifact::Executable(Arc::new(program)),
+        manifest: PackageManifest::new([]),
+        sections: vec![],
+    };
+    package.to_bytes()
+}
+
+#[test]
+fn run_program_returns_outputs() {
+    let bytes = package_bytes("begin sub push.2 mul end");
+    let args = vec![Felt::new(3), Felt::new(10)];
+
+    let outputs = miden_debug::run_program(&bytes, args, vec![]).unwrap();
+    assert_eq!(outputs.get_num_elements(2), &[Felt::new(14), Felt::new(0)]);
+}
+
+#[test]
+fn run_program_reports_failures() {
+    let bytes = package_bytes("begin push.1 assertz end");
+
+    let err = miden_debug::run_program(&bytes, vec![], vec![]).unwrap_err();
+    assert!(err.to_string().contains("program execution failed"), "unexpected error: {err}");
+}
+
+#[test]
+fn run_program_rejects_invalid_packages() {
+    let err = miden_debug::run_program(b"not a package", vec![], vec![]).unwrap_err();
+    assert!(
+        err.to_string().starts_with("failed to load Miden package"),
+        "unexpected error: {err}"
+    );
+}